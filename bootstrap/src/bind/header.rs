@@ -0,0 +1,544 @@
+use std::fmt::{Display, Formatter};
+
+// a c type as written in the header, qualifiers like const are dropped
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CType {
+    // e.g. int, unsigned long, int32_t, struct point, enum color
+    pub(crate) name: String,
+    pub(crate) pointers: usize,
+}
+
+impl Display for CType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.name, " *".repeat(self.pointers))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CField {
+    pub(crate) name: Option<String>,
+    pub(crate) r#type: CType,
+}
+
+// name and value of an enum constant
+pub(crate) type CConstant = (String, i64);
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum CDeclaration {
+    Enum { name: Option<String>, constants: Vec<CConstant> },
+    Function { name: String, result: CType, parameters: Vec<CField>, variadic: bool },
+    Struct { name: String, fields: Vec<CField> },
+    Typedef { name: String, r#type: CType },
+}
+
+// a top level declaration of the header, as text for reporting and as tokens
+#[derive(Debug)]
+pub(crate) struct CSource {
+    pub(crate) text: String,
+    tokens: Vec<String>,
+}
+
+// the declarations of a source, or why they are not supported
+pub(crate) type Parsed = Result<Vec<CDeclaration>, String>;
+
+// the subset of c declarations bind understands, everything else is reported with its reason
+pub(crate) fn parse_header(header: &str) -> Vec<(CSource, Parsed)> {
+    split(&tokenize(header))
+        .into_iter()
+        .map(|source| {
+            let declarations = Parser { tokens: &source.tokens, position: 0 }.declaration();
+            (source, declarations)
+        })
+        .collect()
+}
+
+// identifiers, numbers and single character punctuation - comments and preprocessor lines are dropped
+fn tokenize(header: &str) -> Vec<String> {
+    let mut result = vec![];
+    let chars: Vec<char> = header.chars().collect();
+    let mut idx = 0;
+    let mut line_start = true;
+
+    while idx < chars.len() {
+        let c = chars[idx];
+
+        if c == '\n' {
+            line_start = true;
+            idx += 1;
+            continue;
+        }
+
+        if c.is_whitespace() {
+            idx += 1;
+            continue;
+        }
+
+        if c == '#' && line_start {
+            // directives can continue on the next line with a trailing backslash
+            while idx < chars.len() && !(chars[idx] == '\n' && chars[idx - 1] != '\\') {
+                idx += 1;
+            }
+            continue;
+        }
+        line_start = false;
+
+        if c == '/' && chars.get(idx + 1) == Some(&'/') {
+            while idx < chars.len() && chars[idx] != '\n' {
+                idx += 1;
+            }
+            continue;
+        }
+
+        if c == '/' && chars.get(idx + 1) == Some(&'*') {
+            idx += 2;
+            while idx < chars.len() && !(chars[idx] == '*' && chars.get(idx + 1) == Some(&'/')) {
+                idx += 1;
+            }
+            idx += 2;
+            continue;
+        }
+
+        if c.is_alphanumeric() || c == '_' {
+            let start = idx;
+            while idx < chars.len() && (chars[idx].is_alphanumeric() || chars[idx] == '_') {
+                idx += 1;
+            }
+            result.push(chars[start..idx].iter().collect());
+            continue;
+        }
+
+        if c == '.' && chars.get(idx + 1) == Some(&'.') && chars.get(idx + 2) == Some(&'.') {
+            result.push("...".to_string());
+            idx += 3;
+            continue;
+        }
+
+        result.push(c.to_string());
+        idx += 1;
+    }
+
+    result
+}
+
+// top level declarations end with a semicolon outside of braces, or with the closing brace of a function body
+fn split(tokens: &[String]) -> Vec<CSource> {
+    let mut result = vec![];
+    let mut current = vec![];
+    let mut depth = 0;
+    let mut body = false;
+
+    for token in tokens {
+        match token.as_str() {
+            "{" => {
+                body |= depth == 0 && current.last().is_some_and(|last| last == ")");
+                depth += 1;
+            }
+            "}" => {
+                depth -= 1;
+                if depth == 0 && body {
+                    current.push(token.clone());
+                    result.push(CSource { text: text(&current), tokens: std::mem::take(&mut current) });
+                    body = false;
+                    continue;
+                }
+            }
+            ";" if depth == 0 => {
+                if !current.is_empty() {
+                    result.push(CSource { text: text(&current), tokens: std::mem::take(&mut current) });
+                }
+                continue;
+            }
+            _ => {}
+        }
+        current.push(token.clone());
+    }
+
+    result
+}
+
+fn text(tokens: &[String]) -> String {
+    let mut result = String::new();
+    for token in tokens {
+        let separate = result.chars().last().is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '*' || c == ',')
+            && token.chars().next().is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '*' || c == '.');
+        if separate {
+            result.push(' ');
+        }
+        result.push_str(token);
+    }
+    result
+}
+
+const QUALIFIERS: [&str; 8] = ["const", "volatile", "restrict", "__restrict", "extern", "static", "inline", "register"];
+
+// words which together name a builtin c type, e.g. unsigned long long int
+const TYPE_WORDS: [&str; 10] = ["signed", "unsigned", "char", "short", "int", "long", "float", "double", "_Bool", "__int128"];
+
+struct Parser<'a> {
+    tokens: &'a [String],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn declaration(&mut self) -> Result<Vec<CDeclaration>, String> {
+        if self.consume_if("typedef") {
+            return self.typedef();
+        }
+
+        if self.peek_is("struct") && self.tokens.contains(&"{".to_string()) {
+            let (name, fields) = self.r#struct()?;
+            let name = name.ok_or("anonymous struct".to_string())?;
+            self.end()?;
+            return Ok(vec![CDeclaration::Struct { name: format!("struct {name}"), fields }]);
+        }
+
+        if self.peek_is("enum") && self.tokens.contains(&"{".to_string()) {
+            let (name, constants) = self.r#enum()?;
+            self.end()?;
+            return Ok(vec![CDeclaration::Enum { name: name.map(|name| format!("enum {name}")), constants }]);
+        }
+
+        let result = self.r#type()?;
+        let name = self.identifier()?;
+        if !self.consume_if("(") {
+            return Err("variables are not supported".to_string());
+        }
+
+        let (parameters, variadic) = self.parameters()?;
+        if self.peek_is("{") {
+            return Err("function definitions are not supported".to_string());
+        }
+        self.end()?;
+        Ok(vec![CDeclaration::Function { name, result, parameters, variadic }])
+    }
+
+    fn typedef(&mut self) -> Result<Vec<CDeclaration>, String> {
+        if self.peek_is("struct") && self.tokens.contains(&"{".to_string()) {
+            let (tag, fields) = self.r#struct()?;
+            let name = self.identifier()?;
+            self.end()?;
+
+            let mut result = vec![CDeclaration::Struct { name: name.clone(), fields }];
+            if let Some(tag) = tag {
+                result.push(CDeclaration::Typedef { name: format!("struct {tag}"), r#type: CType { name, pointers: 0 } });
+            }
+            return Ok(result);
+        }
+
+        if self.peek_is("enum") && self.tokens.contains(&"{".to_string()) {
+            let (_, constants) = self.r#enum()?;
+            let name = self.identifier()?;
+            self.end()?;
+            return Ok(vec![CDeclaration::Enum { name: Some(name), constants }]);
+        }
+
+        let r#type = self.r#type()?;
+        if self.peek_is("(") {
+            return Err("function pointers are not supported".to_string());
+        }
+        let name = self.identifier()?;
+        self.end()?;
+        Ok(vec![CDeclaration::Typedef { name, r#type }])
+    }
+
+    // struct tag { int x; int y; }
+    fn r#struct(&mut self) -> Result<(Option<String>, Vec<CField>), String> {
+        self.expect("struct")?;
+        let tag = if self.peek_is("{") { None } else { Some(self.identifier()?) };
+        self.expect("{")?;
+
+        let mut fields = vec![];
+        while !self.consume_if("}") {
+            let r#type = self.r#type()?;
+            loop {
+                let mut r#type = r#type.clone();
+                while self.consume_if("*") {
+                    r#type.pointers += 1;
+                }
+                let name = self.identifier()?;
+                if self.peek_is("[") || self.peek_is(":") {
+                    return Err(format!("field {name} is an array or bit field"));
+                }
+                fields.push(CField { name: Some(name), r#type });
+                if !self.consume_if(",") {
+                    break;
+                }
+            }
+            self.expect(";")?;
+        }
+
+        Ok((tag, fields))
+    }
+
+    // enum tag { A, B = 2, C }
+    fn r#enum(&mut self) -> Result<(Option<String>, Vec<CConstant>), String> {
+        self.expect("enum")?;
+        let tag = if self.peek_is("{") { None } else { Some(self.identifier()?) };
+        self.expect("{")?;
+
+        let mut constants = vec![];
+        let mut next = 0;
+        while !self.consume_if("}") {
+            let name = self.identifier()?;
+            if self.consume_if("=") {
+                next = self.integer()?;
+            }
+            constants.push((name, next));
+            next += 1;
+
+            if !self.consume_if(",") {
+                self.expect("}")?;
+                break;
+            }
+        }
+
+        Ok((tag, constants))
+    }
+
+    fn parameters(&mut self) -> Result<(Vec<CField>, bool), String> {
+        let mut result = vec![];
+
+        if self.peek_is("void") && self.tokens.get(self.position + 1).map(String::as_str) == Some(")") {
+            self.position += 2;
+            return Ok((result, false));
+        }
+
+        while !self.consume_if(")") {
+            if self.consume_if("...") {
+                self.expect(")")?;
+                return Ok((result, true));
+            }
+
+            let r#type = self.r#type()?;
+            if self.peek_is("(") {
+                return Err("function pointers are not supported".to_string());
+            }
+
+            let name = if self.peek_is(",") || self.peek_is(")") { None } else { Some(self.identifier()?) };
+            if self.peek_is("[") {
+                return Err("array parameters are not supported".to_string());
+            }
+            result.push(CField { name, r#type });
+
+            if !self.consume_if(",") {
+                self.expect(")")?;
+                break;
+            }
+        }
+
+        Ok((result, false))
+    }
+
+    // qualifiers are skipped, builtin types can span several words
+    fn r#type(&mut self) -> Result<CType, String> {
+        self.skip_qualifiers();
+
+        let name = if self.peek_is("struct") || self.peek_is("enum") || self.peek_is("union") {
+            let keyword = self.next()?;
+            if keyword == "union" {
+                return Err("unions are not supported".to_string());
+            }
+            format!("{keyword} {}", self.identifier()?)
+        } else if self.peek().is_some_and(|token| TYPE_WORDS.contains(&token)) {
+            let mut words = vec![];
+            while let Some(token) = self.peek().filter(|token| TYPE_WORDS.contains(token) || QUALIFIERS.contains(token)) {
+                if !QUALIFIERS.contains(&token) {
+                    words.push(token.to_string());
+                }
+                self.position += 1;
+            }
+            words.join(" ")
+        } else {
+            self.identifier()?
+        };
+
+        let mut pointers = 0;
+        loop {
+            self.skip_qualifiers();
+            if !self.consume_if("*") {
+                break;
+            }
+            pointers += 1;
+        }
+
+        Ok(CType { name, pointers })
+    }
+
+    fn skip_qualifiers(&mut self) {
+        while self.peek().is_some_and(|token| QUALIFIERS.contains(&token)) {
+            self.position += 1;
+        }
+    }
+
+    fn integer(&mut self) -> Result<i64, String> {
+        let negative = self.consume_if("-");
+        let value = self.next()?;
+        let value = value.trim_end_matches(['u', 'U', 'l', 'L']);
+
+        let parsed = if let Some(hex) = value.strip_prefix("0x").or(value.strip_prefix("0X")) {
+            i64::from_str_radix(hex, 16)
+        } else {
+            value.parse::<i64>()
+        };
+
+        let value = parsed.map_err(|_| format!("{value} is not an integer"))?;
+        Ok(if negative { -value } else { value })
+    }
+
+    fn identifier(&mut self) -> Result<String, String> {
+        let token = self.next()?;
+        if token.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_') {
+            Ok(token)
+        } else {
+            Err(format!("expected identifier, got {token}"))
+        }
+    }
+
+    fn end(&self) -> Result<(), String> {
+        match self.peek() {
+            None => Ok(()),
+            Some(token) => Err(format!("unexpected {token}")),
+        }
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), String> {
+        let token = self.next()?;
+        if token == expected {
+            Ok(())
+        } else {
+            Err(format!("expected {expected}, got {token}"))
+        }
+    }
+
+    fn next(&mut self) -> Result<String, String> {
+        let result = self.tokens.get(self.position).cloned().ok_or("unexpected end".to_string())?;
+        self.position += 1;
+        Ok(result)
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.position).map(String::as_str)
+    }
+
+    fn peek_is(&self, expected: &str) -> bool {
+        self.peek() == Some(expected)
+    }
+
+    fn consume_if(&mut self, expected: &str) -> bool {
+        if self.peek_is(expected) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bind::header::{CDeclaration, CField, CType, parse_header};
+
+    fn declarations(header: &str) -> Vec<CDeclaration> {
+        parse_header(header).into_iter().flat_map(|(_, result)| result.unwrap()).collect()
+    }
+
+    fn c_type(name: &str, pointers: usize) -> CType {
+        CType { name: name.to_string(), pointers }
+    }
+
+    #[test]
+    fn function_prototype() {
+        let result = declarations(r#"
+            #include <stdint.h>
+            // adds two numbers
+            extern unsigned long long add(const int32_t a, int32_t /* second */ b);
+            void reset(void);
+        "#);
+
+        assert_eq!(result, vec![
+            CDeclaration::Function {
+                name: "add".to_string(),
+                result: c_type("unsigned long long", 0),
+                parameters: vec![
+                    CField { name: Some("a".to_string()), r#type: c_type("int32_t", 0) },
+                    CField { name: Some("b".to_string()), r#type: c_type("int32_t", 0) },
+                ],
+                variadic: false,
+            },
+            CDeclaration::Function { name: "reset".to_string(), result: c_type("void", 0), parameters: vec![], variadic: false },
+        ]);
+    }
+
+    #[test]
+    fn struct_and_typedef() {
+        let result = declarations(r#"
+            typedef struct point { int x, y; double *weight; } Point;
+            typedef unsigned int handle;
+        "#);
+
+        assert_eq!(result, vec![
+            CDeclaration::Struct {
+                name: "Point".to_string(),
+                fields: vec![
+                    CField { name: Some("x".to_string()), r#type: c_type("int", 0) },
+                    CField { name: Some("y".to_string()), r#type: c_type("int", 0) },
+                    CField { name: Some("weight".to_string()), r#type: c_type("double", 1) },
+                ],
+            },
+            CDeclaration::Typedef { name: "struct point".to_string(), r#type: c_type("Point", 0) },
+            CDeclaration::Typedef { name: "handle".to_string(), r#type: c_type("unsigned int", 0) },
+        ]);
+    }
+
+    #[test]
+    fn enum_values() {
+        let result = declarations("enum color { RED, GREEN = 0x10, BLUE, BLACK = -1 };");
+
+        assert_eq!(result, vec![
+            CDeclaration::Enum {
+                name: Some("enum color".to_string()),
+                constants: vec![
+                    ("RED".to_string(), 0),
+                    ("GREEN".to_string(), 16),
+                    ("BLUE".to_string(), 17),
+                    ("BLACK".to_string(), -1),
+                ],
+            },
+        ]);
+    }
+
+    #[test]
+    fn unsupported_declarations() {
+        let result = parse_header(r#"
+            typedef void (*callback)(int);
+            int counter;
+            union value { int i; float f; };
+        "#);
+        assert_eq!(result.len(), 3);
+
+        for (source, result) in result {
+            assert!(result.is_err(), "{}", source.text);
+        }
+    }
+
+    #[test]
+    fn inline_function_before_declaration() {
+        let result = parse_header(r#"
+            static inline int twice(int value) { if (value) { return value * 2; } return 0; }
+            extern int global_counter;
+            void reset(void);
+        "#);
+        assert_eq!(result.len(), 3);
+
+        let (source, twice) = &result[0];
+        assert!(source.text.starts_with("static inline int twice"));
+        assert_eq!(twice, &Err("function definitions are not supported".to_string()));
+
+        let (source, global_counter) = &result[1];
+        assert_eq!(source.text, "extern int global_counter");
+        assert_eq!(global_counter, &Err("variables are not supported".to_string()));
+
+        let (_, reset) = &result[2];
+        assert_eq!(reset, &Ok(vec![CDeclaration::Function { name: "reset".to_string(), result: c_type("void", 0), parameters: vec![], variadic: false }]));
+    }
+}
@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+
+use crate::bind::header::{CConstant, CDeclaration, CField, CType, parse_header};
+use crate::common::{is_pascal_snake_case, is_snake_case};
+use crate::frontend::lex::is_keyword;
+
+mod header;
+
+// Elodie declarations generated from a c header
+#[derive(Debug)]
+pub struct Binding {
+    pub source: String,
+    pub skipped: Vec<Skipped>,
+}
+
+// a declaration of the header which has no elodie counterpart
+#[derive(Debug, PartialEq)]
+pub struct Skipped {
+    pub declaration: String,
+    pub reason: String,
+}
+
+// prints the package for the header to stdout, skipped declarations to stderr
+pub fn bind_header_from_file(file: PathBuf) {
+    let package = file.file_stem()
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_lowercase()
+        .replace(['-', '.'], "_");
+
+    let mut file = File::open(&file).unwrap();
+    let mut content = String::new();
+    file.read_to_string(&mut content).unwrap();
+
+    let binding = bind(package.as_str(), content.as_str());
+    for skipped in &binding.skipped {
+        eprintln!("skipped `{}`: {}", skipped.declaration, skipped.reason);
+    }
+    print!("{}", binding.source);
+}
+
+// the c type an Elodie type maps to is the inverse of the ffi type mapping of the c generator
+pub fn bind(package: &str, header: &str) -> Binding {
+    let mut binder = Binder {
+        typedefs: HashMap::new(),
+        structs: HashMap::new(),
+        enums: vec![],
+        types: vec![],
+        constants: vec![],
+        functions: vec![],
+        skipped: vec![],
+    };
+
+    for (source, declarations) in parse_header(header) {
+        let result = declarations.and_then(|declarations| {
+            for declaration in declarations {
+                binder.declaration(declaration)?;
+            }
+            Ok(())
+        });
+
+        if let Err(reason) = result {
+            binder.skipped.push(Skipped { declaration: source.text, reason });
+        }
+    }
+
+    Binding { source: binder.source(package), skipped: binder.skipped }
+}
+
+struct Binder {
+    typedefs: HashMap<String, CType>,
+    // c name of the struct to the name of its external type
+    structs: HashMap<String, String>,
+    enums: Vec<String>,
+    types: Vec<String>,
+    constants: Vec<String>,
+    functions: Vec<String>,
+    skipped: Vec<Skipped>,
+}
+
+impl Binder {
+    fn declaration(&mut self, declaration: CDeclaration) -> Result<(), String> {
+        match declaration {
+            CDeclaration::Enum { name, constants } => self.r#enum(name, constants),
+            CDeclaration::Function { name, result, parameters, variadic } => self.function(name, result, parameters, variadic),
+            CDeclaration::Struct { name, fields } => self.r#struct(name, fields),
+            CDeclaration::Typedef { name, r#type } => {
+                self.typedefs.insert(name, r#type);
+                Ok(())
+            }
+        }
+    }
+
+    // enums are ints in c, their constants become functions
+    fn r#enum(&mut self, name: Option<String>, constants: Vec<CConstant>) -> Result<(), String> {
+        let mut result = vec![];
+        for (constant, value) in constants {
+            let function = constant.to_lowercase();
+            if !is_snake_case(&function) || is_keyword(&function) {
+                return Err(format!("{constant} is no valid function name"));
+            }
+            if value < 0 {
+                return Err(format!("{constant} is negative"));
+            }
+            result.push(format!("export function {function}() -> Int4 {{\n        return {value}\n    }}"));
+        }
+
+        if let Some(name) = name {
+            self.enums.push(name);
+        }
+        self.constants.extend(result);
+        Ok(())
+    }
+
+    fn function(&mut self, name: String, result: CType, parameters: Vec<CField>, variadic: bool) -> Result<(), String> {
+        if variadic {
+            return Err("variadic functions are not supported".to_string());
+        }
+
+        // the name of an external function is the symbol it gets linked against
+        if !is_snake_case(&name) || is_keyword(&name) {
+            return Err(format!("{name} is no valid function name"));
+        }
+
+        let mut arguments = Vec::with_capacity(parameters.len());
+        for (idx, parameter) in parameters.into_iter().enumerate() {
+            let name = parameter.name
+                .filter(|name| is_snake_case(name) && !is_keyword(name))
+                .unwrap_or_else(|| format!("arg_{}", idx + 1));
+            arguments.push(format!("{name}: {}", self.r#type(&parameter.r#type, true)?));
+        }
+
+        let result = if self.is_void(&result) {
+            String::new()
+        } else {
+            format!(" -> {}", self.r#type(&result, true)?)
+        };
+
+        self.functions.push(format!("export external function {name}({}){result}", arguments.join(", ")));
+        Ok(())
+    }
+
+    // the struct gets its own name, c only cares about the layout
+    fn r#struct(&mut self, name: String, fields: Vec<CField>) -> Result<(), String> {
+        let r#type = pascal_snake_case(name.trim_start_matches("struct "));
+        if !is_pascal_snake_case(&r#type) {
+            return Err(format!("{name} is no valid type name"));
+        }
+
+        let mut variables = Vec::with_capacity(fields.len());
+        for field in fields {
+            let variable = field.name.unwrap_or_default().to_lowercase();
+            if !is_snake_case(&variable) || is_keyword(&variable) {
+                return Err(format!("{variable} is no valid variable name"));
+            }
+            variables.push(format!("{variable}: {}", self.r#type(&field.r#type, false)?));
+        }
+
+        self.structs.insert(name, r#type.clone());
+        self.types.push(format!("export external type {type}({})", variables.join(", ")));
+        Ok(())
+    }
+
+    // strings and pointers only cross the boundary as arguments and results, not as fields
+    fn r#type(&self, r#type: &CType, argument: bool) -> Result<String, String> {
+        let resolved = self.resolve(r#type);
+
+        match resolved.pointers {
+            0 => {
+                if let Some(result) = builtin(&resolved.name) {
+                    return Ok(result.to_string());
+                }
+                if let Some(result) = self.structs.get(&resolved.name) {
+                    return Ok(result.clone());
+                }
+                if self.enums.contains(&resolved.name) {
+                    return Ok("Int4".to_string());
+                }
+            }
+            1 if argument && resolved.name == "char" => return Ok("String".to_string()),
+            1 if argument => {
+                if let Some(result) = self.structs.get(&resolved.name) {
+                    return Ok(format!("&{result}"));
+                }
+            }
+            _ => {}
+        }
+
+        Err(format!("{type} is not supported"))
+    }
+
+    // typedefs are replaced by the type they stand for, pointers add up
+    fn resolve(&self, r#type: &CType) -> CType {
+        let mut result = r#type.clone();
+        while let Some(alias) = self.typedefs.get(&result.name) {
+            result = CType { name: alias.name.clone(), pointers: result.pointers + alias.pointers };
+        }
+        result
+    }
+
+    fn is_void(&self, r#type: &CType) -> bool {
+        let resolved = self.resolve(r#type);
+        resolved.name == "void" && resolved.pointers == 0
+    }
+
+    fn source(&self, package: &str) -> String {
+        let mut members = vec![];
+        members.extend(self.types.iter().cloned());
+        members.extend(self.constants.iter().cloned());
+        members.extend(self.functions.iter().cloned());
+
+        let mut result = format!("export package {package} {{\n");
+        for member in members {
+            result.push_str(&format!("    {member}\n"));
+        }
+        result.push_str("}\n");
+        result
+    }
+}
+
+// | C                                       | Elodie  |
+// |-----------------------------------------|---------|
+// | `bool`, `_Bool`                         | Bool    |
+// | `float`                                 | Float4  |
+// | `double`                                | Float8  |
+// | `char`, `signed char`, `int8_t`         | Int1    |
+// | `short`, `int16_t`                      | Int2    |
+// | `int`, `int32_t`                        | Int4    |
+// | `long`, `long long`, `int64_t`, ...     | Int8    |
+// | `__int128`, `__int128_t`                | Int16   |
+// | `unsigned char`, `uint8_t`              | Uint1   |
+// | `unsigned short`, `uint16_t`            | Uint2   |
+// | `unsigned int`, `uint32_t`              | Uint4   |
+// | `unsigned long`, `size_t`, `uint64_t`   | Uint8   |
+// | `unsigned __int128`, `__uint128_t`      | Uint16  |
+fn builtin(name: &str) -> Option<&'static str> {
+    let result = match name {
+        "bool" | "_Bool" => "Bool",
+        "float" => "Float4",
+        "double" => "Float8",
+        "char" | "signed char" | "int8_t" => "Int1",
+        "short" | "short int" | "signed short" | "signed short int" | "int16_t" => "Int2",
+        "int" | "signed" | "signed int" | "int32_t" => "Int4",
+        "long" | "long int" | "signed long" | "signed long int" | "long long" | "long long int" | "signed long long"
+        | "signed long long int" | "int64_t" | "ssize_t" | "ptrdiff_t" | "intptr_t" => "Int8",
+        "__int128" | "__int128_t" => "Int16",
+        "unsigned char" | "uint8_t" => "Uint1",
+        "unsigned short" | "unsigned short int" | "uint16_t" => "Uint2",
+        "unsigned" | "unsigned int" | "uint32_t" => "Uint4",
+        "unsigned long" | "unsigned long int" | "unsigned long long" | "unsigned long long int" | "uint64_t" | "size_t"
+        | "uintptr_t" => "Uint8",
+        "unsigned __int128" | "__uint128_t" => "Uint16",
+        _ => return None
+    };
+    Some(result)
+}
+
+// point becomes Point, sdl_rect becomes Sdl_Rect and the _t suffix of point_t is dropped
+fn pascal_snake_case(name: &str) -> String {
+    name.trim_end_matches("_t")
+        .split('_')
+        .map(|segment| {
+            let mut chars = segment.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bind::bind;
+    use crate::common::Context;
+    use crate::ir::ir_from_str;
+
+    #[test]
+    fn bind_functions_and_types() {
+        let result = bind("geometry", r#"
+            #include <stdint.h>
+            typedef int32_t coordinate;
+            typedef struct point { coordinate x; coordinate y; } Point;
+            enum shape { SHAPE_CIRCLE, SHAPE_SQUARE = 4 };
+
+            int32_t point_sum(Point p);
+            void point_scale(struct point *p, double factor);
+            size_t name_length(const char *name);
+            enum shape shape_of(void);
+        "#);
+
+        assert_eq!(result.skipped, vec![]);
+        assert_eq!(result.source, r#"export package geometry {
+    export external type Point(x: Int4, y: Int4)
+    export function shape_circle() -> Int4 {
+        return 0
+    }
+    export function shape_square() -> Int4 {
+        return 4
+    }
+    export external function point_sum(p: Point) -> Int4
+    export external function point_scale(p: &Point, factor: Float8)
+    export external function name_length(name: String) -> Uint8
+    export external function shape_of() -> Int4
+}
+"#);
+
+        let mut ctx = Context::testing();
+        ir_from_str(&mut ctx, result.source.as_str()).unwrap();
+    }
+
+    #[test]
+    fn unmappable_declarations_are_skipped() {
+        let result = bind("io", r#"
+            int printf(const char *format, ...);
+            void *malloc(size_t size);
+            int SDL_Init(unsigned flags);
+            int puts(const char *s);
+        "#);
+
+        assert_eq!(result.source, "export package io {\n    export external function puts(s: String) -> Int4\n}\n");
+
+        let skipped: Vec<&str> = result.skipped.iter().map(|skipped| skipped.reason.as_str()).collect();
+        assert_eq!(skipped, vec![
+            "variadic functions are not supported",
+            "void * is not supported",
+            "SDL_Init is no valid function name",
+        ]);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::build::c::{BreakStatement, IfStatement, LoopStatement, StatementResult};
+use crate::build::c::{BreakStatement, ContinueStatement, ForStatement, GotoStatement, IfStatement, LabelStatement, LoopStatement, StatementResult};
 use crate::build::c::emitter::Emitter;
 
 impl Emitter {
@@ -6,6 +6,22 @@ impl Emitter {
         self.line("break;")
     }
 
+    pub(crate) fn r#continue(&mut self, _statement: &ContinueStatement) {
+        self.line("continue;")
+    }
+
+    pub(crate) fn goto(&mut self, statement: &GotoStatement) {
+        self.token("goto");
+        self.str(statement.label.as_str());
+        self.line(";")
+    }
+
+    // a label needs a statement to label, at the end of a block there is none
+    pub(crate) fn label(&mut self, statement: &LabelStatement) {
+        self.str(statement.label.as_str());
+        self.line(": ;")
+    }
+
     pub(crate) fn r#loop(&mut self, statement: &LoopStatement) {
         if let Some(result) = &statement.result {
             match result {
@@ -25,6 +41,23 @@ impl Emitter {
         self.block_statement(&statement.block)
     }
 
+    pub(crate) fn r#for(&mut self, statement: &ForStatement) {
+        self.token("for");
+        self.str("(");
+        self.token(statement.r#type.as_str());
+        self.token(statement.counter.as_str());
+        self.token("=");
+        self.expression(&statement.start);
+        self.token(";");
+        self.expression(&statement.condition);
+        self.token(";");
+        self.token(statement.counter.as_str());
+        self.token("+=");
+        self.expression(&statement.step);
+        self.token(")");
+        self.block_statement(&statement.block)
+    }
+
     pub(crate) fn r#if(&mut self, statement: &IfStatement) {
         self.token("if");
         self.str("(");
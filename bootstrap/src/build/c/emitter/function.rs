@@ -53,6 +53,9 @@ impl Emitter {
             self.str(")");
         }
 
+        if let Some(symbol) = &node.symbol {
+            self.str(&format!(" __asm__(\"{symbol}\")"));
+        }
         self.line(";");
     }
 
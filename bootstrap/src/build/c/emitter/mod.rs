@@ -37,7 +37,7 @@ impl Emitter {
                 Node::DeclareStruct(node) => self.declare_struct(node),
                 Node::DefineFunction(node) => self.define_function(node),
                 Node::DefineStruct(node) => self.define_struct(node),
-                Node::DefineGlobalVariable(_) => unimplemented!(),
+                Node::DefineGlobalVariable(node) => self.define_global_variable(node),
             }
         }
         self.output
@@ -10,9 +10,13 @@ impl Emitter {
             Statement::Expression(statement) => self.expression_statement(statement),
             Statement::CallFunction(statement) => self.call_function(statement),
             Statement::Code(statement) => self.code_statement(statement),
+            Statement::Continue(statement) => self.r#continue(statement),
             Statement::DeclareArray(statement) => self.declare_array(statement),
             Statement::DeclareVariable(statement) => self.declare_variable(statement),
+            Statement::For(statement) => self.r#for(statement),
+            Statement::Goto(statement) => self.goto(statement),
             Statement::If(statement) => self.r#if(statement),
+            Statement::Label(statement) => self.label(statement),
             Statement::Loop(statement) => self.r#loop(statement),
             Statement::ReturnFromFunction(statement) => self.return_from_function(statement),
         }
@@ -1,4 +1,4 @@
-use crate::build::c::{AccessVariableOfStructExpression, DeclareArrayStatement, DeclareVariableStatement, VariableExpression};
+use crate::build::c::{AccessVariableOfStructExpression, DeclareArrayStatement, DeclareVariableStatement, DefineGlobalVariableNode, VariableExpression};
 use crate::build::c::emitter::Emitter;
 
 impl Emitter {
@@ -25,6 +25,14 @@ impl Emitter {
         self.line(";");
     }
 
+    pub(crate) fn define_global_variable(&mut self, node: &DefineGlobalVariableNode) {
+        self.token(node.r#type.as_str());
+        self.token(node.identifier.as_str());
+        self.token("=");
+        self.expression(&node.expression);
+        self.line(";");
+    }
+
     pub(crate) fn variable(&mut self, expression: &VariableExpression) {
         if let Some(r#type) = &expression.cast {
             self.str("(");
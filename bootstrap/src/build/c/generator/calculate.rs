@@ -1,181 +1,84 @@
 use crate::build::c;
-use crate::build::c::{CallFunctionExpression, CodeExpression, Expression, VariableExpression};
+use crate::build::c::{CallFunctionExpression, CodeExpression, Expression, ExpressionStatement, Statement, StatementResult, VariableExpression};
 use crate::build::c::generator::Generator;
-use crate::common::node::Node::AccessVariable;
+use crate::build::c::generator::r#type::box_function;
+use crate::build::c::generator::scope::Storage;
+use crate::common::node::Node::{AccessVariable, AccessVariableOfObject, CallFunction, CallFunctionOfObject, CallFunctionOfPackage};
 use crate::common::TypeId;
-use crate::ir::IrCalculateNode;
+use crate::ir::{IrCalculateNode, IrTreeNode};
+
+// the runtime function which calculates two values of the given type
+fn calculate_function(type_id: TypeId) -> &'static str {
+    match type_id {
+        TypeId::NUMBER => "val_num_calc",
+        TypeId::FLOAT4 => "val_f4_calc",
+        TypeId::FLOAT8 => "val_f8_calc",
+        TypeId::INT1 => "val_i1_calc",
+        TypeId::INT2 => "val_i2_calc",
+        TypeId::INT4 => "val_i4_calc",
+        TypeId::INT8 => "val_i8_calc",
+        TypeId::INT16 => "val_i16_calc",
+        TypeId::UINT1 => "val_u1_calc",
+        TypeId::UINT2 => "val_u2_calc",
+        TypeId::UINT4 => "val_u4_calc",
+        TypeId::UINT8 => "val_u8_calc",
+        TypeId::UINT16 => "val_u16_calc",
+        _ => unimplemented!()
+    }
+}
 
 impl Generator {
     pub(crate) fn calculate(
         &mut self,
         node: &IrCalculateNode,
     ) -> c::generator::Result<Expression> {
-        match (node.left.as_ref().node(), node.right.as_ref().node()) {
-            (AccessVariable(left_variable), AccessVariable(right_variable)) => {
-                let left_variable = self.symbol_table.variable(left_variable.variable);
-                let right_variable = self.symbol_table.variable(right_variable.variable);
+        if self.literal_value(&node.left)?.is_some() && self.literal_value(&node.right)?.is_some() {
+            unimplemented!()
+        }
 
-                if left_variable.type_id == Some(TypeId::NUMBER) {
-                    Ok(Expression::CallFunction(
-                        CallFunctionExpression {
-                            function: "val_num_calc".to_string(),
-                            arguments: Box::new([
-                                c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                Expression::Variable(VariableExpression { variable: left_variable.to_string(&self.string_table), cast: None }),
-                                Expression::calculate_operator(&node.operator),
-                                Expression::Variable(VariableExpression { variable: right_variable.to_string(&self.string_table), cast: None }),
-                            ]),
-                        }
-                    ))
-                } else if left_variable.type_id == Some(TypeId::FLOAT4) {
-                    Ok(Expression::CallFunction(
-                        CallFunctionExpression {
-                            function: "val_f4_calc".to_string(),
-                            arguments: Box::new([
-                                c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                Expression::Variable(VariableExpression { variable: left_variable.to_string(&self.string_table), cast: None }),
-                                Expression::calculate_operator(&node.operator),
-                                Expression::Variable(VariableExpression { variable: right_variable.to_string(&self.string_table), cast: None }),
-                            ]),
-                        }
-                    ))
-                } else if left_variable.type_id == Some(TypeId::FLOAT8) {
-                    Ok(Expression::CallFunction(
-                        CallFunctionExpression {
-                            function: "val_f8_calc".to_string(),
-                            arguments: Box::new([
-                                c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                Expression::Variable(VariableExpression { variable: left_variable.to_string(&self.string_table), cast: None }),
-                                Expression::calculate_operator(&node.operator),
-                                Expression::Variable(VariableExpression { variable: right_variable.to_string(&self.string_table), cast: None }),
-                            ]),
-                        }
-                    ))
-                } else if left_variable.type_id == Some(TypeId::INT1) {
-                    Ok(Expression::CallFunction(
-                        CallFunctionExpression {
-                            function: "val_i1_calc".to_string(),
-                            arguments: Box::new([
-                                c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                Expression::Variable(VariableExpression { variable: left_variable.to_string(&self.string_table), cast: None }),
-                                Expression::calculate_operator(&node.operator),
-                                Expression::Variable(VariableExpression { variable: right_variable.to_string(&self.string_table), cast: None }),
-                            ]),
-                        }
-                    ))
-                } else if left_variable.type_id == Some(TypeId::INT2) {
-                    Ok(Expression::CallFunction(
-                        CallFunctionExpression {
-                            function: "val_i2_calc".to_string(),
-                            arguments: Box::new([
-                                c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                Expression::Variable(VariableExpression { variable: left_variable.to_string(&self.string_table), cast: None }),
-                                Expression::calculate_operator(&node.operator),
-                                Expression::Variable(VariableExpression { variable: right_variable.to_string(&self.string_table), cast: None }),
-                            ]),
-                        }
-                    ))
-                } else if left_variable.type_id == Some(TypeId::INT4) {
-                    Ok(Expression::CallFunction(
-                        CallFunctionExpression {
-                            function: "val_i4_calc".to_string(),
-                            arguments: Box::new([
-                                c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                Expression::Variable(VariableExpression { variable: left_variable.to_string(&self.string_table), cast: None }),
-                                Expression::calculate_operator(&node.operator),
-                                Expression::Variable(VariableExpression { variable: right_variable.to_string(&self.string_table), cast: None }),
-                            ]),
-                        }
-                    ))
-                } else if left_variable.type_id == Some(TypeId::INT8) {
-                    Ok(Expression::CallFunction(
-                        CallFunctionExpression {
-                            function: "val_i8_calc".to_string(),
-                            arguments: Box::new([
-                                c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                Expression::Variable(VariableExpression { variable: left_variable.to_string(&self.string_table), cast: None }),
-                                Expression::calculate_operator(&node.operator),
-                                Expression::Variable(VariableExpression { variable: right_variable.to_string(&self.string_table), cast: None }),
-                            ]),
-                        }
-                    ))
-                } else if left_variable.type_id == Some(TypeId::INT16) {
-                    Ok(Expression::CallFunction(
-                        CallFunctionExpression {
-                            function: "val_i16_calc".to_string(),
-                            arguments: Box::new([
-                                c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                Expression::Variable(VariableExpression { variable: left_variable.to_string(&self.string_table), cast: None }),
-                                Expression::calculate_operator(&node.operator),
-                                Expression::Variable(VariableExpression { variable: right_variable.to_string(&self.string_table), cast: None }),
-                            ]),
-                        }
-                    ))
-                } else if left_variable.type_id == Some(TypeId::UINT1) {
-                    Ok(Expression::CallFunction(
-                        CallFunctionExpression {
-                            function: "val_u1_calc".to_string(),
-                            arguments: Box::new([
-                                c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                Expression::Variable(VariableExpression { variable: left_variable.to_string(&self.string_table), cast: None }),
-                                Expression::calculate_operator(&node.operator),
-                                Expression::Variable(VariableExpression { variable: right_variable.to_string(&self.string_table), cast: None }),
-                            ]),
-                        }
-                    ))
-                } else if left_variable.type_id == Some(TypeId::UINT2) {
-                    Ok(Expression::CallFunction(
-                        CallFunctionExpression {
-                            function: "val_u2_calc".to_string(),
-                            arguments: Box::new([
-                                c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                Expression::Variable(VariableExpression { variable: left_variable.to_string(&self.string_table), cast: None }),
-                                Expression::calculate_operator(&node.operator),
-                                Expression::Variable(VariableExpression { variable: right_variable.to_string(&self.string_table), cast: None }),
-                            ]),
-                        }
-                    ))
-                } else if left_variable.type_id == Some(TypeId::UINT4) {
-                    Ok(Expression::CallFunction(
-                        CallFunctionExpression {
-                            function: "val_u4_calc".to_string(),
-                            arguments: Box::new([
-                                c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                Expression::Variable(VariableExpression { variable: left_variable.to_string(&self.string_table), cast: None }),
-                                Expression::calculate_operator(&node.operator),
-                                Expression::Variable(VariableExpression { variable: right_variable.to_string(&self.string_table), cast: None }),
-                            ]),
-                        }
-                    ))
-                } else if left_variable.type_id == Some(TypeId::UINT8) {
-                    Ok(Expression::CallFunction(
-                        CallFunctionExpression {
-                            function: "val_u8_calc".to_string(),
-                            arguments: Box::new([
-                                c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                Expression::Variable(VariableExpression { variable: left_variable.to_string(&self.string_table), cast: None }),
-                                Expression::calculate_operator(&node.operator),
-                                Expression::Variable(VariableExpression { variable: right_variable.to_string(&self.string_table), cast: None }),
-                            ]),
-                        }
-                    ))
-                } else if left_variable.type_id == Some(TypeId::UINT16) {
-                    Ok(Expression::CallFunction(
-                        CallFunctionExpression {
-                            function: "val_u16_calc".to_string(),
-                            arguments: Box::new([
-                                c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                Expression::Variable(VariableExpression { variable: left_variable.to_string(&self.string_table), cast: None }),
-                                Expression::calculate_operator(&node.operator),
-                                Expression::Variable(VariableExpression { variable: right_variable.to_string(&self.string_table), cast: None }),
-                            ]),
-                        }
-                    ))
-                } else {
-                    unimplemented!()
-                }
+        let function = calculate_function(node.left.type_id).to_string();
+        let left = self.operand(node.left.as_ref(), node.left.type_id)?;
+        let right = self.operand(node.right.as_ref(), node.left.type_id)?;
+
+        Ok(Expression::CallFunction(
+            CallFunctionExpression {
+                function,
+                arguments: Box::new([
+                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                    left,
+                    Expression::calculate_operator(&node.operator),
+                    right,
+                ]),
             }
+        ))
+    }
+
+    // variables, fields of objects and results of calls are borrowed by the operator,
+    // a literal gets boxed into a temp which is released with the frame, an erased val gets cast to the type of the operator
+    pub(crate) fn operand(&mut self, node: &IrTreeNode, type_id: TypeId) -> c::generator::Result<Expression> {
+        if let Some(literal) = self.literal_value(node)? {
+            let temp = self.scope.push_temp(Storage::Memory);
+            let r#type = self.value_type(node.type_id);
+            self.statements().push(Statement::Expression(ExpressionStatement {
+                expression: Expression::CallFunction(CallFunctionExpression {
+                    function: box_function(node.type_id),
+                    arguments: Box::new([Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }), literal]),
+                }),
+                result: Some(StatementResult::Declare { variable: temp.to_string(), r#type }),
+            }));
+            return Ok(Expression::Variable(VariableExpression { variable: temp.to_string(), cast: None }));
+        }
+
+        let expression = match node.node() {
+            AccessVariable(_) | AccessVariableOfObject(_) | CallFunction(_) | CallFunctionOfObject(_) | CallFunctionOfPackage(_) => self.expression(node)?,
             _ => unimplemented!()
+        };
+
+        match expression {
+            Expression::Variable(VariableExpression { variable, .. }) if node.type_id == TypeId::ANY && type_id != TypeId::ANY => {
+                Ok(Expression::Variable(VariableExpression { variable, cast: Some(self.value_type(type_id)) }))
+            }
+            expression => Ok(expression)
         }
     }
-}
\ No newline at end of file
+}
@@ -1,31 +1,193 @@
 use std::rc::Rc;
 
 use crate::build::c;
-use crate::build::c::{CallFunctionStatement, Expression};
+use crate::build::c::{CallFunctionExpression, CodeExpression, Expression, ExpressionStatement, Statement, StatementResult, VariableExpression};
 use crate::build::c::generator::Generator;
-use crate::build::c::Statement::CallFunction;
-use crate::ir::{IrCallFunctionOfPackageNode, IrTreeNode};
+use crate::build::c::generator::r#type::{box_function, c_type};
+use crate::build::c::generator::scope::Storage;
+use crate::build::c::generator::tuple::tuple_type;
+use crate::common::{Inferred, Symbol, SymbolId, TypeId};
+use crate::ir::{IrCallFunctionNode, IrCallFunctionOfPackageNode, IrCallFunctionWithLambdaNode, IrTreeNode};
+
+// the trailing lambda is the last argument
+fn call_with_lambda_arguments(node: &IrCallFunctionWithLambdaNode) -> Vec<Rc<IrTreeNode>> {
+    let mut result = node.arguments.to_vec();
+    result.push(node.lambda.clone());
+    result
+}
 
 impl Generator {
+    pub(crate) fn call_function(&mut self, node: &IrCallFunctionNode) -> c::generator::Result<()> {
+        self.call_statement(node.function, &node.arguments)
+    }
+
     pub(crate) fn call_function_of_package(&mut self, node: &IrCallFunctionOfPackageNode) -> c::generator::Result<()> {
-        let arguments = self.generate_call_arguments(&node.arguments)?;
+        self.call_statement(node.function, &node.arguments)
+    }
 
-        self.statements().push(CallFunction(CallFunctionStatement {
-            function: "rt_io_println".to_string(),
-            arguments,
+    pub(crate) fn call_function_with_lambda(&mut self, node: &IrCallFunctionWithLambdaNode) -> c::generator::Result<()> {
+        self.call_statement(node.function, &call_with_lambda_arguments(node))
+    }
+
+    pub(crate) fn call_function_with_lambda_expression(&mut self, node: &IrCallFunctionWithLambdaNode, inferred: TypeId) -> c::generator::Result<Expression> {
+        self.call_expression(node.function, &call_with_lambda_arguments(node), inferred)
+    }
+
+    // a returned value is owned by the caller, so it ends up in a temp which gets released with the frame,
+    // generic functions return erased vals which get cast to the inferred type of the call
+    pub(crate) fn call_expression(&mut self, function: SymbolId, arguments: &[Rc<IrTreeNode>], inferred: TypeId) -> c::generator::Result<Expression> {
+        let expression = self.call(function, arguments)?;
+        Ok(self.result_expression(function, expression, inferred))
+    }
+
+    pub(crate) fn result_expression(&mut self, function: SymbolId, expression: Expression, inferred: TypeId) -> Expression {
+        let expression = self.result_unwrapped(function, expression, inferred);
+
+        let type_id = self.function_return(function);
+        let tuple = self.function_return_tuple(function);
+        let temp = if let Some(tuple) = tuple {
+            self.scope.push_temp(Storage::Tuple(tuple))
+        } else if self.type_table.is_external(type_id) {
+            self.scope.push_temp(Storage::Stack)
+        } else if self.function_return_optional(function) {
+            self.scope.push_temp(Storage::Optional)
+        } else {
+            self.scope.push_temp(Storage::Memory)
+        };
+        let r#type = tuple.map(tuple_type).unwrap_or_else(|| self.value_type(type_id));
+
+        self.statements().push(Statement::Expression(ExpressionStatement {
+            expression,
+            result: Some(StatementResult::Declare {
+                variable: temp.to_string(),
+                r#type,
+            }),
+        }));
+
+        let cast = if type_id == TypeId::ANY && inferred != TypeId::ANY {
+            Some(self.value_type(inferred))
+        } else {
+            None
+        };
+
+        Expression::Variable(VariableExpression { variable: temp.to_string(), cast })
+    }
+
+    // the value of the call is owned by whatever it gets assigned to, an erased val gets cast to the inferred type of the call
+    pub(crate) fn call_value(&mut self, function: SymbolId, arguments: &[Rc<IrTreeNode>], inferred: TypeId) -> c::generator::Result<Expression> {
+        let expression = self.call(function, arguments)?;
+        Ok(self.result_value(function, expression, inferred))
+    }
+
+    pub(crate) fn result_value(&mut self, function: SymbolId, expression: Expression, inferred: TypeId) -> Expression {
+        let expression = self.result_unwrapped(function, expression, inferred);
+        if self.function_return(function) != TypeId::ANY || inferred == TypeId::ANY {
+            return expression;
+        }
+
+        let temp = self.scope.push_temp(Storage::Stack);
+        self.statements().push(Statement::Expression(ExpressionStatement {
+            expression,
+            result: Some(StatementResult::Declare {
+                variable: temp.to_string(),
+                r#type: c_type(TypeId::ANY),
+            }),
+        }));
+
+        Expression::Variable(VariableExpression { variable: temp.to_string(), cast: Some(self.value_type(inferred)) })
+    }
+
+    fn call_statement(&mut self, function: SymbolId, arguments: &[Rc<IrTreeNode>]) -> c::generator::Result<()> {
+        let expression = self.call(function, arguments)?;
+        self.result_statement(function, expression);
+        Ok(())
+    }
+
+    // a returned value still has to be released
+    pub(crate) fn result_statement(&mut self, function: SymbolId, expression: Expression) {
+        if self.function_return(function) != TypeId::UNIT {
+            self.result_expression(function, expression, self.function_return(function));
+            return;
+        }
+
+        self.statements().push(Statement::Expression(ExpressionStatement {
+            expression,
             result: None,
         }));
+    }
 
-        self.include_local("rt/io.h");
+    // the call itself, arguments are borrowed by the called function
+    pub(crate) fn call(&mut self, function: SymbolId, arguments: &[Rc<IrTreeNode>]) -> c::generator::Result<Expression> {
+        if self.function_is_foreign(function) {
+            return self.ffi_call(function, arguments);
+        }
 
-        return Ok(());
+        let arguments = self.generate_boxed_call_arguments(function, arguments)?;
+
+        // a variable holds a closure, not a function
+        if let Symbol::Variable(_) = &self.symbol_table[function] {
+            return Ok(self.closure_call(function, arguments));
+        }
+
+        if let Some(header) = self.function_header(function) {
+            self.include_local(header.as_str());
+        }
+
+        Ok(Expression::CallFunction(CallFunctionExpression {
+            function: self.function_identifier(function),
+            arguments,
+        }))
     }
 
-    fn generate_call_arguments(&mut self, args: &[Rc<IrTreeNode>]) -> c::generator::Result<Box<[Expression]>> {
+    // literals are raw c values, but elodie functions expect vals of their argument types,
+    // arguments of type parameters get passed as erased val
+    pub(crate) fn generate_boxed_call_arguments(&mut self, function: SymbolId, args: &[Rc<IrTreeNode>]) -> c::generator::Result<Box<[Expression]>> {
+        let Inferred::Function(arguments, _) = self.symbol_table.signature(function).clone() else {
+            unreachable!()
+        };
+
         let mut result = vec![];
-        for arg in args {
-            result.push(self.expression(arg)?)
+        for (idx, arg) in args.iter().enumerate() {
+            let erased = arguments.get(idx).and_then(|a| a.type_id()) == Some(TypeId::ANY);
+            let expression = self.expression(arg)?;
+
+            // values of known types passed as trait get wrapped together with the vtable of their type
+            if let Some(r#trait) = arguments.get(idx).and_then(|a| self.parameter_trait(function, a)) {
+                if self.type_table.implements(arg.type_id, r#trait) && arg.type_id != r#trait {
+                    let cast = if erased { Some(c_type(TypeId::ANY)) } else { None };
+                    result.push(self.dyn_new(expression, arg.type_id, r#trait, cast));
+                    continue;
+                }
+            }
+
+            if self.literal_value(arg)?.is_none() {
+                result.push(match expression {
+                    Expression::Variable(VariableExpression { variable, .. }) if erased => {
+                        Expression::Variable(VariableExpression { variable, cast: Some(c_type(TypeId::ANY)) })
+                    }
+                    expression => expression
+                });
+                continue;
+            }
+
+            let type_id = arguments.get(idx).and_then(|a| a.type_id()).filter(|_| !erased).unwrap_or(arg.type_id);
+            let temp = self.scope.push_temp(Storage::Memory);
+            self.statements().push(Statement::Expression(ExpressionStatement {
+                expression: Expression::CallFunction(CallFunctionExpression {
+                    function: box_function(type_id),
+                    arguments: Box::new([
+                        Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                        expression
+                    ]),
+                }),
+                result: Some(StatementResult::Declare {
+                    variable: temp.to_string(),
+                    r#type: c_type(type_id),
+                }),
+            }));
+            let cast = if erased { Some(c_type(TypeId::ANY)) } else { None };
+            result.push(Expression::Variable(VariableExpression { variable: temp.to_string(), cast }));
         }
         Ok(result.into_boxed_slice())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,138 @@
+use crate::build::c;
+use crate::build::c::{CallFunctionExpression, CodeExpression, Expression, ExpressionStatement, ReturnFromFunctionStatement, Statement, StatementResult, VariableExpression};
+use crate::build::c::generator::Generator;
+use crate::build::c::generator::r#type::c_type;
+use crate::build::c::generator::scope::{Argument, Storage};
+use crate::common::{Inferred, Symbol, SymbolId, TypeId};
+use crate::common::node::Node::{AccessVariable, DeclareLambda};
+use crate::ir::IrTreeNode;
+
+impl Generator {
+    // a new closure, owned by the frame it gets created in
+    pub(crate) fn closure_expression(&mut self, node: &IrTreeNode) -> c::generator::Result<Expression> {
+        let temp = self.scope.push_temp(Storage::Memory);
+        self.closure_new(node, temp.to_string())?;
+        Ok(Expression::Variable(VariableExpression { variable: temp.to_string(), cast: None }))
+    }
+
+    // the closure of a lambda retains every value it captures, a function passed by name captures nothing
+    pub(crate) fn closure_new(&mut self, node: &IrTreeNode, variable: String) -> c::generator::Result<()> {
+        let (function, captures) = match node.node() {
+            DeclareLambda(lambda) => {
+                self.define_function(lambda.function, Some(&lambda.captures), &lambda.arguments, &lambda.nodes)?;
+                (self.function_identifier(lambda.function), lambda.captures.to_vec())
+            }
+            AccessVariable(access) => (self.closure_adapter(access.variable), vec![]),
+            _ => unimplemented!("{node:#?} is no function")
+        };
+
+        self.statements().push(Statement::Expression(ExpressionStatement {
+            expression: Expression::CallFunction(CallFunctionExpression {
+                function: "val_clo_new".to_string(),
+                arguments: Box::new([
+                    Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                    Expression::Code(CodeExpression { code: format!("(void *) {function}") }),
+                    Expression::Code(CodeExpression { code: captures.len().to_string() }),
+                ]),
+            }),
+            result: Some(StatementResult::Declare { variable: variable.clone(), r#type: c_type(TypeId::FUNCTION) }),
+        }));
+
+        for (idx, capture) in captures.iter().enumerate() {
+            let captured = self.symbol_table.variable(*capture).to_string(&self.string_table);
+            self.statements().push(Statement::Expression(ExpressionStatement {
+                expression: Expression::CallFunction(CallFunctionExpression {
+                    function: "val_clo_capture".to_string(),
+                    arguments: Box::new([
+                        Expression::Variable(VariableExpression { variable: variable.clone(), cast: None }),
+                        Expression::Code(CodeExpression { code: idx.to_string() }),
+                        Expression::Variable(VariableExpression { variable: captured, cast: Some("struct val *".to_string()) }),
+                    ]),
+                }),
+                result: None,
+            }));
+        }
+
+        Ok(())
+    }
+
+    // the captured values are borrowed from the closure, which outlives the call
+    pub(crate) fn closure_environment(&mut self, captures: &[SymbolId]) {
+        for (idx, capture) in captures.iter().enumerate() {
+            let symbol = self.symbol_table.variable(*capture);
+            let type_id = symbol.type_id.unwrap_or(TypeId::UNKNOWN);
+            if self.type_table.is_external(type_id) {
+                unimplemented!("values of external types can not be captured yet")
+            }
+
+            let variable = symbol.to_string(&self.string_table);
+            let r#type = self.value_type(type_id);
+            self.scope.push_local_variable(variable.clone(), Storage::Stack);
+            self.statements().push(Statement::Expression(ExpressionStatement {
+                expression: Expression::Code(CodeExpression { code: format!("({type}) val_clo_env_at(closure, {idx})") }),
+                result: Some(StatementResult::Declare { variable, r#type }),
+            }));
+        }
+    }
+
+    // the function of the closure gets the closure itself as first argument
+    pub(crate) fn closure_call(&mut self, variable: SymbolId, arguments: Box<[Expression]>) -> Expression {
+        let Inferred::Function(parameters, _) = self.symbol_table.signature(variable).clone() else {
+            unreachable!()
+        };
+
+        let mut types = vec![c_type(TypeId::FUNCTION)];
+        types.extend(parameters.iter().map(|parameter| self.value_type(parameter.type_id().unwrap_or(TypeId::UNKNOWN))));
+        let result = self.value_type(self.function_return(variable));
+
+        let closure = self.symbol_table.variable(variable).to_string(&self.string_table);
+        let mut values = vec![Expression::Variable(VariableExpression { variable: closure.clone(), cast: None })];
+        values.extend(arguments);
+
+        Expression::CallFunction(CallFunctionExpression {
+            function: format!("(({result} (*)({})) {closure}->fn)", types.join(", ")),
+            arguments: values.into_boxed_slice(),
+        })
+    }
+
+    // a function passed by name gets called through an adapter, which ignores the closure
+    fn closure_adapter(&mut self, function: SymbolId) -> String {
+        let identifier = format!("{}_closure", self.function_identifier(function));
+        if self.function_declarations.iter().any(|declaration| declaration.identifier == identifier) {
+            return identifier;
+        }
+
+        let Symbol::Function(symbol) = &self.symbol_table[function] else {
+            unreachable!()
+        };
+        if symbol.external {
+            unimplemented!("external functions can not be passed as value yet")
+        }
+        let Inferred::Function(parameters, _) = symbol.inferred.clone() else {
+            unreachable!()
+        };
+
+        let mut arguments = vec![("closure".to_string(), c_type(TypeId::FUNCTION))];
+        for (idx, parameter) in parameters.iter().enumerate() {
+            arguments.push((Argument(idx as u16 + 1).to_string(), self.value_type(parameter.type_id().unwrap_or(TypeId::UNKNOWN))));
+        }
+
+        let call = Expression::CallFunction(CallFunctionExpression {
+            function: self.function_identifier(function),
+            arguments: arguments.iter()
+                .skip(1)
+                .map(|(argument, _)| Expression::Variable(VariableExpression { variable: argument.clone(), cast: None }))
+                .collect(),
+        });
+
+        let ty = self.value_type(self.function_return(function));
+        let statement = if self.function_return(function) == TypeId::UNIT {
+            Statement::Expression(ExpressionStatement { expression: call, result: None })
+        } else {
+            Statement::ReturnFromFunction(ReturnFromFunctionStatement { node: Some(call) })
+        };
+
+        self.function_define(identifier.clone(), arguments, ty, vec![statement]);
+        identifier
+    }
+}
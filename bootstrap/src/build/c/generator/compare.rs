@@ -1,21 +1,74 @@
-use Node::{AccessVariable, LiteralBoolean, LiteralNumber};
+use Node::{AccessVariable, LiteralBoolean, LiteralFloat4, LiteralFloat8, LiteralInt1, LiteralInt16, LiteralInt2, LiteralInt4, LiteralInt8, LiteralNone, LiteralNumber, LiteralUint1, LiteralUint16, LiteralUint2, LiteralUint4, LiteralUint8};
 
 use crate::build::c;
-use crate::build::c::{CallFunctionExpression, CodeExpression, CompareExpression, Expression, VariableExpression};
+use crate::build::c::{CallFunctionExpression, CodeExpression, CompareExpression, Expression};
 use crate::build::c::generator::Generator;
 use crate::common::node::Node;
 use crate::common::TypeId;
-use crate::ir::IrCompareNode;
+use crate::ir::{IrCompareNode, IrTreeNode};
+
+// the runtime function which compares two values of the given type
+fn compare_function(type_id: TypeId) -> Option<&'static str> {
+    Some(match type_id {
+        TypeId::NUMBER => "val_num_cmp",
+        TypeId::FLOAT4 => "val_f4_cmp",
+        TypeId::FLOAT8 => "val_f8_cmp",
+        TypeId::INT1 => "val_i1_cmp",
+        TypeId::INT2 => "val_i2_cmp",
+        TypeId::INT4 => "val_i4_cmp",
+        TypeId::INT8 => "val_i8_cmp",
+        TypeId::INT16 => "val_i16_cmp",
+        TypeId::UINT1 => "val_u1_cmp",
+        TypeId::UINT2 => "val_u2_cmp",
+        TypeId::UINT4 => "val_u4_cmp",
+        TypeId::UINT8 => "val_u8_cmp",
+        TypeId::UINT16 => "val_u16_cmp",
+        _ => return None
+    })
+}
+
+fn is_literal(node: &IrTreeNode) -> bool {
+    matches!(node.node(), LiteralBoolean(_) | LiteralNone(_) | LiteralNumber(_) | LiteralFloat4(_) | LiteralFloat8(_)
+        | LiteralInt1(_) | LiteralInt2(_) | LiteralInt4(_) | LiteralInt8(_) | LiteralInt16(_)
+        | LiteralUint1(_) | LiteralUint2(_) | LiteralUint4(_) | LiteralUint8(_) | LiteralUint16(_))
+}
 
 impl Generator {
     pub(crate) fn compare(
         &mut self,
         node: &IrCompareNode,
     ) -> c::generator::Result<Expression> {
+        if let Some(function) = self.compare_values(node) {
+            let left = self.operand(node.left.as_ref(), node.left.type_id)?;
+            let right = self.operand(node.right.as_ref(), node.left.type_id)?;
+
+            return Ok(Expression::CallFunction(
+                CallFunctionExpression {
+                    function: function.to_string(),
+                    arguments: Box::new([
+                        c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                        left,
+                        Expression::compare_operator(&node.operator),
+                        right,
+                    ]),
+                }
+            ));
+        }
+
         let left = self.expression(node.left.as_ref())?;
         let right = self.expression(node.right.as_ref())?;
 
         match (node.left.as_ref().node(), node.right.as_ref().node()) {
+            // none is a null value
+            (AccessVariable(_), LiteralNone(_)) | (LiteralNone(_), AccessVariable(_)) => {
+                Ok(Expression::Compare(
+                    CompareExpression {
+                        left: Box::new(left),
+                        operator: node.operator.clone(),
+                        right: Box::new(right),
+                    }
+                ))
+            }
             (LiteralBoolean(_), AccessVariable(variable)) => {
                 let variable = self.symbol_table.variable(variable.variable);
 
@@ -61,184 +114,15 @@ impl Generator {
                     }
                 ))
             }
-            (AccessVariable(left), AccessVariable(right)) => {
-                let left = self.symbol_table.variable(left.variable);
-                let right = self.symbol_table.variable(right.variable);
-
-                match (left.type_id, right.type_id) {
-                    (Some(TypeId::NUMBER), Some(TypeId::NUMBER)) => {
-                        Ok(Expression::CallFunction(
-                            CallFunctionExpression {
-                                function: "val_num_cmp".to_string(),
-                                arguments: Box::new([
-                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                    Expression::Variable(VariableExpression { variable: left.to_string(&self.string_table), cast: None }),
-                                    Expression::compare_operator(&node.operator),
-                                    Expression::Variable(VariableExpression { variable: right.to_string(&self.string_table), cast: None }),
-                                ]),
-                            }
-                        ))
-                    }
-                    (Some(TypeId::FLOAT4), Some(TypeId::FLOAT4)) => {
-                        Ok(Expression::CallFunction(
-                            CallFunctionExpression {
-                                function: "val_f4_cmp".to_string(),
-                                arguments: Box::new([
-                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                    Expression::Variable(VariableExpression { variable: left.to_string(&self.string_table), cast: None }),
-                                    Expression::compare_operator(&node.operator),
-                                    Expression::Variable(VariableExpression { variable: right.to_string(&self.string_table), cast: None }),
-                                ]),
-                            }
-                        ))
-                    }
-                    (Some(TypeId::FLOAT8), Some(TypeId::FLOAT8)) => {
-                        Ok(Expression::CallFunction(
-                            CallFunctionExpression {
-                                function: "val_f8_cmp".to_string(),
-                                arguments: Box::new([
-                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                    Expression::Variable(VariableExpression { variable: left.to_string(&self.string_table), cast: None }),
-                                    Expression::compare_operator(&node.operator),
-                                    Expression::Variable(VariableExpression { variable: right.to_string(&self.string_table), cast: None }),
-                                ]),
-                            }
-                        ))
-                    }
-                    (Some(TypeId::INT1), Some(TypeId::INT1)) => {
-                        Ok(Expression::CallFunction(
-                            CallFunctionExpression {
-                                function: "val_i1_cmp".to_string(),
-                                arguments: Box::new([
-                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                    Expression::Variable(VariableExpression { variable: left.to_string(&self.string_table), cast: None }),
-                                    Expression::compare_operator(&node.operator),
-                                    Expression::Variable(VariableExpression { variable: right.to_string(&self.string_table), cast: None }),
-                                ]),
-                            }
-                        ))
-                    }
-                    (Some(TypeId::INT2), Some(TypeId::INT2)) => {
-                        Ok(Expression::CallFunction(
-                            CallFunctionExpression {
-                                function: "val_i2_cmp".to_string(),
-                                arguments: Box::new([
-                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                    Expression::Variable(VariableExpression { variable: left.to_string(&self.string_table), cast: None }),
-                                    Expression::compare_operator(&node.operator),
-                                    Expression::Variable(VariableExpression { variable: right.to_string(&self.string_table), cast: None }),
-                                ]),
-                            }
-                        ))
-                    }
-                    (Some(TypeId::INT4), Some(TypeId::INT4)) => {
-                        Ok(Expression::CallFunction(
-                            CallFunctionExpression {
-                                function: "val_i4_cmp".to_string(),
-                                arguments: Box::new([
-                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                    Expression::Variable(VariableExpression { variable: left.to_string(&self.string_table), cast: None }),
-                                    Expression::compare_operator(&node.operator),
-                                    Expression::Variable(VariableExpression { variable: right.to_string(&self.string_table), cast: None }),
-                                ]),
-                            }
-                        ))
-                    }
-                    (Some(TypeId::INT8), Some(TypeId::INT8)) => {
-                        Ok(Expression::CallFunction(
-                            CallFunctionExpression {
-                                function: "val_i8_cmp".to_string(),
-                                arguments: Box::new([
-                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                    Expression::Variable(VariableExpression { variable: left.to_string(&self.string_table), cast: None }),
-                                    Expression::compare_operator(&node.operator),
-                                    Expression::Variable(VariableExpression { variable: right.to_string(&self.string_table), cast: None }),
-                                ]),
-                            }
-                        ))
-                    }
-                    (Some(TypeId::INT16), Some(TypeId::INT16)) => {
-                        Ok(Expression::CallFunction(
-                            CallFunctionExpression {
-                                function: "val_i16_cmp".to_string(),
-                                arguments: Box::new([
-                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                    Expression::Variable(VariableExpression { variable: left.to_string(&self.string_table), cast: None }),
-                                    Expression::compare_operator(&node.operator),
-                                    Expression::Variable(VariableExpression { variable: right.to_string(&self.string_table), cast: None }),
-                                ]),
-                            }
-                        ))
-                    }
-                    (Some(TypeId::UINT1), Some(TypeId::UINT1)) => {
-                        Ok(Expression::CallFunction(
-                            CallFunctionExpression {
-                                function: "val_u1_cmp".to_string(),
-                                arguments: Box::new([
-                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                    Expression::Variable(VariableExpression { variable: left.to_string(&self.string_table), cast: None }),
-                                    Expression::compare_operator(&node.operator),
-                                    Expression::Variable(VariableExpression { variable: right.to_string(&self.string_table), cast: None }),
-                                ]),
-                            }
-                        ))
-                    }
-                    (Some(TypeId::UINT2), Some(TypeId::UINT2)) => {
-                        Ok(Expression::CallFunction(
-                            CallFunctionExpression {
-                                function: "val_u2_cmp".to_string(),
-                                arguments: Box::new([
-                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                    Expression::Variable(VariableExpression { variable: left.to_string(&self.string_table), cast: None }),
-                                    Expression::compare_operator(&node.operator),
-                                    Expression::Variable(VariableExpression { variable: right.to_string(&self.string_table), cast: None }),
-                                ]),
-                            }
-                        ))
-                    }
-                    (Some(TypeId::UINT4), Some(TypeId::UINT4)) => {
-                        Ok(Expression::CallFunction(
-                            CallFunctionExpression {
-                                function: "val_u4_cmp".to_string(),
-                                arguments: Box::new([
-                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                    Expression::Variable(VariableExpression { variable: left.to_string(&self.string_table), cast: None }),
-                                    Expression::compare_operator(&node.operator),
-                                    Expression::Variable(VariableExpression { variable: right.to_string(&self.string_table), cast: None }),
-                                ]),
-                            }
-                        ))
-                    }
-                    (Some(TypeId::UINT8), Some(TypeId::UINT8)) => {
-                        Ok(Expression::CallFunction(
-                            CallFunctionExpression {
-                                function: "val_u8_cmp".to_string(),
-                                arguments: Box::new([
-                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                    Expression::Variable(VariableExpression { variable: left.to_string(&self.string_table), cast: None }),
-                                    Expression::compare_operator(&node.operator),
-                                    Expression::Variable(VariableExpression { variable: right.to_string(&self.string_table), cast: None }),
-                                ]),
-                            }
-                        ))
-                    }
-                    (Some(TypeId::UINT16), Some(TypeId::UINT16)) => {
-                        Ok(Expression::CallFunction(
-                            CallFunctionExpression {
-                                function: "val_u16_cmp".to_string(),
-                                arguments: Box::new([
-                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
-                                    Expression::Variable(VariableExpression { variable: left.to_string(&self.string_table), cast: None }),
-                                    Expression::compare_operator(&node.operator),
-                                    Expression::Variable(VariableExpression { variable: right.to_string(&self.string_table), cast: None }),
-                                ]),
-                            }
-                        ))
-                    }
-                    _ => unimplemented!()
-                }
-            }
             _ => unimplemented!()
         }
     }
+
+    // two numbers of the same type get compared by the runtime, unless both are literals or one is none
+    fn compare_values(&self, node: &IrCompareNode) -> Option<&'static str> {
+        if is_literal(&node.left) && is_literal(&node.right) || node.left.type_id != node.right.type_id {
+            return None;
+        }
+        compare_function(node.left.type_id)
+    }
 }
\ No newline at end of file
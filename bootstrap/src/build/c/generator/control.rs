@@ -1,104 +1,361 @@
+use bigdecimal::ToPrimitive;
+
 use crate::build::c;
-use crate::build::c::{BlockStatement, BreakStatement, ExpressionStatement, IfStatement, LoopStatement, Statement, StatementResult};
+use crate::build::c::{BlockStatement, BreakStatement, CallFunctionExpression, CodeExpression, ContinueStatement, DeclareVariableStatement, Expression, ExpressionStatement, ForStatement, GotoStatement, IfStatement, LabelStatement, LoopStatement, Statement, StatementResult, VariableExpression};
 use crate::build::c::generator::Generator;
+use crate::build::c::generator::r#type::{box_function, c_type};
+use crate::build::c::generator::scope::Storage;
 use crate::build::c::Statement::If;
+use crate::common::{StringTableId, TypeId};
 use crate::common::node::Node;
-use crate::ir::{IrIfNode, IrLoopNode};
+use crate::ir::{IrBlockNode, IrBreakLoopNode, IrContinueLoopNode, IrDeclareVariableNode, IrDeferNode, IrIfNode, IrIteratorLoopNode, IrLoopNode, IrRangeLoopNode, IrTreeNode};
 
 impl Generator {
     pub(crate) fn r#loop(&mut self, node: &IrLoopNode, sr: Option<StatementResult>) -> c::generator::Result<()> {
-        // let temp = self.scope.push_temp(Storage::Stack);
+        let label = self.loop_label(node.label);
+        self.scope.enter_loop(sr, label);
+        for node in &node.block.nodes {
+            self.nodes(node.as_ref())?
+        }
 
-        self.scope.enter();
+        let (block, break_label) = self.loop_leave();
 
-        for node in &node.block.nodes {
-            if let Node::BreakLoop(inner) = &node.node {
-                if let Some(value) = &inner.node {
-                    let e = self.expression(value.as_ref())?;
-                    self.statements().push(
-                        Statement::Expression(
-                            ExpressionStatement {
-                                expression: e,
-                                result: sr.clone(),
-                            })
-                    );
-                }
-
-                let frame = self.scope.frame();
-                let cleanup_statements = frame.cleanup_statements();
-                self.statements().extend(cleanup_statements);
-
-                self.statements().push(Statement::Break(BreakStatement {}))
-            } else {
-                self.nodes(node.as_ref())?
+        self.statements().push(Statement::Loop(
+            LoopStatement {
+                block,
+                result: None,
             }
+        ));
+        self.loop_end(break_label);
+
+        Ok(())
+    }
+
+    // the bounds are raw c values which get evaluated once, each iteration boxes the counter into the loop variable
+    pub(crate) fn range_loop(&mut self, node: &IrRangeLoopNode) -> c::generator::Result<()> {
+        let type_id = node.start.type_id;
+        let r#type = raw_type(type_id);
+
+        let start = self.range_bound(node.start.as_ref())?;
+        let end = self.range_bound_fixed(node.end.as_ref())?;
+        let step = match &node.step {
+            Some(step) => self.range_bound_fixed(step.as_ref())?,
+            None => "1".to_string(),
+        };
+
+        let symbol = self.symbol_table.variable(node.variable);
+        let variable = symbol.to_string(&self.string_table);
+        let counter = format!("{variable}_counter");
+
+        let (less, greater) = if node.inclusive { ("<=", ">=") } else { ("<", ">") };
+        // the sign of a step which is no literal is only known at runtime, a step of 0 runs no iteration
+        let condition = match step.parse::<f64>() {
+            Ok(value) if value > 0.0 => format!("{counter} {less} {end}"),
+            Ok(_) => format!("{counter} {greater} {end}"),
+            Err(_) => format!("({step} > 0 ? {counter} {less} {end} : {step} < 0 && {counter} {greater} {end})"),
+        };
+
+        let label = self.loop_label(node.label);
+        self.scope.enter_loop(None, label);
+        self.scope.push_local_variable(variable.clone(), Storage::Memory);
+        self.statements().push(Statement::Expression(ExpressionStatement {
+            expression: Expression::CallFunction(CallFunctionExpression {
+                function: box_function(type_id),
+                arguments: Box::new([
+                    Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                    Expression::Code(CodeExpression { code: counter.clone() }),
+                ]),
+            }),
+            result: Some(StatementResult::Declare { variable, r#type: c_type(type_id) }),
+        }));
+        for node in &node.block.nodes {
+            self.nodes(node.as_ref())?
         }
 
-        let mut frame = self.scope.leave();
-        // let cleanup_statements = frame.cleanup();
+        let (block, break_label) = self.loop_leave();
 
-        let mut statements = vec![];
-        statements.extend(frame.statements);
-        // statements.extend(cleanup_statements);
+        self.statements().push(Statement::For(ForStatement {
+            counter,
+            r#type,
+            start: Expression::Code(CodeExpression { code: start }),
+            condition: Expression::Code(CodeExpression { code: condition }),
+            step: Expression::Code(CodeExpression { code: step }),
+            block,
+        }));
+        self.loop_end(break_label);
 
+        Ok(())
+    }
 
-        // self.scope.push_local_variable("result".to_string(), Storage::Memory);
+    // the iterable lives as long as the loop, each iteration takes the next element until there is none
+    pub(crate) fn iterator_loop(&mut self, node: &IrIteratorLoopNode) -> c::generator::Result<()> {
+        if let Node::AccessVariable(_) = node.value.node() {
+            self.declare_retained_variable(node.iterable, node.value.as_ref())?;
+        } else {
+            self.declare_variable(&IrDeclareVariableNode { variable: node.iterable, value: node.value.clone() })?;
+        }
+        if let Some(iter) = &node.iter {
+            self.declare_variable(&IrDeclareVariableNode { variable: node.iterator, value: iter.clone() })?;
+        }
 
-        self.statements().push(Statement::Loop(
-            LoopStatement {
-                block: BlockStatement { statements },
-                // result: Some(StatementResult::Declare {
-                //     variable: temp.to_string(),
-                //     r#type: "struct val_num *".to_string(),
-                // }),
-                result: None,
+        // a string walks its characters by an index which lives outside of the loop
+        let iterator = self.symbol_table.variable(node.iterator).to_string(&self.string_table);
+        let index = format!("{iterator}_index");
+        if node.next.is_none() {
+            self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
+                variable: index.clone(),
+                r#type: "size_t".to_string(),
+                expression: Expression::Code(CodeExpression { code: "0".to_string() }),
+            }));
+        }
+
+        let symbol = self.symbol_table.variable(node.variable);
+        let variable = symbol.to_string(&self.string_table);
+        let r#type = self.value_type(symbol.type_id.unwrap_or(TypeId::UNKNOWN));
+
+        let label = self.loop_label(node.label);
+        self.scope.enter_loop(None, label);
+        let next = match &node.next {
+            Some(next) => {
+                let Node::CallFunctionOfObject(call) = next.node() else { unreachable!() };
+                self.call_function_of_object_value(call, next.type_id)?
             }
-        ));
+            None => Expression::CallFunction(CallFunctionExpression {
+                function: "val_str_next_char".to_string(),
+                arguments: Box::new([
+                    Expression::Code(CodeExpression { code: iterator }),
+                    Expression::Code(CodeExpression { code: format!("&{index}") }),
+                    Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                ]),
+            })
+        };
+        self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
+            variable: variable.clone(),
+            r#type,
+            expression: next,
+        }));
+        self.statements().push(If(IfStatement {
+            condition: Expression::Code(CodeExpression { code: format!("{variable} == nullptr") }),
+            then: BlockStatement { statements: vec![Statement::Break(BreakStatement {})] },
+            otherwise: None,
+        }));
+        self.scope.push_local_variable(variable, Storage::Memory);
+        for node in &node.block.nodes {
+            self.nodes(node.as_ref())?
+        }
+
+        let (block, break_label) = self.loop_leave();
+
+        self.statements().push(Statement::Loop(LoopStatement {
+            block,
+            result: None,
+        }));
+        self.loop_end(break_label);
 
         Ok(())
     }
 
-    pub(crate) fn r#if(&mut self, node: &IrIfNode) -> c::generator::Result<()> {
-        let condition = self.expression(node.condition.as_ref())?;
+    // the raw c value of a bound, a literal is used as it is
+    fn range_bound(&mut self, node: &IrTreeNode) -> c::generator::Result<String> {
+        let value = match node.node() {
+            Node::LiteralNumber(literal) => literal.value.to_f64().unwrap().to_string(),
+            Node::LiteralInt1(literal) => literal.value.to_string(),
+            Node::LiteralInt2(literal) => literal.value.to_string(),
+            Node::LiteralInt4(literal) => literal.value.to_string(),
+            Node::LiteralInt8(literal) => literal.value.to_string(),
+            Node::LiteralInt16(literal) => literal.value.to_string(),
+            Node::LiteralUint1(literal) => literal.value.to_string(),
+            Node::LiteralUint2(literal) => literal.value.to_string(),
+            Node::LiteralUint4(literal) => literal.value.to_string(),
+            Node::LiteralUint8(literal) => literal.value.to_string(),
+            Node::LiteralUint16(literal) => literal.value.to_string(),
+            _ => {
+                let variable = match self.expression(node)? {
+                    Expression::Variable(VariableExpression { variable, .. }) => variable,
+                    expression => {
+                        let temp = self.scope.push_temp(Storage::Memory);
+                        self.statements().push(Statement::Expression(ExpressionStatement {
+                            expression,
+                            result: Some(StatementResult::Declare { variable: temp.to_string(), r#type: c_type(node.type_id) }),
+                        }));
+                        temp.to_string()
+                    }
+                };
+                format!("(({}) {variable})->data", c_type(node.type_id))
+            }
+        };
+        Ok(value)
+    }
+
+    // end and step are checked on every iteration, so a value behind a variable gets copied before the loop starts
+    fn range_bound_fixed(&mut self, node: &IrTreeNode) -> c::generator::Result<String> {
+        let value = self.range_bound(node)?;
+        if value.parse::<f64>().is_ok() {
+            return Ok(value);
+        }
 
+        let temp = self.scope.push_temp(Storage::Stack);
+        self.statements().push(Statement::Expression(ExpressionStatement {
+            expression: Expression::Code(CodeExpression { code: value }),
+            result: Some(StatementResult::Declare { variable: temp.to_string(), r#type: raw_type(node.type_id) }),
+        }));
+        Ok(temp.to_string())
+    }
+
+    fn loop_label(&self, label: Option<StringTableId>) -> Option<String> {
+        label.map(|label| self.string_table.get(label).to_string())
+    }
+
+    // the body is generated once, the frame it is declared in runs it wherever it gets left
+    pub(crate) fn defer(&mut self, node: &IrDeferNode) -> c::generator::Result<()> {
         self.scope.enter();
-        for node in &node.then.nodes {
+        for node in &node.block.nodes {
             self.nodes(node.as_ref())?
         }
 
-        let mut then_frame = self.scope.leave();
-        let then_cleanup_statements = then_frame.cleanup_statements();
+        let frame = self.scope.leave();
+        let cleanup_statements = frame.cleanup_statements();
 
-        let mut then_statements = vec![];
-        then_statements.extend(then_frame.statements);
-        then_statements.extend(then_cleanup_statements);
+        let mut statements = vec![];
+        statements.extend(frame.statements);
+        statements.extend(cleanup_statements);
+        self.scope.push_defer(BlockStatement { statements });
+        Ok(())
+    }
 
+    // leaves the frame of the body, a continue of a nested loop jumps behind its cleanup
+    fn loop_leave(&mut self) -> (BlockStatement, Option<String>) {
+        let (frame, r#loop) = self.scope.leave_loop();
+        let cleanup_statements = frame.cleanup_statements();
 
-        let otherwise = if let Some(otherwise) = &node.otherwise {
-            self.scope.enter();
-            for node in &otherwise.nodes {
-                self.nodes(node.as_ref())?
-            }
+        let mut statements = vec![];
+        statements.extend(frame.statements);
+        statements.extend(cleanup_statements);
+        if let Some(label) = r#loop.continue_label {
+            statements.push(Statement::Label(LabelStatement { label }));
+        }
+        (BlockStatement { statements }, r#loop.break_label)
+    }
 
-            let mut frame = self.scope.leave();
-            let cleanup_statements = frame.cleanup_statements();
+    // a break of a nested loop jumps behind the loop
+    fn loop_end(&mut self, break_label: Option<String>) {
+        if let Some(label) = break_label {
+            self.statements().push(Statement::Label(LabelStatement { label }));
+        }
+    }
 
-            let mut statements = vec![];
-            statements.extend(frame.statements);
-            statements.extend(cleanup_statements);
+    // break releases what every frame up to the loop it leaves holds, a break with a value hands it to the result of that loop
+    pub(crate) fn r#break(&mut self, node: &IrBreakLoopNode) -> c::generator::Result<()> {
+        let label = self.loop_label(node.label);
+        let label = label.as_deref();
+        if let (Some(value), Some(StatementResult::Assign { variable })) = (&node.node, self.scope.loop_result(label)) {
+            self.branch_result(value.as_ref(), variable)?;
+        }
 
-            Some(BlockStatement { statements })
-        } else {
-            None
+        let cleanup_statements = self.scope.loop_cleanup_statements(label);
+        self.statements().extend(cleanup_statements);
+        let statement = match self.scope.loop_break_label(label) {
+            Some(label) => Statement::Goto(GotoStatement { label }),
+            None => Statement::Break(BreakStatement {})
+        };
+        self.statements().push(statement);
+        Ok(())
+    }
+
+    pub(crate) fn r#continue(&mut self, node: &IrContinueLoopNode) -> c::generator::Result<()> {
+        let label = self.loop_label(node.label);
+        let label = label.as_deref();
+
+        let cleanup_statements = self.scope.loop_cleanup_statements(label);
+        self.statements().extend(cleanup_statements);
+        let statement = match self.scope.loop_continue_label(label) {
+            Some(label) => Statement::Goto(GotoStatement { label }),
+            None => Statement::Continue(ContinueStatement {})
         };
+        self.statements().push(statement);
+        Ok(())
+    }
 
+    pub(crate) fn r#if(&mut self, node: &IrIfNode, sr: Option<StatementResult>) -> c::generator::Result<()> {
+        let condition = self.condition(node.condition.as_ref())?;
+
+        let then = self.if_branch(&node.then, sr.clone())?;
+        let otherwise = match &node.otherwise {
+            Some(otherwise) => Some(self.if_branch(otherwise, sr)?),
+            None => None
+        };
 
         self.statements().push(If(IfStatement {
             condition,
-            then: BlockStatement { statements: then_statements },
+            then,
             otherwise,
         }));
 
-        return Ok(());
+        Ok(())
     }
-}
\ No newline at end of file
+
+    // the last node of a branch is the value of an if used as expression
+    fn if_branch(&mut self, block: &IrBlockNode, sr: Option<StatementResult>) -> c::generator::Result<BlockStatement> {
+        self.scope.enter();
+
+        let count = block.nodes.len();
+        for (idx, node) in block.nodes.iter().enumerate() {
+            if idx + 1 < count {
+                self.nodes(node.as_ref())?;
+                continue;
+            }
+
+            match &sr {
+                Some(StatementResult::Assign { variable }) => self.branch_result(node.as_ref(), variable.clone())?,
+                _ if Self::is_value(node.as_ref()) => {}
+                _ => self.nodes(node.as_ref())?
+            }
+        }
+
+        let frame = self.scope.leave();
+        let cleanup_statements = frame.cleanup_statements();
+
+        let mut statements = vec![];
+        statements.extend(frame.statements);
+        statements.extend(cleanup_statements);
+        Ok(BlockStatement { statements })
+    }
+
+    // a condition is a boxed bool, c branches on the value it holds - a computed one lives until the frame ends,
+    // comparisons against none and bool literals already are plain c values
+    fn condition(&mut self, node: &IrTreeNode) -> c::generator::Result<Expression> {
+        let variable = match self.expression(node)? {
+            expression @ (Expression::Literal(_) | Expression::Compare(_)) => return Ok(expression),
+            Expression::CallFunction(call) if matches!(call.function.as_str(), "val_bool_cmp" | "val_bool_cmp_lit") => {
+                return Ok(Expression::CallFunction(call));
+            }
+            Expression::Variable(VariableExpression { variable, .. }) => variable,
+            expression => {
+                let temp = self.scope.push_temp(Storage::Memory);
+                self.statements().push(Statement::Expression(ExpressionStatement {
+                    expression,
+                    result: Some(StatementResult::Declare { variable: temp.to_string(), r#type: "struct val_bool *".to_string() }),
+                }));
+                temp.to_string()
+            }
+        };
+        Ok(Expression::Code(CodeExpression { code: format!("{variable}->data") }))
+    }
+}
+
+// the c type of the value a val of the given number type holds
+fn raw_type(type_id: TypeId) -> String {
+    match type_id {
+        TypeId::INT1 => "i1",
+        TypeId::INT2 => "i2",
+        TypeId::INT4 => "i4",
+        TypeId::INT8 => "i8",
+        TypeId::INT16 => "i16",
+        TypeId::UINT1 => "u1",
+        TypeId::UINT2 => "u2",
+        TypeId::UINT4 => "u4",
+        TypeId::UINT8 => "u8",
+        TypeId::UINT16 => "u16",
+        _ => "double",
+    }.to_string()
+}
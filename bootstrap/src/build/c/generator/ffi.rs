@@ -0,0 +1,342 @@
+use std::rc::Rc;
+
+use crate::build::c;
+use crate::build::c::{AccessVariableOfStructExpression, BlockStatement, CallFunctionExpression, CodeExpression, DeclareFunctionArgumentNode, DeclareFunctionNode, DefineFunctionArgumentNode, DefineFunctionNode, Expression, ExpressionStatement, ReturnFromFunctionStatement, Statement, StatementResult, VariableExpression};
+use crate::build::c::generator::Generator;
+use crate::build::c::generator::r#type::{box_function, c_type};
+use crate::build::c::generator::scope::Argument;
+use crate::common::{Inferred, Symbol, SymbolId, TypeId};
+use crate::common::node::Node::{AccessVariable, DeclareLambda};
+use crate::ir::{IrDeclareExternalFunctionNode, IrTreeNode};
+
+// the C type an Elodie type becomes when it gets passed to or returned from an external function
+//
+// | Elodie  | C              |
+// |---------|----------------|
+// | Bool    | `bool`         |
+// | Float4  | `float`        |
+// | Float8  | `double`       |
+// | Int1    | `int8_t`       |
+// | Int2    | `int16_t`      |
+// | Int4    | `int32_t`      |
+// | Int8    | `int64_t`      |
+// | Int16   | `__int128_t`   |
+// | Number  | `double`       |
+// | String  | `const char *` |
+// | Uint1   | `uint8_t`      |
+// | Uint2   | `uint16_t`     |
+// | Uint4   | `uint32_t`     |
+// | Uint8   | `uint64_t`     |
+// | Uint16  | `__uint128_t`  |
+// | Unit    | `void`         |
+pub(crate) fn ffi_type(type_id: TypeId) -> String {
+    match type_id {
+        TypeId::BOOLEAN => "bool",
+        TypeId::FLOAT4 => "float",
+        TypeId::FLOAT8 => "double",
+        TypeId::INT1 => "int8_t",
+        TypeId::INT2 => "int16_t",
+        TypeId::INT4 => "int32_t",
+        TypeId::INT8 => "int64_t",
+        TypeId::INT16 => "__int128_t",
+        TypeId::NUMBER => "double",
+        TypeId::STRING => "const char *",
+        TypeId::UINT1 => "uint8_t",
+        TypeId::UINT2 => "uint16_t",
+        TypeId::UINT4 => "uint32_t",
+        TypeId::UINT8 => "uint64_t",
+        TypeId::UINT16 => "__uint128_t",
+        TypeId::UNIT => "void",
+        _ => unimplemented!("{type_id:?} can not be passed to c")
+    }.to_string()
+}
+
+// literals already are raw c values, everything else is a val which carries the raw value as data
+fn ffi_argument(expression: Expression) -> Expression {
+    match expression {
+        Expression::Variable(variable) => Expression::AccessVariableOfStruct(AccessVariableOfStructExpression {
+            r#struct: variable.variable,
+            variable: "data".to_string(),
+        }),
+        expression => expression
+    }
+}
+
+impl Generator {
+    pub(crate) fn declare_external_function(&mut self, node: &IrDeclareExternalFunctionNode) -> c::generator::Result<()> {
+        self.ffi_declare(node.function);
+        Ok(())
+    }
+
+    // external functions outside of sysroot are plain c functions, everything else works with vals
+    pub(crate) fn function_is_foreign(&self, function: SymbolId) -> bool {
+        let Symbol::Function(symbol) = &self.symbol_table[function] else {
+            return false;
+        };
+        symbol.external && self.function_package_path(function).first().map(|s| s != "sysroot").unwrap_or(true)
+    }
+
+    // function types become function pointers, e.g. typeof(int32_t (*)(int32_t)), external types their c struct
+    pub(crate) fn ffi_c_type(&self, inferred: &Inferred) -> String {
+        match inferred {
+            Inferred::Function(arguments, result) => {
+                let arguments = if arguments.is_empty() {
+                    "void".to_string()
+                } else {
+                    arguments.iter().map(|argument| self.ffi_c_type(argument)).collect::<Vec<_>>().join(", ")
+                };
+                format!("typeof({} (*)({arguments}))", self.ffi_c_type(result))
+            }
+            Inferred::Pointer(inferred) => format!("{} *", self.ffi_c_type(inferred)),
+            Inferred::Type(type_id) if self.type_table.is_external(*type_id) => self.value_type(*type_id),
+            inferred => ffi_type(inferred.type_id().unwrap_or(TypeId::UNKNOWN))
+        }
+    }
+
+    // values of external types already are c structs, everything else gets unboxed
+    pub(crate) fn ffi_value(&mut self, node: &IrTreeNode) -> c::generator::Result<Expression> {
+        let expression = self.expression(node)?;
+        if self.type_table.is_external(node.type_id) {
+            Ok(expression)
+        } else {
+            Ok(ffi_argument(expression))
+        }
+    }
+
+    // unboxes the arguments, calls the c function and boxes its result again
+    pub(crate) fn ffi_call(&mut self, function: SymbolId, args: &[Rc<IrTreeNode>]) -> c::generator::Result<Expression> {
+        self.ffi_declare(function);
+
+        let Inferred::Function(parameters, _) = self.symbol_table.function(function).inferred.clone() else {
+            unreachable!()
+        };
+
+        let mut arguments = Vec::with_capacity(args.len());
+        for (arg, parameter) in args.iter().zip(parameters.iter()) {
+            arguments.push(match parameter {
+                Inferred::Function(..) => self.ffi_callback(arg)?,
+                Inferred::Pointer(_) => self.ffi_pointer(arg)?,
+                _ => self.ffi_value(arg)?
+            });
+        }
+
+        let result = Expression::CallFunction(CallFunctionExpression {
+            function: self.ffi_identifier(function),
+            arguments: arguments.into_boxed_slice(),
+        });
+
+        let type_id = self.function_return(function);
+        if type_id == TypeId::UNIT || self.type_table.is_external(type_id) {
+            return Ok(result);
+        }
+
+        Ok(Expression::CallFunction(CallFunctionExpression {
+            function: box_function(type_id),
+            arguments: Box::new([
+                Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                result
+            ]),
+        }))
+    }
+
+    // the c function gets the address of the variable, so it can modify the struct in place
+    fn ffi_pointer(&mut self, node: &IrTreeNode) -> c::generator::Result<Expression> {
+        let AccessVariable(access) = node.node() else {
+            unimplemented!("{node:#?} can not be passed by pointer")
+        };
+
+        let variable = self.symbol_table.variable(access.variable).to_string(&self.string_table);
+        Ok(Expression::Code(CodeExpression { code: format!("&{variable}") }))
+    }
+
+    // c can not call elodie functions directly, it gets the trampoline of the function instead
+    fn ffi_callback(&mut self, node: &IrTreeNode) -> c::generator::Result<Expression> {
+        let function = match node.node() {
+            AccessVariable(access) => access.variable,
+            DeclareLambda(lambda) => {
+                self.define_function(lambda.function, None, &lambda.arguments, &lambda.nodes)?;
+                lambda.function
+            }
+            _ => unimplemented!("{node:#?} can not be passed to c")
+        };
+
+        Ok(Expression::Code(CodeExpression { code: self.ffi_trampoline(function) }))
+    }
+
+    // c can not call elodie functions directly, it calls the trampoline instead
+    fn ffi_trampoline(&mut self, function: SymbolId) -> String {
+        let identifier = format!("{}_trampoline", self.function_identifier(function));
+        if !self.function_declarations.iter().any(|declaration| declaration.identifier == identifier) {
+            self.ffi_wrapper(function, identifier.clone());
+        }
+        identifier
+    }
+
+    // a plain c function which boxes its arguments, calls the elodie function and unboxes the result,
+    // values of custom types cross as they are
+    pub(crate) fn ffi_wrapper(&mut self, function: SymbolId, identifier: String) -> DeclareFunctionNode {
+        let Inferred::Function(parameters, result) = self.symbol_table.function(function).inferred.clone() else {
+            unreachable!()
+        };
+
+        let mut statements = vec![];
+        let mut values = Vec::with_capacity(parameters.len());
+        let mut boxed = vec![];
+        for (idx, parameter) in parameters.iter().enumerate() {
+            let type_id = parameter.type_id().unwrap_or(TypeId::UNKNOWN);
+            let argument = Argument(idx as u16 + 1).to_string();
+            if matches!(type_id, TypeId::Custom(_)) {
+                values.push(argument);
+                continue;
+            }
+
+            let value = format!("value_{}", idx + 1);
+            statements.push(Statement::Expression(ExpressionStatement {
+                expression: Expression::CallFunction(CallFunctionExpression {
+                    function: box_function(type_id),
+                    arguments: Box::new([
+                        Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                        Expression::Code(CodeExpression { code: argument }),
+                    ]),
+                }),
+                result: Some(StatementResult::Declare { variable: value.clone(), r#type: c_type(type_id) }),
+            }));
+            values.push(value.clone());
+            boxed.push(value);
+        }
+
+        let type_id = result.type_id().unwrap_or(TypeId::UNKNOWN);
+        statements.push(Statement::Expression(ExpressionStatement {
+            expression: Expression::CallFunction(CallFunctionExpression {
+                function: self.function_identifier(function),
+                arguments: values.iter()
+                    .map(|value| Expression::Variable(VariableExpression { variable: value.clone(), cast: None }))
+                    .collect(),
+            }),
+            result: if type_id == TypeId::UNIT {
+                None
+            } else {
+                Some(StatementResult::Declare { variable: "result".to_string(), r#type: self.value_type(type_id) })
+            },
+        }));
+
+        statements.extend(boxed.iter().map(Statement::rc_dec));
+
+        if matches!(type_id, TypeId::Custom(_)) {
+            // the caller owns the returned object
+            statements.push(Statement::ReturnFromFunction(ReturnFromFunctionStatement {
+                node: Some(Expression::Variable(VariableExpression { variable: "result".to_string(), cast: None }))
+            }));
+        } else if type_id != TypeId::UNIT {
+            // the data of a string dies together with its val, c gets a copy it has to free
+            let (expression, r#type) = if type_id == TypeId::STRING {
+                self.include_system("string.h");
+                (Expression::Code(CodeExpression { code: "strndup(result->data, result->count)".to_string() }), "char *".to_string())
+            } else {
+                (Expression::AccessVariableOfStruct(AccessVariableOfStructExpression {
+                    r#struct: "result".to_string(),
+                    variable: "data".to_string(),
+                }), ffi_type(type_id))
+            };
+            statements.push(Statement::Expression(ExpressionStatement {
+                expression,
+                result: Some(StatementResult::Declare { variable: "data".to_string(), r#type }),
+            }));
+            statements.push(Statement::rc_dec("result"));
+            statements.push(Statement::ReturnFromFunction(ReturnFromFunctionStatement {
+                node: Some(Expression::Variable(VariableExpression { variable: "data".to_string(), cast: None }))
+            }));
+        }
+
+        let arguments: Vec<(String, String)> = parameters.iter()
+            .enumerate()
+            .map(|(idx, parameter)| (Argument(idx as u16 + 1).to_string(), self.ffi_wrapper_type(parameter)))
+            .collect();
+        let ty = match result.as_ref() {
+            Inferred::String => "char *".to_string(),
+            result => self.ffi_wrapper_type(result)
+        };
+
+        let declaration = DeclareFunctionNode {
+            identifier: identifier.clone(),
+            arguments: arguments.iter()
+                .map(|(identifier, ty)| DeclareFunctionArgumentNode { identifier: identifier.clone(), ty: ty.clone() })
+                .collect(),
+            ty: ty.clone(),
+            symbol: None,
+        };
+        self.function_declarations.push(declaration.clone());
+
+        self.function_definitions.push(DefineFunctionNode {
+            identifier,
+            arguments: arguments.into_iter()
+                .map(|(identifier, ty)| DefineFunctionArgumentNode { identifier, ty })
+                .collect(),
+            ty,
+            block: BlockStatement { statements },
+        });
+
+        declaration
+    }
+
+    // objects are opaque to c
+    fn ffi_wrapper_type(&self, inferred: &Inferred) -> String {
+        match inferred {
+            Inferred::Type(type_id) if !self.type_table.is_external(*type_id) => c_type(*type_id),
+            inferred => self.ffi_c_type(inferred)
+        }
+    }
+
+    // foreign functions get declared by an identifier of their own which refers to the c symbol,
+    // so their prototype never clashes with the one of a header which got included as well
+    fn ffi_identifier(&self, function: SymbolId) -> String {
+        let identifier = self.function_identifier(function);
+        if self.function_is_foreign(function) {
+            format!("{identifier}_external")
+        } else {
+            identifier
+        }
+    }
+
+    // the prototype of the c function, declared once no matter how often it gets called
+    fn ffi_declare(&mut self, function: SymbolId) {
+        let identifier = self.ffi_identifier(function);
+        if self.function_declarations.iter().any(|declaration| declaration.identifier == identifier) {
+            return;
+        }
+        let symbol = Some(self.function_identifier(function)).filter(|symbol| *symbol != identifier);
+
+        let Inferred::Function(arguments, result) = &self.symbol_table.function(function).inferred else {
+            unreachable!()
+        };
+
+        let arguments = arguments.iter()
+            .enumerate()
+            .map(|(idx, argument)| DeclareFunctionArgumentNode {
+                identifier: Argument(idx as u16 + 1).to_string(),
+                ty: self.ffi_c_type(argument),
+            })
+            .collect();
+        let ty = self.ffi_c_type(result);
+
+        self.function_declarations.push(DeclareFunctionNode { identifier, arguments, ty, symbol });
+        self.include_system("stdint.h");
+        self.ffi_link(function);
+    }
+
+    // the c libraries declared by the package of the function and the packages it is nested in
+    fn ffi_link(&mut self, function: SymbolId) {
+        let Some(package) = self.symbol_table.function(function).package_id else {
+            return;
+        };
+
+        for package in self.package_table.path(package) {
+            for link in &self.package_table[package].links {
+                let link = self.string_table.get(*link).to_string();
+                if !self.links.contains(&link) {
+                    self.links.push(link);
+                }
+            }
+        }
+    }
+}
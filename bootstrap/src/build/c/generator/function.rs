@@ -0,0 +1,225 @@
+use std::rc::Rc;
+
+use crate::build::c;
+use crate::build::c::{CallFunctionExpression, CodeExpression, DeclareFunctionArgumentNode, Expression, DeclareFunctionNode, DefineFunctionArgumentNode, DefineFunctionNode, BlockStatement, ExpressionStatement, ReturnFromFunctionStatement, Statement, StatementResult, VariableExpression};
+use crate::build::c::generator::Generator;
+use crate::build::c::generator::r#type::{box_function, c_type};
+use crate::build::c::generator::scope::{Scope, Storage};
+use crate::build::c::generator::tuple::tuple_type;
+use crate::common::{Inferred, Symbol, SymbolId, TypeId};
+use crate::common::node::Node::{AccessVariable, LiteralNone};
+use crate::ir::{IrDeclareFunctionNode, IrDeclarePackageNode, IrReturnFromFunctionNode, IrTreeNode};
+
+impl Generator {
+    pub(crate) fn declare_function(&mut self, node: &IrDeclareFunctionNode) -> c::generator::Result<()> {
+        self.define_function(node.function, None, &node.arguments, &node.nodes)
+    }
+
+    // elodie functions and lambdas share the same calling convention, arguments and result are vals,
+    // closures additionally get the closure which carries their captured values
+    pub(crate) fn define_function(&mut self, function: SymbolId, captures: Option<&[SymbolId]>, args: &[SymbolId], nodes: &[Rc<IrTreeNode>]) -> c::generator::Result<()> {
+        let identifier = self.function_identifier(function);
+        let ty = match self.function_return_tuple(function) {
+            Some(tuple) => tuple_type(tuple),
+            None => self.value_type(self.function_return(function))
+        };
+
+        let mut arguments = Vec::with_capacity(args.len() + 1);
+        if captures.is_some() {
+            arguments.push(("closure".to_string(), c_type(TypeId::FUNCTION)));
+        }
+        for argument in args {
+            let inferred = self.symbol_table.variable(*argument).inferred.clone();
+            let ty = match inferred {
+                Inferred::Tuple(_) | Inferred::Record(_) => self.inferred_type(&inferred),
+                _ => self.value_type(self.symbol_table.variable(*argument).type_id.unwrap_or(TypeId::UNKNOWN))
+            };
+            arguments.push((self.symbol_table.variable(*argument).to_string(&self.string_table), ty));
+        }
+
+        let scope = std::mem::replace(&mut self.scope, Scope::new());
+
+        if let Some(captures) = captures {
+            self.closure_environment(captures);
+        }
+
+        for node in nodes {
+            self.nodes(node)?;
+        }
+
+        let frame = std::mem::replace(&mut self.scope, scope).leave();
+        let cleanup_statements = frame.cleanup_statements();
+
+        let mut statements = vec![];
+        statements.extend(frame.statements);
+        statements.extend(cleanup_statements);
+
+        self.function_define(identifier, arguments, ty, statements);
+        Ok(())
+    }
+
+    pub(crate) fn function_define(&mut self, identifier: String, arguments: Vec<(String, String)>, ty: String, statements: Vec<Statement>) {
+        self.function_declarations.push(DeclareFunctionNode {
+            identifier: identifier.clone(),
+            arguments: arguments.iter()
+                .map(|(identifier, ty)| DeclareFunctionArgumentNode { identifier: identifier.clone(), ty: ty.clone() })
+                .collect(),
+            ty: ty.clone(),
+            symbol: None,
+        });
+
+        self.function_definitions.push(DefineFunctionNode {
+            identifier,
+            arguments: arguments.into_iter()
+                .map(|(identifier, ty)| DefineFunctionArgumentNode { identifier, ty })
+                .collect(),
+            ty,
+            block: BlockStatement { statements },
+        });
+    }
+
+    pub(crate) fn declare_package(&mut self, node: &IrDeclarePackageNode) -> c::generator::Result<()> {
+        for package in &node.packages {
+            self.nodes(package)?;
+        }
+        for r#type in &node.types {
+            self.nodes(r#type)?;
+        }
+        for function in &node.functions {
+            self.nodes(function)?;
+        }
+        for definition in &node.definitions {
+            self.nodes(definition)?;
+        }
+        Ok(())
+    }
+
+    // the caller owns the returned value, a value some frame of the function holds gets retained before the frames are cleaned up
+    pub(crate) fn r#return(&mut self, node: &IrReturnFromFunctionNode) -> c::generator::Result<()> {
+        let result = if let Some(LiteralNone(_)) = node.node.as_ref().map(|n| n.node()) {
+            Some(c::Expression::Code(CodeExpression { code: "nullptr".to_string() }))
+        } else if let Some(node) = &node.node {
+            let (expression, owned) = match self.literal_value(node)? {
+                // literals already got the declared type, boxing them hands a new val to the caller
+                Some(literal) => (self.boxed(node.type_id, literal), true),
+                None => match self.expression(node)? {
+                    expression @ Expression::Compare(_) => (self.boxed(TypeId::BOOLEAN, expression), true),
+                    Expression::CallFunction(call) if matches!(call.function.as_str(), "val_bool_cmp" | "val_bool_cmp_lit") => {
+                        (self.boxed(TypeId::BOOLEAN, Expression::CallFunction(call)), true)
+                    }
+                    // a computed value is held by no frame, its reference goes to the caller as it is
+                    expression @ Expression::CallFunction(_) => (expression, true),
+                    expression => (expression, false)
+                }
+            };
+            let temp = self.scope.push_temp(Storage::Stack);
+            let inferred = self.node_inferred(node);
+            let tuple = self.tuple_of(&inferred);
+            let r#type = tuple.map(tuple_type).unwrap_or_else(|| self.value_type(node.type_id));
+
+            self.statements().push(Statement::Expression(ExpressionStatement {
+                expression,
+                result: Some(StatementResult::Declare {
+                    variable: temp.to_string(),
+                    r#type,
+                }),
+            }));
+
+            // external types are plain c values
+            if let Some(tuple) = tuple.filter(|_| !owned) {
+                self.statements().push(Statement::rc_inc_tuple(temp, tuple));
+            } else if !owned && tuple.is_none() && !self.type_table.is_external(node.type_id) {
+                if self.is_optional(node) {
+                    self.statements().push(Statement::rc_inc_optional(temp));
+                } else {
+                    self.statements().push(Statement::rc_inc(temp));
+                }
+            }
+            Some(c::Expression::Variable(VariableExpression { variable: temp.to_string(), cast: None }))
+        } else {
+            None
+        };
+
+        let cleanup_statements = self.scope.cleanup_statements();
+        self.statements().extend(cleanup_statements);
+
+        self.statements().push(Statement::ReturnFromFunction(ReturnFromFunctionStatement { node: result }));
+        Ok(())
+    }
+
+    fn boxed(&self, type_id: TypeId, value: Expression) -> Expression {
+        Expression::CallFunction(CallFunctionExpression {
+            function: box_function(type_id),
+            arguments: Box::new([
+                Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                value,
+            ]),
+        })
+    }
+
+    pub(crate) fn function_return(&self, function: SymbolId) -> TypeId {
+        match self.symbol_table.signature(function) {
+            Inferred::Function(_, result) => result.type_id().unwrap_or(TypeId::UNKNOWN),
+            _ => TypeId::UNKNOWN
+        }
+    }
+
+    // a variable which may still be none
+    pub(crate) fn is_optional(&self, node: &IrTreeNode) -> bool {
+        match node.node() {
+            AccessVariable(access) => matches!(self.symbol_table[access.variable], Symbol::Variable(_))
+                && self.symbol_table.variable(access.variable).inferred.is_optional(),
+            _ => false
+        }
+    }
+
+    pub(crate) fn function_return_optional(&self, function: SymbolId) -> bool {
+        matches!(self.symbol_table.signature(function), Inferred::Function(_, result) if result.is_optional())
+    }
+
+    // external functions of the sysroot package are provided by the runtime, sysroot::rt::io::print becomes rt_io_print
+    pub(crate) fn function_identifier(&self, function: SymbolId) -> String {
+        let symbol = self.symbol_table.function(function);
+        let name = self.string_table.get(symbol.name.0).to_string();
+
+        if !symbol.external {
+            return format!("{}_{}", name, symbol.id.0);
+        }
+
+        let mut segments = self.function_package_path(function);
+        if segments.first().map(|s| s == "sysroot").unwrap_or(false) {
+            segments.remove(0);
+            segments.push(name);
+            segments.join("_")
+        } else {
+            name
+        }
+    }
+
+    // header of the runtime which provides an external function of the sysroot package
+    pub(crate) fn function_header(&self, function: SymbolId) -> Option<String> {
+        let Symbol::Function(symbol) = &self.symbol_table[function] else {
+            return None;
+        };
+        if !symbol.external {
+            return None;
+        }
+
+        let segments = self.function_package_path(function);
+        if segments.len() > 1 && segments[0] == "sysroot" {
+            Some(format!("{}.h", segments[1..].join("/")))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn function_package_path(&self, function: SymbolId) -> Vec<String> {
+        let symbol = self.symbol_table.function(function);
+        symbol.package_id
+            .map(|package| self.package_table.path(package))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|package| self.string_table.get(self.symbol_table[self.package_table[package].symbol].name().0).to_string())
+            .collect()
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use crate::build::c;
+use crate::build::c::{BlockStatement, CallFunctionExpression, CodeExpression, CodeNode, DeclareFunctionArgumentNode, DeclareFunctionNode, DefineFunctionArgumentNode, DefineFunctionNode, Expression, ExpressionStatement, IncludeSystemDirectiveNode, Statement, StatementResult};
+use crate::build::c::DirectiveNode::IncludeSystemDirective;
+use crate::build::c::generator::Generator;
+use crate::common::node::Node::{DeclareFunction, DeclarePackage, DeclareType};
+use crate::common::{Inferred, SymbolId, TypeId};
+use crate::ir::{IrDeclarePackageNode, IrTreeNode};
+
+// a program built as library instead of executable
+pub(crate) struct Library {
+    pub(crate) name: String,
+    // exported types which are not external, c only sees them as opaque pointers
+    pub(crate) types: HashMap<TypeId, String>,
+    pub(crate) header: Vec<c::Node>,
+}
+
+impl Library {
+    pub(crate) fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            types: HashMap::new(),
+            header: vec![],
+        }
+    }
+}
+
+fn mem_test(function: &str) -> Expression {
+    Expression::CallFunction(CallFunctionExpression {
+        function: function.to_string(),
+        arguments: Box::new([Expression::Code(CodeExpression { code: "tm".to_string() })]),
+    })
+}
+
+impl Generator {
+    // instead of main the library gets an init function, which sets up the memory and runs the top level
+    // statements, and a shutdown function, which verifies and releases the memory again
+    pub(crate) fn library_define(&mut self, statements: Vec<Statement>) -> c::generator::Result<()> {
+        let name = self.library.as_ref().unwrap().name.clone();
+
+        self.function_definitions.push(DefineFunctionNode {
+            identifier: format!("{name}_init"),
+            arguments: Box::new([]),
+            ty: "void".to_string(),
+            block: BlockStatement { statements },
+        });
+
+        self.function_definitions.push(DefineFunctionNode {
+            identifier: format!("{name}_shutdown"),
+            arguments: Box::new([]),
+            ty: "void".to_string(),
+            block: BlockStatement {
+                statements: vec![
+                    Statement::Expression(ExpressionStatement { expression: mem_test("mem_test_verify"), result: None }),
+                    Statement::Expression(ExpressionStatement { expression: mem_test("mem_test_free"), result: None }),
+                    Statement::Expression(ExpressionStatement {
+                        expression: Expression::Code(CodeExpression { code: "NULL".to_string() }),
+                        result: Some(StatementResult::Assign { variable: "tm".to_string() }),
+                    }),
+                ],
+            },
+        });
+
+        // objects returned to c are owned by c, until it hands them back
+        self.function_definitions.push(DefineFunctionNode {
+            identifier: format!("{name}_release"),
+            arguments: Box::new([DefineFunctionArgumentNode { identifier: "value".to_string(), ty: "struct val_obj *".to_string() }]),
+            ty: "void".to_string(),
+            block: BlockStatement { statements: vec![Statement::rc_dec("value")] },
+        });
+
+        Ok(())
+    }
+
+    // every exported function of an exported package gets a c wrapper, named after its package path
+    pub(crate) fn library_export(&mut self, nodes: &[IrTreeNode]) -> c::generator::Result<()> {
+        let mut packages = vec![];
+        for node in nodes {
+            self.library_packages(node, "", &mut packages);
+        }
+
+        // types first, the wrappers refer to them by name
+        for (prefix, package) in &packages {
+            for r#type in &package.types {
+                let DeclareType(r#type) = r#type.node() else { continue };
+                let type_id = self.symbol_table[r#type.r#type].type_id().unwrap_or(TypeId::UNKNOWN);
+                if !self.symbol_table[r#type.r#type].is_exported() || self.type_table.is_external(type_id) {
+                    continue;
+                }
+
+                let name = format!("{prefix}{}", self.string_table.get(self.symbol_table[r#type.r#type].name().0));
+                self.library.as_mut().unwrap().types.insert(type_id, name);
+            }
+        }
+
+        let mut declarations = vec![];
+        for (prefix, package) in &packages {
+            for function in &package.functions {
+                let DeclareFunction(function) = function.node() else { continue };
+                if !self.symbol_table.function(function.function).exported {
+                    continue;
+                }
+
+                let identifier = format!("{prefix}{}", self.string_table.get(self.symbol_table.function(function.function).name.0));
+                let declaration = self.ffi_wrapper(function.function, identifier);
+                declarations.push(self.library_declaration(function.function, declaration));
+            }
+        }
+
+        let name = self.library.as_ref().unwrap().name.clone();
+        let mut header = vec![
+            c::Node::Code(CodeNode { code: "#pragma once".to_string() }),
+            c::Node::Directive(IncludeSystemDirective(IncludeSystemDirectiveNode { path: "stdbool.h".to_string() })),
+            c::Node::Directive(IncludeSystemDirective(IncludeSystemDirectiveNode { path: "stdint.h".to_string() })),
+            c::Node::Code(CodeNode { code: "struct val_obj;".to_string() }),
+        ];
+
+        header.extend(self.struct_definitions.iter().cloned().map(c::Node::DefineStruct));
+
+        let library = self.library.as_mut().unwrap();
+        let mut types: Vec<&String> = library.types.values().collect();
+        types.sort();
+        for r#type in types {
+            header.push(c::Node::Code(CodeNode { code: format!("typedef struct val_obj {type};") }));
+        }
+
+        header.push(c::Node::DeclareFunction(DeclareFunctionNode { identifier: format!("{name}_init"), arguments: Box::new([]), ty: "void".to_string(), symbol: None }));
+        header.push(c::Node::DeclareFunction(DeclareFunctionNode { identifier: format!("{name}_shutdown"), arguments: Box::new([]), ty: "void".to_string(), symbol: None }));
+        header.push(c::Node::DeclareFunction(DeclareFunctionNode {
+            identifier: format!("{name}_release"),
+            arguments: Box::new([DeclareFunctionArgumentNode { identifier: "value".to_string(), ty: "struct val_obj *".to_string() }]),
+            ty: "void".to_string(),
+            symbol: None,
+        }));
+        header.extend(declarations.into_iter().map(c::Node::DeclareFunction));
+
+        library.header = header;
+        Ok(())
+    }
+
+    // exported packages together with the prefix of their c names, nested packages are only exported through exported parents
+    fn library_packages<'a>(&self, node: &'a IrTreeNode, prefix: &str, result: &mut Vec<(String, &'a IrDeclarePackageNode)>) {
+        let DeclarePackage(package) = node.node() else {
+            return;
+        };
+
+        if !self.symbol_table[package.package].is_exported() {
+            return;
+        }
+
+        let prefix = format!("{prefix}{}_", self.string_table.get(self.symbol_table[package.package].name().0));
+        for nested in &package.packages {
+            self.library_packages(nested, &prefix, result);
+        }
+        result.push((prefix, package));
+    }
+
+    // the header refers to exported objects by the name of their type, which is the same struct for c
+    fn library_declaration(&self, function: SymbolId, mut declaration: DeclareFunctionNode) -> DeclareFunctionNode {
+        let Inferred::Function(parameters, result) = &self.symbol_table.function(function).inferred else {
+            unreachable!()
+        };
+
+        let types = &self.library.as_ref().unwrap().types;
+        let named = |inferred: &Inferred| inferred.type_id().and_then(|type_id| types.get(&type_id)).map(|name| format!("{name} *"));
+
+        for (argument, parameter) in declaration.arguments.iter_mut().zip(parameters.iter()) {
+            if let Some(ty) = named(parameter) {
+                argument.ty = ty;
+            }
+        }
+        if let Some(ty) = named(result) {
+            declaration.ty = ty;
+        }
+        declaration
+    }
+}
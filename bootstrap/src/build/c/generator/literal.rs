@@ -1,11 +1,12 @@
 use bigdecimal::ToPrimitive;
 
 use crate::build::c;
-use crate::build::c::{CallFunctionStatement, CallFunctionStatementResult, CodeExpression, Expression, LiteralBooleanExpression, LiteralFloat8Expression, LiteralExpression, Statement, VariableExpression};
+use crate::build::c::{CallFunctionStatement, CallFunctionStatementResult, CodeExpression, Expression, LiteralBooleanExpression, LiteralExpression, LiteralFloat4Expression, LiteralFloat8Expression, LiteralInt16Expression, LiteralInt1Expression, LiteralInt2Expression, LiteralInt4Expression, LiteralInt8Expression, LiteralUint16Expression, LiteralUint1Expression, LiteralUint2Expression, LiteralUint4Expression, LiteralUint8Expression, Statement, VariableExpression};
 use crate::build::c::generator::Generator;
 use crate::build::c::generator::scope::Storage;
 use crate::common::GetString;
-use crate::ir::{IrLiteralBooleanNode, IrLiteralNumberNode, IrLiteralStringNode};
+use crate::common::node::Node;
+use crate::ir::{IrLiteralBooleanNode, IrLiteralNumberNode, IrLiteralStringNode, IrTreeNode};
 
 impl Generator {
     pub(crate) fn literal_bool(
@@ -28,6 +29,28 @@ impl Generator {
         })))
     }
 
+    // the raw c value of a bool or number literal, boxing it hands a new val to its owner
+    pub(crate) fn literal_value(&mut self, node: &IrTreeNode) -> c::generator::Result<Option<Expression>> {
+        let literal = match node.node() {
+            Node::LiteralBoolean(node) => return self.literal_bool(node).map(Some),
+            Node::LiteralNumber(node) => return self.literal_number(node).map(Some),
+            Node::LiteralFloat4(node) => LiteralExpression::Float4(LiteralFloat4Expression { value: node.value }),
+            Node::LiteralFloat8(node) => LiteralExpression::Float8(LiteralFloat8Expression { value: node.value }),
+            Node::LiteralInt1(node) => LiteralExpression::Int1(LiteralInt1Expression { value: node.value }),
+            Node::LiteralInt2(node) => LiteralExpression::Int2(LiteralInt2Expression { value: node.value }),
+            Node::LiteralInt4(node) => LiteralExpression::Int4(LiteralInt4Expression { value: node.value }),
+            Node::LiteralInt8(node) => LiteralExpression::Int8(LiteralInt8Expression { value: node.value }),
+            Node::LiteralInt16(node) => LiteralExpression::Int16(LiteralInt16Expression { value: node.value }),
+            Node::LiteralUint1(node) => LiteralExpression::Uint1(LiteralUint1Expression { value: node.value }),
+            Node::LiteralUint2(node) => LiteralExpression::Uint2(LiteralUint2Expression { value: node.value }),
+            Node::LiteralUint4(node) => LiteralExpression::Uint4(LiteralUint4Expression { value: node.value }),
+            Node::LiteralUint8(node) => LiteralExpression::Uint8(LiteralUint8Expression { value: node.value }),
+            Node::LiteralUint16(node) => LiteralExpression::Uint16(LiteralUint16Expression { value: node.value }),
+            _ => return Ok(None)
+        };
+        Ok(Some(Expression::Literal(literal)))
+    }
+
     pub(crate) fn literal_string(
         &mut self,
         node: &IrLiteralStringNode,
@@ -0,0 +1,227 @@
+use crate::build::c;
+use crate::build::c::{BlockStatement, CallFunctionExpression, CodeExpression, Expression, ExpressionStatement, IfStatement, ReturnFromFunctionStatement, Statement, StatementResult, VariableExpression};
+use crate::build::c::generator::Generator;
+use crate::build::c::generator::r#type::{box_function, c_type};
+use crate::build::c::generator::scope::Storage;
+use crate::common::TypeId;
+use crate::common::node::Node::{AccessVariable, Calculate, Compare, If, InstantiateType, InterpolateString, LiteralBoolean, LiteralNumber, LiteralString, Loop, Match};
+use crate::ir::{IrInstantiateTypeNode, IrMatchArm, IrMatchNode, IrTreeNode, IrTryNode};
+
+impl Generator {
+    // the value of a variant is tagged with the index of the variant, its payload holds the values of its variables
+    pub(crate) fn variant_new(&mut self, node: &IrInstantiateTypeNode, variable: String) -> c::generator::Result<()> {
+        let variant = self.symbol_table[node.r#type].type_id().unwrap();
+        let tag = self.type_table.variant_tag(variant).unwrap();
+        let variables = self.type_table[variant].variables.iter().map(|v| v.name.0).collect::<Vec<_>>();
+
+        let r#type = self.value_type(variant);
+        self.statements().push(Statement::Expression(ExpressionStatement {
+            expression: Expression::CallFunction(CallFunctionExpression {
+                function: "val_enm_new".to_string(),
+                arguments: Box::new([
+                    Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                    Expression::Code(CodeExpression { code: tag.to_string() }),
+                    Expression::Code(CodeExpression { code: variables.len().to_string() }),
+                ]),
+            }),
+            result: Some(StatementResult::Declare { variable: variable.clone(), r#type }),
+        }));
+
+        for (idx, name) in variables.iter().enumerate() {
+            let Some(argument) = node.arguments.iter().find(|a| a.argument == *name) else { continue };
+            let value = self.boxed_expression(&argument.value)?;
+            self.statements().push(Statement::Expression(ExpressionStatement {
+                expression: Expression::CallFunction(CallFunctionExpression {
+                    function: "val_enm_set".to_string(),
+                    arguments: Box::new([
+                        Expression::Variable(VariableExpression { variable: variable.clone(), cast: None }),
+                        Expression::Code(CodeExpression { code: idx.to_string() }),
+                        value,
+                    ]),
+                }),
+                result: None,
+            }));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn variant_expression(&mut self, node: &IrInstantiateTypeNode) -> c::generator::Result<Expression> {
+        let temp = self.scope.push_temp(Storage::Memory);
+        self.variant_new(node, temp.to_string())?;
+        Ok(Expression::Variable(VariableExpression { variable: temp.to_string(), cast: None }))
+    }
+
+    // literals are raw c values, the payload only holds vals
+    fn boxed_expression(&mut self, node: &IrTreeNode) -> c::generator::Result<Expression> {
+        let expression = self.expression(node)?;
+        let expression = match node.node() {
+            LiteralBoolean(_) | LiteralNumber(_) => {
+                let temp = self.scope.push_temp(Storage::Memory);
+                self.statements().push(Statement::Expression(ExpressionStatement {
+                    expression: Self::box_literal(node.type_id, expression),
+                    result: Some(StatementResult::Declare { variable: temp.to_string(), r#type: c_type(node.type_id) }),
+                }));
+                Expression::Variable(VariableExpression { variable: temp.to_string(), cast: None })
+            }
+            _ => expression
+        };
+
+        Ok(match expression {
+            Expression::Variable(VariableExpression { variable, .. }) => Expression::Variable(VariableExpression { variable, cast: Some(c_type(TypeId::ANY)) }),
+            expression => expression
+        })
+    }
+
+    fn box_literal(type_id: TypeId, expression: Expression) -> Expression {
+        Expression::CallFunction(CallFunctionExpression {
+            function: box_function(type_id),
+            arguments: Box::new([
+                Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                expression
+            ]),
+        })
+    }
+
+    // arms become a chain of ifs on the tag, an arm with a value assigns it to the result
+    pub(crate) fn r#match(&mut self, node: &IrMatchNode, sr: Option<StatementResult>) -> c::generator::Result<()> {
+        let value = match self.expression(node.node.as_ref())? {
+            Expression::Variable(VariableExpression { variable, .. }) => variable,
+            expression => {
+                let temp = self.scope.push_temp(Storage::Memory);
+                let r#type = self.value_type(node.node.type_id);
+                self.statements().push(Statement::Expression(ExpressionStatement {
+                    expression,
+                    result: Some(StatementResult::Declare { variable: temp.to_string(), r#type }),
+                }));
+                temp.to_string()
+            }
+        };
+
+        let mut otherwise: Option<BlockStatement> = None;
+        for arm in node.arms.iter().rev() {
+            let block = self.match_arm(arm, &value, sr.clone())?;
+            otherwise = Some(match arm.variant {
+                Some(variant) => {
+                    let tag = self.type_table.variant_tag(variant).unwrap();
+                    BlockStatement {
+                        statements: vec![Statement::If(IfStatement {
+                            condition: Expression::Code(CodeExpression { code: format!("((struct val_enm *) {value})->tag == {tag}") }),
+                            then: block,
+                            otherwise,
+                        })]
+                    }
+                }
+                None => block
+            });
+        }
+
+        if let Some(block) = otherwise {
+            self.statements().extend(block.statements);
+        }
+
+        Ok(())
+    }
+
+    // value? hands an Err to the caller as it is, after releasing everything the function holds - the value of Ok is borrowed
+    pub(crate) fn r#try(&mut self, node: &IrTryNode, type_id: TypeId) -> c::generator::Result<Expression> {
+        let value = match self.expression(node.node.as_ref())? {
+            Expression::Variable(VariableExpression { variable, .. }) => variable,
+            expression => {
+                let temp = self.scope.push_temp(Storage::Memory);
+                let r#type = self.value_type(node.node.type_id);
+                self.statements().push(Statement::Expression(ExpressionStatement {
+                    expression,
+                    result: Some(StatementResult::Declare { variable: temp.to_string(), r#type }),
+                }));
+                temp.to_string()
+            }
+        };
+
+        let mut statements = vec![Statement::rc_inc(value.clone())];
+        statements.extend(self.scope.cleanup_statements());
+        statements.push(Statement::ReturnFromFunction(ReturnFromFunctionStatement {
+            node: Some(Expression::Variable(VariableExpression { variable: value.clone(), cast: None })),
+        }));
+
+        let tag = self.type_table.variant_tag(node.error).unwrap();
+        self.statements().push(Statement::If(IfStatement {
+            condition: Expression::Code(CodeExpression { code: format!("((struct val_enm *) {value})->tag == {tag}") }),
+            then: BlockStatement { statements },
+            otherwise: None,
+        }));
+
+        let r#type = self.value_type(type_id);
+        Ok(Expression::Code(CodeExpression { code: format!("({type}) val_enm_at((struct val_enm *) {value}, 0)") }))
+    }
+
+    // bindings are borrowed from the payload of the matched value
+    fn match_arm(&mut self, arm: &IrMatchArm, value: &str, sr: Option<StatementResult>) -> c::generator::Result<BlockStatement> {
+        self.scope.enter();
+
+        for (idx, binding) in arm.bindings.iter().enumerate() {
+            let symbol = self.symbol_table.variable(*binding);
+            let variable = symbol.to_string(&self.string_table);
+            let r#type = self.value_type(symbol.type_id.unwrap_or(TypeId::UNKNOWN));
+            self.scope.push_local_variable(variable.clone(), Storage::Stack);
+            self.statements().push(Statement::Expression(ExpressionStatement {
+                expression: Expression::Code(CodeExpression { code: format!("({type}) val_enm_at((struct val_enm *) {value}, {idx})") }),
+                result: Some(StatementResult::Declare { variable, r#type }),
+            }));
+        }
+
+        let count = arm.block.nodes.len();
+        for (idx, node) in arm.block.nodes.iter().enumerate() {
+            if idx + 1 < count {
+                self.nodes(node.as_ref())?;
+                continue;
+            }
+
+            match &sr {
+                Some(StatementResult::Assign { variable }) => self.branch_result(node.as_ref(), variable.clone())?,
+                _ if Self::is_value(node.as_ref()) => {}
+                _ => self.nodes(node.as_ref())?
+            }
+        }
+
+        let frame = self.scope.leave();
+        let cleanup_statements = frame.cleanup_statements();
+
+        let mut statements = vec![];
+        statements.extend(frame.statements);
+        statements.extend(cleanup_statements);
+        Ok(BlockStatement { statements })
+    }
+
+    // the result owns its value, a borrowed value gets retained - a nested if, loop or match writes into the same result
+    pub(crate) fn branch_result(&mut self, node: &IrTreeNode, variable: String) -> c::generator::Result<()> {
+        let sr = Some(StatementResult::Assign { variable: variable.clone() });
+        match node.node() {
+            If(r#if) => return self.r#if(r#if, sr),
+            Loop(r#loop) => return self.r#loop(r#loop, sr),
+            Match(r#match) => return self.r#match(r#match, sr),
+            _ => {}
+        }
+
+        let expression = self.expression(node)?;
+        let (expression, borrowed) = match (node.node(), expression) {
+            (LiteralBoolean(_) | LiteralNumber(_), expression) => (Self::box_literal(node.type_id, expression), false),
+            (_, expression @ Expression::Variable(_)) => (expression, true),
+            (_, expression) => (expression, false)
+        };
+
+        self.statements().push(Statement::Expression(ExpressionStatement {
+            expression,
+            result: Some(StatementResult::Assign { variable: variable.clone() }),
+        }));
+        if borrowed {
+            self.statements().push(Statement::rc_inc(variable));
+        }
+        Ok(())
+    }
+
+    // a value which ends an arm of a match used as statement has nothing to do
+    pub(crate) fn is_value(node: &IrTreeNode) -> bool {
+        matches!(node.node(), AccessVariable(_) | Calculate(_) | Compare(_) | InstantiateType(_) | InterpolateString(_) | LiteralBoolean(_) | LiteralNumber(_) | LiteralString(_))
+    }
+}
@@ -1,15 +1,16 @@
 use std::collections::HashSet;
 
-use Node::{AccessVariable, Block, Compare, If, LiteralBoolean, LiteralNumber, LiteralString};
+use Node::{AccessElementOfTuple, AccessVariable, AccessVariableOfObject, AssignVariable, AssignVariableOfObject, Block, Compare, If, LiteralBoolean, LiteralNone, LiteralNumber, LiteralString};
 
 use crate::build::c;
-use crate::build::c::{BlockStatement, CodeStatement, DeclareFunctionNode, DeclareStructNode, DefineFunctionNode, DefineStructNode, DirectiveNode, IncludeLocalDirectiveNode, IncludeSystemDirectiveNode, Statement};
+use crate::build::c::{BlockStatement, CodeExpression, CodeStatement, DefineGlobalVariableNode, DeclareFunctionNode, DeclareStructNode, DefineFunctionNode, DefineStructNode, DirectiveNode, IncludeLocalDirectiveNode, IncludeSystemDirectiveNode, Statement};
 use crate::build::c::DirectiveNode::{IncludeLocalDirective, IncludeSystemDirective};
+use crate::build::c::generator::library::Library;
 use crate::build::c::generator::scope::Scope;
 use crate::build::c::Node::DefineFunction;
-use crate::common::{Context, SymbolTable, TypeTable};
+use crate::common::{Context, Inferred, PackageTable, Symbol, SymbolTable, TypeTable};
 use crate::common::node::Node;
-use crate::common::node::Node::{Calculate, CallFunctionOfPackage, DeclareVariable, InterpolateString, Loop};
+use crate::common::node::Node::{BreakLoop, Calculate, CallFunction, CallFunctionOfObject, CallFunctionOfPackage, CallFunctionWithLambda, ContinueLoop, DeclareExternalFunction, DeclareLambda, DeclareFunction, DeclarePackage, DeclareTrait, DeclareType, DeclareVariable, Defer, DefineType, DestructureTuple, InstantiateTuple, InstantiateType, InterpolateString, IteratorLoop, Loop, Match, RangeLoop, ReturnFromFunction, Try};
 use crate::common::StringTable;
 use crate::ir;
 use crate::ir::node::IrTreeNode;
@@ -24,23 +25,42 @@ mod block;
 mod control;
 mod compare;
 mod calculate;
+mod function;
+mod r#type;
+mod ffi;
+mod library;
+mod closure;
+mod r#trait;
+mod r#match;
+mod tuple;
 
 #[derive(Debug)]
 pub enum Error {}
 
 type Result<T> = core::result::Result<T, Error>;
 
+pub(crate) struct Generated {
+    pub(crate) nodes: Vec<c::Node>,
+    // c libraries the program needs to be linked against
+    pub(crate) links: Vec<String>,
+    // declarations of the exported functions and types, only for libraries
+    pub(crate) header: Option<Vec<c::Node>>,
+}
+
 // #[derive(Debug, Clone, Copy)]
 // pub(crate) struct FunctionPointer(usize);
 
-pub(crate) fn generate(ctx: Context, ir: ir::Ir) -> Result<Vec<c::Node>> {
+// a library has no main, it gets named init and shutdown functions and a header instead
+pub(crate) fn generate(ctx: Context, ir: ir::Ir, library: Option<&str>) -> Result<Generated> {
     let mut generator = Generator {
+        package_table: ctx.package_table,
         string_table: ctx.string_table,
         symbol_table: ctx.symbol_table,
         type_table: ctx.type_table,
         scope: Scope::new(),
 
         directives: HashSet::new(),
+        links: Vec::new(),
 
         // function_pointer: FunctionPointer(0),
         function_declarations: Vec::new(),
@@ -48,25 +68,37 @@ pub(crate) fn generate(ctx: Context, ir: ir::Ir) -> Result<Vec<c::Node>> {
 
         struct_definitions: Vec::new(),
         struct_declarations: Vec::new(),
+        tuples: Vec::new(),
+
+        global_definitions: Vec::new(),
+
+        library: library.map(Library::new),
     };
     generator.generate(ir.nodes)
 }
 
 pub(crate) struct Generator {
+    package_table: PackageTable,
     string_table: StringTable,
     symbol_table: SymbolTable,
     type_table: TypeTable,
     scope: Scope,
     directives: HashSet<DirectiveNode>,
+    links: Vec<String>,
     function_declarations: Vec<DeclareFunctionNode>,
     // function_pointer: FunctionPointer,
     function_definitions: Vec<DefineFunctionNode>,
     struct_declarations: Vec<DeclareStructNode>,
     struct_definitions: Vec<DefineStructNode>,
+    // the element types of every tuple struct, its index is part of the struct name
+    tuples: Vec<Box<[Inferred]>>,
+    // globals which refer to functions, like vtables
+    global_definitions: Vec<DefineGlobalVariableNode>,
+    library: Option<Library>,
 }
 
 impl Generator {
-    pub(crate) fn generate(mut self, nodes: Vec<IrTreeNode>) -> Result<Vec<c::Node>> {
+    pub(crate) fn generate(mut self, nodes: Vec<IrTreeNode>) -> Result<Generated> {
 //         self.function_definitions.push(DefineFunctionNode {
 //             identifier: "main".to_string(),
 //             arguments: vec![].into_boxed_slice(),
@@ -88,7 +120,7 @@ impl Generator {
             c::Statement::Code(
                 CodeStatement {
                     code: r#"
-auto tm = mem_test_new_default (1024 * 1024);
+tm = mem_test_new_default (1024 * 1024);
                             "#.to_string(),
                 }
             )
@@ -98,19 +130,31 @@ auto tm = mem_test_new_default (1024 * 1024);
             self.nodes(node)?
         }
 
-        let mut frame = self.scope.leave();
+        let frame = self.scope.leave();
         let cleanup_statements = frame.cleanup_statements();
 
         let mut statements = vec![];
         statements.extend(frame.statements);
         statements.extend(cleanup_statements);
 
-        self.function_definitions.push(DefineFunctionNode {
-            identifier: "main".to_string(),
-            arguments: vec![].into_boxed_slice(),
-            ty: "int".to_string(),
-            block: BlockStatement { statements },
-        });
+        if self.library.is_some() {
+            self.library_define(statements)?;
+            self.library_export(&nodes)?;
+        } else {
+            statements.push(Statement::Code(CodeStatement {
+                code: r#"
+mem_test_verify (tm);
+mem_test_free (tm);
+            "#.to_string(),
+            }));
+
+            self.function_definitions.push(DefineFunctionNode {
+                identifier: "main".to_string(),
+                arguments: vec![].into_boxed_slice(),
+                ty: "int".to_string(),
+                block: BlockStatement { statements },
+            });
+        }
 
 
         self.include_system("stdio.h");
@@ -121,6 +165,13 @@ auto tm = mem_test_new_default (1024 * 1024);
         let mut result = vec![];
         result.extend(self.directives.into_iter().map(|d| c::Node::Directive(d)));
 
+        // every function allocates from the same memory, which main sets up
+        result.push(c::Node::DefineGlobalVariable(DefineGlobalVariableNode {
+            identifier: "tm".to_string(),
+            r#type: "struct mem_test *".to_string(),
+            expression: c::Expression::Code(CodeExpression { code: "NULL".to_string() }),
+        }));
+
         result.extend(
             self.struct_declarations
                 .into_iter()
@@ -138,14 +189,11 @@ auto tm = mem_test_new_default (1024 * 1024);
                 .map(|df| c::Node::DeclareFunction(df)),
         );
 
-        self.function_definitions[0].block.statements.extend(vec![
-            Statement::Code(CodeStatement {
-                code: r#"
-mem_test_verify (tm);
-mem_test_free (tm);
-            "#.to_string(),
-            })
-        ]);
+        result.extend(
+            self.global_definitions
+                .into_iter()
+                .map(c::Node::DefineGlobalVariable),
+        );
 
         result.extend(
             self.function_definitions
@@ -153,7 +201,8 @@ mem_test_free (tm);
                 .map(|df| DefineFunction(df)),
         );
 
-        Ok(result)
+        let header = self.library.map(|library| library.header);
+        Ok(Generated { nodes: result, links: self.links, header })
     }
 
     pub(crate) fn statements(&mut self) -> &mut Vec<c::Statement> {
@@ -165,12 +214,31 @@ mem_test_free (tm);
 
     pub(crate) fn nodes(&mut self, ir: &IrTreeNode) -> Result<()> {
         match ir.node() {
+            AssignVariable(node) => self.assign_variable(node)?,
+            AssignVariableOfObject(node) => self.assign_variable_of_object(node)?,
             Block(node) => self.block(node)?,
-            // BreakLoop(node) => self.r#break(node)?,
+            BreakLoop(node) => self.r#break(node)?,
+            CallFunction(node) => self.call_function(node)?,
+            CallFunctionOfObject(node) => self.call_function_of_object(node)?,
             CallFunctionOfPackage(node) => self.call_function_of_package(node)?,
+            CallFunctionWithLambda(node) => self.call_function_with_lambda(node)?,
+            ContinueLoop(node) => self.r#continue(node)?,
+            DeclareExternalFunction(node) => self.declare_external_function(node)?,
+            DeclareFunction(node) => self.declare_function(node)?,
+            DeclarePackage(node) => self.declare_package(node)?,
+            DeclareTrait(_) => {}
+            DeclareType(node) => self.declare_type(node)?,
             DeclareVariable(node) => self.declare_variable(node)?,
-            If(node) => self.r#if(node)?,
+            Defer(node) => self.defer(node)?,
+            DefineType(node) => self.define_type(node)?,
+            DestructureTuple(node) => self.destructure_tuple(node)?,
+            If(node) => self.r#if(node, None)?,
             Loop(node) => self.r#loop(node, None)?,
+            Match(node) => self.r#match(node, None)?,
+            RangeLoop(node) => self.range_loop(node)?,
+            IteratorLoop(node) => self.iterator_loop(node)?,
+            ReturnFromFunction(node) => self.r#return(node)?,
+            Try(node) => { self.r#try(node, ir.type_id)?; }
             _ => unimplemented!("{ir:#?}")
         }
         Ok(())
@@ -182,14 +250,30 @@ mem_test_free (tm);
 
     pub(crate) fn expression(&mut self, ir: &IrTreeNode) -> Result<c::Expression> {
         match ir.node() {
+            // a function passed by name
+            AccessElementOfTuple(node) => Ok(self.access_element_of_tuple(node)?),
+            AccessVariable(node) if matches!(self.symbol_table[node.variable], Symbol::Function(_)) => Ok(self.closure_expression(ir)?),
             AccessVariable(node) => Ok(c::Expression::Variable(self.access_variable(node)?)),
+            AccessVariableOfObject(node) => Ok(self.access_variable_of_object_expression(node, ir.type_id)?),
             Calculate(node) => Ok(self.calculate(node)?),
+            CallFunction(node) => Ok(self.call_expression(node.function, &node.arguments, ir.type_id)?),
+            CallFunctionOfObject(node) => Ok(self.call_function_of_object_expression(node, ir.type_id)?),
+            CallFunctionOfPackage(node) => Ok(self.call_expression(node.function, &node.arguments, ir.type_id)?),
+            CallFunctionWithLambda(node) => Ok(self.call_function_with_lambda_expression(node, ir.type_id)?),
             Compare(node) => Ok(self.compare(node)?),
+            DeclareLambda(_) => Ok(self.closure_expression(ir)?),
+            InstantiateTuple(node) => Ok(self.instantiate_tuple(node)?),
+            InstantiateType(node) => Ok(self.instantiate_type_expression(node)?),
             InterpolateString(node) => Ok(c::Expression::Variable(self.interpolate_string(node)?)),
             LiteralBoolean(node) => Ok(self.literal_bool(node)?),
+            LiteralNone(_) => Ok(c::Expression::Code(CodeExpression { code: "nullptr".to_string() })),
             LiteralNumber(node) => Ok(self.literal_number(node)?),
             LiteralString(node) => Ok(self.literal_string(node)?),
-            _ => unimplemented!("{:#?}", ir)
+            Try(node) => Ok(self.r#try(node, ir.type_id)?),
+            _ => match self.literal_value(ir)? {
+                Some(literal) => Ok(literal),
+                None => unimplemented!("{:#?}", ir)
+            }
         }
     }
 
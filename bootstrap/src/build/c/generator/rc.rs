@@ -1,7 +1,17 @@
 use crate::build::c;
-use crate::build::c::{CallFunctionStatement, Statement, VariableExpression};
+use crate::build::c::{BlockStatement, CallFunctionExpression, CallFunctionStatement, CodeExpression, Expression, ExpressionStatement, IfStatement, Statement, VariableExpression};
 
 impl Statement {
+    pub fn rc_inc<T: Into<String>>(variable: T) -> c::Statement {
+        Statement::CallFunction(CallFunctionStatement {
+            function: "val_rc_inc".to_string(),
+            arguments: Box::new([
+                c::Expression::Variable(VariableExpression { variable: variable.into(), cast: Some("struct val *".to_string()) })
+            ]),
+            result: None,
+        })
+    }
+
     pub fn rc_dec<T: Into<String>>(variable: T) -> c::Statement {
         Statement::CallFunction(CallFunctionStatement {
             function: "val_rc_dec".to_string(),
@@ -11,4 +21,48 @@ impl Statement {
             result: None,
         })
     }
-}
\ No newline at end of file
+
+    pub fn rc_inc_optional<T: Into<String>>(variable: T) -> c::Statement {
+        let variable = variable.into();
+        Statement::If(IfStatement {
+            condition: Expression::Code(CodeExpression { code: format!("{variable} != nullptr") }),
+            then: BlockStatement { statements: vec![Statement::rc_inc(variable)] },
+            otherwise: None,
+        })
+    }
+
+    // a value which may be none only gets retained or released if it is there
+    pub fn rc_dec_optional<T: Into<String>>(variable: T) -> c::Statement {
+        let variable = variable.into();
+        Statement::If(IfStatement {
+            condition: Expression::Code(CodeExpression { code: format!("{variable} != nullptr") }),
+            then: BlockStatement { statements: vec![Statement::rc_dec(variable)] },
+            otherwise: None,
+        })
+    }
+
+    // a tuple is a plain c struct, its elements get retained or released by the functions generated for its shape
+    pub fn rc_inc_tuple<T: Into<String>>(variable: T, tuple: usize) -> c::Statement {
+        Statement::Expression(ExpressionStatement {
+            expression: Expression::CallFunction(CallFunctionExpression {
+                function: format!("tuple_{tuple}_rc_inc"),
+                arguments: Box::new([
+                    c::Expression::Variable(VariableExpression { variable: variable.into(), cast: None })
+                ]),
+            }),
+            result: None,
+        })
+    }
+
+    pub fn rc_dec_tuple<T: Into<String>>(variable: T, tuple: usize) -> c::Statement {
+        Statement::Expression(ExpressionStatement {
+            expression: Expression::CallFunction(CallFunctionExpression {
+                function: format!("tuple_{tuple}_rc_dec"),
+                arguments: Box::new([
+                    c::Expression::Variable(VariableExpression { variable: variable.into(), cast: None })
+                ]),
+            }),
+            result: None,
+        })
+    }
+}
@@ -1,5 +1,5 @@
 use crate::build::c;
-use crate::build::c::Statement;
+use crate::build::c::{BlockStatement, Statement, StatementResult};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Argument(pub u16);
@@ -48,7 +48,10 @@ impl Into<String> for Temp {
 pub enum Storage {
     Memory,
     Stack,
-
+    // memory which may be null, because the value is none
+    Optional,
+    // a tuple struct on the stack, whose elements live in memory
+    Tuple(usize),
 }
 
 pub enum Variable {
@@ -78,20 +81,27 @@ pub(crate) struct Frame {
     args_storage: Vec<Storage>,
     local_variables: Vec<LocalVariable>,
     local_variables_storage: Vec<Storage>,
+    // temps of a nested frame are numbered after the ones of the frame it is nested in,
+    // so that leaving several frames at once releases each temp by a name which is not shadowed
+    first_temp: u16,
     temps: u16,
     temps_storage: Vec<Storage>,
+    // defer blocks run before anything of the frame gets released, the last one first
+    defers: Vec<BlockStatement>,
     pub statements: Vec<Statement>,
 }
 
 impl Frame {
-    fn new() -> Self {
+    fn new(first_temp: u16) -> Self {
         Self {
             args: 0,
             args_storage: vec![],
             local_variables: vec![],
             local_variables_storage: vec![],
+            first_temp,
             temps: 0,
             temps_storage: vec![],
+            defers: vec![],
             statements: vec![],
         }
     }
@@ -112,29 +122,39 @@ impl Frame {
     fn push_temp(&mut self, storage: Storage) -> Temp {
         self.temps += 1;
         self.temps_storage.push(storage);
-        Temp(self.temps)
+        Temp(self.first_temp + self.temps)
     }
 
     pub fn cleanup_statements(&self) -> Vec<c::Statement> {
-        let mut result = vec![];
+        let mut result: Vec<c::Statement> = self.defers.iter().rev().cloned().map(Statement::Block).collect();
 
         for arg in 0..self.args {
-            if self.args_storage[arg as usize] == Storage::Memory {
-                result.push(Statement::rc_dec(Argument(arg + 1)))
+            match self.args_storage[arg as usize] {
+                Storage::Memory => result.push(Statement::rc_dec(Argument(arg + 1))),
+                Storage::Optional => result.push(Statement::rc_dec_optional(Argument(arg + 1))),
+                Storage::Tuple(tuple) => result.push(Statement::rc_dec_tuple(Argument(arg + 1), tuple)),
+                Storage::Stack => {}
             }
         }
 
         let mut counter = 0;
         for local in self.local_variables.iter() {
-            if self.local_variables_storage[counter] == Storage::Memory {
-                result.push(Statement::rc_dec(local.clone()))
+            match self.local_variables_storage[counter] {
+                Storage::Memory => result.push(Statement::rc_dec(local.clone())),
+                Storage::Optional => result.push(Statement::rc_dec_optional(local.clone())),
+                Storage::Tuple(tuple) => result.push(Statement::rc_dec_tuple(local.clone(), tuple)),
+                Storage::Stack => {}
             }
             counter += 1
         }
 
         for temp in 0..self.temps {
-            if self.temps_storage[temp as usize] == Storage::Memory {
-                result.push(Statement::rc_dec(Temp(temp + 1)))
+            let name = Temp(self.first_temp + temp + 1);
+            match self.temps_storage[temp as usize] {
+                Storage::Memory => result.push(Statement::rc_dec(name)),
+                Storage::Optional => result.push(Statement::rc_dec_optional(name)),
+                Storage::Tuple(tuple) => result.push(Statement::rc_dec_tuple(name, tuple)),
+                Storage::Stack => {}
             }
         }
 
@@ -142,19 +162,91 @@ impl Frame {
     }
 }
 
+// the frame the body of a loop runs in, and where a break with a value puts it
+pub(crate) struct Loop {
+    frame: usize,
+    result: Option<StatementResult>,
+    label: Option<String>,
+    // c labels are only placed if a break or continue of a nested loop jumps to them
+    id: u16,
+    pub break_label: Option<String>,
+    pub continue_label: Option<String>,
+}
+
 pub(crate) struct Scope {
     frames: Vec<Frame>,
+    loops: Vec<Loop>,
+    // c labels are visible in the whole function, every labeled loop gets a number of its own
+    labeled_loops: u16,
 }
 
 impl Scope {
     pub(crate) fn new() -> Self {
         Self {
-            frames: vec![Frame::new()]
+            frames: vec![Frame::new(0)],
+            loops: vec![],
+            labeled_loops: 0,
         }
     }
 
     pub(crate) fn enter(&mut self) {
-        self.frames.push(Frame::new())
+        let frame = self.frame();
+        self.frames.push(Frame::new(frame.first_temp + frame.temps))
+    }
+
+    pub(crate) fn enter_loop(&mut self, result: Option<StatementResult>, label: Option<String>) {
+        self.enter();
+        if label.is_some() {
+            self.labeled_loops += 1;
+        }
+        self.loops.push(Loop {
+            frame: self.frames.len() - 1,
+            result,
+            label,
+            id: self.labeled_loops,
+            break_label: None,
+            continue_label: None,
+        });
+    }
+
+    pub(crate) fn leave_loop(&mut self) -> (Frame, Loop) {
+        let r#loop = self.loops.pop().unwrap();
+        (self.leave(), r#loop)
+    }
+
+    // the loop break and continue refer to, the innermost one if there is no label
+    fn loop_index(&self, label: Option<&str>) -> usize {
+        match label {
+            Some(label) => self.loops.iter().rposition(|l| l.label.as_deref() == Some(label)).unwrap(),
+            None => self.loops.len() - 1
+        }
+    }
+
+    pub(crate) fn loop_result(&self, label: Option<&str>) -> Option<StatementResult> {
+        self.loops[self.loop_index(label)].result.clone()
+    }
+
+    // the c label a break of a nested loop jumps to, the innermost loop gets left by a plain break
+    pub(crate) fn loop_break_label(&mut self, label: Option<&str>) -> Option<String> {
+        let index = self.loop_index(label);
+        if index + 1 == self.loops.len() {
+            return None;
+        }
+        let r#loop = &mut self.loops[index];
+        let result = format!("{}_{}_break", r#loop.label.as_ref().unwrap(), r#loop.id);
+        r#loop.break_label = Some(result.clone());
+        Some(result)
+    }
+
+    pub(crate) fn loop_continue_label(&mut self, label: Option<&str>) -> Option<String> {
+        let index = self.loop_index(label);
+        if index + 1 == self.loops.len() {
+            return None;
+        }
+        let r#loop = &mut self.loops[index];
+        let result = format!("{}_{}_continue", r#loop.label.as_ref().unwrap(), r#loop.id);
+        r#loop.continue_label = Some(result.clone());
+        Some(result)
     }
 
     // pub(crate) fn leave(&mut self) {
@@ -171,6 +263,25 @@ impl Scope {
         self.frames.last().unwrap()
     }
 
+    // cleanup of every frame, innermost first - used when leaving all of them at once
+    pub(crate) fn cleanup_statements(&self) -> Vec<Statement> {
+        let mut result = vec![];
+        for frame in self.frames.iter().rev() {
+            result.extend(frame.cleanup_statements());
+        }
+        result
+    }
+
+    // cleanup of every frame break and continue leave, from the innermost one down to the body of the loop
+    pub(crate) fn loop_cleanup_statements(&self, label: Option<&str>) -> Vec<Statement> {
+        let frame = self.loops[self.loop_index(label)].frame;
+        let mut result = vec![];
+        for frame in self.frames[frame..].iter().rev() {
+            result.extend(frame.cleanup_statements());
+        }
+        result
+    }
+
     pub(crate) fn push_argument(&mut self, storage: Storage) -> Argument {
         self.frames.last_mut().unwrap().push_argument(storage)
     }
@@ -183,6 +294,11 @@ impl Scope {
         self.frames.last_mut().unwrap().push_temp(storage)
     }
 
+    // copied into every cleanup of the current frame which gets generated from now on
+    pub(crate) fn push_defer(&mut self, block: BlockStatement) {
+        self.frames.last_mut().unwrap().defers.push(block)
+    }
+
     pub(crate) fn statements(&mut self) -> &mut Vec<Statement> {
         &mut self.frames.last_mut().unwrap().statements
     }
@@ -1,5 +1,5 @@
 use crate::build::c;
-use crate::build::c::{CallFunctionStatement, CallFunctionStatementResult, CodeExpression, DeclareArrayStatement, ExpressionStatement, StatementResult, LiteralExpression, LiteralInt4Expression, LiteralStringExpression, Statement, VariableExpression};
+use crate::build::c::{CallFunctionExpression, CallFunctionStatement, CallFunctionStatementResult, CodeExpression, DeclareArrayStatement, ExpressionStatement, StatementResult, LiteralExpression, LiteralInt4Expression, LiteralStringExpression, Statement, VariableExpression};
 use crate::build::c::Expression::{Code, Literal, Variable};
 use crate::build::c::generator::{Generator, scope};
 use crate::build::c::generator::scope::{LocalVariable, Storage};
@@ -256,6 +256,23 @@ impl Generator {
                         }),
                     }));
                     variables.push(scope::Variable::Temp(temp, Storage::Memory))
+                } else if symbol.type_id.is_some() && TypeId::ANY == symbol.type_id.unwrap() {
+                    // the value of a type parameter, the runtime knows its kind
+                    let temp = self.scope.push_temp(Storage::Memory);
+
+                    let variable = symbol.to_string(&self.string_table);
+
+                    self.statements().push(Statement::Expression(ExpressionStatement {
+                        expression: c::Expression::CallFunction(CallFunctionExpression {
+                            function: "val_to_str".to_string(),
+                            arguments: Box::new([
+                                c::Expression::Variable(VariableExpression { variable, cast: None }),
+                                c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() })
+                            ]),
+                        }),
+                        result: Some(StatementResult::Declare { variable: temp.to_string(), r#type: "struct val_str *".to_string() }),
+                    }));
+                    variables.push(scope::Variable::Temp(temp, Storage::Memory))
                 } else {
                     let variable = symbol.to_string(&self.string_table);
 
@@ -296,8 +313,34 @@ impl Generator {
                 variables.push(scope::Variable::Temp(temp, Storage::Memory))
             } else {
                 match node.node() {
-                    IrNode::Calculate(_) => {
-                        let expression = self.expression(node)?;
+                    IrNode::AccessVariableOfObject(access) if !self.is_external_object(access.object) => {
+                        // the field is borrowed from the object, only its string is owned
+                        let Code(CodeExpression { code }) = self.access_variable_of_object(access, node.type_id)? else { unreachable!() };
+                        if node.type_id == TypeId::STRING {
+                            variables.push(scope::Variable::Variable(LocalVariable(code), Storage::Memory));
+                            continue;
+                        }
+
+                        let string = self.scope.push_temp(Storage::Memory);
+                        self.statements().push(Statement::Expression(ExpressionStatement {
+                            expression: c::Expression::CallFunction(CallFunctionExpression {
+                                function: "val_to_str".to_string(),
+                                arguments: Box::new([
+                                    c::Expression::Variable(VariableExpression { variable: code, cast: Some("struct val *".to_string()) }),
+                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() })
+                                ]),
+                            }),
+                            result: Some(StatementResult::Declare { variable: string.to_string(), r#type: "struct val_str *".to_string() }),
+                        }));
+
+                        variables.push(scope::Variable::Temp(string, Storage::Memory));
+                    }
+                    IrNode::AccessVariableOfObject(_) | IrNode::Calculate(_) => {
+                        // the boxed field is owned by the temp below
+                        let expression = match node.node() {
+                            IrNode::AccessVariableOfObject(access) => self.access_variable_of_object(access, node.type_id)?,
+                            _ => self.expression(node)?
+                        };
 
                         let string = self.scope.push_temp(Storage::Memory);
 
@@ -324,6 +367,24 @@ impl Generator {
 
                         variables.push(scope::Variable::Temp(string, Storage::Memory));
                     }
+                    IrNode::AccessElementOfTuple(access) => {
+                        // the element is borrowed from the tuple, only its string is owned
+                        let Code(CodeExpression { code }) = self.access_element_of_tuple(access)? else { unreachable!() };
+
+                        let string = self.scope.push_temp(Storage::Memory);
+                        self.statements().push(Statement::Expression(ExpressionStatement {
+                            expression: c::Expression::CallFunction(CallFunctionExpression {
+                                function: "val_to_str".to_string(),
+                                arguments: Box::new([
+                                    c::Expression::Variable(VariableExpression { variable: code, cast: Some("struct val *".to_string()) }),
+                                    c::Expression::Code(CodeExpression { code: "MEM(tm)".to_string() })
+                                ]),
+                            }),
+                            result: Some(StatementResult::Declare { variable: string.to_string(), r#type: "struct val_str *".to_string() }),
+                        }));
+
+                        variables.push(scope::Variable::Temp(string, Storage::Memory));
+                    }
                     IrNode::Compare(_) => {
                         let string = self.scope.push_temp(Storage::Memory);
 
@@ -402,7 +463,7 @@ impl Generator {
             ]),
             result: Some(CallFunctionStatementResult {
                 identifier: arg.to_string(),
-                r#type: "const struct val_str *".to_string(),
+                r#type: "struct val_str *".to_string(),
             }),
         }));
 
@@ -0,0 +1,173 @@
+use std::rc::Rc;
+
+use crate::build::c;
+use crate::build::c::{CallFunctionExpression, CodeExpression, DefineGlobalVariableNode, Expression, ExpressionStatement, Statement, StatementResult, VariableExpression};
+use crate::build::c::generator::Generator;
+use crate::build::c::generator::r#type::c_type;
+use crate::build::c::generator::scope::Storage;
+use crate::common::{Dispatch, Inferred, Span, Symbol, SymbolId, TypeId};
+use crate::common::node::Node::AccessVariable;
+use crate::ir::{IrAccessVariableNode, IrCallFunctionOfObjectNode, IrDefineTypeNode, IrTreeNode};
+
+impl Generator {
+    // methods are plain functions, every implemented trait gets a vtable with the methods in the order of the trait
+    pub(crate) fn define_type(&mut self, node: &IrDefineTypeNode) -> c::generator::Result<()> {
+        for function in &node.functions {
+            self.nodes(function)?;
+        }
+
+        let type_id = self.symbol_table[node.r#type].type_id().unwrap_or(TypeId::UNKNOWN);
+        for r#trait in self.type_table[type_id].traits.clone() {
+            let functions = self.type_table[r#trait].methods.iter()
+                .map(|method| self.type_table.method(type_id, method.name).unwrap().function)
+                .map(|function| format!("(void *) {}", self.function_identifier(function)))
+                .collect::<Vec<_>>();
+
+            self.global_definitions.push(DefineGlobalVariableNode {
+                identifier: format!("{}[]", self.vtable_identifier(type_id, r#trait)),
+                r#type: "void *".to_string(),
+                expression: Expression::Code(CodeExpression { code: format!("{{ {} }}", functions.join(", ")) }),
+            });
+        }
+        Ok(())
+    }
+
+    pub(crate) fn vtable_identifier(&self, type_id: TypeId, r#trait: TypeId) -> String {
+        format!(
+            "{}_{}_vtable",
+            self.string_table.get(self.type_table[r#trait].name.0),
+            self.string_table.get(self.type_table[type_id].name.0)
+        )
+    }
+
+    // the trait of a parameter which takes trait values, either declared as trait or as bound type parameter
+    pub(crate) fn parameter_trait(&self, function: SymbolId, parameter: &Inferred) -> Option<TypeId> {
+        match parameter {
+            Inferred::Parameter(parameter) => match &self.symbol_table[function] {
+                Symbol::Function(symbol) => symbol.bounds.iter().find(|(p, _)| p == parameter).map(|(_, bound)| *bound),
+                _ => None
+            },
+            _ => parameter.type_id().filter(|type_id| self.type_table.is_trait(*type_id))
+        }
+    }
+
+    // the trait value is a temp, it retains the value for as long as it lives
+    pub(crate) fn dyn_new(&mut self, expression: Expression, type_id: TypeId, r#trait: TypeId, cast: Option<String>) -> Expression {
+        let temp = self.scope.push_temp(Storage::Memory);
+        let expression = self.dyn_new_expression(expression, type_id, r#trait);
+        self.statements().push(Statement::Expression(ExpressionStatement {
+            expression,
+            result: Some(StatementResult::Declare {
+                variable: temp.to_string(),
+                r#type: "struct val_dyn *".to_string(),
+            }),
+        }));
+        Expression::Variable(VariableExpression { variable: temp.to_string(), cast })
+    }
+
+    pub(crate) fn dyn_new_expression(&self, expression: Expression, type_id: TypeId, r#trait: TypeId) -> Expression {
+        let expression = match expression {
+            Expression::Variable(VariableExpression { variable, .. }) => Expression::Variable(VariableExpression { variable, cast: Some(c_type(TypeId::ANY)) }),
+            expression => expression
+        };
+
+        Expression::CallFunction(CallFunctionExpression {
+            function: "val_dyn_new".to_string(),
+            arguments: Box::new([
+                Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                Expression::Code(CodeExpression { code: self.vtable_identifier(type_id, r#trait) }),
+                expression,
+            ]),
+        })
+    }
+
+    // a bound type parameter gets returned as trait value, the caller which knows the type takes the value out of it
+    pub(crate) fn result_unwrapped(&mut self, function: SymbolId, expression: Expression, inferred: TypeId) -> Expression {
+        let Inferred::Function(_, result) = self.symbol_table.signature(function) else {
+            return expression;
+        };
+        let Some(r#trait) = self.parameter_trait(function, result) else {
+            return expression;
+        };
+        if !matches!(result.as_ref(), Inferred::Parameter(_)) || inferred == r#trait || !self.type_table.implements(inferred, r#trait) {
+            return expression;
+        }
+
+        let temp = self.scope.push_temp(Storage::Stack);
+        self.statements().push(Statement::Expression(ExpressionStatement {
+            expression,
+            result: Some(StatementResult::Declare {
+                variable: temp.to_string(),
+                r#type: c_type(TypeId::ANY),
+            }),
+        }));
+
+        Expression::CallFunction(CallFunctionExpression {
+            function: "val_dyn_unwrap".to_string(),
+            arguments: Box::new([Expression::Variable(VariableExpression { variable: temp.to_string(), cast: Some("struct val_dyn *".to_string()) })]),
+        })
+    }
+
+    pub(crate) fn call_function_of_object(&mut self, node: &IrCallFunctionOfObjectNode) -> c::generator::Result<()> {
+        let expression = self.call_of_object(node)?;
+        self.result_statement(node.function, expression);
+        Ok(())
+    }
+
+    pub(crate) fn call_function_of_object_expression(&mut self, node: &IrCallFunctionOfObjectNode, inferred: TypeId) -> c::generator::Result<Expression> {
+        let expression = self.call_of_object(node)?;
+        Ok(self.result_expression(node.function, expression, inferred))
+    }
+
+    pub(crate) fn call_function_of_object_value(&mut self, node: &IrCallFunctionOfObjectNode, inferred: TypeId) -> c::generator::Result<Expression> {
+        let expression = self.call_of_object(node)?;
+        Ok(self.result_value(node.function, expression, inferred))
+    }
+
+    // the object is the first argument, a trait value calls the function its vtable holds with the value it wraps
+    fn call_of_object(&mut self, node: &IrCallFunctionOfObjectNode) -> c::generator::Result<Expression> {
+        let variable = self.symbol_table.variable(node.object);
+        let object = variable.to_string(&self.string_table);
+
+        let mut arguments = Vec::with_capacity(node.arguments.len() + 1);
+        arguments.push(Rc::new(IrTreeNode::new(
+            AccessVariable(IrAccessVariableNode { variable: node.object }),
+            Span::default(),
+            variable.type_id.unwrap_or(TypeId::UNKNOWN),
+        )));
+        arguments.extend(node.arguments.iter().cloned());
+
+        let Dispatch::Vtable(idx) = node.dispatch else {
+            return self.call(node.function, &arguments);
+        };
+
+        let Inferred::Function(parameters, _) = self.symbol_table.signature(node.function).clone() else {
+            unreachable!()
+        };
+
+        let mut expressions = self.generate_boxed_call_arguments(node.function, &arguments)?.into_vec();
+        expressions[0] = Expression::Code(CodeExpression { code: format!("((struct val_dyn *) {object})->value") });
+
+        // the implementation takes the values of its own type, not trait values
+        for (idx, (parameter, argument)) in parameters.iter().zip(arguments.iter()).enumerate().skip(1) {
+            if matches!(parameter, Inferred::Parameter(p) if self.string_table.get(*p) == "Self") && (argument.type_id == TypeId::ANY || self.type_table.is_trait(argument.type_id)) {
+                if let Expression::Variable(VariableExpression { variable, .. }) = &expressions[idx] {
+                    expressions[idx] = Expression::Code(CodeExpression { code: format!("((struct val_dyn *) {variable})->value") });
+                }
+            }
+        }
+
+        let function = format!(
+            "(({} (*)({})) val_dyn_fn_at((struct val_dyn *) {}, {}))",
+            self.value_type(self.function_return(node.function)),
+            parameters.iter().map(|p| self.value_type(p.type_id().unwrap_or(TypeId::UNKNOWN))).collect::<Vec<_>>().join(", "),
+            object,
+            idx
+        );
+
+        Ok(Expression::CallFunction(CallFunctionExpression {
+            function,
+            arguments: expressions.into_boxed_slice(),
+        }))
+    }
+}
@@ -0,0 +1,221 @@
+use crate::build::c;
+use crate::build::c::{CallFunctionExpression, CodeExpression, DeclareVariableStatement, DefineStructFieldNode, DefineStructNode, Expression, ExpressionStatement, InitialiseStructExpression, InitialiseStructField, Statement, StatementResult, VariableExpression};
+use crate::build::c::generator::Generator;
+use crate::build::c::generator::r#type::{box_function, c_type};
+use crate::build::c::generator::scope::Storage;
+use crate::common::{Inferred, Symbol, SymbolId, TypeId};
+use crate::common::node::Node::{AccessElementOfTuple, AccessVariable, CallFunction, CallFunctionOfPackage, InstantiateTuple, LiteralBoolean, LiteralNumber};
+use crate::ir::{IrAccessElementOfTupleNode, IrDestructureTupleNode, IrInstantiateTupleNode, IrTreeNode};
+
+pub(crate) fn tuple_type(tuple: usize) -> String {
+    format!("struct tuple_{tuple}")
+}
+
+// how an element is held - nested tuples are held by value, everything else like a variable of its type
+pub(crate) fn retain(variable: String, storage: Storage) -> Option<Statement> {
+    match storage {
+        Storage::Memory => Some(Statement::rc_inc(variable)),
+        Storage::Optional => Some(Statement::rc_inc_optional(variable)),
+        Storage::Tuple(tuple) => Some(Statement::rc_inc_tuple(variable, tuple)),
+        Storage::Stack => None
+    }
+}
+
+pub(crate) fn release(variable: String, storage: Storage) -> Option<Statement> {
+    match storage {
+        Storage::Memory => Some(Statement::rc_dec(variable)),
+        Storage::Optional => Some(Statement::rc_dec_optional(variable)),
+        Storage::Tuple(tuple) => Some(Statement::rc_dec_tuple(variable, tuple)),
+        Storage::Stack => None
+    }
+}
+
+impl Generator {
+    // every shape of tuple becomes a struct of its own with the fields _0, _1, .. together with
+    // functions which retain and release all of its elements
+    pub(crate) fn tuple(&mut self, elements: &[Inferred]) -> usize {
+        if let Some(tuple) = self.tuples.iter().position(|t| t.as_ref() == elements) {
+            return tuple;
+        }
+
+        let mut fields = Vec::with_capacity(elements.len());
+        let mut storages = Vec::with_capacity(elements.len());
+        for (idx, element) in elements.iter().enumerate() {
+            fields.push(DefineStructFieldNode {
+                identifier: format!("_{idx}"),
+                ty: self.inferred_type(element),
+            });
+            storages.push(self.inferred_storage(element));
+        }
+
+        let tuple = self.tuples.len();
+        self.tuples.push(elements.into());
+        self.struct_definitions.push(DefineStructNode {
+            identifier: format!("tuple_{tuple}"),
+            fields: fields.into_boxed_slice(),
+        });
+
+        let retained = storages.iter().enumerate()
+            .filter_map(|(idx, storage)| retain(format!("tuple._{idx}"), *storage))
+            .collect();
+        self.function_define(format!("tuple_{tuple}_rc_inc"), vec![("tuple".to_string(), tuple_type(tuple))], "void".to_string(), retained);
+
+        let released = storages.iter().enumerate()
+            .filter_map(|(idx, storage)| release(format!("tuple._{idx}"), *storage))
+            .collect();
+        self.function_define(format!("tuple_{tuple}_rc_dec"), vec![("tuple".to_string(), tuple_type(tuple))], "void".to_string(), released);
+
+        tuple
+    }
+
+    // a record is laid out like the tuple of its values
+    pub(crate) fn tuple_of(&mut self, inferred: &Inferred) -> Option<usize> {
+        match inferred {
+            Inferred::Tuple(elements) => Some(self.tuple(elements)),
+            Inferred::Record(fields) => Some(self.tuple(&fields.iter().map(|(_, f)| f.clone()).collect::<Vec<_>>())),
+            _ => None
+        }
+    }
+
+    pub(crate) fn inferred_type(&mut self, inferred: &Inferred) -> String {
+        match self.tuple_of(inferred) {
+            Some(tuple) => tuple_type(tuple),
+            None => self.value_type(inferred.type_id().unwrap_or(TypeId::UNKNOWN))
+        }
+    }
+
+    pub(crate) fn inferred_storage(&mut self, inferred: &Inferred) -> Storage {
+        if let Some(tuple) = self.tuple_of(inferred) {
+            return Storage::Tuple(tuple);
+        }
+        match inferred {
+            Inferred::Optional(_) => Storage::Optional,
+            inferred if self.type_table.is_external(inferred.type_id().unwrap_or(TypeId::UNKNOWN)) => Storage::Stack,
+            _ => Storage::Memory
+        }
+    }
+
+    // the type of a value as far as the tuple it may end up in has to know it
+    pub(crate) fn node_inferred(&self, node: &IrTreeNode) -> Inferred {
+        match node.node() {
+            AccessVariable(access) if matches!(self.symbol_table[access.variable], Symbol::Variable(_)) => {
+                self.symbol_table.variable(access.variable).inferred.clone()
+            }
+            CallFunction(call) => self.function_result(call.function),
+            CallFunctionOfPackage(call) => self.function_result(call.function),
+            InstantiateTuple(instantiate) => Inferred::Tuple(instantiate.nodes.iter().map(|n| self.node_inferred(n)).collect()),
+            AccessElementOfTuple(access) => match self.node_inferred(&access.node) {
+                Inferred::Tuple(elements) => elements[access.index].clone(),
+                Inferred::Record(fields) => fields[access.index].1.clone(),
+                _ => Inferred::from_type_id(node.type_id)
+            },
+            _ => Inferred::from_type_id(node.type_id)
+        }
+    }
+
+    fn function_result(&self, function: SymbolId) -> Inferred {
+        match self.symbol_table.signature(function) {
+            Inferred::Function(_, result) => result.as_ref().clone(),
+            _ => Inferred::Unknown
+        }
+    }
+
+    pub(crate) fn function_return_tuple(&mut self, function: SymbolId) -> Option<usize> {
+        let result = self.function_result(function);
+        self.tuple_of(&result)
+    }
+
+    // the elements are borrowed by the tuple, literals get boxed first
+    pub(crate) fn instantiate_tuple(&mut self, node: &IrInstantiateTupleNode) -> c::generator::Result<Expression> {
+        let elements: Box<[Inferred]> = node.nodes.iter().map(|n| self.node_inferred(n)).collect();
+        let tuple = self.tuple(&elements);
+
+        let mut fields = Vec::with_capacity(node.nodes.len());
+        for (idx, element) in node.nodes.iter().enumerate() {
+            let expression = self.expression(element)?;
+
+            let expression = if matches!(element.node(), LiteralBoolean(_) | LiteralNumber(_)) {
+                let temp = self.scope.push_temp(Storage::Memory);
+                self.statements().push(Statement::Expression(ExpressionStatement {
+                    expression: Expression::CallFunction(CallFunctionExpression {
+                        function: box_function(element.type_id),
+                        arguments: Box::new([
+                            Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                            expression
+                        ]),
+                    }),
+                    result: Some(StatementResult::Declare {
+                        variable: temp.to_string(),
+                        r#type: c_type(element.type_id),
+                    }),
+                }));
+                Expression::Variable(VariableExpression { variable: temp.to_string(), cast: None })
+            } else {
+                expression
+            };
+
+            fields.push(InitialiseStructField { identifier: format!("_{idx}"), expression });
+        }
+
+        let temp = self.scope.push_temp(Storage::Tuple(tuple));
+        self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
+            variable: temp.to_string(),
+            r#type: tuple_type(tuple),
+            expression: Expression::StructInitialisation(InitialiseStructExpression { fields: fields.into_boxed_slice() }),
+        }));
+        self.statements().push(Statement::rc_inc_tuple(temp, tuple));
+
+        Ok(Expression::Variable(VariableExpression { variable: temp.to_string(), cast: None }))
+    }
+
+    // the element is borrowed from the tuple
+    pub(crate) fn access_element_of_tuple(&mut self, node: &IrAccessElementOfTupleNode) -> c::generator::Result<Expression> {
+        let tuple = self.tuple_value(&node.node)?;
+        Ok(Expression::Code(CodeExpression { code: format!("{tuple}._{}", node.index) }))
+    }
+
+    // every variable retains its element, the tuple itself gets released with its frame
+    pub(crate) fn destructure_tuple(&mut self, node: &IrDestructureTupleNode) -> c::generator::Result<()> {
+        let tuple = self.tuple_value(&node.value)?;
+
+        for (idx, variable) in node.variables.iter().enumerate() {
+            let expression = Expression::Code(CodeExpression { code: format!("{tuple}._{idx}") });
+            self.declare_retained(*variable, expression);
+        }
+
+        Ok(())
+    }
+
+    // variables of tuples or elements of tuples retain the borrowed value
+    pub(crate) fn declare_retained_variable(&mut self, variable: SymbolId, value: &IrTreeNode) -> c::generator::Result<()> {
+        let expression = self.expression(value)?;
+        self.declare_retained(variable, expression);
+        Ok(())
+    }
+
+    fn declare_retained(&mut self, variable: SymbolId, expression: Expression) {
+        let inferred = self.symbol_table.variable(variable).inferred.clone();
+        let name = self.symbol_table.variable(variable).to_string(&self.string_table);
+
+        let storage = self.inferred_storage(&inferred);
+        let r#type = self.inferred_type(&inferred);
+        self.scope.push_local_variable(name.clone(), storage);
+
+        self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
+            variable: name.clone(),
+            r#type,
+            expression,
+        }));
+        if let Some(statement) = retain(name, storage) {
+            self.statements().push(statement);
+        }
+    }
+
+    fn tuple_value(&mut self, node: &IrTreeNode) -> c::generator::Result<String> {
+        match self.expression(node)? {
+            Expression::Variable(VariableExpression { variable, .. }) => Ok(variable),
+            Expression::Code(CodeExpression { code }) => Ok(code),
+            expression => unimplemented!("{expression:#?}")
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use crate::build::c;
+use crate::build::c::{CallFunctionExpression, CodeExpression, DefineStructFieldNode, DefineStructNode, Expression, ExpressionStatement, InitialiseStructExpression, InitialiseStructField, LiteralExpression, LiteralStringExpression, Statement, StatementResult, VariableExpression};
+use crate::build::c::generator::Generator;
+use crate::build::c::generator::scope::Storage;
+use crate::common::{Inferred, TypeId};
+use crate::ir::{IrDeclareTypeNode, IrInstantiateTypeNode, IrTreeNode};
+
+pub(crate) fn c_type(type_id: TypeId) -> String {
+    match type_id {
+        TypeId::BOOLEAN => "struct val_bool *",
+        TypeId::FLOAT4 => "struct val_f4 *",
+        TypeId::FLOAT8 => "struct val_f8 *",
+        TypeId::INT1 => "struct val_i1 *",
+        TypeId::INT2 => "struct val_i2 *",
+        TypeId::INT4 => "struct val_i4 *",
+        TypeId::INT8 => "struct val_i8 *",
+        TypeId::INT16 => "struct val_i16 *",
+        TypeId::NUMBER => "struct val_num *",
+        TypeId::STRING => "struct val_str *",
+        TypeId::UINT1 => "struct val_u1 *",
+        TypeId::UINT2 => "struct val_u2 *",
+        TypeId::UINT4 => "struct val_u4 *",
+        TypeId::UINT8 => "struct val_u8 *",
+        TypeId::UINT16 => "struct val_u16 *",
+        TypeId::UNIT => "void",
+        TypeId::FUNCTION => "struct val_clo *",
+        TypeId::Custom(_) => "struct val_obj *",
+        _ => "struct val *",
+    }.to_string()
+}
+
+// the function which boxes a raw c value into a val of the given type
+pub(crate) fn box_function(type_id: TypeId) -> String {
+    match type_id {
+        TypeId::BOOLEAN => "val_bool_new",
+        TypeId::FLOAT4 => "val_f4_new",
+        TypeId::FLOAT8 => "val_f8_new",
+        TypeId::INT1 => "val_i1_new",
+        TypeId::INT2 => "val_i2_new",
+        TypeId::INT4 => "val_i4_new",
+        TypeId::INT8 => "val_i8_new",
+        TypeId::INT16 => "val_i16_new",
+        TypeId::NUMBER => "val_num_new",
+        TypeId::STRING => "val_str_new_from_c_str",
+        TypeId::UINT1 => "val_u1_new",
+        TypeId::UINT2 => "val_u2_new",
+        TypeId::UINT4 => "val_u4_new",
+        TypeId::UINT8 => "val_u8_new",
+        TypeId::UINT16 => "val_u16_new",
+        _ => unimplemented!("{type_id:?} can not be boxed")
+    }.to_string()
+}
+
+impl Generator {
+    // external types are plain c structs, every other type is a val_obj at runtime
+    pub(crate) fn declare_type(&mut self, node: &IrDeclareTypeNode) -> c::generator::Result<()> {
+        let type_id = self.symbol_table[node.r#type].type_id().unwrap_or(TypeId::UNKNOWN);
+        if !self.type_table.is_external(type_id) {
+            return Ok(());
+        }
+
+        let fields = self.type_table[type_id].variables.iter()
+            .map(|variable| DefineStructFieldNode {
+                identifier: self.string_table.get(variable.name.0).to_string(),
+                ty: self.ffi_c_type(&Inferred::from_type_id(variable.type_id)),
+            })
+            .collect();
+
+        self.include_system("stdint.h");
+        self.struct_definitions.push(DefineStructNode {
+            identifier: self.string_table.get(self.type_table[type_id].name.0).to_string(),
+            fields,
+        });
+
+        Ok(())
+    }
+
+    // external types are passed around by value, trait values carry a vtable, sum values a tag, everything else is a val
+    pub(crate) fn value_type(&self, type_id: TypeId) -> String {
+        if self.type_table.is_external(type_id) {
+            format!("struct {}", self.string_table.get(self.type_table[type_id].name.0))
+        } else if self.type_table.is_trait(type_id) {
+            "struct val_dyn *".to_string()
+        } else if self.type_table.is_sum(type_id) || self.type_table.is_variant(type_id) {
+            "struct val_enm *".to_string()
+        } else {
+            c_type(type_id)
+        }
+    }
+
+    pub(crate) fn is_variant_instance(&self, node: &IrInstantiateTypeNode) -> bool {
+        self.symbol_table[node.r#type].type_id().is_some_and(|type_id| self.type_table.is_variant(type_id))
+    }
+
+    pub(crate) fn instantiate_type(&mut self, node: &IrInstantiateTypeNode) -> c::generator::Result<Expression> {
+        let type_id = self.symbol_table[node.r#type].type_id().unwrap_or(TypeId::UNKNOWN);
+        if self.type_table.is_external(type_id) {
+            let mut fields = Vec::with_capacity(node.arguments.len());
+            for argument in &node.arguments {
+                fields.push(InitialiseStructField {
+                    identifier: self.string_table.get(argument.argument).to_string(),
+                    expression: self.ffi_value(&argument.value)?,
+                });
+            }
+            return Ok(Expression::StructInitialisation(InitialiseStructExpression { fields: fields.into_boxed_slice() }));
+        }
+
+        let name = self.string_table.get(self.symbol_table[node.r#type].name().0).to_string();
+
+        let mut result = Expression::CallFunction(CallFunctionExpression {
+            function: "val_obj_new".to_string(),
+            arguments: Box::new([
+                Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                Expression::CallFunction(CallFunctionExpression {
+                    function: "val_str_view_from_c_str".to_string(),
+                    arguments: Box::new([
+                        Expression::Literal(LiteralExpression::String(LiteralStringExpression { value: name }))
+                    ]),
+                }),
+            ]),
+        });
+
+        // the object keeps the values of its fields in the order they are declared in
+        let variables = self.type_table[type_id].variables.clone();
+        for variable in &variables {
+            let argument = node.arguments.iter()
+                .find(|argument| argument.argument == variable.name.0)
+                .expect("every field to be initialised");
+            let value = self.field_value(&argument.value, variable.type_id)?;
+            result = Expression::CallFunction(CallFunctionExpression {
+                function: "val_obj_field_append".to_string(),
+                arguments: Box::new([result, value]),
+            });
+        }
+
+        let Some(drop) = self.drop_function(type_id) else {
+            return Ok(result);
+        };
+        Ok(Expression::CallFunction(CallFunctionExpression {
+            function: "val_obj_drop_set".to_string(),
+            arguments: Box::new([
+                result,
+                Expression::Code(CodeExpression { code: format!("(void (*)(struct val_obj *)) {drop}") }),
+            ]),
+        }))
+    }
+
+    // the value of a field is held by a variable, a new value belongs to a temp which releases it after the object took it
+    pub(crate) fn field_value(&mut self, value: &IrTreeNode, field_type: TypeId) -> c::generator::Result<Expression> {
+        let expression = if let Some(literal) = self.literal_value(value)? {
+            Expression::CallFunction(CallFunctionExpression {
+                function: box_function(value.type_id),
+                arguments: Box::new([Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }), literal]),
+            })
+        } else {
+            self.expression(value)?
+        };
+
+        if self.type_table.is_trait(field_type) && value.type_id != field_type {
+            return Ok(self.dyn_new(expression, value.type_id, field_type, Some(c_type(TypeId::ANY))));
+        }
+
+        let variable = match expression {
+            Expression::Variable(VariableExpression { variable, .. }) => variable,
+            Expression::Code(CodeExpression { code }) => code,
+            expression => {
+                let temp = self.scope.push_temp(Storage::Memory);
+                let r#type = self.value_type(value.type_id);
+                self.statements().push(Statement::Expression(ExpressionStatement {
+                    expression,
+                    result: Some(StatementResult::Declare { variable: temp.to_string(), r#type }),
+                }));
+                temp.to_string()
+            }
+        };
+        Ok(Expression::Variable(VariableExpression { variable, cast: Some(c_type(TypeId::ANY)) }))
+    }
+
+    // the runtime calls drop() of a Drop when it frees the value
+    fn drop_function(&self, type_id: TypeId) -> Option<String> {
+        if !matches!(type_id, TypeId::Custom(_)) {
+            return None;
+        }
+        let r#trait = self.type_table[type_id].traits.iter()
+            .find(|r#trait| self.string_table.get(self.type_table[**r#trait].name.0) == "Drop")?;
+        let method = self.type_table[*r#trait].methods.first()?;
+        let function = self.type_table.method(type_id, method.name)?.function;
+        Some(self.function_identifier(function))
+    }
+
+    pub(crate) fn instantiate_type_expression(&mut self, node: &IrInstantiateTypeNode) -> c::generator::Result<Expression> {
+        if self.is_variant_instance(node) {
+            return self.variant_expression(node);
+        }
+
+        let expression = self.instantiate_type(node)?;
+        let type_id = self.symbol_table[node.r#type].type_id().unwrap_or(TypeId::UNKNOWN);
+        let temp = if self.type_table.is_external(type_id) {
+            self.scope.push_temp(Storage::Stack)
+        } else {
+            self.scope.push_temp(Storage::Memory)
+        };
+        let r#type = self.value_type(type_id);
+
+        self.statements().push(Statement::Expression(ExpressionStatement {
+            expression,
+            result: Some(StatementResult::Declare {
+                variable: temp.to_string(),
+                r#type,
+            }),
+        }));
+
+        Ok(Expression::Variable(VariableExpression { variable: temp.to_string(), cast: None }))
+    }
+}
@@ -1,12 +1,14 @@
 use bigdecimal::ToPrimitive;
 
 use crate::build::c;
-use crate::build::c::{CallFunctionStatement, CallFunctionStatementResult, CodeExpression, DeclareVariableStatement, Expression, Statement, StatementResult, VariableExpression};
+use crate::build::c::{CallFunctionExpression, CallFunctionStatement, CallFunctionStatementResult, CodeExpression, DeclareVariableStatement, Expression, ExpressionStatement, Statement, StatementResult, VariableExpression};
 use crate::build::c::generator::Generator;
+use crate::build::c::generator::r#type::{box_function, c_type};
 use crate::build::c::generator::scope::Storage;
-use crate::common::GetString;
-use crate::common::node::Node::{LiteralBoolean, LiteralFloat4, LiteralFloat8, LiteralInt1, LiteralInt16, LiteralInt2, LiteralInt4, LiteralInt8, LiteralNumber, LiteralString, LiteralUint1, LiteralUint16, LiteralUint2, LiteralUint4, LiteralUint8, Loop};
-use crate::ir::{IrAccessVariableNode, IrDeclareVariableNode, IrLiteralBooleanNode, IrLiteralFloat4Node, IrLiteralFloat8Node, IrLiteralInt16Node, IrLiteralInt1Node, IrLiteralInt2Node, IrLiteralInt4Node, IrLiteralInt8Node, IrLiteralNumberNode, IrLiteralStringNode, IrLiteralUint16Node, IrLiteralUint1Node, IrLiteralUint2Node, IrLiteralUint4Node, IrLiteralUint8Node};
+use crate::build::c::generator::tuple::{release, retain};
+use crate::common::{GetString, Symbol, SymbolId, TypeId, TypeVariableId};
+use crate::common::node::Node::{AccessElementOfTuple, AccessVariable, AccessVariableOfObject, DeclareLambda, If, LiteralBoolean, LiteralFloat4, LiteralFloat8, LiteralInt1, LiteralInt16, LiteralInt2, LiteralInt4, LiteralInt8, LiteralNone, LiteralNumber, LiteralString, LiteralUint1, LiteralUint16, LiteralUint2, LiteralUint4, LiteralUint8, Loop, Match, CallFunction, CallFunctionOfObject, CallFunctionOfPackage, InstantiateType, Try};
+use crate::ir::{IrAccessVariableNode, IrTreeNode, IrAccessVariableOfObjectNode, IrAssignVariableNode, IrAssignVariableOfObjectNode, IrDeclareVariableNode, IrLiteralBooleanNode, IrLiteralFloat4Node, IrLiteralFloat8Node, IrLiteralInt16Node, IrLiteralInt1Node, IrLiteralInt2Node, IrLiteralInt4Node, IrLiteralInt8Node, IrLiteralNumberNode, IrLiteralStringNode, IrLiteralUint16Node, IrLiteralUint1Node, IrLiteralUint2Node, IrLiteralUint4Node, IrLiteralUint8Node};
 
 impl Generator {
     pub(crate) fn access_variable(
@@ -21,13 +23,223 @@ impl Generator {
         })
     }
 
+    // fields of external types are raw c values, they get boxed when read - every other object holds vals, which are borrowed from it
+    pub(crate) fn access_variable_of_object(
+        &mut self,
+        node: &IrAccessVariableOfObjectNode,
+        type_id: TypeId,
+    ) -> c::generator::Result<Expression> {
+        if !self.is_external_object(node.object) {
+            let object = self.symbol_table.variable(node.object).to_string(&self.string_table);
+            return Ok(Expression::Code(CodeExpression {
+                code: format!("(({}) val_obj_field_at({object}, {}))", self.value_type(type_id), node.variable.0)
+            }));
+        }
+
+        let (field, type_id) = self.object_field(node.object, node.variable);
+        if self.type_table.is_external(type_id) {
+            return Ok(Expression::Code(CodeExpression { code: field }));
+        }
+
+        Ok(Expression::CallFunction(CallFunctionExpression {
+            function: box_function(type_id),
+            arguments: Box::new([
+                Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                Expression::Code(CodeExpression { code: field }),
+            ]),
+        }))
+    }
+
+    pub(crate) fn access_variable_of_object_expression(
+        &mut self,
+        node: &IrAccessVariableOfObjectNode,
+        type_id: TypeId,
+    ) -> c::generator::Result<Expression> {
+        let expression = self.access_variable_of_object(node, type_id)?;
+        let (_, field_type) = self.object_field(node.object, node.variable);
+        if !self.is_external_object(node.object) || self.type_table.is_external(field_type) {
+            return Ok(expression);
+        }
+
+        let temp = self.scope.push_temp(Storage::Memory);
+        self.statements().push(Statement::Expression(ExpressionStatement {
+            expression,
+            result: Some(StatementResult::Declare {
+                variable: temp.to_string(),
+                r#type: c_type(field_type),
+            }),
+        }));
+
+        Ok(Expression::Variable(VariableExpression { variable: temp.to_string(), cast: None }))
+    }
+
+    pub(crate) fn is_external_object(&self, object: SymbolId) -> bool {
+        self.symbol_table.variable(object).type_id.is_some_and(|type_id| self.type_table.is_external(type_id))
+    }
+
+    // the new value gets retained before the old one is released, so x = x keeps the value alive
+    pub(crate) fn assign_variable(
+        &mut self,
+        node: &IrAssignVariableNode,
+    ) -> c::generator::Result<()> {
+        let symbol = self.symbol_table.variable(node.variable);
+        let variable = symbol.to_string(&self.string_table);
+        let inferred = symbol.inferred.clone();
+        let type_id = symbol.type_id.unwrap_or(TypeId::UNKNOWN);
+        let storage = self.inferred_storage(&inferred);
+
+        let expression = match node.value.node() {
+            LiteralBoolean(_) | LiteralNumber(_) => Expression::CallFunction(CallFunctionExpression {
+                function: box_function(node.value.type_id),
+                arguments: Box::new([
+                    Expression::Code(CodeExpression { code: "MEM(tm)".to_string() }),
+                    self.expression(&node.value)?,
+                ]),
+            }),
+            _ => self.expression(&node.value)?
+        };
+
+        let expression = if self.type_table.is_trait(type_id) && node.value.type_id != type_id {
+            self.dyn_new_expression(expression, node.value.type_id, type_id)
+        } else {
+            expression
+        };
+
+        // a new value belongs to a temp, which releases it together with its frame
+        let value = match expression {
+            Expression::Variable(VariableExpression { variable, .. }) => variable,
+            Expression::Code(CodeExpression { code }) => code,
+            expression => {
+                let temp = self.scope.push_temp(storage);
+                let r#type = self.inferred_type(&inferred);
+                self.statements().push(Statement::Expression(ExpressionStatement {
+                    expression,
+                    result: Some(StatementResult::Declare { variable: temp.to_string(), r#type }),
+                }));
+                temp.to_string()
+            }
+        };
+
+        if let Some(statement) = retain(value.clone(), storage) {
+            self.statements().push(statement);
+        }
+        if let Some(statement) = release(variable.clone(), storage) {
+            self.statements().push(statement);
+        }
+        self.statements().push(Statement::Expression(ExpressionStatement {
+            expression: Expression::Variable(VariableExpression { variable: value, cast: None }),
+            result: Some(StatementResult::Assign { variable }),
+        }));
+
+        Ok(())
+    }
+
+    pub(crate) fn assign_variable_of_object(
+        &mut self,
+        node: &IrAssignVariableOfObjectNode,
+    ) -> c::generator::Result<()> {
+        let (field, type_id) = self.object_field(node.object, node.variable);
+        if !self.is_external_object(node.object) {
+            let object = self.symbol_table.variable(node.object).to_string(&self.string_table);
+            let value = self.field_value(&node.value, type_id)?;
+            self.statements().push(Statement::Expression(ExpressionStatement {
+                expression: Expression::CallFunction(CallFunctionExpression {
+                    function: "val_obj_field_replace".to_string(),
+                    arguments: Box::new([
+                        Expression::Code(CodeExpression { code: object }),
+                        Expression::Code(CodeExpression { code: node.variable.0.to_string() }),
+                        value,
+                    ]),
+                }),
+                result: None,
+            }));
+            return Ok(());
+        }
+
+        let expression = self.ffi_value(&node.value)?;
+
+        self.statements().push(Statement::Expression(ExpressionStatement {
+            expression,
+            result: Some(StatementResult::Assign { variable: field }),
+        }));
+
+        Ok(())
+    }
+
+    // lambdas and functions passed by name become closures
+    fn is_function_value(&self, node: &IrTreeNode) -> bool {
+        match node.node() {
+            DeclareLambda(_) => true,
+            AccessVariable(access) => matches!(self.symbol_table[access.variable], Symbol::Function(_)),
+            _ => false
+        }
+    }
+
+    // e.g. point.x together with the type of x
+    fn object_field(&self, object: SymbolId, variable: TypeVariableId) -> (String, TypeId) {
+        let symbol = self.symbol_table.variable(object);
+        let r#type = &self.type_table[symbol.type_id.unwrap()];
+        let variable = &r#type.variables[variable.0];
+
+        (
+            format!("{}.{}", symbol.to_string(&self.string_table), self.string_table.get(variable.name.0)),
+            variable.type_id
+        )
+    }
+
     pub(crate) fn declare_variable(
         &mut self,
         node: &IrDeclareVariableNode,
     ) -> c::generator::Result<()> {
         let variable = self.symbol_table.variable(node.variable).to_string(&self.string_table);
 
-        self.scope.push_local_variable(variable.clone(), Storage::Memory);
+        let type_id = self.symbol_table.variable(node.variable).type_id.unwrap_or(TypeId::UNKNOWN);
+        if self.type_table.is_trait(type_id) && node.value.type_id != type_id {
+            self.scope.push_local_variable(variable.clone(), Storage::Memory);
+            let expression = self.expression(&node.value)?;
+            let expression = self.dyn_new_expression(expression, node.value.type_id, type_id);
+            let r#type = self.value_type(type_id);
+            self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
+                variable,
+                r#type,
+                expression,
+            }));
+            return Ok(());
+        }
+
+        // the Err of value? may return early, which releases the locals so far - so the variable exists only after it
+        if let Try(r#try) = &node.value.node() {
+            let expression = self.r#try(r#try, node.value.type_id)?;
+            self.scope.push_local_variable(variable.clone(), Storage::Memory);
+            let r#type = self.value_type(node.value.type_id);
+            self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
+                variable: variable.clone(),
+                r#type,
+                expression,
+            }));
+            // the value is borrowed from the result
+            self.statements().push(Statement::rc_inc(variable));
+            return Ok(());
+        }
+
+        // a tuple and the elements taken out of it are borrowed, so are other variables and the fields of an object
+        if node.value.type_id == TypeId::TUPLE || matches!(node.value.node(), AccessElementOfTuple(_) | AccessVariable(_)) {
+            return self.declare_retained_variable(node.variable, &node.value);
+        }
+        if let AccessVariableOfObject(access) = node.value.node() {
+            if !self.is_external_object(access.object) {
+                return self.declare_retained_variable(node.variable, &node.value);
+            }
+        }
+
+        // values of external types live on the stack, a value which may be none is released only if it is there
+        if self.type_table.is_external(node.value.type_id) {
+            self.scope.push_local_variable(variable.clone(), Storage::Stack);
+        } else if self.symbol_table.variable(node.variable).inferred.is_optional() {
+            self.scope.push_local_variable(variable.clone(), Storage::Optional);
+        } else {
+            self.scope.push_local_variable(variable.clone(), Storage::Memory);
+        }
 
         if let LiteralString(IrLiteralStringNode { value }) = &node.value.node() {
             let value = self.string_table.get_string(value);
@@ -330,16 +542,104 @@ impl Generator {
                 })
             );
 
+            Ok(())
+        } else if let LiteralNone(_) = &node.value.node() {
+            let r#type = self.value_type(type_id);
+            self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
+                variable,
+                r#type,
+                expression: Expression::Code(CodeExpression { code: "nullptr".to_string() }),
+            }));
+
             Ok(())
         } else if let Loop(r#loop) = &node.value.node() {
+            let r#type = self.value_type(node.value.type_id);
             self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
                 variable: variable.clone(),
-                r#type: "struct val_num *".to_string(),
+                r#type,
                 expression: Expression::Code(CodeExpression { code: "nullptr".to_string() }),
             }));
 
             self.r#loop(r#loop, Some(StatementResult::Assign { variable }))?;
 
+            Ok(())
+        } else if let If(r#if) = &node.value.node() {
+            let r#type = self.value_type(node.value.type_id);
+            self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
+                variable: variable.clone(),
+                r#type,
+                expression: Expression::Code(CodeExpression { code: "nullptr".to_string() }),
+            }));
+
+            self.r#if(r#if, Some(StatementResult::Assign { variable }))?;
+
+            Ok(())
+        } else if let Match(r#match) = &node.value.node() {
+            let r#type = self.value_type(node.value.type_id);
+            self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
+                variable: variable.clone(),
+                r#type,
+                expression: Expression::Code(CodeExpression { code: "nullptr".to_string() }),
+            }));
+
+            self.r#match(r#match, Some(StatementResult::Assign { variable }))?;
+
+            Ok(())
+        } else if let CallFunction(call) = &node.value.node() {
+            let expression = self.call_value(call.function, &call.arguments, node.value.type_id)?;
+            let r#type = self.value_type(node.value.type_id);
+            self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
+                variable,
+                r#type,
+                expression,
+            }));
+
+            Ok(())
+        } else if let CallFunctionOfObject(call) = &node.value.node() {
+            let expression = self.call_function_of_object_value(call, node.value.type_id)?;
+            let r#type = self.value_type(node.value.type_id);
+            self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
+                variable,
+                r#type,
+                expression,
+            }));
+
+            Ok(())
+        } else if let CallFunctionOfPackage(call) = &node.value.node() {
+            let expression = self.call_value(call.function, &call.arguments, node.value.type_id)?;
+            let r#type = self.value_type(node.value.type_id);
+            self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
+                variable,
+                r#type,
+                expression,
+            }));
+
+            Ok(())
+        } else if let InstantiateType(instantiate) = &node.value.node() {
+            if self.is_variant_instance(instantiate) {
+                return self.variant_new(instantiate, variable);
+            }
+
+            let expression = self.instantiate_type(instantiate)?;
+            let r#type = self.value_type(node.value.type_id);
+            self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
+                variable,
+                r#type,
+                expression,
+            }));
+
+            Ok(())
+        } else if self.is_function_value(&node.value) {
+            self.closure_new(&node.value, variable)
+        } else if let AccessVariableOfObject(access) = &node.value.node() {
+            let expression = self.access_variable_of_object(access, node.value.type_id)?;
+            let r#type = self.value_type(node.value.type_id);
+            self.statements().push(Statement::DeclareVariable(DeclareVariableStatement {
+                variable,
+                r#type,
+                expression,
+            }));
+
             Ok(())
         } else {
             unimplemented!("{node:#?}")
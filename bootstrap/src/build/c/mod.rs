@@ -9,7 +9,7 @@ pub use node::*;
 use crate::build::c::emitter::emit;
 use crate::build::c::generator::generate;
 use crate::common::Context;
-use crate::ir::ir_from_str;
+use crate::ir::ir_from_program;
 
 mod emitter;
 mod generator;
@@ -35,7 +35,17 @@ impl From<emitter::Error> for Error {
 
 type Result<T> = core::result::Result<T, Error>;
 
-pub fn build_c_code_from_file(file: PathBuf) {
+// what gets built from a program
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Target {
+    Executable,
+    // lib<name>.a together with <name>.h
+    StaticLibrary,
+    // lib<name>.so together with <name>.h
+    SharedLibrary,
+}
+
+pub fn build_c_code_from_file(file: PathBuf, target: Target) {
     let name = file.file_name()
         .unwrap()
         .to_str()
@@ -47,15 +57,18 @@ pub fn build_c_code_from_file(file: PathBuf) {
     file.read_to_string(&mut content).unwrap();
 
     let mut ctx = Context::new();
-    let ir = ir_from_str(&mut ctx, content.as_str()).unwrap();
-    let nodes = generate(ctx, ir).unwrap();
-    let c_code = emit(&nodes);
+    let ir = ir_from_program(&mut ctx, content.as_str()).unwrap();
+
+    let library = if target == Target::Executable { None } else { Some(name.as_str()) };
+    let generated = generate(ctx, ir, library).unwrap();
+    let c_code = emit(&generated.nodes);
+    let header = generated.header.map(|header| emit(&header));
 
-    build(name.as_str(), c_code.as_str()).unwrap();
+    build(name.as_str(), c_code.as_str(), header.as_deref(), &generated.links, target).unwrap();
 }
 
 
-pub fn build(name: &str, c_code: &str) -> io::Result<()> {
+pub fn build(name: &str, c_code: &str, header: Option<&str>, links: &[String], target: Target) -> io::Result<()> {
     // FIXME needs context so that it know whichs core / std or lib to include
 
     let dir = PathBuf::from(format!("/tmp/elodie/{name}").as_str());
@@ -64,28 +77,52 @@ pub fn build(name: &str, c_code: &str) -> io::Result<()> {
     fs::create_dir_all(dir.clone()).unwrap();
 
     let c_file_path = dir.join(format!("main.c").as_str());
-    let binary_path = dir.join(name);
     let gcc_err_path = dir.join("compiler.err");
 
     let mut c_file = File::create(&c_file_path)?;
     c_file.write_all(c_code.as_bytes())?;
     drop(c_file);
 
+    if let Some(header) = header {
+        let mut header_file = File::create(dir.join(format!("{name}.h")))?;
+        header_file.write_all(header.as_bytes())?;
+    }
+
     let gcc_err_file = File::create(&gcc_err_path)?;
 
-    let gcc_output = Command::new("gcc")
-        .arg(c_file_path.to_str().unwrap())
+    let mut gcc = Command::new("gcc");
+    gcc.arg(c_file_path.to_str().unwrap())
         // .arg(dir.join("rt/io.c"))
         .arg("-std=gnu2x")
         .arg("-I/home/ddymke/repo/elodie/src/sysroot/c/project/core/include")
-        .arg("-I/home/ddymke/repo/elodie/src/sysroot/c/project/rt/include")
-        .arg("-L/home/ddymke/repo/elodie/src/sysroot/c/build/project/core")
-        .arg("-lcore")
-        .arg("-L/home/ddymke/repo/elodie/src/sysroot/c/build/project/rt")
-        .arg("-lrt")
-        .arg("-lm")
+        .arg("-I/home/ddymke/repo/elodie/src/sysroot/c/project/rt/include");
+
+    // the static library only holds the program, core, rt and the c libraries get linked by its user
+    let output_path = match target {
+        Target::Executable => dir.join(name),
+        Target::StaticLibrary => dir.join("main.o"),
+        Target::SharedLibrary => dir.join(format!("lib{name}.so")),
+    };
+
+    match target {
+        Target::StaticLibrary => {
+            gcc.arg("-c");
+        }
+        Target::Executable | Target::SharedLibrary => {
+            if target == Target::SharedLibrary {
+                gcc.arg("-shared").arg("-fPIC");
+            }
+            gcc.arg("-L/home/ddymke/repo/elodie/src/sysroot/c/build/project/core")
+                .arg("-lcore")
+                .arg("-L/home/ddymke/repo/elodie/src/sysroot/c/build/project/rt")
+                .arg("-lrt")
+                .args(links.iter().map(|link| format!("-l{link}")));
+        }
+    }
+
+    let gcc_output = gcc
         .arg("-o")
-        .arg(binary_path.to_str().unwrap())
+        .arg(output_path.to_str().unwrap())
         .stderr(Stdio::from(gcc_err_file))
         .output()?;
 
@@ -97,5 +134,18 @@ pub fn build(name: &str, c_code: &str) -> io::Result<()> {
         ));
     }
 
+    if target == Target::StaticLibrary {
+        let ar_output = Command::new("ar")
+            .arg("rcs")
+            .arg(dir.join(format!("lib{name}.a")).to_str().unwrap())
+            .arg(output_path.to_str().unwrap())
+            .output()?;
+
+        if !ar_output.status.success() {
+            eprintln!("ar failed with status: {}\n", ar_output.status);
+            return Err(io::Error::other("ar archiving failed"));
+        }
+    }
+
     Ok(())
 }
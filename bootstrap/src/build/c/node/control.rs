@@ -1,6 +1,17 @@
 use crate::build::c::{BlockStatement, Expression};
 
-#[derive(Debug)]
+// counts a raw c value, the counter is declared by the loop itself
+#[derive(Debug, Clone)]
+pub struct ForStatement {
+    pub counter: String,
+    pub r#type: String,
+    pub start: Expression,
+    pub condition: Expression,
+    pub step: Expression,
+    pub block: BlockStatement,
+}
+
+#[derive(Debug, Clone)]
 pub struct IfStatement {
     pub condition: Expression,
     pub then: BlockStatement,
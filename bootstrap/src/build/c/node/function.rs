@@ -1,39 +1,41 @@
 use crate::build::c::{BlockStatement, Expression};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CallFunctionStatement {
     pub function: String,
     pub arguments: Box<[Expression]>,
     pub result: Option<CallFunctionStatementResult>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CallFunctionExpression {
     pub function: String,
     pub arguments: Box<[Expression]>,
 }
 
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CallFunctionStatementResult {
     pub identifier: String,
     pub r#type: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DeclareFunctionNode {
     pub identifier: String,
     pub arguments: Box<[DeclareFunctionArgumentNode]>,
     pub ty: String,
+    // the name the linker knows the function by, if c refers to it by another identifier
+    pub symbol: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DeclareFunctionArgumentNode {
     pub identifier: String,
     pub ty: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DefineFunctionNode {
     pub identifier: String,
     pub arguments: Box<[DefineFunctionArgumentNode]>,
@@ -41,13 +43,13 @@ pub struct DefineFunctionNode {
     pub block: BlockStatement,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DefineFunctionArgumentNode {
     pub identifier: String,
     pub ty: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ReturnFromFunctionStatement {
     pub node: Option<Expression>,
 }
@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum LiteralExpression {
     Bool(LiteralBooleanExpression),
     Float4(LiteralFloat4Expression),
@@ -16,73 +16,73 @@ pub enum LiteralExpression {
     Uint16(LiteralUint16Expression),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LiteralBooleanExpression {
     pub value: bool,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LiteralFloat4Expression {
     pub value: f32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LiteralFloat8Expression {
     pub value: f64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LiteralInt1Expression {
     pub value: i8,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LiteralInt2Expression {
     pub value: i16,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LiteralInt4Expression {
     pub value: i32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LiteralInt8Expression {
     pub value: i64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LiteralInt16Expression {
     pub value: i128,
 }
 
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LiteralStringExpression {
     pub value: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LiteralUint1Expression {
     pub value: u8,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LiteralUint2Expression {
     pub value: u16,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LiteralUint4Expression {
     pub value: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LiteralUint8Expression {
     pub value: u64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LiteralUint16Expression {
     pub value: u128,
 }
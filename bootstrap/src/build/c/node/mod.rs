@@ -29,12 +29,12 @@ pub enum Node {
     Directive(DirectiveNode),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CodeNode {
     pub code: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Expression {
     AccessVariableOfStruct(AccessVariableOfStructExpression),
     CallFunction(CallFunctionExpression),
@@ -91,7 +91,7 @@ impl Expression {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Statement {
     Block(BlockStatement),
     Break(BreakStatement),
@@ -99,25 +99,42 @@ pub enum Statement {
     CallFunction(CallFunctionStatement), // Use expression
     #[deprecated]
     Code(CodeStatement), // Use expressions
+    Continue(ContinueStatement),
     DeclareArray(DeclareArrayStatement),
     DeclareVariable(DeclareVariableStatement),
     Expression(ExpressionStatement),
+    For(ForStatement),
+    Goto(GotoStatement),
     If(IfStatement),
+    Label(LabelStatement),
     Loop(LoopStatement),
     ReturnFromFunction(ReturnFromFunctionStatement),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BreakStatement {}
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+pub struct ContinueStatement {}
+
+#[derive(Debug, Clone)]
+pub struct GotoStatement {
+    pub label: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct LabelStatement {
+    pub label: String,
+}
+
+#[derive(Debug, Clone)]
 pub struct LoopStatement {
     pub block: BlockStatement,
     pub result: Option<StatementResult>,
 }
 
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExpressionStatement {
     pub expression: Expression,
     pub result: Option<StatementResult>,
@@ -134,28 +151,28 @@ pub enum StatementResult {
     },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BlockStatement {
     pub statements: Vec<Statement>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CodeStatement {
     pub code: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CodeExpression {
     pub code: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AccessVariableOfStructExpression {
     pub r#struct: String,
     pub variable: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CompareExpression {
     pub left: Box<Expression>,
     pub operator: CompareOperator,
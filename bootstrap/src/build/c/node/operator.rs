@@ -1,6 +1,6 @@
 use crate::build::c::Expression;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum InfixOperator {
     Add,         // +
     Subtract,    // -
@@ -14,7 +14,7 @@ pub enum InfixOperator {
     Assign,      // =
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct InfixExpression {
     pub left: Box<Expression>,
     pub operator: InfixOperator,
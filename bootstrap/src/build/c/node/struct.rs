@@ -1,28 +1,28 @@
 use crate::build::c::Expression;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DeclareStructNode {
     pub identifier: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DefineStructNode {
     pub identifier: String,
     pub fields: Box<[DefineStructFieldNode]>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DefineStructFieldNode {
     pub identifier: String,
     pub ty: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct InitialiseStructExpression {
     pub fields: Box<[InitialiseStructField]>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct InitialiseStructField {
     pub identifier: String,
     pub expression: Expression,
@@ -1,6 +1,6 @@
 use crate::build::c::Expression;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DeclareArrayStatement {
     pub identifier: String,
     pub r#type: String,
@@ -8,20 +8,20 @@ pub struct DeclareArrayStatement {
     // pub expression: Expression, some initialisation
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DeclareVariableStatement {
     pub variable: String,
     pub r#type: String,
     pub expression: Expression,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct VariableExpression {
     pub variable: String,
     pub cast: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DefineGlobalVariableNode {
     pub identifier: String,
     pub r#type: String,
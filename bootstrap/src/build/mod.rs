@@ -1,3 +1,3 @@
-pub use c::{build_c_code_from_file, Error};
+pub use c::{build_c_code_from_file, Error, Target};
 
 mod c;
@@ -1,8 +1,9 @@
-use crate::common::{StringTable, StringTableId, TypeId, TypeTable};
+use crate::common::{PackageTable, StringTable, StringTableId, TypeId, TypeTable};
 use crate::common::symbol::{SymbolId, SymbolTable};
 
 #[derive(Debug)]
 pub struct Context {
+    pub package_table: PackageTable,
     pub string_table: StringTable,
     pub symbol_table: SymbolTable,
     pub type_table: TypeTable,
@@ -30,6 +31,7 @@ impl Context {
         let mut string_table = StringTable::new();
         let type_table = TypeTable::new(&mut string_table);
         Self {
+            package_table: PackageTable::new(),
             string_table,
             symbol_table: SymbolTable::new(),
             type_table,
@@ -42,6 +44,7 @@ impl Default for Context {
         let mut string_table = StringTable::new();
         let type_table = TypeTable::new(&mut string_table);
         Self {
+            package_table: PackageTable::new(),
             string_table,
             symbol_table: SymbolTable::new(),
             type_table,
@@ -1,6 +1,7 @@
+use std::collections::HashMap;
 use std::ops::Index;
 
-use crate::common::{StringTable, Type, TypeId, TypeTable};
+use crate::common::{StringTable, StringTableId, Type, TypeId, TypeTable};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Inferred {
@@ -17,6 +18,15 @@ pub enum Inferred {
     Int16,
 
     Number,
+    // the value or none, at runtime none is a null value of the inner type
+    Optional(Box<Inferred>),
+    // a type parameter like the T of first<T>(l: List<T>) -> T
+    Parameter(StringTableId),
+    // a custom type together with its type arguments like List<Number>
+    Parameterized(TypeId, Box<[Inferred]>),
+    Pointer(Box<Inferred>),
+    // the fields ordered by name, at runtime a record is the tuple of their values
+    Record(Box<[(StringTableId, Inferred)]>),
     String,
     Tuple(Box<[Inferred]>),
     Type(TypeId),
@@ -33,11 +43,133 @@ pub enum Inferred {
 }
 
 impl Inferred {
-    pub fn to_string(&self, string_table: &StringTable) -> String {
+    pub fn type_id(&self) -> Option<TypeId> {
         match self {
+            Inferred::Boolean => Some(TypeId::BOOLEAN),
+            Inferred::Function(..) => Some(TypeId::FUNCTION),
+            Inferred::Float4 => Some(TypeId::FLOAT4),
+            Inferred::Float8 => Some(TypeId::FLOAT8),
+            Inferred::Int1 => Some(TypeId::INT1),
+            Inferred::Int2 => Some(TypeId::INT2),
+            Inferred::Int4 => Some(TypeId::INT4),
+            Inferred::Int8 => Some(TypeId::INT8),
+            Inferred::Int16 => Some(TypeId::INT16),
+            Inferred::Number => Some(TypeId::NUMBER),
+            Inferred::Optional(inner) => inner.type_id(),
+            // values of type parameters are erased
+            Inferred::Parameter(_) => Some(TypeId::ANY),
+            Inferred::Parameterized(type_id, _) => Some(*type_id),
+            Inferred::Record(_) => Some(TypeId::TUPLE),
+            Inferred::String => Some(TypeId::STRING),
+            Inferred::Tuple(_) => Some(TypeId::TUPLE),
+            Inferred::Type(type_id) => Some(*type_id),
+            Inferred::Uint1 => Some(TypeId::UINT1),
+            Inferred::Uint2 => Some(TypeId::UINT2),
+            Inferred::Uint4 => Some(TypeId::UINT4),
+            Inferred::Uint8 => Some(TypeId::UINT8),
+            Inferred::Uint16 => Some(TypeId::UINT16),
+            Inferred::Unit => Some(TypeId::UNIT),
+            _ => None
+        }
+    }
+
+    pub fn from_type_id(type_id: TypeId) -> Inferred {
+        match type_id {
+            TypeId::BOOLEAN => Inferred::Boolean,
+            TypeId::FLOAT4 => Inferred::Float4,
+            TypeId::FLOAT8 => Inferred::Float8,
+            TypeId::INT1 => Inferred::Int1,
+            TypeId::INT2 => Inferred::Int2,
+            TypeId::INT4 => Inferred::Int4,
+            TypeId::INT8 => Inferred::Int8,
+            TypeId::INT16 => Inferred::Int16,
+            TypeId::NUMBER => Inferred::Number,
+            TypeId::STRING => Inferred::String,
+            TypeId::UINT1 => Inferred::Uint1,
+            TypeId::UINT2 => Inferred::Uint2,
+            TypeId::UINT4 => Inferred::Uint4,
+            TypeId::UINT8 => Inferred::Uint8,
+            TypeId::UINT16 => Inferred::Uint16,
+            TypeId::UNIT => Inferred::Unit,
+            TypeId::Custom(_) => Inferred::Type(type_id),
+            _ => Inferred::Unknown
+        }
+    }
+
+    // the type of none, before it gets the type of what it is assigned to
+    pub fn is_none(&self) -> bool {
+        matches!(self, Inferred::Optional(inner) if **inner == Inferred::Unknown)
+    }
+
+    pub fn is_optional(&self) -> bool {
+        matches!(self, Inferred::Optional(_))
+    }
+
+    pub fn is_parameterized(&self) -> bool {
+        match self {
+            Inferred::Parameter(_) => true,
+            Inferred::Function(arguments, result) => arguments.iter().any(Inferred::is_parameterized) || result.is_parameterized(),
+            Inferred::Parameterized(_, arguments) | Inferred::Tuple(arguments) => arguments.iter().any(Inferred::is_parameterized),
+            Inferred::Record(fields) => fields.iter().any(|(_, inferred)| inferred.is_parameterized()),
+            Inferred::Optional(inner) | Inferred::Pointer(inner) => inner.is_parameterized(),
+            _ => false
+        }
+    }
+
+    // replaces every bound type parameter by its type argument
+    pub fn substitute(&self, arguments: &HashMap<StringTableId, Inferred>) -> Inferred {
+        let substitute_all = |inferred: &[Inferred]| inferred.iter().map(|i| i.substitute(arguments)).collect();
+        match self {
+            Inferred::Parameter(parameter) => arguments.get(parameter).cloned().unwrap_or(self.clone()),
+            Inferred::Function(parameters, result) => Inferred::Function(substitute_all(parameters), Box::new(result.substitute(arguments))),
+            Inferred::Parameterized(type_id, inner) => Inferred::Parameterized(*type_id, substitute_all(inner)),
+            Inferred::Optional(inner) => Inferred::Optional(Box::new(inner.substitute(arguments))),
+            Inferred::Pointer(inner) => Inferred::Pointer(Box::new(inner.substitute(arguments))),
+            Inferred::Record(fields) => Inferred::Record(fields.iter().map(|(name, inferred)| (*name, inferred.substitute(arguments))).collect()),
+            Inferred::Tuple(inner) => Inferred::Tuple(substitute_all(inner)),
+            _ => self.clone()
+        }
+    }
+
+    pub fn to_string(&self, string_table: &StringTable, type_table: &TypeTable) -> String {
+        let join = |inferred: &[Inferred]| inferred.iter()
+            .map(|i| i.to_string(string_table, type_table))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        match self {
+            Inferred::Unknown => "Unknown".to_string(),
             Inferred::Boolean => "Boolean".to_string(),
+            Inferred::Function(arguments, result) => match result.as_ref() {
+                Inferred::Unit => format!("function({})", join(arguments)),
+                result => format!("function({}) -> {}", join(arguments), result.to_string(string_table, type_table)),
+            },
+            Inferred::Float4 => "Float4".to_string(),
+            Inferred::Float8 => "Float8".to_string(),
+            Inferred::Int1 => "Int1".to_string(),
+            Inferred::Int2 => "Int2".to_string(),
+            Inferred::Int4 => "Int4".to_string(),
+            Inferred::Int8 => "Int8".to_string(),
+            Inferred::Int16 => "Int16".to_string(),
             Inferred::Number => "Number".to_string(),
+            Inferred::Optional(_) if self.is_none() => "none".to_string(),
+            Inferred::Optional(inner) => format!("{}?", inner.to_string(string_table, type_table)),
+            Inferred::Parameter(parameter) => string_table.get(*parameter).to_string(),
+            Inferred::Parameterized(type_id, arguments) => format!("{}<{}>", string_table.get(type_table[*type_id].name.0), join(arguments)),
+            Inferred::Pointer(inner) => format!("&{}", inner.to_string(string_table, type_table)),
+            Inferred::Record(fields) => format!(
+                "({})",
+                fields.iter().map(|(name, inferred)| format!("{}: {}", string_table.get(*name), inferred.to_string(string_table, type_table))).collect::<Vec<_>>().join(", ")
+            ),
             Inferred::String => "String".to_string(),
+            Inferred::Tuple(inner) => format!("({})", join(inner)),
+            Inferred::Type(type_id) => string_table.get(type_table[*type_id].name.0).to_string(),
+            Inferred::Uint1 => "Uint1".to_string(),
+            Inferred::Uint2 => "Uint2".to_string(),
+            Inferred::Uint4 => "Uint4".to_string(),
+            Inferred::Uint8 => "Uint8".to_string(),
+            Inferred::Uint16 => "Uint16".to_string(),
+            Inferred::Unit => "Unit".to_string(),
             _ => unimplemented!("{self:#?}")
         }
     }
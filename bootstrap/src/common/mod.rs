@@ -1,7 +1,7 @@
 pub use context::Context;
 pub use inferred::Inferred;
-pub use package::PackagePath;
-pub use r#type::{Type, TypeId, TypeTable};
+pub use package::{PackageId, PackagePath, PackageTable};
+pub use r#type::{Dispatch, Type, TypeId, TypeName, TypeTable, TypeVariableId, TypeVariableName};
 pub use span::*;
 pub use string::{GetString, StringTable, StringTableId};
 pub use symbol::{Symbol, SymbolId, SymbolName, SymbolTable, VariableSymbol};
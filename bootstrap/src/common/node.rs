@@ -6,9 +6,13 @@ pub trait Variant: Debug {}
 #[derive(Clone, Debug, PartialEq)]
 pub enum Node<
     V: Variant,
+    AccessElementOfTuple: AccessElementOfTupleNode<V>,
+    AccessIndex: AccessIndexNode<V>,
     AccessVariable: AccessVariableNode<V>,
     AccessVariableOfObject: AccessVariableOfObjectNode<V>,
     AccessVariableOfSelf: AccessVariableOfSelfNode<V>,
+    AssignVariable: AssignVariableNode<V>,
+    AssignVariableOfObject: AssignVariableOfObjectNode<V>,
     Block: BlockNode<V>,
     BreakLoop: BreakLoopNode<V>,
     Calculate: CalculateNode<V>,
@@ -20,13 +24,20 @@ pub enum Node<
     ContinueLoop: ContinueLoopNode<V>,
     DeclareExternalFunction: DeclareExternalFunctionNode<V>,
     DeclareFunction: DeclareFunctionNode<V>,
+    DeclareLambda: DeclareLambdaNode<V>,
     DeclarePackage: DeclarePackageNode<V>,
+    DeclareTrait: DeclareTraitNode<V>,
     DeclareType: DeclareTypeNode<V>,
     DeclareVariable: DeclareVariableNode<V>,
+    Defer: DeferNode<V>,
     DefineType: DefineTypeNode<V>,
+    DestructureTuple: DestructureTupleNode<V>,
     ExportPackage: ExportPackageNode<V>,
     If: IfNode<V>,
     InterpolateString: InterpolateStringNode<V>,
+    IteratorLoop: IteratorLoopNode<V>,
+    InstantiateRecord: InstantiateRecordNode<V>,
+    InstantiateTuple: InstantiateTupleNode<V>,
     InstantiateType: InstantiateTypeNode<V>,
     LiteralBoolean: LiteralBooleanNode<V>,
     LiteralFloat4: LiteralFloat4Node<V>,
@@ -36,6 +47,7 @@ pub enum Node<
     LiteralInt4: LiteralInt4Node<V>,
     LiteralInt8: LiteralInt8Node<V>,
     LiteralInt16: LiteralInt16Node<V>,
+    LiteralNone: LiteralNoneNode<V>,
     LiteralNumber: LiteralNumberNode<V>,
     LiteralString: LiteralStringNode<V>,
     LiteralUint1: LiteralUint1Node<V>,
@@ -44,11 +56,18 @@ pub enum Node<
     LiteralUint8: LiteralUint8Node<V>,
     LiteralUint16: LiteralUint16Node<V>,
     Loop: LoopNode<V>,
+    Match: MatchNode<V>,
+    RangeLoop: RangeLoopNode<V>,
     ReturnFromFunction: ReturnFromFunctionNode<V>,
+    Try: TryNode<V>,
 > {
+    AccessElementOfTuple(AccessElementOfTuple),
+    AccessIndex(AccessIndex),
     AccessVariable(AccessVariable),
     AccessVariableOfObject(AccessVariableOfObject),
     AccessVariableOfSelf(AccessVariableOfSelf),
+    AssignVariable(AssignVariable),
+    AssignVariableOfObject(AssignVariableOfObject),
     Block(Block),
     BreakLoop(BreakLoop),
     Calculate(Calculate),
@@ -60,13 +79,20 @@ pub enum Node<
     ContinueLoop(ContinueLoop),
     DeclareExternalFunction(DeclareExternalFunction),
     DeclareFunction(DeclareFunction),
+    DeclareLambda(DeclareLambda),
     DeclarePackage(DeclarePackage),
+    DeclareTrait(DeclareTrait),
     DeclareType(DeclareType),
     DeclareVariable(DeclareVariable),
+    Defer(Defer),
     DefineType(DefineType),
+    DestructureTuple(DestructureTuple),
     ExportPackage(ExportPackage),
     If(If),
     InterpolateString(InterpolateString),
+    IteratorLoop(IteratorLoop),
+    InstantiateRecord(InstantiateRecord),
+    InstantiateTuple(InstantiateTuple),
     InstantiateType(InstantiateType),
     LiteralBoolean(LiteralBoolean),
     LiteralFloat4(LiteralFloat4),
@@ -76,6 +102,7 @@ pub enum Node<
     LiteralInt4(LiteralInt4),
     LiteralInt8(LiteralInt8),
     LiteralInt16(LiteralInt16),
+    LiteralNone(LiteralNone),
     LiteralNumber(LiteralNumber),
     LiteralString(LiteralString),
     LiteralUint1(LiteralUint1),
@@ -84,17 +111,28 @@ pub enum Node<
     LiteralUint8(LiteralUint8),
     LiteralUint16(LiteralUint16),
     Loop(Loop),
+    Match(Match),
+    RangeLoop(RangeLoop),
     ReturnFromFunction(ReturnFromFunction),
+    Try(Try),
     Marker(PhantomData<V>),
 }
 
 
+pub trait AccessElementOfTupleNode<V: Variant> {}
+
+pub trait AccessIndexNode<V: Variant> {}
+
 pub trait AccessVariableNode<V: Variant> {}
 
 pub trait AccessVariableOfObjectNode<V: Variant> {}
 
 pub trait AccessVariableOfSelfNode<V: Variant> {}
 
+pub trait AssignVariableNode<V: Variant> {}
+
+pub trait AssignVariableOfObjectNode<V: Variant> {}
+
 pub trait BlockNode<V: Variant> {}
 
 pub trait BreakLoopNode<V: Variant> {}
@@ -117,20 +155,34 @@ pub trait DeclareExternalFunctionNode<V: Variant> {}
 
 pub trait DeclareFunctionNode<V: Variant> {}
 
+pub trait DeclareLambdaNode<V: Variant> {}
+
 pub trait DeclarePackageNode<V: Variant> {}
 
+pub trait DeclareTraitNode<V: Variant> {}
+
 pub trait DeclareTypeNode<V: Variant> {}
 
 pub trait DeclareVariableNode<V: Variant> {}
 
+pub trait DeferNode<V: Variant> {}
+
 pub trait DefineTypeNode<V: Variant> {}
 
+pub trait DestructureTupleNode<V: Variant> {}
+
 pub trait ExportPackageNode<V: Variant> {}
 
 pub trait IfNode<V: Variant> {}
 
 pub trait InterpolateStringNode<V: Variant> {}
 
+pub trait IteratorLoopNode<V: Variant> {}
+
+pub trait InstantiateRecordNode<V: Variant> {}
+
+pub trait InstantiateTupleNode<V: Variant> {}
+
 pub trait InstantiateTypeNode<V: Variant> {}
 
 pub trait LiteralBooleanNode<V: Variant> {}
@@ -149,6 +201,8 @@ pub trait LiteralInt8Node<V: Variant> {}
 
 pub trait LiteralInt16Node<V: Variant> {}
 
+pub trait LiteralNoneNode<V: Variant> {}
+
 pub trait LiteralNumberNode<V: Variant> {}
 
 pub trait LiteralStringNode<V: Variant> {}
@@ -165,8 +219,14 @@ pub trait LiteralUint16Node<V: Variant> {}
 
 pub trait LoopNode<V: Variant> {}
 
+pub trait MatchNode<V: Variant> {}
+
+pub trait RangeLoopNode<V: Variant> {}
+
 pub trait ReturnFromFunctionNode<V: Variant> {}
 
+pub trait TryNode<V: Variant> {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum CalculateOperator {
     Add,
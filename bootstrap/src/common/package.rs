@@ -1,8 +1,7 @@
 use std::collections::HashMap;
-use std::ops::Deref;
-use std::rc::Rc;
+use std::ops::{Deref, Index, IndexMut};
 
-use crate::common::{StringTable, StringTableId};
+use crate::common::{StringTable, StringTableId, SymbolId, SymbolName};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct PackageId(pub usize);
@@ -53,11 +52,106 @@ impl PackagePath {
 
 #[derive(Debug)]
 pub struct Package {
+    pub id: PackageId,
+    pub symbol: SymbolId,
+    pub parent: Option<PackageId>,
+    pub functions: HashMap<SymbolName, SymbolId>,
+    pub packages: HashMap<SymbolName, SymbolId>,
+    pub types: HashMap<SymbolName, SymbolId>,
+    // c libraries the external functions of this package get linked against
+    pub links: Vec<StringTableId>,
+}
 
+impl Package {
+    // a package sees its own members and the members of every package it is nested in
+    pub fn is_within(&self, package_table: &PackageTable, other: PackageId) -> bool {
+        let mut current = Some(self.id);
+        while let Some(id) = current {
+            if id == other {
+                return true;
+            }
+            current = package_table[id].parent;
+        }
+        false
+    }
 }
 
 #[derive(Debug)]
 pub struct PackageTable {
-    index: HashMap<PackagePath, PackageId>,
-    values: Vec<Rc<Package>>,
-}
\ No newline at end of file
+    packages: Vec<Package>,
+}
+
+impl PackageTable {
+    pub fn new() -> Self {
+        Self { packages: Vec::new() }
+    }
+
+    pub fn register(&mut self, symbol: SymbolId, parent: Option<PackageId>) -> PackageId {
+        let id = PackageId(self.packages.len());
+        self.packages.push(Package {
+            id,
+            symbol,
+            parent,
+            functions: HashMap::new(),
+            packages: HashMap::new(),
+            types: HashMap::new(),
+            links: Vec::new(),
+        });
+        id
+    }
+
+    // packages from the root down to the given package
+    pub fn path(&self, package: PackageId) -> Vec<PackageId> {
+        let mut result = vec![];
+        let mut current = Some(package);
+        while let Some(id) = current {
+            result.push(id);
+            current = self[id].parent;
+        }
+        result.reverse();
+        result
+    }
+}
+
+impl Index<PackageId> for PackageTable {
+    type Output = Package;
+    fn index(&self, index: PackageId) -> &Self::Output {
+        &self.packages[index.0]
+    }
+}
+
+impl IndexMut<PackageId> for PackageTable {
+    fn index_mut(&mut self, index: PackageId) -> &mut Self::Output {
+        &mut self.packages[index.0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::package::PackageTable;
+    use crate::common::SymbolId;
+
+    #[test]
+    fn nested_package_is_within_parent() {
+        let mut table = PackageTable::new();
+        let outer = table.register(SymbolId(1), None);
+        let inner = table.register(SymbolId(2), Some(outer));
+        let other = table.register(SymbolId(3), None);
+
+        assert!(table[inner].is_within(&table, outer));
+        assert!(table[inner].is_within(&table, inner));
+        assert!(!table[outer].is_within(&table, inner));
+        assert!(!table[other].is_within(&table, outer));
+    }
+
+    #[test]
+    fn path_of_nested_package() {
+        let mut table = PackageTable::new();
+        let outer = table.register(SymbolId(1), None);
+        let inner = table.register(SymbolId(2), Some(outer));
+        let innermost = table.register(SymbolId(3), Some(inner));
+
+        assert_eq!(table.path(innermost), vec![outer, inner, innermost]);
+        assert_eq!(table.path(outer), vec![outer]);
+    }
+}
@@ -1,6 +1,6 @@
 use std::ops::{Index, IndexMut};
 
-use crate::common::{Inferred, StringTable, StringTableId, TypeId};
+use crate::common::{Inferred, Span, StringTable, StringTableId, TypeId};
 use crate::common::Context;
 use crate::common::package::PackageId;
 use crate::common::string::GetString;
@@ -69,7 +69,20 @@ pub struct ArgumentSymbol {
 pub struct FunctionSymbol {
     pub id: SymbolId,
     pub name: SymbolName,
+    pub inferred: Inferred,
     pub type_id: Option<TypeId>,
+    pub package_id: Option<PackageId>,
+    // type parameters which only take types implementing the trait
+    pub bounds: Vec<(StringTableId, TypeId)>,
+    pub external: bool,
+    pub exported: bool,
+    // for each argument whether it got declared readonly, for a method the first one is self
+    pub readonly: Vec<bool>,
+    // the names of the arguments, which calls can pass them by
+    pub arguments: Vec<StringTableId>,
+    // calls have to pass every argument by its name
+    pub named: bool,
+    pub span: Span,
 }
 
 
@@ -78,6 +91,8 @@ pub struct PackageSymbol {
     pub id: SymbolId,
     pub name: SymbolName,
     pub package_id: Option<PackageId>,
+    pub exported: bool,
+    pub span: Span,
 }
 
 
@@ -86,6 +101,8 @@ pub struct TypeSymbol {
     pub id: SymbolId,
     pub name: SymbolName,
     pub type_id: Option<TypeId>,
+    pub exported: bool,
+    pub span: Span,
 }
 
 
@@ -95,6 +112,11 @@ pub struct VariableSymbol {
     pub name: SymbolName,
     pub inferred: Inferred,
     pub type_id: Option<TypeId>,
+    // only let bindings can be reassigned, arguments and match bindings are borrowed
+    pub mutable: bool,
+    // the value is reachable through a readonly path, nothing inside of it can be changed
+    pub readonly: bool,
+    pub span: Span,
 }
 
 impl VariableSymbol {
@@ -157,6 +179,42 @@ impl Symbol {
             _ => unreachable!()
         }
     }
+
+    pub fn is_exported(&self) -> bool {
+        match self {
+            Symbol::Function(inner) => inner.exported,
+            Symbol::Package(inner) => inner.exported,
+            Symbol::Type(inner) => inner.exported,
+            Symbol::Argument(_) | Symbol::Variable(_) => unreachable!()
+        }
+    }
+
+    pub fn set_exported(&mut self, exported: bool) {
+        match self {
+            Symbol::Function(inner) => inner.exported = exported,
+            Symbol::Package(inner) => inner.exported = exported,
+            Symbol::Type(inner) => inner.exported = exported,
+            Symbol::Argument(_) | Symbol::Variable(_) => unreachable!()
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Symbol::Function(inner) => inner.span.clone(),
+            Symbol::Package(inner) => inner.span.clone(),
+            Symbol::Type(inner) => inner.span.clone(),
+            Symbol::Argument(_) | Symbol::Variable(_) => unreachable!()
+        }
+    }
+
+    pub fn set_span(&mut self, span: Span) {
+        match self {
+            Symbol::Function(inner) => inner.span = span,
+            Symbol::Package(inner) => inner.span = span,
+            Symbol::Type(inner) => inner.span = span,
+            Symbol::Argument(_) | Symbol::Variable(_) => unreachable!()
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -190,7 +248,16 @@ impl SymbolTable {
         self.symbols.push(Symbol::Function(FunctionSymbol {
             id: new_id.clone(),
             name,
+            inferred: Inferred::Unknown,
             type_id: None,
+            package_id: None,
+            bounds: vec![],
+            external: false,
+            exported: false,
+            readonly: vec![],
+            arguments: vec![],
+            named: false,
+            span: Span::default(),
         }));
         new_id
     }
@@ -201,6 +268,8 @@ impl SymbolTable {
             id: new_id.clone(),
             name,
             package_id: None,
+            exported: false,
+            span: Span::default(),
         }));
         new_id
     }
@@ -211,6 +280,8 @@ impl SymbolTable {
             id: new_id.clone(),
             name,
             type_id: None,
+            exported: false,
+            span: Span::default(),
         }));
         new_id
     }
@@ -222,16 +293,56 @@ impl SymbolTable {
             name,
             inferred,
             type_id: None,
+            mutable: false,
+            readonly: false,
+            span: Span::default(),
         }));
         new_id
     }
 
+    pub(crate) fn function(&self, id: SymbolId) -> &FunctionSymbol {
+        match self.index(id) {
+            Symbol::Function(symbol) => symbol,
+            _ => panic!("Not function symbol")
+        }
+    }
+
+    pub(crate) fn function_mut(&mut self, id: SymbolId) -> &mut FunctionSymbol {
+        match self.index_mut(id) {
+            Symbol::Function(symbol) => symbol,
+            _ => panic!("Not function symbol")
+        }
+    }
+
+    // functions and variables which hold a function can both be called
+    pub(crate) fn signature(&self, id: SymbolId) -> &Inferred {
+        match self.index(id) {
+            Symbol::Function(symbol) => &symbol.inferred,
+            Symbol::Variable(symbol) => &symbol.inferred,
+            _ => panic!("Not callable symbol")
+        }
+    }
+
+    pub(crate) fn package(&self, id: SymbolId) -> &PackageSymbol {
+        match self.index(id) {
+            Symbol::Package(symbol) => symbol,
+            _ => panic!("Not package symbol")
+        }
+    }
+
     pub(crate) fn variable(&self, id: SymbolId) -> &VariableSymbol {
         match self.index(id) {
             Symbol::Variable(symbol) => symbol,
             _ => panic!("Not variable symbol")
         }
     }
+
+    pub(crate) fn variable_mut(&mut self, id: SymbolId) -> &mut VariableSymbol {
+        match self.index_mut(id) {
+            Symbol::Variable(symbol) => symbol,
+            _ => panic!("Not variable symbol")
+        }
+    }
 }
 
 impl Index<SymbolId> for SymbolTable {
@@ -307,6 +418,18 @@ mod tests {
         let symbol = &table[1];
         assert_eq!(symbol.id(), SymbolId(1));
         assert_eq!(symbol.name_str(&ctx.string_table), "package");
+        assert!(!symbol.is_exported());
+    }
+
+    #[test]
+    fn export_symbol() {
+        let mut ctx = Context::testing();
+        let mut table = SymbolTable::new();
+
+        let id = table.register_function(SymbolName(ctx.str_push("function")));
+        table[id].set_exported(true);
+
+        assert!(table[id].is_exported());
     }
 
     #[test]
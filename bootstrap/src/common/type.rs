@@ -1,7 +1,7 @@
 use std::collections::HashMap;
-use std::ops::Index;
+use std::ops::{Index, IndexMut};
 
-use crate::common::{StringTable, StringTableId};
+use crate::common::{Inferred, StringTable, StringTableId, SymbolId};
 
 #[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
 pub enum TypeId {
@@ -29,6 +29,9 @@ impl TypeId {
     pub const UINT4: TypeId = TypeId::Builtin(16);
     pub const UINT8: TypeId = TypeId::Builtin(17);
     pub const UINT16: TypeId = TypeId::Builtin(18);
+    pub const FUNCTION: TypeId = TypeId::Builtin(19);
+    // the types of the elements are only known to the inferred tuple
+    pub const TUPLE: TypeId = TypeId::Builtin(20);
 }
 
 
@@ -40,14 +43,28 @@ pub struct Type {
     pub id: TypeId,
     pub name: TypeName,
     pub variables: Vec<TypeVariable>,
-    // trait
-    // definitions
+    // the names of the type parameters, List<T> has T
+    pub parameters: Vec<StringTableId>,
+    // c struct which is laid out like its variables
+    pub external: bool,
+    // only declares methods, the types which implement it provide them
+    pub r#trait: bool,
+    // the traits the type implements
+    pub traits: Vec<TypeId>,
+    // the functions defined for the type, a trait keeps them in the order of its vtable
+    pub methods: Vec<TypeMethod>,
+    // the alternatives of a sum type, the tag of a value is the index of its variant
+    pub variants: Vec<TypeId>,
+    // the sum type a variant belongs to, values of a variant are values of the sum type
+    pub variant_of: Option<TypeId>,
     // FIXME track declaration like file, position etc..
 }
 
 #[derive(Debug, Clone)]
 pub struct TypeVariable {
     pub type_id: TypeId,
+    // the declared type, which might refer to type parameters of the type
+    pub inferred: Inferred,
     pub variable_id: TypeVariableId,
     pub name: TypeVariableName,
 }
@@ -55,6 +72,26 @@ pub struct TypeVariable {
 #[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
 pub struct TypeVariableId(pub usize);
 
+// methods get the object they are called on as first argument
+#[derive(Debug, Clone)]
+pub struct TypeMethod {
+    pub name: StringTableId,
+    pub function: SymbolId,
+    // a method of a trait with a default implementation
+    pub default: bool,
+    // declared as operator function, a + b calls add of a with b
+    pub operator: bool,
+}
+
+// how the call of a method finds its function
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dispatch {
+    // the type of the object is known, the function gets called directly
+    Static,
+    // the object is a trait value, the function is at the index of its vtable
+    Vtable(usize),
+}
+
 #[derive(Debug, Clone)]
 pub struct TypeVariableName(pub StringTableId);
 
@@ -72,9 +109,9 @@ impl TypeTable {
             custom: Vec::new(),
         };
 
-        result.builtin.insert(TypeId::BOOLEAN, Type { id: TypeId::BOOLEAN, name: TypeName(string_table.push_str("Boolean")), variables: vec![] });
-        result.builtin.insert(TypeId::NUMBER, Type { id: TypeId::NUMBER, name: TypeName(string_table.push_str("Number")), variables: vec![] });
-        result.builtin.insert(TypeId::STRING, Type { id: TypeId::STRING, name: TypeName(string_table.push_str("String")), variables: vec![] });
+        result.builtin.insert(TypeId::BOOLEAN, Type { id: TypeId::BOOLEAN, name: TypeName(string_table.push_str("Boolean")), variables: vec![], parameters: vec![], external: false, r#trait: false, traits: vec![], methods: vec![], variants: vec![], variant_of: None });
+        result.builtin.insert(TypeId::NUMBER, Type { id: TypeId::NUMBER, name: TypeName(string_table.push_str("Number")), variables: vec![], parameters: vec![], external: false, r#trait: false, traits: vec![], methods: vec![], variants: vec![], variant_of: None });
+        result.builtin.insert(TypeId::STRING, Type { id: TypeId::STRING, name: TypeName(string_table.push_str("String")), variables: vec![], parameters: vec![], external: false, r#trait: false, traits: vec![], methods: vec![], variants: vec![], variant_of: None });
 
         // result.types.push(Type {
         //     id: any_id.clone(),
@@ -110,10 +147,104 @@ impl TypeTable {
             id,
             name,
             variables: Vec::new(),
+            parameters: Vec::new(),
+            external: false,
+            r#trait: false,
+            traits: Vec::new(),
+            methods: Vec::new(),
+            variants: Vec::new(),
+            variant_of: None,
         });
         id
     }
 
+    pub fn register_trait(&mut self, name: TypeName) -> TypeId {
+        let id = self.register(name);
+        self.index_mut(id).r#trait = true;
+        id
+    }
+
+    pub fn register_external(&mut self, name: TypeName) -> TypeId {
+        let id = self.register(name);
+        self.index_mut(id).external = true;
+        id
+    }
+
+    // a variant takes the type parameters of its sum type
+    pub fn register_variant(&mut self, sum: TypeId, name: TypeName) -> TypeId {
+        let id = self.register(name);
+        let parameters = self[sum].parameters.clone();
+        let variant = self.index_mut(id);
+        variant.parameters = parameters;
+        variant.variant_of = Some(sum);
+        self.index_mut(sum).variants.push(id);
+        id
+    }
+
+    // variables keep the order of their declaration
+    pub fn register_variable(&mut self, type_id: TypeId, name: TypeVariableName, inferred: Inferred) -> TypeVariableId {
+        let r#type = self.index_mut(type_id);
+        let variable_id = TypeVariableId(r#type.variables.len());
+        r#type.variables.push(TypeVariable {
+            type_id: inferred.type_id().unwrap_or(TypeId::UNKNOWN),
+            inferred,
+            variable_id,
+            name,
+        });
+        variable_id
+    }
+
+    pub fn variable(&self, type_id: TypeId, name: StringTableId) -> Option<&TypeVariable> {
+        self[type_id].variables.iter().find(|v| v.name.0 == name)
+    }
+
+    pub fn is_external(&self, type_id: TypeId) -> bool {
+        matches!(type_id, TypeId::Custom(_)) && self[type_id].external
+    }
+
+    pub fn is_trait(&self, type_id: TypeId) -> bool {
+        matches!(type_id, TypeId::Custom(_)) && self[type_id].r#trait
+    }
+
+    pub fn is_sum(&self, type_id: TypeId) -> bool {
+        matches!(type_id, TypeId::Custom(_)) && !self[type_id].variants.is_empty()
+    }
+
+    pub fn is_variant(&self, type_id: TypeId) -> bool {
+        matches!(type_id, TypeId::Custom(_)) && self[type_id].variant_of.is_some()
+    }
+
+    pub fn variant(&self, sum: TypeId, name: StringTableId) -> Option<TypeId> {
+        self[sum].variants.iter().find(|v| self[**v].name.0 == name).cloned()
+    }
+
+    pub fn variant_tag(&self, variant: TypeId) -> Option<usize> {
+        let sum = self[variant].variant_of?;
+        self[sum].variants.iter().position(|v| *v == variant)
+    }
+
+    pub fn register_method(&mut self, type_id: TypeId, name: StringTableId, function: SymbolId, default: bool) {
+        self.index_mut(type_id).methods.push(TypeMethod { name, function, default, operator: false });
+    }
+
+    // an operator function is a method as well, it can still be called by its name
+    pub fn register_operator(&mut self, type_id: TypeId, name: StringTableId, function: SymbolId) {
+        self.index_mut(type_id).methods.push(TypeMethod { name, function, default: false, operator: true });
+    }
+
+    pub fn operator(&self, type_id: TypeId, name: StringTableId) -> Option<&TypeMethod> {
+        self[type_id].methods.iter().find(|m| m.operator && m.name == name)
+    }
+
+    pub fn method(&self, type_id: TypeId, name: StringTableId) -> Option<&TypeMethod> {
+        self[type_id].methods.iter().find(|m| m.name == name)
+    }
+
+    // a trait implements itself, its values carry the vtable of the type they were created from
+    pub fn implements(&self, type_id: TypeId, r#trait: TypeId) -> bool {
+        type_id == r#trait || (matches!(type_id, TypeId::Custom(_)) && self[type_id].traits.contains(&r#trait))
+    }
+
     // pub fn append_variable(&mut self, type_id: TypeId, name: TypeVariableName, variable_type_id: TypeId) -> TypeId {
     //     // let mut t = self.builtin.get(type_id.0 - self.offset).unwrap().clone();
     //     //
@@ -131,8 +262,6 @@ impl TypeTable {
     //     // result
     // }
 
-    // append_trait
-    // ...
 }
 
 impl Index<TypeId> for TypeTable {
@@ -143,4 +272,13 @@ impl Index<TypeId> for TypeTable {
             TypeId::Custom(id) => self.custom.index(id)
         }
     }
+}
+
+impl IndexMut<TypeId> for TypeTable {
+    fn index_mut(&mut self, index: TypeId) -> &mut Self::Output {
+        match index {
+            TypeId::Builtin(_) => self.builtin.get_mut(&index).unwrap(),
+            TypeId::Custom(id) => self.custom.index_mut(id)
+        }
+    }
 }
\ No newline at end of file
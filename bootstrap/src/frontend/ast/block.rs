@@ -6,6 +6,12 @@ use crate::frontend::{ast, parse};
 
 impl<'a> Generator<'a> {
     pub(crate) fn generate_block(&mut self, node: &parse::BlockNode) -> ast::Result<AstTreeNode> {
+        if let [parse::Node::Infix(infix)] = node.nodes.as_slice() {
+            if matches!(infix.operator, parse::InfixOperator::Arrow(_)) && infix.left.is_tuple() {
+                return self.generate_declare_lambda(node, infix);
+            }
+        }
+
         let mut nodes = Vec::with_capacity(node.nodes.len());
 
         for node in &node.nodes {
@@ -3,34 +3,48 @@ use std::rc::Rc;
 
 use crate::common::node::Node;
 use crate::frontend::ast::{
-    AstBlockNode, AstBreakLoopNode, AstContinueLoopNode, AstIfNode, AstLoopNode, AstNode,
-    AstTreeNode, Generator, SPAN_NOT_IMPLEMENTED,
+    AstBlockNode, AstBreakLoopNode, AstContinueLoopNode, AstDeferNode, AstIdentifier, AstIfNode, AstLoopNode, AstMatchArm,
+    AstIteratorLoopNode, AstMatchNode, AstMatchPattern, AstNode, AstRangeLoopNode, AstTreeNode, AstTryNode, Generator,
 };
 use crate::frontend::{ast, parse};
 
 impl<'a> Generator<'a> {
     pub(crate) fn generate_break(&mut self, node: &parse::BreakNode) -> ast::Result<AstTreeNode> {
+        let label = node.label.as_ref().map(|label| AstIdentifier(label.value()));
         if node.result.is_none() {
             Ok(AstTreeNode::new(
-                AstNode::BreakLoop(AstBreakLoopNode { node: None }),
-                SPAN_NOT_IMPLEMENTED.clone(),
+                AstNode::BreakLoop(AstBreakLoopNode { label, node: None }),
+                node.token.span.clone(),
             ))
         } else {
-            let node = Some(Rc::new(self.generate_node(node.result.as_ref().unwrap())?));
+            let result = Some(Rc::new(self.generate_node(node.result.as_ref().unwrap())?));
             Ok(AstTreeNode::new(
-                AstNode::BreakLoop(AstBreakLoopNode { node }),
-                SPAN_NOT_IMPLEMENTED.clone(),
+                AstNode::BreakLoop(AstBreakLoopNode { label, node: result }),
+                node.token.span.clone(),
             ))
         }
     }
 
     pub(crate) fn generate_continue(
         &mut self,
-        _node: &parse::ContinueNode,
+        node: &parse::ContinueNode,
     ) -> ast::Result<AstTreeNode> {
+        let label = node.label.as_ref().map(|label| AstIdentifier(label.value()));
         Ok(AstTreeNode::new(
-            Node::ContinueLoop(AstContinueLoopNode {}),
-            SPAN_NOT_IMPLEMENTED.clone(),
+            Node::ContinueLoop(AstContinueLoopNode { label }),
+            node.token.span.clone(),
+        ))
+    }
+
+    pub(crate) fn generate_defer(&mut self, node: &parse::DeferNode) -> ast::Result<AstTreeNode> {
+        let mut nodes = Vec::with_capacity(node.block.nodes.len());
+        for node in &node.block.nodes {
+            nodes.push(self.generate_node(node)?)
+        }
+
+        Ok(AstTreeNode::new(
+            Node::Defer(AstDeferNode { nodes }),
+            node.token.span.clone(),
         ))
     }
 
@@ -42,11 +56,43 @@ impl<'a> Generator<'a> {
         }
 
         Ok(AstTreeNode::new(
-            Node::Loop(AstLoopNode { nodes }),
-            SPAN_NOT_IMPLEMENTED.clone(),
+            Node::Loop(AstLoopNode { label: node.label.as_ref().map(|label| AstIdentifier(label.value())), nodes }),
+            node.token.span.clone(),
         ))
     }
 
+    pub(crate) fn generate_for(&mut self, node: &parse::ForNode) -> ast::Result<AstTreeNode> {
+        let label = node.label.as_ref().map(|label| AstIdentifier(label.value()));
+        let variable = AstIdentifier(node.identifier.value());
+        let result = match &node.source {
+            parse::ForSource::Range(range) => {
+                let start = Rc::new(self.generate_node(range.start.deref())?);
+                let end = Rc::new(self.generate_node(range.end.deref())?);
+                let step = match &range.step {
+                    Some(step) => Some(Rc::new(self.generate_node(step.deref())?)),
+                    None => None
+                };
+                let nodes = self.generate_for_block(&node.block)?;
+                Node::RangeLoop(AstRangeLoopNode { label, variable, start, end, inclusive: range.inclusive, step, nodes })
+            }
+            parse::ForSource::Value(value) => {
+                let iterable = Rc::new(self.generate_node(value.deref())?);
+                let nodes = self.generate_for_block(&node.block)?;
+                Node::IteratorLoop(AstIteratorLoopNode { label, variable, iterable, nodes })
+            }
+        };
+
+        Ok(AstTreeNode::new(result, node.token.span.clone()))
+    }
+
+    fn generate_for_block(&mut self, block: &parse::BlockNode) -> ast::Result<Vec<AstTreeNode>> {
+        let mut nodes = Vec::with_capacity(block.nodes.len());
+        for node in &block.nodes {
+            nodes.push(self.generate_node(node)?)
+        }
+        Ok(nodes)
+    }
+
     pub(crate) fn generate_if(&mut self, node: &parse::IfNode) -> ast::Result<AstTreeNode> {
         // condition needs to be of type boolean --> every node has a type?!
         let condition = Rc::new(self.generate_node(node.condition.deref())?);
@@ -74,7 +120,48 @@ impl<'a> Generator<'a> {
                 then: Rc::new(AstBlockNode { nodes: then_body }),
                 otherwise,
             }),
-            SPAN_NOT_IMPLEMENTED.clone(),
+            node.token.span.clone(),
+        ))
+    }
+
+    pub(crate) fn generate_match(&mut self, node: &parse::MatchNode) -> ast::Result<AstTreeNode> {
+        let value = Rc::new(self.generate_node(node.node.deref())?);
+
+        let mut arms = Vec::with_capacity(node.arms.len());
+        for arm in &node.arms {
+            let mut nodes = Vec::with_capacity(arm.block.nodes.len());
+            for node in &arm.block.nodes {
+                nodes.push(self.generate_node(node)?)
+            }
+
+            let (pattern, span) = match &arm.pattern {
+                parse::MatchPatternNode::Variant { identifier, bindings } => (
+                    AstMatchPattern::Variant {
+                        variant: AstIdentifier(identifier.value()),
+                        bindings: bindings.iter().map(|b| AstIdentifier(b.value())).collect(),
+                    },
+                    identifier.0.span.clone()
+                ),
+                parse::MatchPatternNode::Otherwise(token) => (AstMatchPattern::Otherwise, token.span.clone())
+            };
+
+            arms.push(AstMatchArm {
+                pattern,
+                nodes: Rc::new(AstBlockNode { nodes }),
+                span,
+            });
+        }
+
+        Ok(AstTreeNode::new(
+            Node::Match(AstMatchNode { node: value, arms }),
+            node.token.span.clone(),
+        ))
+    }
+
+    pub(crate) fn generate_try(&mut self, node: &parse::TryNode) -> ast::Result<AstTreeNode> {
+        Ok(AstTreeNode::new(
+            Node::Try(AstTryNode { node: Rc::new(self.generate_node(node.node.deref())?) }),
+            node.token.span.clone(),
         ))
     }
 }
@@ -4,7 +4,7 @@ use std::rc::Rc;
 use crate::common::node::Node;
 use crate::common::node::Node::ReturnFromFunction;
 use crate::frontend::ast::{
-    AstBlockNode, AstDeclareExternalFunctionNode, AstDeclareFunctionNode, AstFunctionArgument,
+    AstBlockNode, AstDeclareExternalFunctionNode, AstDeclareFunctionNode, AstDeclareLambdaNode, AstFunctionArgument,
     AstIdentifier, AstReturnFromFunctionNode, AstTreeNode, Generator, SPAN_NOT_IMPLEMENTED,
 };
 use crate::frontend::{ast, parse};
@@ -19,13 +19,24 @@ impl<'a> Generator<'a> {
             arguments.push(self.generate_declare_function_argument(arg)?)
         }
 
+        let return_type = if let Some(type_node) = node.return_type.as_deref() {
+            Some(self.to_ast_type(type_node))
+        } else {
+            None
+        };
+
+        let span = node.identifier.0.span.clone();
+
         Ok(AstTreeNode::new(
             Node::DeclareExternalFunction(AstDeclareExternalFunctionNode {
                 function: AstIdentifier(node.identifier.value()),
+                type_parameters: self.to_ast_type_parameters(&node.type_parameters),
+                modifiers: node.modifiers.clone(),
+                span: span.clone(),
                 arguments,
-                return_type: None,
+                return_type,
             }),
-            SPAN_NOT_IMPLEMENTED.clone(),
+            span,
         ))
     }
 
@@ -49,14 +60,62 @@ impl<'a> Generator<'a> {
             None
         };
 
+        let span = node.identifier.0.span.clone();
+
         Ok(AstTreeNode::new(
             Node::DeclareFunction(AstDeclareFunctionNode {
                 function: AstIdentifier(node.identifier.value()),
+                type_parameters: self.to_ast_type_parameters(&node.type_parameters),
+                modifiers: node.modifiers.clone(),
+                span: span.clone(),
                 arguments,
                 return_type,
                 nodes: Rc::new(AstBlockNode { nodes }),
             }),
-            SPAN_NOT_IMPLEMENTED.clone(),
+            span,
+        ))
+    }
+
+    // { (x, y: Int4) -> x + y }
+    pub(crate) fn generate_declare_lambda(
+        &mut self,
+        block: &parse::BlockNode,
+        node: &parse::InfixNode,
+    ) -> ast::Result<AstTreeNode> {
+        let mut arguments = Vec::with_capacity(node.left.as_tuple().nodes.len());
+        for argument in &node.left.as_tuple().nodes {
+            arguments.push(match argument {
+                parse::Node::Infix(parse::InfixNode { left, right, .. }) => AstFunctionArgument {
+                    argument: AstIdentifier(left.as_identifier().value()),
+                    argument_type: Some(self.to_ast_type(right.as_type())),
+                    readonly: false,
+                    default: None,
+                    span: left.as_identifier().0.span.clone(),
+                },
+                argument => AstFunctionArgument {
+                    argument: AstIdentifier(argument.as_identifier().value()),
+                    argument_type: None,
+                    readonly: false,
+                    default: None,
+                    span: argument.as_identifier().0.span.clone(),
+                }
+            })
+        }
+
+        let mut nodes = vec![];
+        for node in &node.right.as_block().nodes {
+            nodes.push(self.generate_node(node)?)
+        }
+
+        let span = block.token.span.clone();
+
+        Ok(AstTreeNode::new(
+            Node::DeclareLambda(AstDeclareLambdaNode {
+                span: span.clone(),
+                arguments,
+                nodes: Rc::new(AstBlockNode { nodes }),
+            }),
+            span,
         ))
     }
 
@@ -70,9 +129,18 @@ impl<'a> Generator<'a> {
             None
         };
 
+        let default = if let Some(default) = node.default.as_deref() {
+            Some(self.generate_node(default)?)
+        } else {
+            None
+        };
+
         Ok(AstFunctionArgument {
             argument: AstIdentifier(node.identifier.value()),
             argument_type,
+            readonly: node.modifiers.is_readonly(),
+            default,
+            span: node.identifier.0.span.clone(),
         })
     }
 
@@ -1,6 +1,6 @@
 use crate::common::node::Node::AccessVariable;
 use crate::frontend::ast::{
-    AstAccessVariableNode, AstIdentifier, AstTreeNode, Generator, SPAN_NOT_IMPLEMENTED,
+    AstAccessVariableNode, AstIdentifier, AstTreeNode, Generator,
 };
 use crate::frontend::{ast, parse};
 
@@ -13,7 +13,7 @@ impl<'a> Generator<'a> {
             AccessVariable(AstAccessVariableNode {
                 variable: AstIdentifier(node.value()),
             }),
-            SPAN_NOT_IMPLEMENTED.clone(),
+            node.0.span.clone(),
         ));
     }
 
@@ -22,7 +22,7 @@ impl<'a> Generator<'a> {
             AccessVariable(AstAccessVariableNode {
                 variable: AstIdentifier(node.value()),
             }),
-            SPAN_NOT_IMPLEMENTED.clone(),
+            node.0.span.clone(),
         ));
     }
 }
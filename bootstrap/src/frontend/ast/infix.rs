@@ -3,18 +3,18 @@ use std::rc::Rc;
 
 use crate::common::node::{CalculateOperator, CompareOperator};
 use crate::common::node::Node::{
-    AccessVariableOfObject, AccessVariableOfSelf, Block, Calculate, CallFunction,
+    AccessIndex, AccessVariableOfObject, AccessVariableOfSelf, AssignVariable, AssignVariableOfObject, Block, Calculate, CallFunction,
     CallFunctionOfObject, CallFunctionOfPackage, CallFunctionWithLambda, Compare, InstantiateType,
 };
-use crate::common::PackagePath;
+use crate::common::{PackagePath, Span, WithSpan};
 use crate::frontend::{ast, parse};
 use crate::frontend::ast::{
-    AstAccessVariableOfObjectNode, AstAccessVariableOfSelfNode, AstCalculateNode,
+    AstAccessIndexNode, AstAccessVariableOfObjectNode, AstAccessVariableOfSelfNode, AstAssignVariableNode, AstAssignVariableOfObjectNode, AstCalculateNode,
     AStCallFunctionNode, AstCallFunctionOfObjectNode, AstCallFunctionOfPackageNode,
     AstCallFunctionWithLambdaNode, AstCompareNode, AstIdentifier, AstInstantiateTypeNode,
     AstNamedArgument, AstTreeNode, Generator, SPAN_NOT_IMPLEMENTED,
 };
-use crate::frontend::parse::{InfixNode, InfixOperator, Node, TypeNode};
+use crate::frontend::parse::{InfixNode, InfixOperator, LiteralNode, Node, TypeNode};
 use crate::frontend::parse::Node::Type;
 
 impl<'a> Generator<'a> {
@@ -35,13 +35,14 @@ impl<'a> Generator<'a> {
             let Node::Identifier(function_identifier) = left.deref() else {
                 todo!()
             };
-            let arguments = self.generate_arguments(right.as_tuple())?;
+            let (arguments, named) = self.generate_call_arguments(right.as_tuple())?;
             return Ok(AstTreeNode::new(
                 CallFunction(AStCallFunctionNode {
                     function: AstIdentifier(function_identifier.0.value),
                     arguments,
+                    named,
                 }),
-                SPAN_NOT_IMPLEMENTED.clone(),
+                function_identifier.0.span.clone(),
             ));
         }
 
@@ -50,14 +51,15 @@ impl<'a> Generator<'a> {
             && matches!(left.as_infix().operator, InfixOperator::AccessProperty(_))
             && matches!(operator, InfixOperator::Call(_))
         {
-            let AccessVariableOfObject(AstAccessVariableOfObjectNode { object, variable }) = self
-                .generate_access_variable(left.as_infix())?
-                .node_to_owned()
-                else {
-                    panic!()
-                };
+            let access = self.generate_access_variable(left.as_infix())?;
+            let span = access.span();
+            let (object, variable) = match access.node_to_owned() {
+                AccessVariableOfObject(AstAccessVariableOfObjectNode { object, variable }) => (object, variable),
+                AccessVariableOfSelf(AstAccessVariableOfSelfNode { variable }) => (AstIdentifier(left.as_infix().left.as_itself().value()), variable),
+                _ => panic!()
+            };
 
-            let arguments = self.generate_arguments(right.as_tuple())?;
+            let (arguments, named) = self.generate_call_arguments(right.as_tuple())?;
 
             // FIXME add type information
             return Ok(AstTreeNode::new(
@@ -65,11 +67,22 @@ impl<'a> Generator<'a> {
                     object: ast::AstIdentifier(object.0.clone()),
                     function: ast::AstIdentifier(variable.0.clone()),
                     arguments,
+                    named,
                 }),
-                SPAN_NOT_IMPLEMENTED.clone(),
+                span,
             ));
         };
 
+        if let InfixOperator::Index(_) = operator {
+            let node = Rc::new(self.generate_node(left.deref())?);
+            let index = Rc::new(self.generate_node(right.deref())?);
+
+            return Ok(AstTreeNode::new(
+                AccessIndex(AstAccessIndexNode { node, index }),
+                SPAN_NOT_IMPLEMENTED.clone(),
+            ));
+        }
+
         // lambda call
         if let InfixOperator::LambdaCall(_) = operator {
             let left = self.generate_node(left.deref())?;
@@ -87,6 +100,7 @@ impl<'a> Generator<'a> {
                     function: call_function.function.clone(),
                     lambda: Rc::new(lambda),
                     arguments: call_function.arguments.clone(),
+                    named: call_function.named.clone(),
                 }),
                 SPAN_NOT_IMPLEMENTED.clone(),
             ));
@@ -97,8 +111,6 @@ impl<'a> Generator<'a> {
             && matches!(left.as_infix().operator, InfixOperator::AccessPackage(_))
             && matches!(operator, InfixOperator::Call(_))
         {
-            let arguments = self.generate_arguments(node.right.as_tuple())?;
-
             // FIXME
             let paths = {
                 if left.as_infix().left.is_infix()
@@ -113,18 +125,40 @@ impl<'a> Generator<'a> {
                 }
             };
 
+            let package = PackagePath::from(paths.into_iter().map(|p| p.0).collect::<Vec<_>>());
+
+            // instantiate type of package
+            if let Type(TypeNode::Type(type_node)) = left.as_infix().right.deref() {
+                let arguments = self.generate_named_arguments(right.as_tuple())?;
+                return Ok(AstTreeNode::new(
+                    InstantiateType(AstInstantiateTypeNode {
+                        package: Some(package),
+                        r#type: AstIdentifier(type_node.value),
+                        arguments,
+                    }),
+                    type_node.span.clone(),
+                ));
+            }
+
+            let (arguments, named) = self.generate_call_arguments(node.right.as_tuple())?;
             let function_identifier = left.as_infix().right.as_identifier();
 
             return Ok(AstTreeNode::new(
                 CallFunctionOfPackage(AstCallFunctionOfPackageNode {
-                    package: PackagePath::from(paths.into_iter().map(|p| p.0).collect::<Vec<_>>()),
+                    package,
                     function: AstIdentifier(function_identifier.value()),
                     arguments,
+                    named,
                 }),
-                SPAN_NOT_IMPLEMENTED.clone(),
+                function_identifier.0.span.clone(),
             ));
         }
 
+        // tuple.0
+        if let (InfixOperator::AccessProperty(_), Node::Literal(LiteralNode::Number(index))) = (operator, right.deref()) {
+            return self.generate_access_element_of_tuple(left.deref(), index);
+        }
+
         // self.variable
         if left.is_itself()
             && matches!(operator, InfixOperator::AccessProperty(_))
@@ -135,7 +169,7 @@ impl<'a> Generator<'a> {
                 AccessVariableOfSelf(AstAccessVariableOfSelfNode {
                     variable: ast::AstIdentifier(variable.value()),
                 }),
-                SPAN_NOT_IMPLEMENTED.clone(),
+                Span::new(left.as_itself().0.span.start.clone(), variable.0.span.end.clone()),
             ));
         }
 
@@ -153,7 +187,41 @@ impl<'a> Generator<'a> {
                     object: ast::AstIdentifier(object.value()),
                     variable: ast::AstIdentifier(variable.value()),
                 }),
-                SPAN_NOT_IMPLEMENTED.clone(),
+                Span::new(object.0.span.start.clone(), variable.0.span.end.clone()),
+            ));
+        }
+
+        // variable = value
+        if matches!(operator, InfixOperator::Assign(_)) && left.is_identifier() {
+            let variable = AstIdentifier(left.as_identifier().value());
+            let value = Rc::new(self.generate_node(right.deref())?);
+
+            return Ok(AstTreeNode::new(
+                AssignVariable(AstAssignVariableNode { variable, value }),
+                token.span.clone(),
+            ));
+        }
+
+        // variable.variable = value
+        if matches!(operator, InfixOperator::Assign(_))
+            && left.is_infix()
+            && matches!(left.as_infix().operator, InfixOperator::AccessProperty(_))
+        {
+            let (object, variable) = match self.generate_access_variable(left.as_infix())?.node_to_owned() {
+                AccessVariableOfObject(AstAccessVariableOfObjectNode { object, variable }) => (object, variable),
+                AccessVariableOfSelf(AstAccessVariableOfSelfNode { variable }) => (AstIdentifier(left.as_infix().left.as_itself().value()), variable),
+                _ => panic!()
+            };
+
+            let value = Rc::new(self.generate_node(right.deref())?);
+
+            return Ok(AstTreeNode::new(
+                AssignVariableOfObject(AstAssignVariableOfObjectNode {
+                    object,
+                    variable,
+                    value,
+                }),
+                token.span.clone(),
             ));
         }
 
@@ -328,7 +396,7 @@ impl<'a> Generator<'a> {
                     AccessVariableOfSelf(AstAccessVariableOfSelfNode {
                         variable: AstIdentifier(variable.0.value),
                     }),
-                    SPAN_NOT_IMPLEMENTED.clone(),
+                    Span::new(left.as_itself().0.span.start.clone(), variable.0.span.end.clone()),
                 ));
             }
         }
@@ -346,7 +414,7 @@ impl<'a> Generator<'a> {
                 object: ast::AstIdentifier(object_identifier.0.value),
                 variable: ast::AstIdentifier(variable.0.value),
             }),
-            SPAN_NOT_IMPLEMENTED.clone(),
+            Span::new(object_identifier.0.span.start.clone(), variable.0.span.end.clone()),
         ));
     }
 
@@ -369,28 +437,40 @@ impl<'a> Generator<'a> {
 
         return Ok(AstTreeNode::new(
             InstantiateType(AstInstantiateTypeNode {
+                package: None,
                 r#type: AstIdentifier(type_node.value),
                 arguments,
             }),
-            SPAN_NOT_IMPLEMENTED.clone(),
+            type_node.span.clone(),
         ));
     }
 
-    fn generate_arguments(&mut self, node: &parse::TupleNode) -> ast::Result<Vec<AstTreeNode>> {
-        let mut result = Vec::with_capacity(node.nodes.len());
-        for node in &node.nodes {
-            result.push(self.generate_node(node)?)
+    // f(1, message = 'two') - the parser made sure the named arguments come last
+    fn generate_call_arguments(&mut self, node: &parse::TupleNode) -> ast::Result<(Vec<AstTreeNode>, Vec<AstNamedArgument>)> {
+        let count = node.nodes.iter()
+            .take_while(|node| !matches!(node, Node::Infix(InfixNode { operator: InfixOperator::Assign(_), .. })))
+            .count();
+
+        let mut arguments = Vec::with_capacity(count);
+        for node in &node.nodes[..count] {
+            arguments.push(self.generate_node(node)?)
         }
-        Ok(result)
+
+        let named = self.generate_named_nodes(&node.nodes[count..])?;
+        Ok((arguments, named))
     }
 
-    fn generate_named_arguments(
+    pub(crate) fn generate_named_arguments(
         &mut self,
         node: &parse::TupleNode,
     ) -> ast::Result<Vec<AstNamedArgument>> {
-        let mut result = Vec::with_capacity(node.nodes.len());
+        self.generate_named_nodes(&node.nodes)
+    }
+
+    fn generate_named_nodes(&mut self, nodes: &[parse::Node]) -> ast::Result<Vec<AstNamedArgument>> {
+        let mut result = Vec::with_capacity(nodes.len());
 
-        for node in &node.nodes {
+        for node in nodes {
             let Node::Infix(InfixNode {
                                 left,
                                 operator,
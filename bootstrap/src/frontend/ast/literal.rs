@@ -1,7 +1,6 @@
-use crate::common::node::Node::{LiteralBoolean, LiteralNumber, LiteralString};
+use crate::common::node::Node::{LiteralBoolean, LiteralNone, LiteralNumber, LiteralString};
 use crate::frontend::ast::{
-    AstLiteralBooleanNode, AstLiteralNumberNode, AstLiteralStringNode, AstTreeNode, Generator,
-    SPAN_NOT_IMPLEMENTED,
+    AstLiteralBooleanNode, AstLiteralNoneNode, AstLiteralNumberNode, AstLiteralStringNode, AstTreeNode, Generator,
 };
 use crate::frontend::{ast, parse};
 
@@ -13,15 +12,19 @@ impl<'a> Generator<'a> {
         match node {
             parse::LiteralNode::Boolean(v) => Ok(AstTreeNode::new(
                 LiteralBoolean(AstLiteralBooleanNode(v.0.clone())),
-                SPAN_NOT_IMPLEMENTED.clone(),
+                v.0.span.clone(),
+            )),
+            parse::LiteralNode::None(v) => Ok(AstTreeNode::new(
+                LiteralNone(AstLiteralNoneNode(v.0.clone())),
+                v.0.span.clone(),
             )),
             parse::LiteralNode::Number(v) => Ok(AstTreeNode::new(
                 LiteralNumber(AstLiteralNumberNode(v.0.clone())),
-                SPAN_NOT_IMPLEMENTED.clone(),
+                v.0.span.clone(),
             )),
             parse::LiteralNode::String(v) => Ok(AstTreeNode::new(
                 LiteralString(AstLiteralStringNode(v.0.clone())),
-                SPAN_NOT_IMPLEMENTED.clone(),
+                v.0.span.clone(),
             )),
         }
     }
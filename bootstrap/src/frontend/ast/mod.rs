@@ -11,6 +11,8 @@ mod literal;
 pub(crate) mod node;
 mod package;
 mod string;
+mod r#trait;
+mod tuple;
 mod r#type;
 mod variable;
 
@@ -20,17 +22,24 @@ pub enum Error {}
 pub(crate) type Result<T, E = Error> = core::result::Result<T, E>;
 
 pub(crate) fn from(ctx: &mut Context, nodes: Vec<parse::Node>) -> Result<Ast> {
-    let mut compiler = Generator::new(ctx);
+    let mut compiler = Generator::new(ctx, None);
+    compiler.generate(nodes)
+}
+
+pub(crate) fn from_library(ctx: &mut Context, nodes: Vec<parse::Node>, directory: &str) -> Result<Ast> {
+    let mut compiler = Generator::new(ctx, Some(directory.to_string()));
     compiler.generate(nodes)
 }
 
 pub(crate) struct Generator<'a> {
     ctx: &'a mut Context,
+    // directory of the library file which gets generated, none for user programs
+    directory: Option<String>,
 }
 
 impl<'a> Generator<'a> {
-    fn new(ctx: &'a mut Context) -> Self {
-        Self { ctx }
+    fn new(ctx: &'a mut Context, directory: Option<String>) -> Self {
+        Self { ctx, directory }
     }
 }
 
@@ -52,10 +61,12 @@ impl<'a> Generator<'a> {
             parse::Node::Break(break_node) => Ok(self.generate_break(break_node)?),
             parse::Node::Continue(continue_node) => Ok(self.generate_continue(continue_node)?),
             parse::Node::DefineDeclaration(node) => Ok(self.generate_define_type(node)?),
+            parse::Node::Defer(node) => self.generate_defer(node),
             parse::Node::From(from_node) => Ok(self.generate_from(from_node)?),
             parse::Node::ExternalFunctionDeclaration(node) => {
                 self.generate_declare_external_function(node)
             }
+            parse::Node::ExternalTypeDeclaration(node) => self.generate_declare_external_type(node),
             parse::Node::FunctionDeclaration(declaration_node) => {
                 Ok(self.generate_declare_function(declaration_node)?)
             }
@@ -65,13 +76,20 @@ impl<'a> Generator<'a> {
             parse::Node::Identifier(identifier_node) => {
                 Ok(self.generate_identifier(identifier_node)?)
             }
+            parse::Node::For(node) => self.generate_for(node),
             parse::Node::If(if_node) => Ok(self.generate_if(if_node)?),
             parse::Node::Infix(infix_node) => Ok(self.generate_infix(infix_node)?),
             parse::Node::StringInterpolation(node) => self.generate_interpolate_string(node),
             parse::Node::Itself(node) => Ok(self.generate_self(node)?),
             parse::Node::Literal(literal_node) => Ok(self.generate_literal(literal_node)?),
             parse::Node::Loop(loop_node) => Ok(self.generate_loop(loop_node)?),
+            parse::Node::Match(match_node) => self.generate_match(match_node),
             parse::Node::Return(return_node) => Ok(self.generate_function_return(return_node)?),
+            parse::Node::TraitDeclaration(node) => Ok(self.generate_declare_trait(node)?),
+            parse::Node::Try(node) => self.generate_try(node),
+            parse::Node::Tuple(node) => self.generate_tuple(node),
+            parse::Node::TupleDeclaration(node) => self.generate_declare_tuple(node),
+            parse::Node::Type(parse::TypeNode::Type(token)) => self.generate_type_value(token),
             parse::Node::TypeDeclaration(node) => Ok(self.generate_declare_type(node)?),
             parse::Node::VariableDeclaration(let_node) => {
                 Ok(self.generate_declare_variable(let_node)?)
@@ -5,7 +5,7 @@ use std::rc::Rc;
 use node::CalculateNode;
 
 use crate::common::{Column, Index, node, PackagePath, Position, Row, Span, StringTable, StringTableId, Type, TypeId, TypeTable, WithSpan};
-use crate::common::node::{AccessVariableNode, AccessVariableOfObjectNode, AccessVariableOfSelfNode, BlockNode, BreakLoopNode, CallFunctionNode, CallFunctionOfObjectNode, CallFunctionOfPackageNode, CallFunctionWithLambdaNode, CompareNode, CompareOperator, ContinueLoopNode, DeclareExternalFunctionNode, DeclareFunctionNode, DeclarePackageNode, DeclareTypeNode, DeclareVariableNode, DefineTypeNode, ExportPackageNode, IfNode, InstantiateTypeNode, InterpolateStringNode, LiteralBooleanNode, LiteralFloat4Node, LiteralFloat8Node, LiteralInt16Node, LiteralInt1Node, LiteralInt2Node, LiteralInt4Node, LiteralInt8Node, LiteralNumberNode, LiteralStringNode, LiteralUint16Node, LiteralUint1Node, LiteralUint2Node, LiteralUint4Node, LiteralUint8Node, LoopNode, Node, ReturnFromFunctionNode, Source, Variant};
+use crate::common::node::{AccessElementOfTupleNode, AccessIndexNode, AccessVariableNode, AccessVariableOfObjectNode, AccessVariableOfSelfNode, AssignVariableNode, AssignVariableOfObjectNode, BlockNode, BreakLoopNode, CallFunctionNode, CallFunctionOfObjectNode, CallFunctionOfPackageNode, CallFunctionWithLambdaNode, CompareNode, CompareOperator, ContinueLoopNode, DeclareExternalFunctionNode, DeclareFunctionNode, DeclareLambdaNode, DeclarePackageNode, DeclareTraitNode, DeclareTypeNode, DeclareVariableNode, DeferNode, DefineTypeNode, DestructureTupleNode, ExportPackageNode, IfNode, InstantiateRecordNode, InstantiateTupleNode, InstantiateTypeNode, InterpolateStringNode, IteratorLoopNode, LiteralBooleanNode, LiteralFloat4Node, LiteralFloat8Node, LiteralInt16Node, LiteralInt1Node, LiteralInt2Node, LiteralInt4Node, LiteralInt8Node, LiteralNoneNode, LiteralNumberNode, LiteralStringNode, LiteralUint16Node, LiteralUint1Node, LiteralUint2Node, LiteralUint4Node, LiteralUint8Node, LoopNode, MatchNode, Node, RangeLoopNode, ReturnFromFunctionNode, TryNode, Source, Variant};
 use crate::frontend::lex::token::Token;
 use crate::frontend::modifier::Modifiers;
 
@@ -16,9 +16,13 @@ impl Variant for AstVariant {}
 
 pub type AstNode = Node<
     AstVariant,
+    AstAccessElementOfTupleNode,
+    AstAccessIndexNode,
     AstAccessVariableNode,
     AstAccessVariableOfObjectNode,
     AstAccessVariableOfSelfNode,
+    AstAssignVariableNode,
+    AstAssignVariableOfObjectNode,
     AstBlockNode,
     AstBreakLoopNode,
     AstCalculateNode,
@@ -30,13 +34,20 @@ pub type AstNode = Node<
     AstContinueLoopNode,
     AstDeclareExternalFunctionNode,
     AstDeclareFunctionNode,
+    AstDeclareLambdaNode,
     AstDeclarePackageNode,
+    AstDeclareTraitNode,
     AstDeclareTypeNode,
     AstDeclareVariableNode,
+    AstDeferNode,
     AstDefineTypeNode,
+    AstDestructureTupleNode,
     AstExportPackageNode,
     AstIfNode,
     AstInterpolateStringNode,
+    AstIteratorLoopNode,
+    AstInstantiateRecordNode,
+    AstInstantiateTupleNode,
     AstInstantiateTypeNode,
     AstLiteralBooleanNode,
     AstLiteralFloat4Node,
@@ -46,6 +57,7 @@ pub type AstNode = Node<
     AstLiteralInt4Node,
     AstLiteralInt8Node,
     AstLiteralInt16Node,
+    AstLiteralNoneNode,
     AstLiteralNumberNode,
     AstLiteralStringNode,
     AstLiteralUint1Node,
@@ -54,7 +66,10 @@ pub type AstNode = Node<
     AstLiteralUint8Node,
     AstLiteralUint16Node,
     AstLoopNode,
+    AstMatchNode,
+    AstRangeLoopNode,
     AstReturnFromFunctionNode,
+    AstTryNode,
 >;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -97,6 +112,24 @@ pub static SPAN_NOT_IMPLEMENTED: Span = Span {
     },
 };
 
+// tuple.0 - the index of the element
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstAccessElementOfTupleNode {
+    pub node: Rc<AstTreeNode>,
+    pub index: usize,
+}
+
+impl AccessElementOfTupleNode<AstVariant> for AstAccessElementOfTupleNode {}
+
+// value[index] - calls the index operator function of the value
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstAccessIndexNode {
+    pub node: Rc<AstTreeNode>,
+    pub index: Rc<AstTreeNode>,
+}
+
+impl AccessIndexNode<AstVariant> for AstAccessIndexNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct AstAccessVariableNode {
     pub variable: AstIdentifier,
@@ -119,6 +152,23 @@ pub struct AstAccessVariableOfSelfNode {
 
 impl AccessVariableOfSelfNode<AstVariant> for AstAccessVariableOfSelfNode {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstAssignVariableNode {
+    pub variable: AstIdentifier,
+    pub value: Rc<AstTreeNode>,
+}
+
+impl AssignVariableNode<AstVariant> for AstAssignVariableNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstAssignVariableOfObjectNode {
+    pub object: AstIdentifier,
+    pub variable: AstIdentifier,
+    pub value: Rc<AstTreeNode>,
+}
+
+impl AssignVariableOfObjectNode<AstVariant> for AstAssignVariableOfObjectNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct AstBlockNode {
     pub nodes: Vec<AstTreeNode>,
@@ -128,6 +178,7 @@ impl BlockNode<AstVariant> for AstBlockNode {}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct AstBreakLoopNode {
+    pub label: Option<AstIdentifier>,
     pub node: Option<Rc<AstTreeNode>>,
 }
 
@@ -146,6 +197,8 @@ impl CalculateNode<AstVariant> for AstCalculateNode {}
 pub struct AStCallFunctionNode {
     pub function: AstIdentifier,
     pub arguments: Vec<AstTreeNode>,
+    // passed by name after the positional arguments, placed by the analyser
+    pub named: Vec<AstNamedArgument>,
 }
 
 impl CallFunctionNode<AstVariant> for AStCallFunctionNode {}
@@ -154,6 +207,7 @@ impl CallFunctionNode<AstVariant> for AStCallFunctionNode {}
 pub struct AstCallFunctionWithLambdaNode {
     pub function: AstIdentifier,
     pub arguments: Vec<AstTreeNode>,
+    pub named: Vec<AstNamedArgument>,
     pub lambda: Rc<AstBlockNode>,
 }
 
@@ -164,6 +218,7 @@ pub struct AstCallFunctionOfObjectNode {
     pub object: AstIdentifier,
     pub function: AstIdentifier,
     pub arguments: Vec<AstTreeNode>,
+    pub named: Vec<AstNamedArgument>,
 }
 
 impl CallFunctionOfObjectNode<AstVariant> for AstCallFunctionOfObjectNode {}
@@ -173,6 +228,7 @@ pub struct AstCallFunctionOfPackageNode {
     pub package: PackagePath,
     pub function: AstIdentifier,
     pub arguments: Vec<AstTreeNode>,
+    pub named: Vec<AstNamedArgument>,
 }
 
 impl CallFunctionOfPackageNode<AstVariant> for AstCallFunctionOfPackageNode {}
@@ -187,13 +243,18 @@ pub struct AstCompareNode {
 impl CompareNode<AstVariant> for AstCompareNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct AstContinueLoopNode {}
+pub struct AstContinueLoopNode {
+    pub label: Option<AstIdentifier>,
+}
 
 impl ContinueLoopNode<AstVariant> for AstContinueLoopNode {}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct AstDeclareExternalFunctionNode {
     pub function: AstIdentifier,
+    pub type_parameters: Vec<AstTypeParameter>,
+    pub modifiers: Modifiers,
+    pub span: Span,
     pub arguments: Vec<AstFunctionArgument>,
     pub return_type: Option<AstType>,
 }
@@ -203,6 +264,9 @@ impl DeclareExternalFunctionNode<AstVariant> for AstDeclareExternalFunctionNode
 #[derive(Debug, Clone, PartialEq)]
 pub struct AstDeclareFunctionNode {
     pub function: AstIdentifier,
+    pub type_parameters: Vec<AstTypeParameter>,
+    pub modifiers: Modifiers,
+    pub span: Span,
     pub arguments: Vec<AstFunctionArgument>,
     pub return_type: Option<AstType>,
     pub nodes: Rc<AstBlockNode>,
@@ -210,41 +274,94 @@ pub struct AstDeclareFunctionNode {
 
 impl DeclareFunctionNode<AstVariant> for AstDeclareFunctionNode {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstDeclareLambdaNode {
+    pub span: Span,
+    pub arguments: Vec<AstFunctionArgument>,
+    pub nodes: Rc<AstBlockNode>,
+}
+
+impl DeclareLambdaNode<AstVariant> for AstDeclareLambdaNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct AstDeclarePackageNode {
     pub package: AstIdentifier,
     pub modifiers: Modifiers,
+    pub span: Span,
     pub external_functions: Vec<AstDeclareExternalFunctionNode>,
     pub functions: Vec<AstDeclareFunctionNode>,
     pub packages: Vec<AstDeclarePackageNode>,
     pub definitions: Vec<AstDefineTypeNode>,
+    pub types: Vec<AstDeclareTypeNode>,
+    pub links: Vec<StringTableId>,
 }
 
 impl DeclarePackageNode<AstVariant> for AstDeclarePackageNode {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstDeclareTraitNode {
+    pub r#trait: AstIdentifier,
+    pub type_parameters: Vec<AstTypeParameter>,
+    pub modifiers: Modifiers,
+    pub span: Span,
+    pub functions: Vec<AstTraitFunction>,
+}
+
+impl DeclareTraitNode<AstVariant> for AstDeclareTraitNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct AstDeclareTypeNode {
     pub r#type: AstIdentifier,
+    pub type_parameters: Vec<AstTypeParameter>,
     pub modifiers: Modifiers,
+    pub span: Span,
     pub variables: Vec<TypeVariable>,
+    // sum type, every variant is a type of its own
+    pub variants: Vec<AstTypeVariant>,
+    // c struct with the layout of its variables
+    pub external: bool,
 }
 
 impl DeclareTypeNode<AstVariant> for AstDeclareTypeNode {}
 
+// defer { nodes } - runs when the scope it is declared in gets left
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstDeferNode {
+    pub nodes: Vec<AstTreeNode>,
+}
+
+impl DeferNode<AstVariant> for AstDeferNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct AstDefineTypeNode {
     pub r#type: AstIdentifier,
+    pub type_parameters: Vec<AstTypeParameter>,
+    pub traits: Vec<AstType>,
     pub modifiers: Modifiers,
+    pub span: Span,
     pub functions: Vec<AstDeclareFunctionNode>,
 }
 
 impl DefineTypeNode<AstVariant> for AstDefineTypeNode {}
 
+// let (a, b) = value - one variable for each element of the tuple
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstDestructureTupleNode {
+    pub variables: Vec<AstIdentifier>,
+    pub value: Rc<AstTreeNode>,
+}
+
+impl DestructureTupleNode<AstVariant> for AstDestructureTupleNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct AstDeclareVariableNode {
     pub variable: AstIdentifier,
     pub value: Rc<AstTreeNode>,
     pub value_type: Option<AstType>,
+    // declared by let, a const can not be reassigned
+    pub mutable: bool,
+    // nothing inside the value can be changed through the variable
+    pub readonly: bool,
 }
 
 impl DeclareVariableNode<AstVariant> for AstDeclareVariableNode {}
@@ -273,8 +390,24 @@ pub struct AstInterpolateStringNode {
 
 impl InterpolateStringNode<AstVariant> for AstInterpolateStringNode {}
 
+// (x = 1, y = 2) - a value of an anonymous record type
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstInstantiateRecordNode {
+    pub fields: Vec<AstNamedArgument>,
+}
+
+impl InstantiateRecordNode<AstVariant> for AstInstantiateRecordNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstInstantiateTupleNode {
+    pub nodes: Vec<AstTreeNode>,
+}
+
+impl InstantiateTupleNode<AstVariant> for AstInstantiateTupleNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct AstInstantiateTypeNode {
+    pub package: Option<PackagePath>,
     pub r#type: AstIdentifier,
     pub arguments: Vec<AstNamedArgument>,
 }
@@ -321,6 +454,11 @@ pub struct AstLiteralInt16Node(pub Token);
 
 impl LiteralInt16Node<AstVariant> for AstLiteralInt16Node {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstLiteralNoneNode(pub Token);
+
+impl LiteralNoneNode<AstVariant> for AstLiteralNoneNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct AstLiteralNumberNode(pub Token);
 
@@ -359,11 +497,59 @@ impl LiteralUint16Node<AstVariant> for AstLiteralUint16Node {}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct AstLoopNode {
+    pub label: Option<AstIdentifier>,
     pub nodes: Vec<AstTreeNode>,
 }
 
 impl LoopNode<AstVariant> for AstLoopNode {}
 
+// for variable in iterable { nodes }
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstIteratorLoopNode {
+    pub label: Option<AstIdentifier>,
+    pub variable: AstIdentifier,
+    pub iterable: Rc<AstTreeNode>,
+    pub nodes: Vec<AstTreeNode>,
+}
+
+impl IteratorLoopNode<AstVariant> for AstIteratorLoopNode {}
+
+// for variable in start..end step step { nodes }
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstRangeLoopNode {
+    pub label: Option<AstIdentifier>,
+    pub variable: AstIdentifier,
+    pub start: Rc<AstTreeNode>,
+    pub end: Rc<AstTreeNode>,
+    pub inclusive: bool,
+    pub step: Option<Rc<AstTreeNode>>,
+    pub nodes: Vec<AstTreeNode>,
+}
+
+impl RangeLoopNode<AstVariant> for AstRangeLoopNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstMatchNode {
+    pub node: Rc<AstTreeNode>,
+    pub arms: Vec<AstMatchArm>,
+}
+
+impl MatchNode<AstVariant> for AstMatchNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstMatchArm {
+    pub pattern: AstMatchPattern,
+    pub nodes: Rc<AstBlockNode>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AstMatchPattern {
+    // the bindings take the variables of the variant in the order of their declaration
+    Variant { variant: AstIdentifier, bindings: Vec<AstIdentifier> },
+    Otherwise,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct AstReturnFromFunctionNode {
     pub node: Option<Rc<AstTreeNode>>,
@@ -371,10 +557,38 @@ pub struct AstReturnFromFunctionNode {
 
 impl ReturnFromFunctionNode<AstVariant> for AstReturnFromFunctionNode {}
 
+// value? - the value of Ok, Err returns from the function
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstTryNode {
+    pub node: Rc<AstTreeNode>,
+}
+
+impl TryNode<AstVariant> for AstTryNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct AstFunctionArgument {
     pub argument: AstIdentifier,
     pub argument_type: Option<AstType>,
+    pub readonly: bool,
+    // evaluated at every call which leaves out the argument
+    pub default: Option<AstTreeNode>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstTraitFunction {
+    pub function: AstIdentifier,
+    pub span: Span,
+    pub arguments: Vec<AstFunctionArgument>,
+    pub return_type: Option<AstType>,
+    // the default implementation
+    pub nodes: Option<Rc<AstBlockNode>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstTypeParameter {
+    pub parameter: AstIdentifier,
+    pub bound: Option<AstType>,
 }
 
 #[derive(Clone, Debug, PartialEq, Hash, Eq)]
@@ -402,10 +616,18 @@ pub enum AstType {
     Int8,
     Int16,
     Number,
+    // the value or none
+    Optional(Box<AstType>),
+    Pointer(Box<AstType>),
     String,
+    // (x: Number, y: Number)
+    Record(Vec<(AstIdentifier, AstType)>),
     Tuple(Vec<AstType>),
     Type {
-        r#type: AstIdentifier
+        // the package of a type like std::collection::list::List
+        package: Option<PackagePath>,
+        r#type: AstIdentifier,
+        arguments: Vec<AstType>,
     },
     Uint1,
     Uint2,
@@ -428,7 +650,22 @@ impl AstType {
             AstType::Int16 => "Int16".to_string(),
 
             AstType::Number => "Number".to_string(),
+            AstType::Optional(inner) => format!("{}?", inner.to_string(string_table)),
             AstType::String => "String".to_string(),
+            AstType::Record(fields) => format!(
+                "({})",
+                fields.iter().map(|(name, t)| format!("{}: {}", string_table.get(name.0), t.to_string(string_table))).collect::<Vec<_>>().join(", ")
+            ),
+            AstType::Tuple(types) => format!(
+                "({})",
+                types.iter().map(|t| t.to_string(string_table)).collect::<Vec<_>>().join(", ")
+            ),
+            AstType::Type { r#type, arguments, .. } if arguments.is_empty() => string_table.get(r#type.0).to_string(),
+            AstType::Type { r#type, arguments, .. } => format!(
+                "{}<{}>",
+                string_table.get(r#type.0),
+                arguments.iter().map(|a| a.to_string(string_table)).collect::<Vec<_>>().join(", ")
+            ),
 
             AstType::Uint1 => "Uint1".to_string(),
             AstType::Uint2 => "Uint2".to_string(),
@@ -461,3 +698,10 @@ pub struct TypeVariable {
     pub variable: AstIdentifier,
     pub r#type: AstType,
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstTypeVariant {
+    pub r#type: AstIdentifier,
+    pub variables: Vec<TypeVariable>,
+    pub span: Span,
+}
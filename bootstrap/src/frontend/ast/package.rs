@@ -1,18 +1,15 @@
-use std::fs::File;
-use std::io;
-use std::io::Read;
 use std::ops::Deref;
-use std::path::PathBuf;
 
 use crate::common::node::Node::{Block, ExportPackage};
 use crate::common::node::{Node, Source};
 use crate::frontend::ast::{
-    AstBlockNode, AstDeclareExternalFunctionNode, AstDeclareFunctionNode, AstDeclarePackageNode,
+    AstBlockNode, AstDeclareExternalFunctionNode, AstDeclareFunctionNode, AstDeclarePackageNode, AstDeclareTypeNode,
     AstDefineTypeNode, AstExportPackageNode, AstIdentifier, AstTreeNode, Generator,
     SPAN_NOT_IMPLEMENTED,
 };
 use crate::frontend::parse::LiteralNode;
-use crate::frontend::{ast, ast_from_str, parse};
+use crate::frontend::{ast, ast_from_library, parse};
+use crate::frontend::library::library_path;
 
 impl<'a> Generator<'a> {
     pub(crate) fn generate_from(&mut self, node: &parse::FromNode) -> ast::Result<AstTreeNode> {
@@ -62,42 +59,27 @@ impl<'a> Generator<'a> {
         node: &parse::PackageDeclarationNode,
     ) -> ast::Result<AstTreeNode> {
         let mut compiled_body = vec![];
+        let mut links = vec![];
 
         for node in &node.block.nodes {
-            compiled_body.push(self.generate_node(node)?);
+            if let parse::Node::ExternalLinkDeclaration(link) = node {
+                links.push(link.library.value());
+            } else {
+                compiled_body.push(self.generate_node(node)?);
+            }
         }
 
         let mut external_functions: Vec<AstDeclareExternalFunctionNode> = vec![];
         let mut functions: Vec<AstDeclareFunctionNode> = vec![];
         let mut definitions: Vec<AstDefineTypeNode> = vec![];
         let mut packages: Vec<AstDeclarePackageNode> = vec![];
+        let mut types: Vec<AstDeclareTypeNode> = vec![];
 
         for node in compiled_body.into_iter() {
             if let Block(block) = node.node() {
                 for node in &block.nodes {
-                    if let ExportPackage(AstExportPackageNode { package, .. }) = node.node() {
-                        let package = self.ctx.str_get(package.0).to_string();
-
-                        // FIXME temporary hack to load std packages
-                        // FIXME compiler needs to track scope so that the parent package can easily be determined
-
-                        match package.as_str() {
-                            "io" => packages.extend(self.load_declared_packages("std/io/index.ec")),
-                            "collection" => packages
-                                .extend(self.load_declared_packages("std/collection/index.ec")),
-                            "list" => packages.extend(
-                                self.load_declared_packages("std/collection/list/index.ec"),
-                            ),
-                            "math" => {
-                                packages.extend(self.load_declared_packages("std/math/index.ec"))
-                            }
-                            "process" => {
-                                packages.extend(self.load_declared_packages("std/process/index.ec"))
-                            }
-                            "intrinsics" => packages
-                                .extend(self.load_declared_packages("core/intrinsics/index.ec")),
-                            _ => unimplemented!(),
-                        }
+                    if let ExportPackage(AstExportPackageNode { source, .. }) = node.node() {
+                        packages.extend(self.load_declared_packages(source));
                     } else if let Node::DeclareFunction(declare_function) = node.node() {
                         functions.push(declare_function.clone())
                     } else if let Node::DefineType(define_type) = node.node() {
@@ -112,27 +94,40 @@ impl<'a> Generator<'a> {
                 packages.push(package.clone());
             } else if let Node::DeclareExternalFunction(external) = node.node() {
                 external_functions.push(external.clone());
+            } else if let Node::DeclareType(declare_type) = node.node() {
+                types.push(declare_type.clone());
             } else {
                 // unimplemented!("{:?}", node)
             }
         }
 
+        let span = node.identifier.0.span.clone();
+
         Ok(AstTreeNode::new(
             Node::DeclarePackage(AstDeclarePackageNode {
                 package: AstIdentifier(node.identifier.value()),
                 modifiers: node.modifiers.clone(),
+                span: span.clone(),
                 functions,
                 packages,
                 definitions: definitions,
                 external_functions,
+                types,
+                links,
             }),
-            SPAN_NOT_IMPLEMENTED.clone(),
+            span,
         ))
     }
 
-    fn load_declared_packages(&mut self, name: &str) -> Vec<AstDeclarePackageNode> {
-        let content = crate::load_library_file(name).unwrap();
-        let ast = ast_from_str(&mut self.ctx, content.as_str()).unwrap();
+    fn load_declared_packages(&mut self, source: &Source) -> Vec<AstDeclarePackageNode> {
+        let Source::LocalFile { path } = source;
+
+        let Some(directory) = &self.directory else {
+            unimplemented!("exporting packages from files outside of the library")
+        };
+
+        let path = library_path(directory, path);
+        let ast = ast_from_library(self.ctx, path.as_str()).unwrap();
 
         let mut result = vec![];
 
@@ -144,19 +139,3 @@ impl<'a> Generator<'a> {
         result
     }
 }
-
-fn load_library_file(filename: &str) -> io::Result<String> {
-    // Get the path to the project root directory
-    let manifest_dir = "/home/ddymke/repo/elodie/src/lib/";
-
-    // Construct the full path to the file
-    let file_path = PathBuf::from(manifest_dir).join(filename);
-    // println!("{file_path:?}");
-
-    // Read the file's contents
-    let mut file = File::open(file_path)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-    // println!("{contents}");
-    Ok(contents)
-}
@@ -0,0 +1,51 @@
+use std::rc::Rc;
+
+use crate::common::node::Node::DeclareTrait;
+use crate::frontend::ast::{AstBlockNode, AstDeclareTraitNode, AstIdentifier, AstTraitFunction, AstTreeNode, Generator};
+use crate::frontend::{ast, parse};
+
+impl<'a> Generator<'a> {
+    pub(crate) fn generate_declare_trait(
+        &mut self,
+        node: &parse::TraitDeclarationNode,
+    ) -> ast::Result<AstTreeNode> {
+        let mut functions = Vec::with_capacity(node.functions.len());
+        for function in &node.functions {
+            let mut arguments = Vec::with_capacity(function.arguments.len());
+            for arg in &function.arguments {
+                arguments.push(self.generate_declare_function_argument(arg)?)
+            }
+
+            let nodes = if let Some(block) = &function.block {
+                let mut nodes = vec![];
+                for node in &block.nodes {
+                    nodes.push(self.generate_node(node)?)
+                }
+                Some(Rc::new(AstBlockNode { nodes }))
+            } else {
+                None
+            };
+
+            functions.push(AstTraitFunction {
+                function: AstIdentifier(function.identifier.value()),
+                span: function.identifier.0.span.clone(),
+                arguments,
+                return_type: function.return_type.as_deref().map(|r| self.to_ast_type(r)),
+                nodes,
+            });
+        }
+
+        let span = node.identifier.0.span.clone();
+
+        Ok(AstTreeNode::new(
+            DeclareTrait(AstDeclareTraitNode {
+                r#trait: AstIdentifier(node.identifier.value()),
+                type_parameters: self.to_ast_type_parameters(&node.type_parameters),
+                modifiers: node.modifiers.clone(),
+                span: span.clone(),
+                functions,
+            }),
+            span,
+        ))
+    }
+}
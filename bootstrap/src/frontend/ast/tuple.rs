@@ -0,0 +1,69 @@
+use std::ops::Deref;
+use std::rc::Rc;
+
+use crate::common::node::Node;
+use crate::frontend::ast::{AstAccessElementOfTupleNode, AstDestructureTupleNode, AstIdentifier, AstInstantiateRecordNode, AstInstantiateTupleNode, AstTreeNode, Generator};
+use crate::frontend::{ast, parse};
+use crate::frontend::parse::{InfixNode, InfixOperator};
+
+// x = 1
+fn is_named(node: &parse::Node) -> bool {
+    matches!(node, parse::Node::Infix(InfixNode { operator: InfixOperator::Assign(_), left, .. }) if left.is_identifier())
+}
+
+impl<'a> Generator<'a> {
+    // a single value in parens only groups it, values which all have a name are a record, everything else is a tuple
+    pub(crate) fn generate_tuple(&mut self, node: &parse::TupleNode) -> ast::Result<AstTreeNode> {
+        if !node.nodes.is_empty() && node.nodes.iter().all(is_named) {
+            let fields = self.generate_named_arguments(node)?;
+            return Ok(AstTreeNode::new(
+                Node::InstantiateRecord(AstInstantiateRecordNode { fields }),
+                node.token.span.clone(),
+            ));
+        }
+
+        if node.nodes.len() == 1 {
+            return self.generate_node(&node.nodes[0]);
+        }
+
+        let mut nodes = Vec::with_capacity(node.nodes.len());
+        for node in &node.nodes {
+            nodes.push(self.generate_node(node)?);
+        }
+
+        Ok(AstTreeNode::new(
+            Node::InstantiateTuple(AstInstantiateTupleNode { nodes }),
+            node.token.span.clone(),
+        ))
+    }
+
+    pub(crate) fn generate_declare_tuple(&mut self, node: &parse::TupleDeclarationNode) -> ast::Result<AstTreeNode> {
+        let variables = node.identifiers.iter()
+            .map(|identifier| AstIdentifier(identifier.value()))
+            .collect();
+
+        let value = Rc::new(self.generate_node(node.node.deref())?);
+        Ok(AstTreeNode::new(
+            Node::DestructureTuple(AstDestructureTupleNode { variables, value }),
+            node.token.span.clone(),
+        ))
+    }
+
+    // the lexer takes tuple.0.1 as tuple . 0.1, every part of the number goes one level deeper
+    pub(crate) fn generate_access_element_of_tuple(&mut self, tuple: &parse::Node, index: &parse::LiteralNumberNode) -> ast::Result<AstTreeNode> {
+        let mut result = self.generate_node(tuple)?;
+
+        let indices = self.ctx.str_get(index.value()).split('.')
+            .map(|index| index.parse::<usize>().unwrap())
+            .collect::<Vec<_>>();
+
+        for index_of_element in indices {
+            result = AstTreeNode::new(
+                Node::AccessElementOfTuple(AstAccessElementOfTupleNode { node: Rc::new(result), index: index_of_element }),
+                index.0.span.clone(),
+            );
+        }
+
+        Ok(result)
+    }
+}
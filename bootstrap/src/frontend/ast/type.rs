@@ -1,21 +1,80 @@
 use std::ops::Deref;
 
 use crate::common::node::Node;
-use crate::common::node::Node::{DeclareType, DefineType};
+use crate::common::PackagePath;
+use crate::common::node::Node::{DeclareType, DefineType, InstantiateType};
 use crate::frontend::ast::{
-    AstDeclareTypeNode, AstDefineTypeNode, AstIdentifier, AstTreeNode, AstType, Generator,
+    AstDeclareTypeNode, AstDefineTypeNode, AstIdentifier, AstInstantiateTypeNode, AstTreeNode, AstType, AstTypeParameter, AstTypeVariant, Generator,
     TypeVariable, SPAN_NOT_IMPLEMENTED,
 };
-use crate::frontend::parse::{InfixNode, InfixOperator, TypeFunctionNode, TypeNode};
+use crate::frontend::parse::{InfixNode, InfixOperator, TypeFunctionNode, TypeNode, TypePackageNode, TypeParameterizedNode};
 use crate::frontend::{ast, parse};
+use crate::frontend::lex::token::Token;
+use crate::frontend::modifier::Modifiers;
 
 impl<'a> Generator<'a> {
     pub(crate) fn generate_declare_type(
         &mut self,
         node: &parse::TypeDeclarationNode,
     ) -> ast::Result<AstTreeNode> {
-        let mut variables = Vec::with_capacity(node.properties.nodes.len());
-        for node in &node.properties.nodes {
+        let variants = node.variants.iter()
+            .map(|variant| AstTypeVariant {
+                r#type: AstIdentifier(variant.identifier.value()),
+                variables: self.type_variables(&variant.properties),
+                span: variant.identifier.0.span.clone(),
+            })
+            .collect();
+        self.declare_type(&node.identifier, &node.type_parameters, &node.properties, variants, &node.modifiers, false)
+    }
+
+    pub(crate) fn generate_declare_external_type(
+        &mut self,
+        node: &parse::ExternalTypeDeclarationNode,
+    ) -> ast::Result<AstTreeNode> {
+        self.declare_type(&node.identifier, &[], &node.properties, vec![], &node.modifiers, true)
+    }
+
+    // a type named without arguments, like a variant without variables
+    pub(crate) fn generate_type_value(&mut self, token: &Token) -> ast::Result<AstTreeNode> {
+        Ok(AstTreeNode::new(
+            InstantiateType(AstInstantiateTypeNode {
+                package: None,
+                r#type: AstIdentifier(token.value),
+                arguments: vec![],
+            }),
+            token.span.clone(),
+        ))
+    }
+
+    fn declare_type(
+        &mut self,
+        identifier: &parse::IdentifierNode,
+        type_parameters: &[parse::TypeParameterNode],
+        properties: &parse::TupleNode,
+        variants: Vec<AstTypeVariant>,
+        modifiers: &Modifiers,
+        external: bool,
+    ) -> ast::Result<AstTreeNode> {
+        let variables = self.type_variables(properties);
+        let span = identifier.0.span.clone();
+
+        Ok(AstTreeNode::new(
+            DeclareType(AstDeclareTypeNode {
+                r#type: AstIdentifier(identifier.value()),
+                type_parameters: self.to_ast_type_parameters(type_parameters),
+                modifiers: modifiers.clone(),
+                span: span.clone(),
+                variables,
+                variants,
+                external,
+            }),
+            span,
+        ))
+    }
+
+    fn type_variables(&self, properties: &parse::TupleNode) -> Vec<TypeVariable> {
+        let mut variables = Vec::with_capacity(properties.nodes.len());
+        for node in &properties.nodes {
             let parse::Node::Infix(InfixNode {
                                        left,
                                        right,
@@ -33,15 +92,7 @@ impl<'a> Generator<'a> {
                 r#type,
             })
         }
-
-        Ok(AstTreeNode::new(
-            DeclareType(AstDeclareTypeNode {
-                r#type: AstIdentifier(node.identifier.value()),
-                modifiers: node.modifiers.clone(),
-                variables,
-            }),
-            SPAN_NOT_IMPLEMENTED.clone(),
-        ))
+        variables
     }
 
     pub(crate) fn to_ast_type(&self, node: &parse::TypeNode) -> AstType {
@@ -57,7 +108,13 @@ impl<'a> Generator<'a> {
             TypeNode::Int16(_) => AstType::Int16,
 
             TypeNode::Number(_) => AstType::Number,
+            TypeNode::Optional(optional) => AstType::Optional(Box::new(self.to_ast_type(optional.r#type.deref()))),
+            TypeNode::Pointer(pointer) => AstType::Pointer(Box::new(self.to_ast_type(pointer.r#type.deref()))),
+            TypeNode::Record(record) => AstType::Record(
+                record.fields.iter().map(|f| (AstIdentifier(f.identifier.value()), self.to_ast_type(&f.r#type))).collect()
+            ),
             TypeNode::String(_) => AstType::String,
+            TypeNode::Tuple(tuple) => AstType::Tuple(tuple.types.iter().map(|t| self.to_ast_type(t)).collect()),
             TypeNode::Function(TypeFunctionNode {
                                    token,
                                    arguments,
@@ -74,8 +131,23 @@ impl<'a> Generator<'a> {
                     .as_ref()
                     .map(|r| Box::new(self.to_ast_type(r.deref()))),
             },
+            TypeNode::Package(TypePackageNode { package, r#type }) => match self.to_ast_type(r#type) {
+                AstType::Type { r#type, arguments, .. } => AstType::Type {
+                    package: Some(PackagePath::from(package.iter().map(|p| p.value()).collect::<Vec<_>>())),
+                    r#type,
+                    arguments,
+                },
+                _ => unreachable!()
+            },
+            TypeNode::Parameterized(TypeParameterizedNode { token, arguments }) => AstType::Type {
+                package: None,
+                r#type: AstIdentifier(token.value()),
+                arguments: arguments.iter().map(|a| self.to_ast_type(a)).collect(),
+            },
             TypeNode::Type(token) => AstType::Type {
-                r#type: AstIdentifier(token.value())
+                package: None,
+                r#type: AstIdentifier(token.value()),
+                arguments: vec![],
             },
 
             TypeNode::Uint1(_) => AstType::Uint1,
@@ -86,6 +158,15 @@ impl<'a> Generator<'a> {
         }
     }
 
+    pub(crate) fn to_ast_type_parameters(&self, parameters: &[parse::TypeParameterNode]) -> Vec<AstTypeParameter> {
+        parameters.iter()
+            .map(|p| AstTypeParameter {
+                parameter: AstIdentifier(p.value()),
+                bound: p.bound.as_ref().map(|b| self.to_ast_type(b)),
+            })
+            .collect()
+    }
+
     pub(crate) fn generate_define_type(
         &mut self,
         node: &parse::DefineDeclarationNode,
@@ -99,7 +180,10 @@ impl<'a> Generator<'a> {
         Ok(AstTreeNode::new(
             DefineType(AstDefineTypeNode {
                 r#type: AstIdentifier(node.identifier.value()),
+                type_parameters: self.to_ast_type_parameters(&node.type_parameters),
+                traits: node.traits.iter().map(|t| self.to_ast_type(t)).collect(),
                 modifiers: node.modifiers.clone(),
+                span: node.identifier.0.span.clone(),
                 functions: compiled_body
                     .into_iter()
                     .filter_map(|n| {
@@ -2,10 +2,9 @@ use std::ops::Deref;
 use std::rc::Rc;
 
 use crate::common::node::Node;
-use crate::frontend::ast::{
-    AstDeclareVariableNode, AstIdentifier, AstTreeNode, Generator, SPAN_NOT_IMPLEMENTED,
-};
+use crate::frontend::ast::{AstDeclareVariableNode, AstIdentifier, AstTreeNode, Generator};
 use crate::frontend::{ast, parse};
+use crate::frontend::lex::token::KeywordToken;
 
 impl<'a> Generator<'a> {
     pub(crate) fn generate_declare_variable(
@@ -20,14 +19,20 @@ impl<'a> Generator<'a> {
             None
         };
 
+        let mutable = node.token.is_keyword(KeywordToken::Let);
+        let readonly = node.modifiers.is_readonly();
+        let span = node.token.span.clone();
+
         let node = Rc::new(self.generate_node(node.node.deref())?);
         Ok(AstTreeNode::new(
             Node::DeclareVariable(AstDeclareVariableNode {
                 variable,
                 value: node,
                 value_type: node_type,
+                mutable,
+                readonly,
             }),
-            SPAN_NOT_IMPLEMENTED.clone(),
+            span,
         ))
     }
 }
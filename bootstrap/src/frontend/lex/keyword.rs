@@ -4,6 +4,11 @@ use crate::common::Span;
 use crate::frontend::lex::token::{KeywordToken, Token, TokenKind};
 use crate::frontend::lex::Lexer;
 
+// words which can not be used as identifiers
+pub fn is_keyword(value: &str) -> bool {
+    Lexer::keyword_map().contains_key(value) || matches!(value, "true" | "false" | "none")
+}
+
 impl Lexer<'_> {
     pub(crate) fn is_keyword(&self, c: char) -> bool {
         let look_ahead = self.look_ahead().unwrap();
@@ -11,11 +16,14 @@ impl Lexer<'_> {
         match c {
             'b' => look_ahead == "break",
             'c' => matches!(look_ahead.as_str(), "const" | "continue"),
-            'd' => look_ahead == "define",
+            'd' => matches!(look_ahead.as_str(), "define" | "defer"),
             'e' => matches!(look_ahead.as_str(), "else" | "export" | "external"),
             'f' => matches!(look_ahead.as_str(), "from" | "for" | "function"),
             'i' => matches!(look_ahead.as_str(), "if" | "import" | "in"),
             'l' => matches!(look_ahead.as_str(), "let" | "loop"),
+            'm' => look_ahead == "match",
+            'n' => look_ahead == "named",
+            'o' => matches!(look_ahead.as_str(), "operator" | "otherwise"),
             'p' => look_ahead == "package",
             'r' => matches!(look_ahead.as_str(), "readonly" | "return"),
             's' => look_ahead == "self",
@@ -53,6 +61,7 @@ impl Lexer<'_> {
         keywords.insert("const", KeywordToken::Const);
         keywords.insert("continue", KeywordToken::Continue);
         keywords.insert("define", KeywordToken::Define);
+        keywords.insert("defer", KeywordToken::Defer);
         keywords.insert("else", KeywordToken::Else);
         keywords.insert("export", KeywordToken::Export);
         keywords.insert("external", KeywordToken::External);
@@ -64,6 +73,10 @@ impl Lexer<'_> {
         keywords.insert("in", KeywordToken::In);
         keywords.insert("let", KeywordToken::Let);
         keywords.insert("loop", KeywordToken::Loop);
+        keywords.insert("match", KeywordToken::Match);
+        keywords.insert("named", KeywordToken::Named);
+        keywords.insert("operator", KeywordToken::Operator);
+        keywords.insert("otherwise", KeywordToken::Otherwise);
         keywords.insert("package", KeywordToken::Package);
         keywords.insert("readonly", KeywordToken::Readonly);
         keywords.insert("return", KeywordToken::Return);
@@ -179,6 +192,28 @@ mod test {
         assert_eq!(ctx.str_get(result.value()), "defined");
     }
 
+    #[test]
+    fn defer() {
+        let text = "defer";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert!(result.is_keyword(Defer));
+        assert_eq!(result.span.start, (1, 1, 0));
+        assert_eq!(result.span.end, (1, 6, 5));
+        assert_eq!(ctx.str_get(result.value()), "defer");
+    }
+
+    #[test]
+    fn not_defer() {
+        let text = "deferred";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert_eq!(result.kind, identifier());
+        assert_eq!(ctx.str_get(result.value()), "deferred");
+    }
+
     #[test]
     fn r#else() {
         let text = "else";
@@ -443,6 +478,86 @@ mod test {
         assert_eq!(ctx.str_get(result.value()), "loophole");
     }
 
+    #[test]
+    fn r#match() {
+        let text = "match";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert!(result.is_keyword(Match));
+        assert_eq!(result.span.start, (1, 1, 0));
+        assert_eq!(result.span.end, (1, 6, 5));
+        assert_eq!(ctx.str_get(result.value()), "match");
+    }
+
+    #[test]
+    fn not_match() {
+        let text = "matches";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert_eq!(result.kind, identifier());
+        assert_eq!(result.span.start, (1, 1, 0));
+        assert_eq!(result.span.end, (1, 8, 7));
+        assert_eq!(ctx.str_get(result.value()), "matches");
+    }
+
+    #[test]
+    fn named() {
+        let text = "named";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert!(result.is_keyword(Named));
+        assert_eq!(result.span.start, (1, 1, 0));
+        assert_eq!(result.span.end, (1, 6, 5));
+        assert_eq!(ctx.str_get(result.value()), "named");
+    }
+
+    #[test]
+    fn not_named() {
+        let text = "names";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert_eq!(result.kind, identifier());
+        assert_eq!(ctx.str_get(result.value()), "names");
+    }
+
+    #[test]
+    fn operator() {
+        let text = "operator";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert!(result.is_keyword(Operator));
+        assert_eq!(result.span.start, (1, 1, 0));
+        assert_eq!(result.span.end, (1, 9, 8));
+        assert_eq!(ctx.str_get(result.value()), "operator");
+    }
+
+    #[test]
+    fn not_operator() {
+        let text = "operators";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert_eq!(result.kind, identifier());
+        assert_eq!(ctx.str_get(result.value()), "operators");
+    }
+
+    #[test]
+    fn otherwise() {
+        let text = "otherwise";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert!(result.is_keyword(Otherwise));
+        assert_eq!(result.span.start, (1, 1, 0));
+        assert_eq!(result.span.end, (1, 10, 9));
+        assert_eq!(ctx.str_get(result.value()), "otherwise");
+    }
+
     #[test]
     fn package() {
         let text = "package";
@@ -0,0 +1,197 @@
+use crate::common::Span;
+use crate::frontend::lex::token::{KeywordToken, Token, TokenKind};
+use crate::frontend::lex::token::OperatorToken::{CloseCurly, Equal, OpenCurly};
+use crate::frontend::lex::token::SeparatorToken::{NewLine, Semicolon};
+use crate::frontend::lex::token::TokenKind::{Keyword, Operator, Separator};
+use crate::frontend::lex::Lexer;
+
+impl Lexer<'_> {
+    // a quote starts a string as well, the token before it tells which one it is:
+    // 'name: loop / 'name: for starts a statement or a value, break 'name and continue 'name follow their keyword
+    pub(crate) fn is_label(&self, c: char) -> bool {
+        if c != '\'' {
+            return false;
+        }
+
+        let pos = *self.reader.pos.borrow();
+        let line: String = self.reader.content.chars().skip(pos + 1).take_while(|c| *c != '\n').collect();
+        let name_length = line.chars().take_while(|c| c.is_alphanumeric() || *c == '_').count();
+        if name_length == 0 || !line.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_') {
+            return false;
+        }
+        let rest: String = line.chars().skip(name_length).collect();
+
+        match &self.previous {
+            None | Some(Separator(NewLine)) | Some(Separator(Semicolon))
+            | Some(Operator(OpenCurly)) | Some(Operator(CloseCurly)) | Some(Operator(Equal)) => self.is_labeled_loop(&rest),
+            Some(Keyword(KeywordToken::Break)) | Some(Keyword(KeywordToken::Continue)) => !rest.starts_with('\'') && !self.is_closed_string(&line),
+            _ => false
+        }
+    }
+
+    // : loop { or : for name in
+    fn is_labeled_loop(&self, rest: &str) -> bool {
+        let whitespace = |c: char| self.is_whitespace(c);
+        let Some(rest) = rest.trim_start_matches(whitespace).strip_prefix(':') else {
+            return false;
+        };
+        let rest = rest.trim_start_matches(whitespace);
+        if let Some(rest) = rest.strip_prefix("loop") {
+            return rest.trim_start_matches(whitespace).starts_with('{');
+        }
+        let Some(rest) = rest.strip_prefix("for").filter(|rest| rest.starts_with(whitespace)) else {
+            return false;
+        };
+        let rest = rest.trim_start_matches(whitespace).trim_start_matches(|c: char| c.is_alphanumeric() || c == '_');
+        rest.trim_start_matches(whitespace).strip_prefix("in").is_some_and(|rest| rest.starts_with(whitespace))
+    }
+
+    // the quote opens a string which ends before any comment and leaves balanced quotes behind it
+    fn is_closed_string(&self, line: &str) -> bool {
+        let mut chars = line.chars();
+        loop {
+            match chars.next() {
+                None => return false,
+                Some('\\') => { chars.next(); }
+                Some('\'') => break,
+                Some('/') if chars.as_str().starts_with('/') => return false,
+                Some(_) => {}
+            }
+        }
+
+        let mut in_string = false;
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' if in_string => { chars.next(); }
+                '\'' => in_string = !in_string,
+                '/' if !in_string && chars.as_str().starts_with('/') => break,
+                _ => {}
+            }
+        }
+        !in_string
+    }
+
+    pub(crate) fn consume_label(&mut self) -> crate::frontend::lex::Result<Token> {
+        let start = self.position();
+        self.consume_next()?; // Consume '
+
+        let text = self.consume_while(|c| c.is_alphanumeric() || c == '_')?;
+
+        Ok(Token {
+            kind: TokenKind::Label,
+            span: Span {
+                start,
+                end: self.position(),
+            },
+            value: self.ctx.string_table.push_str(text.as_str()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common::Context;
+    use crate::frontend::lex::token::{KeywordToken, LiteralToken, OperatorToken, TokenKind};
+    use crate::frontend::lex::{lex, Lexer};
+
+    #[test]
+    fn label_of_loop() {
+        let text = "'outer: loop {}";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+
+        let label = lexer.advance().unwrap();
+        assert_eq!(label.kind, TokenKind::Label);
+        assert_eq!(label.span.start, (1, 1, 0));
+        assert_eq!(label.span.end, (1, 7, 6));
+        assert_eq!(ctx.str_get(label.value()), "outer");
+
+        let mut lexer = Lexer::new(&mut ctx, text);
+        lexer.advance().unwrap();
+        assert_eq!(lexer.advance().unwrap().kind, TokenKind::Operator(OperatorToken::Colon));
+        assert_eq!(lexer.advance().unwrap().kind, TokenKind::Keyword(KeywordToken::Loop));
+    }
+
+    #[test]
+    fn label_of_break() {
+        let text = "break 'outer }";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+
+        assert_eq!(lexer.advance().unwrap().kind, TokenKind::Keyword(KeywordToken::Break));
+        let label = lexer.advance().unwrap();
+        assert_eq!(label.kind, TokenKind::Label);
+        assert_eq!(ctx.str_get(label.value()), "outer");
+    }
+
+    #[test]
+    fn label_of_break_with_value() {
+        let text = "break 'outer 'value'";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+
+        lexer.advance().unwrap();
+        let label = lexer.advance().unwrap();
+        let value = lexer.advance().unwrap();
+        assert_eq!(label.kind, TokenKind::Label);
+        assert_eq!(ctx.str_get(label.value()), "outer");
+        assert_eq!(value.kind, TokenKind::Literal(LiteralToken::String));
+    }
+
+    #[test]
+    fn break_with_string() {
+        let text = "break 'hello world'";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+
+        lexer.advance().unwrap();
+        assert_eq!(lexer.advance().unwrap().kind, TokenKind::Literal(LiteralToken::String));
+    }
+
+    #[test]
+    fn string_is_no_label() {
+        for text in ["'outer'", "'hello world'", "'key: value'", "'outer: loop'", "'outer'\n"] {
+            let mut ctx = Context::testing();
+            let mut lexer = Lexer::new(&mut ctx, text);
+            let result = lexer.advance().unwrap();
+            assert_eq!(result.kind, TokenKind::Literal(LiteralToken::String), "{text}");
+        }
+    }
+
+    #[test]
+    fn label_of_value_loop() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "let found = 'outer: loop {}\n'rows: for row in 0..3 {}").unwrap();
+        let labels = tokens.iter().filter(|t| t.kind == TokenKind::Label).map(|t| ctx.str_get(t.value())).collect::<Vec<_>>();
+        assert_eq!(labels, vec!["outer", "rows"]);
+    }
+
+    #[test]
+    fn string_with_loop_keyword_is_no_label() {
+        for text in ["rt::io::println('note: for later')", "let note = 'note: for later'", "let note = 'note: loop'"] {
+            let mut ctx = Context::testing();
+            let tokens = lex(&mut ctx, text).unwrap();
+            assert!(tokens.iter().all(|t| t.kind != TokenKind::Label), "{text}");
+        }
+    }
+
+    #[test]
+    fn break_with_string_before_comment() {
+        let text = "break 'done' // it's over";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+
+        lexer.advance().unwrap();
+        assert_eq!(lexer.advance().unwrap().kind, TokenKind::Literal(LiteralToken::String));
+    }
+
+    #[test]
+    fn label_of_break_before_comment() {
+        let text = "break 'outer // it's done";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+
+        lexer.advance().unwrap();
+        assert_eq!(lexer.advance().unwrap().kind, TokenKind::Label);
+    }
+}
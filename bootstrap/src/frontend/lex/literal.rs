@@ -98,7 +98,8 @@ impl Lexer<'_> {
             let next = self.consume_while(|c| c.is_digit(10))?;
             text.push_str(&next);
 
-            if let Some('.') = self.peek_next() {
+            // 1..10 is a range, the dot belongs to the range operator
+            if self.peek_next() == Some('.') && self.peek_if("..").is_none() {
                 let _ = self.consume_next()?;
                 text.push('.');
                 let next = self.consume_while(|c| c.is_digit(10))?;
@@ -150,13 +151,34 @@ impl Lexer<'_> {
             value: self.ctx.string_table.push_str("false"),
         })
     }
+
+    pub(crate) fn is_none(&self, c: char) -> bool {
+        if c != 'n' {
+            return false;
+        }
+        let look_ahead = self.look_ahead().unwrap();
+        look_ahead == "none"
+    }
+
+    pub(crate) fn consume_none(&mut self) -> crate::frontend::lex::Result<Token> {
+        let start = self.position();
+        self.consume_if("none").unwrap();
+        Ok(Token {
+            kind: TokenKind::Literal(LiteralToken::None),
+            span: Span {
+                start,
+                end: self.position(),
+            },
+            value: self.ctx.string_table.push_str("none"),
+        })
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::common::Context;
     use crate::frontend::lex::token::LiteralToken::{False, Number, String, True};
-    use crate::frontend::lex::token::TokenKind;
+    use crate::frontend::lex::token::{LiteralToken, TokenKind};
     use crate::frontend::lex::Lexer;
 
     #[test]
@@ -231,6 +253,18 @@ mod test {
         assert_eq!(ctx.str_get(result.value()), "42.24");
     }
 
+    #[test]
+    fn number_before_range() {
+        let text = "42..";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert_eq!(result.kind, TokenKind::Literal(Number));
+        assert_eq!(result.span.start, (1, 1, 0));
+        assert_eq!(result.span.end, (1, 3, 2));
+        assert_eq!(ctx.str_get(result.value()), "42");
+    }
+
     #[test]
     fn hex() {
         let text = "0xDEADBEEF";
@@ -314,4 +348,28 @@ mod test {
         assert_eq!(result.span.end, (1, 7, 6));
         assert_eq!(ctx.str_get(result.value()), "false_");
     }
+
+    #[test]
+    fn none() {
+        let text = "none";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert_eq!(result.kind, TokenKind::Literal(LiteralToken::None));
+        assert_eq!(result.span.start, (1, 1, 0));
+        assert_eq!(result.span.end, (1, 5, 4));
+        assert_eq!(ctx.str_get(result.value()), "none");
+    }
+
+    #[test]
+    fn not_none() {
+        let text = "nones";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert_eq!(result.kind, TokenKind::Identifier);
+        assert_eq!(result.span.start, (1, 1, 0));
+        assert_eq!(result.span.end, (1, 6, 5));
+        assert_eq!(ctx.str_get(result.value()), "nones");
+    }
 }
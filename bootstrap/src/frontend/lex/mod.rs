@@ -2,17 +2,21 @@ use std::cell::RefCell;
 
 use crate::common::{Column, Context, Index, Position, Row, Span};
 use crate::frontend::lex::token::Token;
+use crate::frontend::lex::token::TokenKind;
 use crate::frontend::lex::token::TokenKind::EOF;
 use crate::frontend::lex::Error::UnexpectedEndOfFile;
 
 mod comment;
 mod identifier;
 mod keyword;
+mod label;
 mod literal;
 mod operator;
 mod separator;
 pub mod token;
 
+pub use keyword::is_keyword;
+
 #[derive(Debug)]
 pub enum Error {
     UnexpectedEndOfFile,
@@ -162,6 +166,8 @@ pub(crate) struct Lexer<'a> {
     reader: Reader<'a>,
     current_line: RefCell<Row>,
     current_column: RefCell<Column>,
+    // the kind of the last token, it tells whether a quote starts a label or a string
+    previous: Option<TokenKind>,
 }
 
 impl<'a> Lexer<'a> {
@@ -171,6 +177,7 @@ impl<'a> Lexer<'a> {
             reader: Reader::new(str),
             current_line: RefCell::new(Row(1)),
             current_column: RefCell::new(Column(1)),
+            previous: None,
         }
     }
 
@@ -223,15 +230,19 @@ impl<'a> Lexer<'a> {
         }
 
         if let Some(next) = self.reader.peek_next() {
-            match next {
+            let result = match next {
                 _ if self.is_operator(next) => self.consume_operator(),
                 _ if self.is_separator(next) => self.consume_separator(),
                 _ if self.is_keyword(next) => self.consume_keyword(),
+                _ if self.is_label(next) => self.consume_label(),
                 _ if self.is_string(next) => self.consume_string(),
                 _ if self.is_number(next) => self.consume_number(),
                 _ if self.is_bool(next) => self.consume_bool(),
+                _ if self.is_none(next) => self.consume_none(),
                 _ => self.consume_identifier(),
-            }
+            }?;
+            self.previous = Some(result.kind.clone());
+            Ok(result)
         } else {
             return Err(UnexpectedEndOfFile);
         }
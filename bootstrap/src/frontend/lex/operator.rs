@@ -119,6 +119,11 @@ impl Lexer<'_> {
                     text.push('=');
                     TokenKind::Operator(DoubleEqual)
                 }
+                Some('>') => {
+                    let _ = self.consume_next()?;
+                    text.push('>');
+                    TokenKind::Operator(FatArrow)
+                }
                 _ => TokenKind::Operator(Equal),
             },
             "!" => match self.peek_next() {
@@ -129,6 +134,7 @@ impl Lexer<'_> {
                 }
                 _ => TokenKind::Operator(Bang),
             },
+            "?" => TokenKind::Operator(QuestionMark),
             _ => return Err(crate::frontend::lex::Error::UnknownOperator(text)),
         };
 
@@ -366,6 +372,18 @@ mod test {
         assert_eq!(ctx.str_get(result.value()), "->");
     }
 
+    #[test]
+    fn fat_arrow() {
+        let text = "=>";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert_eq!(result.kind, TokenKind::Operator(FatArrow));
+        assert_eq!(result.span.start, (1, 1, 0));
+        assert_eq!(result.span.end, (1, 3, 2));
+        assert_eq!(ctx.str_get(result.value()), "=>");
+    }
+
     #[test]
     fn plus() {
         let text = "+";
@@ -438,6 +456,18 @@ mod test {
         assert_eq!(ctx.str_get(result.value()), "|");
     }
 
+    #[test]
+    fn question_mark() {
+        let text = "?";
+        let mut ctx = Context::testing();
+        let mut lexer = Lexer::new(&mut ctx, text);
+        let result = lexer.advance().unwrap();
+        assert_eq!(result.kind, TokenKind::Operator(QuestionMark));
+        assert_eq!(result.span.start, (1, 1, 0));
+        assert_eq!(result.span.end, (1, 2, 1));
+        assert_eq!(ctx.str_get(result.value()), "?");
+    }
+
     #[test]
     fn double_pipe() {
         let text = "||";
@@ -22,6 +22,9 @@ impl Token {
     pub fn is_separator(&self, separator: SeparatorToken) -> bool {
         self.kind == TokenKind::Separator(separator)
     }
+    pub fn is_label(&self) -> bool {
+        self.kind == TokenKind::Label
+    }
     pub fn is_keyword(&self, keyword: KeywordToken) -> bool {
         self.kind == TokenKind::Keyword(keyword)
     }
@@ -40,6 +43,7 @@ pub enum TokenKind {
     Operator(OperatorToken),
     Separator(SeparatorToken),
     Identifier,
+    Label,
     EOF,
 }
 
@@ -111,10 +115,15 @@ pub enum KeywordToken {
     Function,
     If,
     Define,
+    Defer,
     Import,
     In,
     Let,
     Loop,
+    Match,
+    Named,
+    Operator,
+    Otherwise,
     Package,
     Readonly,
     Return,
@@ -129,6 +138,7 @@ pub enum LiteralToken {
     String,
     True,
     False,
+    None,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -149,6 +159,7 @@ pub enum OperatorToken {
     Colon,            // :
     DoubleColon,      // ::
     Arrow,            // ->
+    FatArrow,         // =>
     DoubleDot,        // ..
     Plus,             // +
     Minus,            // -
@@ -0,0 +1,67 @@
+// core, std and rt are embedded into the compiler, so that every program can be analysed against them
+// without depending on where the compiler was built or installed
+
+pub(crate) const LIBRARIES: [&str; 4] = [
+    "sysroot/index.ec",
+    "core/index.ec",
+    "std/index.ec",
+    "rt/index.ec",
+];
+
+pub(crate) fn library_file(path: &str) -> Option<&'static str> {
+    let result = match path {
+        "core/index.ec" => include_str!("../../../src/lib/core/index.ec"),
+        "core/ffi/index.ec" => include_str!("../../../src/lib/core/ffi/index.ec"),
+        "core/ffi/c/index.ec" => include_str!("../../../src/lib/core/ffi/c/index.ec"),
+        "core/intrinsics/index.ec" => include_str!("../../../src/lib/core/intrinsics/index.ec"),
+        "rt/index.ec" => include_str!("../../../src/lib/rt/index.ec"),
+        "rt/io/index.ec" => include_str!("../../../src/lib/rt/io/index.ec"),
+        "rt/process/index.ec" => include_str!("../../../src/lib/rt/process/index.ec"),
+        "std/index.ec" => include_str!("../../../src/lib/std/index.ec"),
+        "std/collection/index.ec" => include_str!("../../../src/lib/std/collection/index.ec"),
+        "std/collection/list/index.ec" => include_str!("../../../src/lib/std/collection/list/index.ec"),
+        "std/math/index.ec" => include_str!("../../../src/lib/std/math/index.ec"),
+        "sysroot/index.ec" => include_str!("../../../src/lib/sysroot/index.ec"),
+        _ => return None,
+    };
+    Some(result)
+}
+
+// resolves the source of `from './io' export io` relative to the directory of the exporting library file
+pub(crate) fn library_path(directory: &str, source: &str) -> String {
+    let source = source.trim_start_matches("./").trim_end_matches('/');
+    if directory.is_empty() {
+        format!("{source}/index.ec")
+    } else {
+        format!("{directory}/{source}/index.ec")
+    }
+}
+
+pub(crate) fn library_directory(path: &str) -> &str {
+    path.rsplit_once('/').map(|(directory, _)| directory).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frontend::library::{library_directory, library_file, library_path, LIBRARIES};
+
+    #[test]
+    fn every_library_is_embedded() {
+        for library in LIBRARIES {
+            assert!(library_file(library).is_some(), "{library} is not embedded");
+        }
+    }
+
+    #[test]
+    fn resolve_relative_to_directory() {
+        assert_eq!(library_path("std", "./collection"), "std/collection/index.ec");
+        assert_eq!(library_path("std/collection", "./list"), "std/collection/list/index.ec");
+        assert!(library_file(library_path("std/collection", "./list").as_str()).is_some());
+    }
+
+    #[test]
+    fn directory_of_library_file() {
+        assert_eq!(library_directory("std/collection/index.ec"), "std/collection");
+        assert_eq!(library_directory("index.ec"), "");
+    }
+}
@@ -3,10 +3,12 @@ use crate::common::Context;
 
 use crate::frontend::ast::AstTreeNode;
 use crate::frontend::lex::lex;
+use crate::frontend::library::{library_directory, library_file};
 use crate::frontend::parse::parse;
 
 pub mod ast;
 pub mod lex;
+pub(crate) mod library;
 pub mod modifier;
 pub mod parse;
 
@@ -66,3 +68,10 @@ pub fn ast_from_str(ctx: &mut Context, str: &str) -> Result<Ast> {
     let nodes = parse(ctx, lexed)?;
     Ok(ast::from(ctx, nodes)?)
 }
+
+pub fn ast_from_library(ctx: &mut Context, path: &str) -> Result<Ast> {
+    let content = library_file(path).unwrap_or_else(|| panic!("library file {path} is not embedded"));
+    let lexed = lex(ctx, content)?;
+    let nodes = parse(ctx, lexed)?;
+    Ok(ast::from_library(ctx, nodes, library_directory(path))?)
+}
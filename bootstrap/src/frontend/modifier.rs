@@ -7,9 +7,27 @@ impl Modifiers {
     pub fn is_exported(&self) -> bool {
         self.0.iter().any(|m| matches!(m, Modifier::Export(_)))
     }
+
+    pub fn is_named(&self) -> bool {
+        self.0.iter().any(|m| matches!(m, Modifier::Named(_)))
+    }
+
+    pub fn is_operator(&self) -> bool {
+        self.0.iter().any(|m| matches!(m, Modifier::Operator(_)))
+    }
+
+    pub fn is_readonly(&self) -> bool {
+        self.0.iter().any(|m| matches!(m, Modifier::Readonly(_)))
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Modifier {
     Export(Token),
+    // the arguments of the function can only be passed by their name
+    Named(Token),
+    // the function of a define which an operator on values of the type calls
+    Operator(Token),
+    // nothing reachable through the value can be changed, not even the values inside of it
+    Readonly(Token),
 }
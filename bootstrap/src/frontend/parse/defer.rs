@@ -0,0 +1,44 @@
+use crate::frontend::lex::token::KeywordToken;
+use crate::frontend::parse::node::DeferNode;
+use crate::frontend::parse::Parser;
+
+impl<'a> Parser<'a> {
+    pub(crate) fn parse_defer(&mut self) -> crate::frontend::parse::Result<DeferNode> {
+        let token = self.consume_keyword(KeywordToken::Defer)?;
+        Ok(DeferNode {
+            token,
+            block: self.parse_block()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::Context;
+    use crate::frontend::lex::lex;
+    use crate::frontend::parse::parse;
+
+    #[test]
+    fn empty_defer() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "defer {}").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_defer();
+        assert_eq!(node.block.nodes.len(), 0);
+    }
+
+    #[test]
+    fn defer_with_call() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "defer { close() }\nread()").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 2);
+
+        let node = result[0].as_defer();
+        assert_eq!(node.block.nodes.len(), 1);
+        assert!(node.block.nodes[0].is_infix());
+        assert!(result[1].is_infix());
+    }
+}
@@ -1,6 +1,8 @@
 use KeywordToken::Define;
 
 use crate::frontend::lex::token::KeywordToken;
+use crate::frontend::lex::token::OperatorToken::Colon;
+use crate::frontend::lex::token::SeparatorToken::Comma;
 use crate::frontend::modifier::Modifiers;
 use crate::frontend::parse::{DefineDeclarationNode, Parser};
 
@@ -15,11 +17,28 @@ impl<'a> Parser<'a> {
     ) -> crate::frontend::parse::Result<DefineDeclarationNode> {
         let token = self.consume_keyword(Define)?;
         let identifier = self.parse_type_identifier()?;
+        let type_parameters = self.parse_type_parameters()?;
+
+        // define Point: Equal, Show { .. }
+        let mut traits = vec![];
+        if self.current()?.is_operator(Colon) {
+            self.consume_operator(Colon)?;
+            loop {
+                traits.push(self.parse_type()?);
+                if !self.current()?.is_separator(Comma) {
+                    break;
+                }
+                self.consume_separator(Comma)?;
+            }
+        }
+
         let block = self.parse_block()?;
 
         Ok(DefineDeclarationNode {
             token,
             identifier,
+            type_parameters,
+            traits,
             block,
             modifiers,
         })
@@ -30,7 +49,7 @@ impl<'a> Parser<'a> {
 mod tests {
     use crate::common::Context;
     use crate::frontend::lex::lex;
-    use crate::frontend::parse::parse;
+    use crate::frontend::parse::{parse, TypeNode};
 
     #[test]
     fn empty_definition() {
@@ -82,4 +101,66 @@ mod tests {
         assert_eq!(ctx.str_get(fn_decl.identifier.value()), "some_fn");
         assert!(fn_decl.modifiers.is_exported());
     }
+
+    #[test]
+    fn definition_with_type_parameter() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "define List<T> { function get(idx: Number) -> T {} }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_define_declaration();
+        assert_eq!(ctx.str_get(node.identifier.value()), "List");
+        assert_eq!(node.type_parameters.len(), 1);
+        assert_eq!(ctx.str_get(node.type_parameters[0].value()), "T");
+        assert_eq!(node.block.nodes.len(), 1);
+    }
+
+    #[test]
+    fn definition_of_traits() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "define Point: Equal<Point>, Show { function show() -> String {} }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_define_declaration();
+        assert_eq!(ctx.str_get(node.identifier.value()), "Point");
+        assert_eq!(node.traits.len(), 2);
+
+        let TypeNode::Parameterized(equal) = &node.traits[0] else {
+            panic!("not parameterized")
+        };
+        assert_eq!(ctx.str_get(equal.token.value()), "Equal");
+
+        let TypeNode::Type(show) = &node.traits[1] else {
+            panic!("not type")
+        };
+        assert_eq!(ctx.str_get(show.value()), "Show");
+        assert_eq!(node.block.nodes.len(), 1);
+    }
+
+    #[test]
+    fn readonly_definition() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "readonly define Point: Show { function show() -> String {} }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_define_declaration();
+        assert_eq!(ctx.str_get(node.identifier.value()), "Point");
+        assert!(node.modifiers.is_readonly());
+        assert!(!node.modifiers.is_exported());
+    }
+
+    #[test]
+    fn exported_readonly_definition() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "export readonly define Point { }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_define_declaration();
+        assert!(node.modifiers.is_readonly());
+        assert!(node.modifiers.is_exported());
+    }
 }
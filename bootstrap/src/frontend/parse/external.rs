@@ -1,11 +1,11 @@
-use KeywordToken::{External, Function};
+use KeywordToken::{External, Function, Type};
 
-use crate::frontend::lex::token::KeywordToken;
+use crate::frontend::lex::token::{KeywordToken, LiteralToken};
 use crate::frontend::lex::token::OperatorToken::{Arrow, CloseParen, OpenParen};
 use crate::frontend::lex::token::SeparatorToken::Comma;
 use crate::frontend::lex::token::TokenKind::{Operator, Separator};
 use crate::frontend::modifier::Modifiers;
-use crate::frontend::parse::{ExternalFunctionDeclarationNode, Parser};
+use crate::frontend::parse::{ExternalFunctionDeclarationNode, ExternalLinkDeclarationNode, ExternalTypeDeclarationNode, LiteralStringNode, Parser};
 
 impl<'a> Parser<'a> {
     pub(crate) fn parse_external(
@@ -21,6 +21,7 @@ impl<'a> Parser<'a> {
         let external = self.consume_keyword(External)?;
         let _ = self.consume_keyword(Function)?;
         let identifier = self.parse_identifier()?;
+        let type_parameters = self.parse_type_parameters()?;
         self.consume_operator(OpenParen)?;
 
         let mut arguments = vec![];
@@ -43,11 +44,54 @@ impl<'a> Parser<'a> {
         Ok(ExternalFunctionDeclarationNode {
             token: external,
             identifier,
+            type_parameters,
             arguments,
             return_type,
             modifiers,
         })
     }
+
+    // external link 'm' - the c library the external functions of the surrounding package are linked against
+    pub(crate) fn is_external_link(&self) -> crate::frontend::parse::Result<bool> {
+        let next = self.peek()?;
+        Ok(next.is_identifier() && self.ctx.str_get(next.value()) == "link")
+    }
+
+    pub(crate) fn parse_external_link(
+        &mut self,
+    ) -> crate::frontend::parse::Result<ExternalLinkDeclarationNode> {
+        let token = self.consume_keyword(External)?;
+        let _ = self.parse_identifier()?;
+        let library = LiteralStringNode(self.consume_literal(LiteralToken::String)?);
+
+        Ok(ExternalLinkDeclarationNode { token, library })
+    }
+
+    // external type Point(x: Int4, y: Int4) - a c struct, fields keep their order
+    pub(crate) fn is_external_type(&self) -> crate::frontend::parse::Result<bool> {
+        Ok(self.peek()?.is_keyword(Type))
+    }
+
+    pub(crate) fn parse_external_type(
+        &mut self,
+    ) -> crate::frontend::parse::Result<ExternalTypeDeclarationNode> {
+        self.parse_external_type_with_modifiers(Modifiers(vec![]))
+    }
+
+    pub(crate) fn parse_external_type_with_modifiers(
+        &mut self,
+        modifiers: Modifiers,
+    ) -> crate::frontend::parse::Result<ExternalTypeDeclarationNode> {
+        let token = self.consume_keyword(External)?;
+        let declaration = self.parse_type_declaration_with_modifiers(modifiers)?;
+
+        Ok(ExternalTypeDeclarationNode {
+            token,
+            identifier: declaration.identifier,
+            properties: declaration.properties,
+            modifiers: declaration.modifiers,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -159,4 +203,58 @@ mod tests {
             panic!("not bool")
         };
     }
+
+    #[test]
+    fn external_link() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "external link 'm'").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_external_link_declaration();
+        assert_eq!(ctx.str_get(node.library.value()), "m");
+    }
+
+    #[test]
+    fn external_type() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "export external type Point(x: Int4, y: Int4)").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_external_type_declaration();
+        assert_eq!(ctx.str_get(node.identifier.value()), "Point");
+        assert_eq!(node.properties.nodes.len(), 2);
+        assert!(node.modifiers.is_exported());
+    }
+
+    #[test]
+    fn external_function_with_pointer_arg() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "external function area(p: &Point) -> Int4").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+
+        let node = result[0].as_external_function_declaration();
+        let TypeNode::Pointer(pointer) = node.arguments[0].as_type() else {
+            panic!("not pointer")
+        };
+        let TypeNode::Type(_) = pointer.r#type.as_ref() else {
+            panic!("not type")
+        };
+    }
+
+    #[test]
+    fn external_function_with_type_parameters() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "external function get<L, T>(list: L, idx: Number) -> T").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_external_function_declaration();
+        assert_eq!(ctx.str_get(node.identifier.value()), "get");
+        assert_eq!(node.type_parameters.len(), 2);
+        assert_eq!(ctx.str_get(node.type_parameters[0].value()), "L");
+        assert_eq!(ctx.str_get(node.type_parameters[1].value()), "T");
+        assert_eq!(node.arguments.len(), 2);
+    }
 }
@@ -7,7 +7,7 @@ use crate::frontend::lex::token::OperatorToken::{Arrow, CloseParen};
 use crate::frontend::lex::token::{KeywordToken, OperatorToken, SeparatorToken, TokenKind};
 use crate::frontend::modifier::Modifiers;
 use crate::frontend::parse::node::{
-    FunctionDeclarationArgumentNode, FunctionDeclarationNode, ReturnNode,
+    FunctionDeclarationArgumentNode, FunctionDeclarationNode, ReturnNode, TypeNode,
 };
 use crate::frontend::parse::precedence::Precedence;
 use crate::frontend::parse::Parser;
@@ -25,6 +25,25 @@ impl<'a> Parser<'a> {
     ) -> crate::frontend::parse::Result<FunctionDeclarationNode> {
         let fun_token = self.consume_keyword(KeywordToken::Function)?;
         let identifier = self.parse_identifier()?;
+        let type_parameters = self.parse_type_parameters()?;
+        let (arguments, return_type) = self.parse_function_signature()?;
+        let block = self.parse_block()?;
+
+        Ok(FunctionDeclarationNode {
+            token: fun_token,
+            identifier,
+            type_parameters,
+            arguments,
+            return_type,
+            block,
+            modifiers,
+        })
+    }
+
+    // (left: Number, right: Number) -> Number
+    pub(crate) fn parse_function_signature(
+        &mut self,
+    ) -> crate::frontend::parse::Result<(Vec<FunctionDeclarationArgumentNode>, Option<Box<TypeNode>>)> {
         self.consume_operator(OpenParen)?;
 
         let mut arguments = vec![];
@@ -44,30 +63,30 @@ impl<'a> Parser<'a> {
             None
         };
 
-        let block = self.parse_block()?;
-
-        Ok(FunctionDeclarationNode {
-            token: fun_token,
-            identifier,
-            arguments,
-            return_type,
-            block,
-            modifiers,
-        })
+        Ok((arguments, return_type))
     }
 
     pub(crate) fn parse_function_declaration_argument(
         &mut self,
     ) -> crate::frontend::parse::Result<FunctionDeclarationArgumentNode> {
         let identifier = self.parse_identifier()?;
-        let r#type = if self.current()?.is_operator(OperatorToken::Colon) {
+        let (r#type, modifiers) = if self.current()?.is_operator(OperatorToken::Colon) {
             self.advance()?;
-            Some(Box::new(self.parse_type()?))
+            let modifiers = self.parse_readonly_modifier()?;
+            (Some(Box::new(self.parse_type()?)), modifiers)
+        } else {
+            (None, Modifiers(vec![]))
+        };
+
+        // message: String = 'default' - the value gets passed when the call leaves out the argument
+        let default = if self.current()?.is_operator(OperatorToken::Equal) {
+            self.advance()?;
+            Some(Box::new(self.parse_node(Precedence::None)?))
         } else {
             None
         };
 
-        Ok(FunctionDeclarationArgumentNode { identifier, r#type })
+        Ok(FunctionDeclarationArgumentNode { identifier, r#type, modifiers, default })
     }
 
     pub(crate) fn parse_return(&mut self) -> crate::frontend::parse::Result<ReturnNode> {
@@ -164,6 +183,32 @@ mod tests {
         };
     }
 
+    #[test]
+    fn function_with_default_argument() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "function magic(arg_1: Number, arg_2: String = 'magic'){ }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+
+        let node = result[0].as_function_declaration();
+        assert_eq!(node.arguments.len(), 2);
+        assert_eq!(node.arguments[0].default, None);
+
+        let Some(default) = node.arguments[1].default.as_deref() else { panic!() };
+        let Literal(LiteralNode::String(value)) = default else { panic!() };
+        assert_eq!(ctx.str_get(value.value()), "magic");
+    }
+
+    #[test]
+    fn named_function() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "export named function magic(arg_1: Number){ }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+
+        let node = result[0].as_function_declaration();
+        assert!(node.modifiers.is_exported());
+        assert!(node.modifiers.is_named());
+    }
+
     #[test]
     fn function_with_single_arg() {
         let mut ctx = Context::testing();
@@ -186,6 +231,24 @@ mod tests {
         assert_eq!(node.return_type, None);
     }
 
+    #[test]
+    fn function_with_readonly_arg() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "function magic(config: readonly Config){ }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_function_declaration();
+        let arg = &node.arguments[0];
+        assert_eq!(ctx.str_get(arg.identifier.value()), "config");
+        assert!(arg.modifiers.is_readonly());
+
+        let TypeNode::Type(r#type) = arg.as_type() else {
+            panic!("not type")
+        };
+        assert_eq!(ctx.str_get(r#type.value()), "Config");
+    }
+
     #[test]
     fn function_with_multiple_args() {
         let mut ctx = Context::testing();
@@ -241,4 +304,46 @@ mod tests {
             panic!("not bool")
         };
     }
+
+    #[test]
+    fn function_with_type_parameters() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "function first<T>(l: List<T>) -> T { }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_function_declaration();
+        assert_eq!(ctx.str_get(node.identifier.value()), "first");
+        assert_eq!(node.type_parameters.len(), 1);
+        assert_eq!(ctx.str_get(node.type_parameters[0].value()), "T");
+
+        let TypeNode::Parameterized(argument) = node.arguments[0].as_type() else {
+            panic!("not parameterized")
+        };
+        assert_eq!(ctx.str_get(argument.token.value()), "List");
+
+        let TypeNode::Type(result) = node.as_return_type() else {
+            panic!("not type")
+        };
+        assert_eq!(ctx.str_get(result.value()), "T");
+    }
+
+    #[test]
+    fn function_with_bounded_type_parameter() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "function same<T: Equal<T>, U>(l: T, r: T) -> Bool { }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_function_declaration();
+        assert_eq!(node.type_parameters.len(), 2);
+        assert_eq!(ctx.str_get(node.type_parameters[0].value()), "T");
+        let Some(TypeNode::Parameterized(bound)) = &node.type_parameters[0].bound else {
+            panic!("not parameterized")
+        };
+        assert_eq!(ctx.str_get(bound.token.value()), "Equal");
+
+        assert_eq!(ctx.str_get(node.type_parameters[1].value()), "U");
+        assert_eq!(node.type_parameters[1].bound, None);
+    }
 }
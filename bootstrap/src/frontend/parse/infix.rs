@@ -1,10 +1,11 @@
-use OperatorToken::CloseCurly;
+use OperatorToken::{CloseBracket, CloseCurly};
 
 use crate::frontend::lex::token::OperatorToken;
 use crate::frontend::lex::token::TokenKind::Operator;
 use crate::frontend::parse::node::{InfixNode, InfixOperator, Node};
 use crate::frontend::parse::Error::UnsupportedToken;
 use crate::frontend::parse::Parser;
+use crate::frontend::parse::precedence::Precedence;
 
 impl<'a> Parser<'a> {
     pub(crate) fn parse_infix(&mut self, left: Node) -> crate::frontend::parse::Result<InfixNode> {
@@ -18,6 +19,10 @@ impl<'a> Parser<'a> {
             let result = Node::Block(self.parse_block_inner(left.token())?);
             self.consume_operator(CloseCurly)?;
             result
+        } else if let InfixOperator::Index(_) = &operator {
+            let result = self.parse_node(Precedence::None)?;
+            self.consume_operator(CloseBracket)?;
+            result
         } else if let InfixOperator::Arrow(_) = &operator {
             Node::Block(self.parse_block_inner(left.token())?)
         } else {
@@ -38,6 +43,7 @@ impl<'a> Parser<'a> {
             Operator(operator) => match operator {
                 OperatorToken::OpenParen => Ok(InfixOperator::Call(token)),
                 OperatorToken::OpenCurly => Ok(InfixOperator::LambdaCall(token)),
+                OperatorToken::OpenBracket => Ok(InfixOperator::Index(token)),
                 OperatorToken::Plus => Ok(InfixOperator::Add(token)),
                 OperatorToken::Minus => Ok(InfixOperator::Subtract(token)),
                 OperatorToken::Asterisk => Ok(InfixOperator::Multiply(token)),
@@ -708,4 +714,28 @@ mod tests {
         };
         assert_eq!(ctx.str_get(right.value()), "1");
     }
+
+    #[test]
+    fn index() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "values[1 + 2]").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let InfixNode { left, operator, right, .. } = &result[0].as_infix();
+        assert_eq!(ctx.str_get(left.as_identifier().value()), "values");
+        assert!(matches!(operator, InfixOperator::Index(_)));
+        assert!(matches!(right.as_infix().operator, InfixOperator::Add(_)));
+    }
+
+    #[test]
+    fn index_binds_before_calculation() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "1 + values[0]").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+
+        let InfixNode { operator, right, .. } = &result[0].as_infix();
+        assert!(matches!(operator, InfixOperator::Add(_)));
+        assert!(matches!(right.as_infix().operator, InfixOperator::Index(_)));
+    }
 }
@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use crate::frontend::lex::token::LiteralToken;
-use crate::frontend::parse::node::{LiteralBooleanNode, LiteralNode, LiteralNumberNode};
+use crate::frontend::parse::node::{LiteralBooleanNode, LiteralNode, LiteralNoneNode, LiteralNumberNode};
 use crate::frontend::parse::Parser;
 
 impl<'a> Parser<'a> {
@@ -19,6 +19,11 @@ impl<'a> Parser<'a> {
         let token = self.consume_literal(LiteralToken::False)?;
         return Ok(LiteralNode::Boolean(LiteralBooleanNode(token)));
     }
+
+    pub(crate) fn parse_literal_none(&mut self) -> crate::frontend::parse::Result<LiteralNode> {
+        let token = self.consume_literal(LiteralToken::None)?;
+        Ok(LiteralNode::None(LiteralNoneNode(token)))
+    }
 }
 
 #[cfg(test)]
@@ -1,8 +1,8 @@
-use OperatorToken::CloseCurly;
+use OperatorToken::{CloseCurly, Colon, DoubleDot, Equal, OpenCurly};
 use SeparatorToken::NewLine;
 
-use crate::frontend::lex::token::{KeywordToken, OperatorToken, SeparatorToken};
-use crate::frontend::parse::node::{BreakNode, ContinueNode, LoopNode};
+use crate::frontend::lex::token::{KeywordToken, OperatorToken, SeparatorToken, TokenKind};
+use crate::frontend::parse::node::{BreakNode, ContinueNode, ForNode, ForSource, LoopNode, Node, RangeNode};
 use crate::frontend::parse::precedence::Precedence;
 use crate::frontend::parse::Parser;
 
@@ -11,17 +11,86 @@ impl<'a> Parser<'a> {
         let token = self.consume_keyword(KeywordToken::Loop)?;
         Ok(LoopNode {
             token,
+            label: None,
             block: self.parse_block()?,
         })
     }
 
+    // 'label: in front of a loop or a for, so that break and continue of a nested loop can refer to it
+    pub(crate) fn parse_labeled(&mut self) -> crate::frontend::parse::Result<Node> {
+        let label = self.consume(TokenKind::Label)?;
+        self.consume_operator(Colon)?;
+
+        if self.current()?.is_keyword(KeywordToken::For) {
+            let mut result = self.parse_for()?;
+            result.label = Some(label);
+            Ok(Node::For(result))
+        } else {
+            let mut result = self.parse_loop()?;
+            result.label = Some(label);
+            Ok(Node::Loop(result))
+        }
+    }
+
+    pub(crate) fn parse_for(&mut self) -> crate::frontend::parse::Result<ForNode> {
+        let token = self.consume_keyword(KeywordToken::For)?;
+        let identifier = self.parse_identifier()?;
+        self.consume_keyword(KeywordToken::In)?;
+
+        let start = Box::new(self.parse_node_before_block()?);
+        if !self.current()?.is_operator(DoubleDot) {
+            return Ok(ForNode {
+                token,
+                label: None,
+                identifier,
+                source: ForSource::Value(start),
+                block: self.parse_block()?,
+            });
+        }
+
+        let range_token = self.consume_operator(DoubleDot)?;
+        let inclusive = self.current()?.is_operator(Equal);
+        if inclusive {
+            self.consume_operator(Equal)?;
+        }
+        let end = Box::new(self.parse_node_before_block()?);
+
+        // step is no keyword, it only has a meaning after the end of a range
+        let step = if self.current()?.is_identifier() && self.ctx.str_get(self.current()?.value()) == "step" {
+            self.advance()?;
+            Some(Box::new(self.parse_node_before_block()?))
+        } else {
+            None
+        };
+
+        Ok(ForNode {
+            token,
+            label: None,
+            identifier,
+            source: ForSource::Range(RangeNode { token: range_token, start, end, inclusive, step }),
+            block: self.parse_block()?,
+        })
+    }
+
+    // the { after the range opens the block of the loop, it is no lambda passed to the value in front of it
+    fn parse_node_before_block(&mut self) -> crate::frontend::parse::Result<Node> {
+        let lambda_call = self.precedence_map.remove(&TokenKind::Operator(OpenCurly));
+        let result = self.parse_node(Precedence::None);
+        if let Some(precedence) = lambda_call {
+            self.precedence_map.insert(TokenKind::Operator(OpenCurly), precedence);
+        }
+        result
+    }
+
     pub(crate) fn parse_continue(&mut self) -> crate::frontend::parse::Result<ContinueNode> {
         let token = self.consume_keyword(KeywordToken::Continue)?;
-        Ok(ContinueNode { token })
+        let label = self.consume_if(TokenKind::Label)?;
+        Ok(ContinueNode { token, label })
     }
 
     pub(crate) fn parse_break(&mut self) -> crate::frontend::parse::Result<BreakNode> {
         let token = self.consume_keyword(KeywordToken::Break)?;
+        let label = self.consume_if(TokenKind::Label)?;
 
         let current = self.current()?;
         let has_result = !current.is_operator(CloseCurly) && !current.is_separator(NewLine);
@@ -31,7 +100,7 @@ impl<'a> Parser<'a> {
         } else {
             None
         };
-        Ok(BreakNode { token, result })
+        Ok(BreakNode { token, label, result })
     }
 }
 
@@ -140,4 +209,139 @@ mod tests {
         };
         assert_eq!(ctx.str_get(node.value()), "9924");
     }
+
+    #[test]
+    fn for_range() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "for i in 0..10 { i }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_for();
+        assert_eq!(ctx.str_get(node.identifier.value()), "i");
+        let range = node.source.as_range();
+        assert!(!range.inclusive);
+        assert_eq!(range.step, None);
+        assert_eq!(node.block.nodes.len(), 1);
+
+        let Literal(LiteralNode::Number(start)) = range.start.deref() else {
+            panic!()
+        };
+        assert_eq!(ctx.str_get(start.value()), "0");
+
+        let Literal(LiteralNode::Number(end)) = range.end.deref() else {
+            panic!()
+        };
+        assert_eq!(ctx.str_get(end.value()), "10");
+    }
+
+    #[test]
+    fn for_inclusive_range_with_step() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "for i in 0..=10 step 2 { }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_for();
+        let range = node.source.as_range();
+        assert!(range.inclusive);
+
+        let Some(step) = &range.step else {
+            panic!()
+        };
+        let Literal(LiteralNode::Number(step)) = step.deref() else {
+            panic!()
+        };
+        assert_eq!(ctx.str_get(step.value()), "2");
+    }
+
+    #[test]
+    fn for_range_of_variables() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "for i in start..end { }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_for();
+        let range = node.source.as_range();
+        assert_eq!(ctx.str_get(range.start.as_identifier().value()), "start");
+        assert_eq!(ctx.str_get(range.end.as_identifier().value()), "end");
+        assert_eq!(node.block.nodes, vec![]);
+    }
+
+    #[test]
+    fn for_value() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "for x in list { x }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_for();
+        assert_eq!(ctx.str_get(node.identifier.value()), "x");
+        assert_eq!(ctx.str_get(node.source.as_value().as_identifier().value()), "list");
+        assert_eq!(node.block.nodes.len(), 1);
+    }
+
+    #[test]
+    fn for_string() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "for c in 'elodie' { }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_for();
+        let Literal(LiteralNode::String(value)) = node.source.as_value() else {
+            panic!()
+        };
+        assert_eq!(ctx.str_get(value.value()), "elodie");
+    }
+
+    #[test]
+    fn labeled_loop() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "'outer: loop { break 'outer }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_loop();
+        assert_eq!(ctx.str_get(node.label.as_ref().unwrap().value()), "outer");
+
+        let node = node.block.nodes[0].as_break();
+        assert_eq!(ctx.str_get(node.label.as_ref().unwrap().value()), "outer");
+        assert_eq!(node.result, None);
+    }
+
+    #[test]
+    fn labeled_for() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "'rows: for i in 0..10 { continue 'rows }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_for();
+        assert_eq!(ctx.str_get(node.label.as_ref().unwrap().value()), "rows");
+
+        let node = node.block.nodes[0].as_continue();
+        assert_eq!(ctx.str_get(node.label.as_ref().unwrap().value()), "rows");
+    }
+
+    #[test]
+    fn labeled_break_with_result() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "loop{ break 'outer 9924 }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+
+        let node = result[0].as_loop();
+        assert_eq!(node.label, None);
+
+        let node = node.block.nodes[0].as_break();
+        assert_eq!(ctx.str_get(node.label.as_ref().unwrap().value()), "outer");
+        let Some(ref node) = node.result else {
+            panic!()
+        };
+        let Literal(LiteralNode::Number(node)) = &node.deref() else {
+            panic!()
+        };
+        assert_eq!(ctx.str_get(node.value()), "9924");
+    }
 }
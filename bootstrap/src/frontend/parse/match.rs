@@ -0,0 +1,133 @@
+use OperatorToken::{CloseCurly, CloseParen, FatArrow, OpenCurly, OpenParen};
+use SeparatorToken::{Comma, NewLine};
+
+use crate::frontend::lex::token::{KeywordToken, OperatorToken, SeparatorToken};
+use crate::frontend::lex::token::TokenKind::Separator;
+use crate::frontend::parse::node::{BlockNode, MatchArmNode, MatchNode, MatchPatternNode};
+use crate::frontend::parse::precedence::Precedence;
+use crate::frontend::parse::Parser;
+
+impl<'a> Parser<'a> {
+    pub(crate) fn parse_match(&mut self) -> crate::frontend::parse::Result<MatchNode> {
+        let token = self.consume_keyword(KeywordToken::Match)?;
+
+        // the curly of the arms must not be taken as lambda call of the matched value
+        let mut node = self.parse_primary()?;
+        while !self.is_eof() && !self.current()?.is_operator(OpenCurly) && Precedence::None < self.current_precedence()? {
+            node = self.parse_postfix_or_infix(node)?;
+        }
+
+        self.consume_operator(OpenCurly)?;
+        let mut arms = vec![];
+        loop {
+            self.skip_new_line()?;
+            if self.current()?.is_operator(CloseCurly) {
+                break;
+            }
+            arms.push(self.parse_match_arm()?);
+            self.consume_if(Separator(Comma))?;
+        }
+        self.consume_operator(CloseCurly)?;
+
+        Ok(MatchNode {
+            token,
+            node: Box::new(node),
+            arms,
+        })
+    }
+
+    fn parse_match_arm(&mut self) -> crate::frontend::parse::Result<MatchArmNode> {
+        let pattern = if self.current()?.is_keyword(KeywordToken::Otherwise) {
+            MatchPatternNode::Otherwise(self.consume_keyword(KeywordToken::Otherwise)?)
+        } else {
+            let identifier = self.parse_type_identifier()?;
+            let mut bindings = vec![];
+            if self.current()?.is_operator(OpenParen) {
+                self.consume_operator(OpenParen)?;
+                while !self.current()?.is_operator(CloseParen) {
+                    bindings.push(self.parse_identifier()?);
+                    self.consume_if(Separator(Comma))?;
+                }
+                self.consume_operator(CloseParen)?;
+            }
+            MatchPatternNode::Variant { identifier, bindings }
+        };
+
+        let token = self.consume_operator(FatArrow)?;
+        self.skip_new_line()?;
+
+        let block = if self.current()?.is_operator(OpenCurly) {
+            self.parse_block()?
+        } else {
+            BlockNode {
+                token: token.clone(),
+                nodes: vec![self.parse_node(Precedence::None)?],
+            }
+        };
+        self.consume_while(Separator(NewLine))?;
+
+        Ok(MatchArmNode { token, pattern, block })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::Context;
+    use crate::frontend::lex::lex;
+    use crate::frontend::parse::node::MatchPatternNode;
+    use crate::frontend::parse::{parse, Error};
+
+    #[test]
+    fn match_with_variants() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, r#"match shape {
+            Circle(radius) => { radius }
+            Square(side) => side
+            Empty => 0
+        }"#).unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_match();
+        assert_eq!(ctx.str_get(node.node.as_identifier().value()), "shape");
+        assert_eq!(node.arms.len(), 3);
+
+        let MatchPatternNode::Variant { identifier, bindings } = &node.arms[0].pattern else { panic!() };
+        assert_eq!(ctx.str_get(identifier.value()), "Circle");
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(ctx.str_get(bindings[0].value()), "radius");
+        assert_eq!(node.arms[0].block.nodes.len(), 1);
+
+        let MatchPatternNode::Variant { identifier, bindings } = &node.arms[1].pattern else { panic!() };
+        assert_eq!(ctx.str_get(identifier.value()), "Square");
+        assert_eq!(ctx.str_get(bindings[0].value()), "side");
+        assert_eq!(ctx.str_get(node.arms[1].block.nodes[0].as_identifier().value()), "side");
+
+        let MatchPatternNode::Variant { identifier, bindings } = &node.arms[2].pattern else { panic!() };
+        assert_eq!(ctx.str_get(identifier.value()), "Empty");
+        assert_eq!(bindings.len(), 0);
+        assert!(node.arms[2].block.nodes[0].is_literal());
+    }
+
+    #[test]
+    fn match_with_otherwise() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "match self.channel { Red => 1, otherwise => { 2 } }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_match();
+        assert!(node.node.is_infix());
+        assert_eq!(node.arms.len(), 2);
+        assert!(matches!(node.arms[0].pattern, MatchPatternNode::Variant { .. }));
+        assert!(matches!(node.arms[1].pattern, MatchPatternNode::Otherwise(_)));
+    }
+
+    #[test]
+    fn match_arm_without_arrow() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "match shape { Circle(radius) { radius } }").unwrap();
+        let result = parse(&mut ctx, tokens);
+        let Error::UnexpectedToken { .. } = result.err().unwrap() else { panic!() };
+    }
+}
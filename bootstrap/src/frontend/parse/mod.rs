@@ -13,6 +13,7 @@ use crate::frontend::parse::Error::UnexpectedEndOfFile;
 
 mod block;
 mod define;
+mod defer;
 mod external;
 mod from;
 mod function;
@@ -21,12 +22,15 @@ mod r#if;
 mod infix;
 mod literal;
 mod r#loop;
+mod r#match;
 mod modifier;
 mod node;
 mod package;
 pub(crate) mod precedence;
 mod primary;
 mod string;
+mod r#trait;
+mod r#try;
 mod tuple;
 mod r#type;
 mod type_declaration;
@@ -36,6 +40,7 @@ mod variable;
 pub enum Error {
     InvalidIdentifier(Token),
     InvalidType(Token),
+    PositionalAfterNamed(Token),
     UnexpectedEndOfFile,
     UnexpectedToken { expected: TokenKind, got: Token },
     UnsupportedNumber(String),
@@ -94,6 +99,7 @@ impl<'a> Parser<'a> {
         precedence_map.insert(Operator(OperatorToken::Percent), Precedence::Factor);
 
         precedence_map.insert(Operator(OperatorToken::OpenParen), Precedence::Call);
+        precedence_map.insert(Operator(OperatorToken::OpenBracket), Precedence::Call);
         precedence_map.insert(Operator(OperatorToken::OpenCurly), Precedence::LambdaCall);
 
         precedence_map.insert(Operator(OperatorToken::Dot), Precedence::Primary);
@@ -101,6 +107,7 @@ impl<'a> Parser<'a> {
 
         precedence_map.insert(Operator(OperatorToken::Arrow), Precedence::Primary);
         precedence_map.insert(Operator(OperatorToken::Colon), Precedence::Primary);
+        precedence_map.insert(Operator(OperatorToken::QuestionMark), Precedence::Primary);
 
         let mut tokens = tokens;
         tokens.pop();
@@ -131,7 +138,7 @@ impl<'a> Parser<'a> {
         let mut left = self.parse_primary()?;
 
         while !self.is_eof() && precedence < self.current_precedence()? {
-            left = Node::Infix(self.parse_infix(left)?);
+            left = self.parse_postfix_or_infix(left)?;
         }
         Ok(left)
     }
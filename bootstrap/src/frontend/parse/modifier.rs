@@ -1,9 +1,9 @@
-use KeywordToken::Export;
+use KeywordToken::{Export, Named, Operator, Readonly};
 
 use crate::frontend::lex::token::KeywordToken;
-use crate::frontend::lex::token::KeywordToken::{Define, External, Function, Package, Type};
+use crate::frontend::lex::token::KeywordToken::{Define, External, Function, Package, Trait, Type};
 use crate::frontend::modifier::{Modifier, Modifiers};
-use crate::frontend::parse::{Node, Parser};
+use crate::frontend::parse::{FunctionDeclarationNode, Node, Parser};
 
 impl<'a> Parser<'a> {
     pub(crate) fn parse_export(&mut self) -> crate::frontend::parse::Result<Node> {
@@ -12,12 +12,26 @@ impl<'a> Parser<'a> {
 
         let current = self.current()?.clone();
 
+        if current.is_keyword(Readonly) {
+            let readonly = Modifier::Readonly(self.consume_keyword(Readonly)?);
+            return Ok(Node::DefineDeclaration(
+                self.parse_define_with_modifiers(Modifiers(vec![modifier, readonly]))?,
+            ));
+        }
+
         if current.is_keyword(Function) {
             return Ok(Node::FunctionDeclaration(
                 self.parse_function_declaration_with_modifiers(Modifiers(vec![modifier]))?,
             ));
         }
 
+        if current.is_keyword(Named) {
+            let named = Modifier::Named(self.consume_keyword(Named)?);
+            return Ok(Node::FunctionDeclaration(
+                self.parse_function_declaration_with_modifiers(Modifiers(vec![modifier, named]))?,
+            ));
+        }
+
         if current.is_keyword(Package) {
             return Ok(Node::PackageDeclaration(
                 self.parse_package_declaration_with_modifiers(Modifiers(vec![modifier]))?,
@@ -30,12 +44,24 @@ impl<'a> Parser<'a> {
             ));
         }
 
+        if current.is_keyword(Trait) {
+            return Ok(Node::TraitDeclaration(
+                self.parse_trait_declaration_with_modifiers(Modifiers(vec![modifier]))?,
+            ));
+        }
+
         if current.is_keyword(Define) {
             return Ok(Node::DefineDeclaration(
                 self.parse_define_with_modifiers(Modifiers(vec![modifier]))?,
             ));
         }
 
+        if current.is_keyword(External) && self.is_external_type()? {
+            return Ok(Node::ExternalTypeDeclaration(
+                self.parse_external_type_with_modifiers(Modifiers(vec![modifier]))?,
+            ));
+        }
+
         if current.is_keyword(External) {
             return Ok(Node::ExternalFunctionDeclaration(
                 self.parse_external_with_modifiers(Modifiers(vec![modifier]))?,
@@ -44,4 +70,32 @@ impl<'a> Parser<'a> {
 
         unimplemented!();
     }
+
+    // readonly define Point: Show { .. } - none of the functions can change the point
+    pub(crate) fn parse_readonly(&mut self) -> crate::frontend::parse::Result<Node> {
+        let token = self.consume_keyword(Readonly)?;
+        Ok(Node::DefineDeclaration(
+            self.parse_define_with_modifiers(Modifiers(vec![Modifier::Readonly(token)]))?,
+        ))
+    }
+
+    // operator function add(other: Vector) -> Vector { .. } - a + b calls add of a with b
+    pub(crate) fn parse_operator(&mut self) -> crate::frontend::parse::Result<FunctionDeclarationNode> {
+        let token = self.consume_keyword(Operator)?;
+        self.parse_function_declaration_with_modifiers(Modifiers(vec![Modifier::Operator(token)]))
+    }
+
+    // named function connect(host: String, port: Number) { .. } - connect(host = 'localhost', port = 80)
+    pub(crate) fn parse_named(&mut self) -> crate::frontend::parse::Result<FunctionDeclarationNode> {
+        let token = self.consume_keyword(Named)?;
+        self.parse_function_declaration_with_modifiers(Modifiers(vec![Modifier::Named(token)]))
+    }
+
+    // config: readonly Config
+    pub(crate) fn parse_readonly_modifier(&mut self) -> crate::frontend::parse::Result<Modifiers> {
+        if !self.current()?.is_keyword(Readonly) {
+            return Ok(Modifiers(vec![]));
+        }
+        Ok(Modifiers(vec![Modifier::Readonly(self.consume_keyword(Readonly)?)]))
+    }
 }
@@ -10,8 +10,12 @@ pub(crate) enum Node {
     Break(BreakNode),
     Call(CallNode),
     Continue(ContinueNode),
+    Defer(DeferNode),
+    For(ForNode),
     From(FromNode),
     ExternalFunctionDeclaration(ExternalFunctionDeclarationNode),
+    ExternalLinkDeclaration(ExternalLinkDeclarationNode),
+    ExternalTypeDeclaration(ExternalTypeDeclarationNode),
     FunctionDeclaration(FunctionDeclarationNode),
     DefineDeclaration(DefineDeclarationNode),
     Identifier(IdentifierNode),
@@ -20,13 +24,17 @@ pub(crate) enum Node {
     Itself(ItselfNode),
     Literal(LiteralNode),
     Loop(LoopNode),
+    Match(MatchNode),
     Nop,
     PackageDeclaration(PackageDeclarationNode),
     Prefix(PrefixNode),
     Return(ReturnNode),
     StringInterpolation(StringInterpolationNode),
+    TraitDeclaration(TraitDeclarationNode),
     Tuple(TupleNode),
+    TupleDeclaration(TupleDeclarationNode),
     Type(TypeNode),
+    Try(TryNode),
     TypeDeclaration(TypeDeclarationNode),
     VariableDeclaration(VariableDeclarationNode),
 }
@@ -38,10 +46,14 @@ impl Node {
             Node::Break(n) => n.token.clone(),
             Node::Call(n) => n.token.clone(),
             Node::Continue(n) => n.token.clone(),
+            Node::Defer(n) => n.token.clone(),
+            Node::For(n) => n.token.clone(),
             Node::From(n) => match n {
                 FromNode::Export(n) => n.token.clone(),
             },
             Node::ExternalFunctionDeclaration(n) => n.token.clone(),
+            Node::ExternalLinkDeclaration(n) => n.token.clone(),
+            Node::ExternalTypeDeclaration(n) => n.token.clone(),
             Node::FunctionDeclaration(n) => n.token.clone(),
             Node::DefineDeclaration(n) => n.token.clone(),
             Node::Identifier(n) => n.0.clone(),
@@ -52,8 +64,10 @@ impl Node {
                 LiteralNode::Number(n) => n.0.clone(),
                 LiteralNode::String(n) => n.0.clone(),
                 LiteralNode::Boolean(n) => n.0.clone(),
+                LiteralNode::None(n) => n.0.clone(),
             },
             Node::Loop(n) => n.token.clone(),
+            Node::Match(n) => n.token.clone(),
             Node::Nop => unimplemented!(),
             Node::PackageDeclaration(n) => n.token.clone(),
             Node::Prefix(n) => match &n.operator {
@@ -63,6 +77,8 @@ impl Node {
             },
             Node::Return(n) => n.token.clone(),
             Node::StringInterpolation(n) => n.token.clone(),
+            Node::TraitDeclaration(n) => n.token.clone(),
+            Node::Try(n) => n.token.clone(),
             Node::Tuple(n) => n.token.clone(),
             Node::Type(n) => match n {
                 TypeNode::Boolean(t) => t.clone(),
@@ -75,7 +91,13 @@ impl Node {
                 TypeNode::Int8(t) => t.clone(),
                 TypeNode::Int16(t) => t.clone(),
                 TypeNode::Number(t) => t.clone(),
+                TypeNode::Optional(n) => n.token.clone(),
+                TypeNode::Package(n) => n.package[0].0.clone(),
+                TypeNode::Parameterized(n) => n.token.clone(),
+                TypeNode::Pointer(n) => n.token.clone(),
+                TypeNode::Record(n) => n.token.clone(),
                 TypeNode::String(t) => t.clone(),
+                TypeNode::Tuple(n) => n.token.clone(),
                 TypeNode::Type(n) => n.clone(),
                 TypeNode::Uint1(t) => t.clone(),
                 TypeNode::Uint2(t) => t.clone(),
@@ -83,6 +105,7 @@ impl Node {
                 TypeNode::Uint8(t) => t.clone(),
                 TypeNode::Uint16(t) => t.clone(),
             },
+            Node::TupleDeclaration(n) => n.token.clone(),
             Node::TypeDeclaration(n) => n.token.clone(),
             Node::VariableDeclaration(n) => n.token.clone(),
         }
@@ -134,6 +157,17 @@ impl Node {
         }
     }
 
+    pub(crate) fn is_defer(&self) -> bool {
+        matches!(self, Node::Defer(_))
+    }
+    pub(crate) fn as_defer(&self) -> &DeferNode {
+        if let Node::Defer(result) = self {
+            result
+        } else {
+            panic!("not defer")
+        }
+    }
+
     pub(crate) fn is_define_declaration(&self) -> bool {
         matches!(self, Node::DefineDeclaration(_))
     }
@@ -167,6 +201,36 @@ impl Node {
         }
     }
 
+    pub(crate) fn is_external_link_declaration(&self) -> bool {
+        matches!(self, Node::ExternalLinkDeclaration(_))
+    }
+    pub(crate) fn as_external_link_declaration(&self) -> &ExternalLinkDeclarationNode {
+        if let Node::ExternalLinkDeclaration(result) = self {
+            result
+        } else {
+            panic!("not external link declaration")
+        }
+    }
+
+    pub(crate) fn is_external_type_declaration(&self) -> bool {
+        matches!(self, Node::ExternalTypeDeclaration(_))
+    }
+    pub(crate) fn as_external_type_declaration(&self) -> &ExternalTypeDeclarationNode {
+        if let Node::ExternalTypeDeclaration(result) = self {
+            result
+        } else {
+            panic!("not external type declaration")
+        }
+    }
+
+    pub(crate) fn as_for(&self) -> &ForNode {
+        if let Node::For(result) = self {
+            result
+        } else {
+            panic!("not for")
+        }
+    }
+
     pub(crate) fn is_function_declaration(&self) -> bool {
         matches!(self, Node::FunctionDeclaration(_))
     }
@@ -222,6 +286,14 @@ impl Node {
         }
     }
 
+    pub(crate) fn as_tuple_declaration(&self) -> &TupleDeclarationNode {
+        if let Node::TupleDeclaration(result) = self {
+            result
+        } else {
+            panic!("not tuple declaration")
+        }
+    }
+
     pub(crate) fn is_literal(&self) -> bool {
         matches!(self, Node::Literal(_))
     }
@@ -244,6 +316,28 @@ impl Node {
         }
     }
 
+    pub(crate) fn is_match(&self) -> bool {
+        matches!(self, Node::Match(_))
+    }
+    pub(crate) fn as_match(&self) -> &MatchNode {
+        if let Node::Match(result) = self {
+            result
+        } else {
+            panic!("not match")
+        }
+    }
+
+    pub(crate) fn is_try(&self) -> bool {
+        matches!(self, Node::Try(_))
+    }
+    pub(crate) fn as_try(&self) -> &TryNode {
+        if let Node::Try(result) = self {
+            result
+        } else {
+            panic!("not try")
+        }
+    }
+
     pub(crate) fn is_package_declaration(&self) -> bool {
         matches!(self, Node::PackageDeclaration(_))
     }
@@ -288,6 +382,17 @@ impl Node {
         }
     }
 
+    pub(crate) fn is_trait_declaration(&self) -> bool {
+        matches!(self, Node::TraitDeclaration(_))
+    }
+    pub(crate) fn as_trait_declaration(&self) -> &TraitDeclarationNode {
+        if let Node::TraitDeclaration(result) = self {
+            result
+        } else {
+            panic!("not trait declaration")
+        }
+    }
+
     pub(crate) fn is_tuple(&self) -> bool {
         matches!(self, Node::Tuple(_))
     }
@@ -331,6 +436,7 @@ pub(crate) struct BlockNode {
 #[derive(Debug, PartialEq)]
 pub(crate) struct BreakNode {
     pub(crate) token: Token,
+    pub(crate) label: Option<Token>,
     pub(crate) result: Option<Box<Node>>,
 }
 
@@ -360,17 +466,74 @@ pub(crate) struct CallArgument {
 #[derive(Debug, PartialEq)]
 pub(crate) struct ContinueNode {
     pub(crate) token: Token,
+    pub(crate) label: Option<Token>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct DeferNode {
+    pub(crate) token: Token,
+    pub(crate) block: BlockNode,
 }
 
 #[derive(Debug, PartialEq)]
 pub(crate) struct ExternalFunctionDeclarationNode {
     pub(crate) token: Token,
     pub(crate) identifier: IdentifierNode,
+    pub(crate) type_parameters: Vec<TypeParameterNode>,
     pub(crate) arguments: Vec<FunctionDeclarationArgumentNode>,
     pub(crate) return_type: Option<Box<TypeNode>>,
     pub(crate) modifiers: Modifiers,
 }
 
+#[derive(Debug, PartialEq)]
+pub(crate) struct ExternalLinkDeclarationNode {
+    pub(crate) token: Token,
+    pub(crate) library: LiteralStringNode,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct ExternalTypeDeclarationNode {
+    pub(crate) token: Token,
+    pub(crate) identifier: IdentifierNode,
+    pub(crate) properties: TupleNode,
+    pub(crate) modifiers: Modifiers,
+}
+
+// for i in 0..10 { } or for x in list { }
+#[derive(Debug, PartialEq)]
+pub(crate) struct ForNode {
+    pub(crate) token: Token,
+    pub(crate) label: Option<Token>,
+    pub(crate) identifier: IdentifierNode,
+    pub(crate) source: ForSource,
+    pub(crate) block: BlockNode,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ForSource {
+    Range(RangeNode),
+    // an iterator, a value with iter() or a string
+    Value(Box<Node>),
+}
+
+impl ForSource {
+    pub(crate) fn as_range(&self) -> &RangeNode {
+        if let ForSource::Range(result) = self {
+            result
+        } else {
+            panic!("not range")
+        }
+    }
+
+    pub(crate) fn as_value(&self) -> &Node {
+        if let ForSource::Value(result) = self {
+            result
+        } else {
+            panic!("not value")
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub(crate) struct FromExportNode {
     pub(crate) token: Token,
@@ -387,6 +550,7 @@ pub(crate) enum FromNode {
 pub(crate) struct FunctionDeclarationNode {
     pub(crate) token: Token,
     pub(crate) identifier: IdentifierNode,
+    pub(crate) type_parameters: Vec<TypeParameterNode>,
     pub(crate) arguments: Vec<FunctionDeclarationArgumentNode>,
     pub(crate) return_type: Option<Box<TypeNode>>,
     pub(crate) block: BlockNode,
@@ -407,6 +571,8 @@ impl FunctionDeclarationNode {
 pub(crate) struct FunctionDeclarationArgumentNode {
     pub(crate) identifier: IdentifierNode,
     pub(crate) r#type: Option<Box<TypeNode>>,
+    pub(crate) modifiers: Modifiers,
+    pub(crate) default: Option<Box<Node>>,
 }
 
 impl FunctionDeclarationArgumentNode {
@@ -467,6 +633,7 @@ pub(crate) enum InfixOperator {
     AccessProperty(Token),
     Assign(Token),
     Call(Token),
+    Index(Token),
     Subtract(Token),
     Multiply(Token),
     Divide(Token),
@@ -490,6 +657,7 @@ impl InfixOperator {
             InfixOperator::AccessProperty(t) => t.clone(),
             InfixOperator::Assign(t) => t.clone(),
             InfixOperator::Call(t) => t.clone(),
+            InfixOperator::Index(t) => t.clone(),
             InfixOperator::Subtract(t) => t.clone(),
             InfixOperator::Multiply(t) => t.clone(),
             InfixOperator::Divide(t) => t.clone(),
@@ -512,6 +680,15 @@ pub(crate) struct VariableDeclarationNode {
     pub(crate) identifier: IdentifierNode,
     pub(crate) node: Rc<Node>,
     pub(crate) r#type: Option<TypeNode>,
+    pub(crate) modifiers: Modifiers,
+}
+
+// let (a, b) = value
+#[derive(Debug, PartialEq)]
+pub(crate) struct TupleDeclarationNode {
+    pub(crate) token: Token,
+    pub(crate) identifiers: Vec<IdentifierNode>,
+    pub(crate) node: Rc<Node>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -519,6 +696,7 @@ pub(crate) enum LiteralNode {
     Number(LiteralNumberNode),
     String(LiteralStringNode),
     Boolean(LiteralBooleanNode),
+    None(LiteralNoneNode),
 }
 
 #[derive(Debug, PartialEq)]
@@ -548,12 +726,41 @@ impl LiteralBooleanNode {
     }
 }
 
+#[derive(Debug, PartialEq)]
+pub(crate) struct LiteralNoneNode(pub(crate) Token);
+
 #[derive(Debug, PartialEq)]
 pub(crate) struct LoopNode {
     pub(crate) token: Token,
+    pub(crate) label: Option<Token>,
     pub(crate) block: BlockNode,
 }
 
+#[derive(Debug, PartialEq)]
+pub(crate) struct MatchNode {
+    pub(crate) token: Token,
+    pub(crate) node: Box<Node>,
+    pub(crate) arms: Vec<MatchArmNode>,
+}
+
+// an arm which is a single expression gets its own block
+#[derive(Debug, PartialEq)]
+pub(crate) struct MatchArmNode {
+    pub(crate) token: Token,
+    pub(crate) pattern: MatchPatternNode,
+    pub(crate) block: BlockNode,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum MatchPatternNode {
+    // Circle(radius) binds the properties of the variant in the order of its declaration
+    Variant {
+        identifier: IdentifierNode,
+        bindings: Vec<IdentifierNode>,
+    },
+    Otherwise(Token),
+}
+
 #[derive(Debug, PartialEq)]
 pub(crate) struct PackageDeclarationNode {
     pub(crate) token: Token,
@@ -566,6 +773,9 @@ pub(crate) struct PackageDeclarationNode {
 pub(crate) struct DefineDeclarationNode {
     pub(crate) token: Token,
     pub(crate) identifier: IdentifierNode,
+    pub(crate) type_parameters: Vec<TypeParameterNode>,
+    // the traits the type implements with the functions of the block
+    pub(crate) traits: Vec<TypeNode>,
     pub(crate) block: BlockNode,
     pub(crate) modifiers: Modifiers,
 }
@@ -576,12 +786,29 @@ pub(crate) struct PrefixNode {
     pub(crate) node: Box<Node>,
 }
 
+// 0..10 excludes the end, 0..=10 includes it, 0..10 step 2 takes every second number
+#[derive(Debug, PartialEq)]
+pub(crate) struct RangeNode {
+    pub(crate) token: Token,
+    pub(crate) start: Box<Node>,
+    pub(crate) end: Box<Node>,
+    pub(crate) inclusive: bool,
+    pub(crate) step: Option<Box<Node>>,
+}
+
 #[derive(Debug, PartialEq)]
 pub(crate) struct ReturnNode {
     pub(crate) token: Token,
     pub(crate) result: Option<Box<Node>>,
 }
 
+// value? - the token is the question mark
+#[derive(Debug, PartialEq)]
+pub(crate) struct TryNode {
+    pub(crate) token: Token,
+    pub(crate) node: Box<Node>,
+}
+
 impl ReturnNode {
     pub(crate) fn as_result(&self) -> &Node {
         if let Some(ref node) = self.result {
@@ -605,6 +832,25 @@ pub(crate) struct StringInterpolationNode {
     pub(crate) nodes: Vec<Node>,
 }
 
+#[derive(Debug, PartialEq)]
+pub(crate) struct TraitDeclarationNode {
+    pub(crate) token: Token,
+    pub(crate) identifier: IdentifierNode,
+    pub(crate) type_parameters: Vec<TypeParameterNode>,
+    pub(crate) functions: Vec<TraitFunctionNode>,
+    pub(crate) modifiers: Modifiers,
+}
+
+// a function without block has to be implemented by every type, the block of the others is their default
+#[derive(Debug, PartialEq)]
+pub(crate) struct TraitFunctionNode {
+    pub(crate) token: Token,
+    pub(crate) identifier: IdentifierNode,
+    pub(crate) arguments: Vec<FunctionDeclarationArgumentNode>,
+    pub(crate) return_type: Option<Box<TypeNode>>,
+    pub(crate) block: Option<BlockNode>,
+}
+
 #[derive(Debug, PartialEq)]
 pub(crate) struct TupleNode {
     pub(crate) token: Token,
@@ -626,7 +872,13 @@ pub(crate) enum TypeNode {
     Int16(Token),
 
     Number(Token),
+    Optional(TypeOptionalNode),
+    Package(TypePackageNode),
+    Parameterized(TypeParameterizedNode),
+    Pointer(TypePointerNode),
+    Record(TypeRecordNode),
     String(Token),
+    Tuple(TypeTupleNode),
     Type(Token),
 
     Uint1(Token),
@@ -636,6 +888,65 @@ pub(crate) enum TypeNode {
     Uint16(Token),
 }
 
+#[derive(Debug, PartialEq)]
+pub(crate) struct TypeParameterNode {
+    pub(crate) identifier: IdentifierNode,
+    // T: Equal only takes types which implement Equal
+    pub(crate) bound: Option<TypeNode>,
+}
+
+impl TypeParameterNode {
+    pub(crate) fn value(&self) -> StringTableId {
+        self.identifier.value()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct TypeParameterizedNode {
+    pub(crate) token: Token,
+    pub(crate) arguments: Vec<TypeNode>,
+}
+
+// std::collection::list::List<Number> - a type of another package
+#[derive(Debug, PartialEq)]
+pub(crate) struct TypePackageNode {
+    pub(crate) package: Vec<IdentifierNode>,
+    pub(crate) r#type: Box<TypeNode>,
+}
+
+// Number? - the value or none
+#[derive(Debug, PartialEq)]
+pub(crate) struct TypeOptionalNode {
+    pub(crate) token: Token,
+    pub(crate) r#type: Box<TypeNode>,
+}
+
+// (x: Number, y: Number) - the token is the open paren
+#[derive(Debug, PartialEq)]
+pub(crate) struct TypeRecordNode {
+    pub(crate) token: Token,
+    pub(crate) fields: Vec<TypeRecordFieldNode>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct TypeRecordFieldNode {
+    pub(crate) identifier: IdentifierNode,
+    pub(crate) r#type: TypeNode,
+}
+
+// (Number, String) - the token is the open paren
+#[derive(Debug, PartialEq)]
+pub(crate) struct TypeTupleNode {
+    pub(crate) token: Token,
+    pub(crate) types: Vec<TypeNode>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct TypePointerNode {
+    pub(crate) token: Token,
+    pub(crate) r#type: Box<TypeNode>,
+}
+
 #[derive(Debug, PartialEq)]
 pub(crate) struct TypeFunctionNode {
     pub(crate) token: Token,
@@ -663,6 +974,15 @@ pub(crate) struct TypeFunctionArgumentNode {
 pub(crate) struct TypeDeclarationNode {
     pub(crate) token: Token,
     pub(crate) identifier: IdentifierNode,
+    pub(crate) type_parameters: Vec<TypeParameterNode>,
     pub(crate) properties: TupleNode,
+    // type Shape = Circle(radius: Number) | Empty has variants instead of properties
+    pub(crate) variants: Vec<TypeVariantNode>,
     pub(crate) modifiers: Modifiers,
 }
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct TypeVariantNode {
+    pub(crate) identifier: IdentifierNode,
+    pub(crate) properties: TupleNode,
+}
@@ -3,7 +3,7 @@ use SeparatorToken::NewLine;
 use crate::common::{is_pascal_snake_case, is_snake_case};
 use crate::frontend::lex::token::LiteralToken::{False, Number, String, True};
 use crate::frontend::lex::token::TokenKind::{Keyword, Operator};
-use crate::frontend::lex::token::{KeywordToken, OperatorToken, SeparatorToken};
+use crate::frontend::lex::token::{KeywordToken, LiteralToken, OperatorToken, SeparatorToken};
 use crate::frontend::parse::node::Node::{
     Break, Continue, FunctionDeclaration, If, Loop, Return, VariableDeclaration,
 };
@@ -11,7 +11,7 @@ use crate::frontend::parse::node::{Node, PrefixNode, PrefixOperator};
 use crate::frontend::parse::precedence::Precedence;
 use crate::frontend::parse::Error::UnsupportedToken;
 use crate::frontend::parse::Node::{
-    DefineDeclaration, ExternalFunctionDeclaration, Itself, PackageDeclaration, TypeDeclaration,
+    DefineDeclaration, ExternalFunctionDeclaration, ExternalLinkDeclaration, ExternalTypeDeclaration, Itself, PackageDeclaration, TraitDeclaration, TypeDeclaration,
 };
 use crate::frontend::parse::{Error, Parser};
 
@@ -45,20 +45,37 @@ impl<'a> Parser<'a> {
             },
             Keyword(keyword) => match keyword {
                 KeywordToken::Break => Ok(Break(self.parse_break()?)),
+                KeywordToken::Const => Ok(VariableDeclaration(self.parse_variable_declaration()?)),
                 KeywordToken::Continue => Ok(Continue(self.parse_continue()?)),
                 KeywordToken::Define => Ok(DefineDeclaration(self.parse_define()?)),
+                KeywordToken::Defer => Ok(Node::Defer(self.parse_defer()?)),
                 KeywordToken::Export => Ok(self.parse_export()?),
-                KeywordToken::External => Ok(ExternalFunctionDeclaration(self.parse_external()?)),
+                KeywordToken::External => {
+                    if self.is_external_link()? {
+                        Ok(ExternalLinkDeclaration(self.parse_external_link()?))
+                    } else if self.is_external_type()? {
+                        Ok(ExternalTypeDeclaration(self.parse_external_type()?))
+                    } else {
+                        Ok(ExternalFunctionDeclaration(self.parse_external()?))
+                    }
+                }
+                KeywordToken::For => Ok(Node::For(self.parse_for()?)),
                 KeywordToken::From => Ok(Node::From(self.parse_from()?)),
                 KeywordToken::Function => {
                     Ok(FunctionDeclaration(self.parse_function_declaration()?))
                 }
                 KeywordToken::If => Ok(If(self.parse_if()?)),
                 KeywordToken::Itself => Ok(Itself(self.parse_self()?)),
+                KeywordToken::Let if self.peek()?.is_operator(OperatorToken::OpenParen) => Ok(Node::TupleDeclaration(self.parse_tuple_declaration()?)),
                 KeywordToken::Let => Ok(VariableDeclaration(self.parse_variable_declaration()?)),
                 KeywordToken::Loop => Ok(Loop(self.parse_loop()?)),
+                KeywordToken::Match => Ok(Node::Match(self.parse_match()?)),
+                KeywordToken::Named => Ok(FunctionDeclaration(self.parse_named()?)),
+                KeywordToken::Operator => Ok(FunctionDeclaration(self.parse_operator()?)),
                 KeywordToken::Package => Ok(PackageDeclaration(self.parse_package_declaration()?)),
+                KeywordToken::Readonly => Ok(self.parse_readonly()?),
                 KeywordToken::Return => Ok(Return(self.parse_return()?)),
+                KeywordToken::Trait => Ok(TraitDeclaration(self.parse_trait_declaration()?)),
                 KeywordToken::Type => Ok(TypeDeclaration(self.parse_type_declaration()?)),
                 _ => Err(Error::unsupported(self.advance()?)),
             },
@@ -66,7 +83,9 @@ impl<'a> Parser<'a> {
                 _ if current.is_literal(Number) => Ok(Node::Literal(self.parse_literal_number()?)),
                 _ if current.is_literal(True) => Ok(Node::Literal(self.parse_literal_true()?)),
                 _ if current.is_literal(False) => Ok(Node::Literal(self.parse_literal_false()?)),
+                _ if current.is_literal(LiteralToken::None) => Ok(Node::Literal(self.parse_literal_none()?)),
                 _ if current.is_literal(String) => Ok(self.parse_string()?),
+                _ if current.is_label() => Ok(self.parse_labeled()?),
                 _ if current.is_identifier() => {
                     if is_snake_case(self.ctx.str_get(current.value())) {
                         Ok(Node::Identifier(self.parse_identifier()?))
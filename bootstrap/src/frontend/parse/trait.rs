@@ -0,0 +1,108 @@
+use KeywordToken::{Function, Trait};
+use OperatorToken::{CloseCurly, OpenCurly};
+
+use crate::frontend::lex::token::{KeywordToken, OperatorToken};
+use crate::frontend::modifier::Modifiers;
+use crate::frontend::parse::{Parser, TraitDeclarationNode, TraitFunctionNode};
+
+impl<'a> Parser<'a> {
+    pub(crate) fn parse_trait_declaration(&mut self) -> crate::frontend::parse::Result<TraitDeclarationNode> {
+        self.parse_trait_declaration_with_modifiers(Modifiers(vec![]))
+    }
+
+    pub(crate) fn parse_trait_declaration_with_modifiers(
+        &mut self,
+        modifiers: Modifiers,
+    ) -> crate::frontend::parse::Result<TraitDeclarationNode> {
+        let token = self.consume_keyword(Trait)?;
+        let identifier = self.parse_type_identifier()?;
+        let type_parameters = self.parse_type_parameters()?;
+
+        self.consume_operator(OpenCurly)?;
+        let mut functions = vec![];
+        loop {
+            self.skip_new_line()?;
+            if self.current()?.is_operator(CloseCurly) {
+                break;
+            }
+            functions.push(self.parse_trait_function()?);
+        }
+        self.consume_operator(CloseCurly)?;
+
+        Ok(TraitDeclarationNode {
+            token,
+            identifier,
+            type_parameters,
+            functions,
+            modifiers,
+        })
+    }
+
+    // the block of a default function starts on the same line as its signature
+    fn parse_trait_function(&mut self) -> crate::frontend::parse::Result<TraitFunctionNode> {
+        let token = self.consume_keyword(Function)?;
+        let identifier = self.parse_identifier()?;
+        let (arguments, return_type) = self.parse_function_signature()?;
+
+        let block = if !self.is_eof() && self.current()?.is_operator(OpenCurly) {
+            Some(self.parse_block()?)
+        } else {
+            None
+        };
+
+        Ok(TraitFunctionNode {
+            token,
+            identifier,
+            arguments,
+            return_type,
+            block,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::Context;
+    use crate::frontend::lex::lex;
+    use crate::frontend::parse::{parse, TypeNode};
+
+    #[test]
+    fn empty_trait() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "trait Magic { }").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_trait_declaration();
+        assert_eq!(ctx.str_get(node.identifier.value()), "Magic");
+        assert_eq!(node.functions.len(), 0);
+        assert!(!node.modifiers.is_exported());
+    }
+
+    #[test]
+    fn trait_with_required_and_default_function() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, r#"export trait Equal<Rhs> {
+            function eq(other: Rhs) -> Bool
+            function ne(other: Rhs) -> Bool { return true }
+        }"#).unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_trait_declaration();
+        assert_eq!(ctx.str_get(node.identifier.value()), "Equal");
+        assert_eq!(ctx.str_get(node.type_parameters[0].value()), "Rhs");
+        assert!(node.modifiers.is_exported());
+        assert_eq!(node.functions.len(), 2);
+
+        let eq = &node.functions[0];
+        assert_eq!(ctx.str_get(eq.identifier.value()), "eq");
+        assert_eq!(eq.arguments.len(), 1);
+        assert!(matches!(eq.return_type.as_deref(), Some(TypeNode::Boolean(_))));
+        assert_eq!(eq.block, None);
+
+        let ne = &node.functions[1];
+        assert_eq!(ctx.str_get(ne.identifier.value()), "ne");
+        assert_eq!(ne.block.as_ref().unwrap().nodes.len(), 1);
+    }
+}
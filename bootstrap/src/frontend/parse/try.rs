@@ -0,0 +1,62 @@
+use crate::frontend::lex::token::OperatorToken::QuestionMark;
+use crate::frontend::parse::node::{Node, TryNode};
+use crate::frontend::parse::Parser;
+
+impl<'a> Parser<'a> {
+    // ? is the only postfix operator, everything else continues as infix
+    pub(crate) fn parse_postfix_or_infix(&mut self, left: Node) -> crate::frontend::parse::Result<Node> {
+        if self.current()?.is_operator(QuestionMark) {
+            return Ok(Node::Try(self.parse_try(left)?));
+        }
+        Ok(Node::Infix(self.parse_infix(left)?))
+    }
+
+    pub(crate) fn parse_try(&mut self, node: Node) -> crate::frontend::parse::Result<TryNode> {
+        let token = self.consume_operator(QuestionMark)?;
+        Ok(TryNode { token, node: Box::new(node) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::Context;
+    use crate::frontend::lex::lex;
+    use crate::frontend::parse::node::InfixOperator;
+    use crate::frontend::parse::parse;
+
+    #[test]
+    fn try_call() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "read()?").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_try();
+        let call = node.node.as_infix();
+        assert!(matches!(call.operator, InfixOperator::Call(_)));
+        assert_eq!(ctx.str_get(call.left.as_identifier().value()), "read");
+    }
+
+    #[test]
+    fn try_binds_before_calculation() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "1 + read()?").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+
+        let node = result[0].as_infix();
+        assert!(matches!(node.operator, InfixOperator::Add(_)));
+        assert!(node.right.is_try());
+    }
+
+    #[test]
+    fn try_method_call() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "file.read()?").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+
+        let node = result[0].as_try();
+        let call = node.node.as_infix();
+        assert!(matches!(call.operator, InfixOperator::Call(_)));
+        assert!(matches!(call.left.as_infix().operator, InfixOperator::AccessProperty(_)));
+    }
+}
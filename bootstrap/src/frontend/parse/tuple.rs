@@ -3,9 +3,9 @@ use SeparatorToken::Comma;
 use crate::frontend::lex::token::OperatorToken::CloseParen;
 use crate::frontend::lex::token::TokenKind::Separator;
 use crate::frontend::lex::token::{OperatorToken, SeparatorToken, Token};
-use crate::frontend::parse::node::TupleNode;
+use crate::frontend::parse::node::{InfixOperator, Node, TupleNode};
 use crate::frontend::parse::precedence::Precedence;
-use crate::frontend::parse::Parser;
+use crate::frontend::parse::{Error, Parser};
 
 impl<'a> Parser<'a> {
     pub(crate) fn parse_tuple(&mut self) -> crate::frontend::parse::Result<TupleNode> {
@@ -24,7 +24,12 @@ impl<'a> Parser<'a> {
             if self.current()?.is_operator(CloseParen) {
                 break;
             }
-            nodes.push(self.parse_node(Precedence::None)?);
+            let node = self.parse_node(Precedence::None)?;
+            // f(1, message = 'two') - once an argument got passed by name the following ones need a name too
+            if !is_named(&node) && nodes.last().is_some_and(is_named) {
+                return Err(Error::PositionalAfterNamed(node.token()));
+            }
+            nodes.push(node);
             self.consume_if(Separator(Comma))?;
         }
 
@@ -36,6 +41,10 @@ impl<'a> Parser<'a> {
     }
 }
 
+fn is_named(node: &Node) -> bool {
+    matches!(node, Node::Infix(infix) if matches!(infix.operator, InfixOperator::Assign(_)))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::common::Context;
@@ -43,7 +52,7 @@ mod tests {
     use crate::frontend::parse::node::LiteralNode::Number;
     use crate::frontend::parse::node::Node::{Identifier, Infix, Literal, Type};
     use crate::frontend::parse::node::{InfixNode, TypeNode};
-    use crate::frontend::parse::{parse, InfixOperator, LiteralNode};
+    use crate::frontend::parse::{parse, Error, InfixOperator, LiteralNode};
 
     #[test]
     fn empty_tuple() {
@@ -345,4 +354,14 @@ mod tests {
             panic!()
         };
     }
+
+    #[test]
+    fn positional_after_named() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "magic(message = 'one', 2)").unwrap();
+        let result = parse(&mut ctx, tokens);
+
+        let Err(Error::PositionalAfterNamed(token)) = result else { panic!() };
+        assert_eq!(ctx.str_get(token.value()), "2");
+    }
 }
@@ -1,14 +1,41 @@
 use crate::common::is_pascal_snake_case;
-use crate::frontend::lex::token::OperatorToken::{Arrow, CloseParen, Colon, OpenParen};
+use crate::frontend::lex::token::OperatorToken::{Ampersand, Arrow, CloseParen, Colon, DoubleColon, DoubleRightAngle, LeftAngle, OpenParen, QuestionMark, RightAngle};
 use crate::frontend::lex::token::SeparatorToken::Comma;
+use crate::frontend::lex::token::Token;
 use crate::frontend::lex::token::TokenKind::{Operator, Separator};
 use crate::frontend::parse::Error::InvalidType;
-use crate::frontend::parse::node::{TypeFunctionArgumentNode, TypeFunctionNode, TypeNode};
+use crate::frontend::parse::node::{IdentifierNode, TypeFunctionArgumentNode, TypeFunctionNode, TypeNode, TypeOptionalNode, TypePackageNode, TypeParameterizedNode, TypeParameterNode, TypePointerNode, TypeRecordFieldNode, TypeRecordNode, TypeTupleNode};
 use crate::frontend::parse::Parser;
 
 impl<'a> Parser<'a> {
     pub(crate) fn parse_type(&mut self) -> crate::frontend::parse::Result<TypeNode> {
+        let result = self.parse_non_optional_type()?;
+        if !self.is_eof() && self.current()?.is_operator(QuestionMark) {
+            let token = self.consume_operator(QuestionMark)?;
+            return Ok(TypeNode::Optional(TypeOptionalNode { token, r#type: Box::new(result) }));
+        }
+        Ok(result)
+    }
+
+    fn parse_non_optional_type(&mut self) -> crate::frontend::parse::Result<TypeNode> {
+        if self.current()?.is_operator(Ampersand) {
+            return Ok(TypeNode::Pointer(self.parse_pointer_type()?));
+        }
+
+        if self.current()?.is_operator(OpenParen) {
+            let token = self.consume_operator(OpenParen)?;
+            // the elements of a record have names
+            if !self.current()?.is_operator(CloseParen) && self.peek()?.is_operator(Colon) {
+                return Ok(TypeNode::Record(self.parse_record_type(token)?));
+            }
+            return Ok(TypeNode::Tuple(self.parse_tuple_type(token)?));
+        }
+
         let token = self.advance()?;
+        if !self.is_eof() && self.current()?.is_operator(DoubleColon) {
+            return Ok(TypeNode::Package(self.parse_package_type(token)?));
+        }
+
         let value = self.ctx.str_get(token.value());
         if !(is_pascal_snake_case(value) || value == "function") {
             return Err(InvalidType(token));
@@ -30,10 +57,123 @@ impl<'a> Parser<'a> {
             "Uint4" => Ok(TypeNode::Uint4(token)),
             "Uint8" => Ok(TypeNode::Uint8(token)),
             "Uint16" => Ok(TypeNode::Uint16(token)),
+            _ if !self.is_eof() && self.current()?.is_operator(LeftAngle) => Ok(TypeNode::Parameterized(TypeParameterizedNode {
+                token,
+                arguments: self.parse_type_arguments()?,
+            })),
             _ => Ok(TypeNode::Type(token)),
         }
     }
 
+    // std::collection::list::List<Number>
+    fn parse_package_type(&mut self, token: Token) -> crate::frontend::parse::Result<TypePackageNode> {
+        let mut package = vec![IdentifierNode(token)];
+        self.consume_operator(DoubleColon)?;
+        while self.peek().is_ok_and(|t| t.is_operator(DoubleColon)) {
+            package.push(IdentifierNode(self.advance()?));
+            self.consume_operator(DoubleColon)?;
+        }
+
+        let r#type = self.parse_non_optional_type()?;
+        if !matches!(r#type, TypeNode::Type(_) | TypeNode::Parameterized(_)) {
+            return Err(InvalidType(package.pop().unwrap().0));
+        }
+        Ok(TypePackageNode { package, r#type: Box::new(r#type) })
+    }
+
+    // List<Number>
+    pub(crate) fn parse_type_arguments(&mut self) -> crate::frontend::parse::Result<Vec<TypeNode>> {
+        self.consume_operator(LeftAngle)?;
+
+        let mut result = vec![];
+        loop {
+            if self.current()?.is_operator(RightAngle) || self.current()?.is_operator(DoubleRightAngle) {
+                self.consume_right_angle()?;
+                break;
+            }
+            result.push(self.parse_type()?);
+            self.consume_if(Separator(Comma))?;
+        }
+        Ok(result)
+    }
+
+    // function first<T>(l: List<T>) -> T
+    pub(crate) fn parse_type_parameters(&mut self) -> crate::frontend::parse::Result<Vec<TypeParameterNode>> {
+        if self.is_eof() || !self.current()?.is_operator(LeftAngle) {
+            return Ok(vec![]);
+        }
+        self.consume_operator(LeftAngle)?;
+
+        let mut result = vec![];
+        loop {
+            if self.current()?.is_operator(RightAngle) {
+                self.consume_operator(RightAngle)?;
+                break;
+            }
+            let identifier = self.parse_type_identifier()?;
+            let bound = if self.current()?.is_operator(Colon) {
+                self.consume_operator(Colon)?;
+                Some(self.parse_type()?)
+            } else {
+                None
+            };
+            result.push(TypeParameterNode { identifier, bound });
+            self.consume_if(Separator(Comma))?;
+        }
+        Ok(result)
+    }
+
+    // the lexer turns the end of List<List<T>> into a single >>, which closes two type argument lists
+    fn consume_right_angle(&mut self) -> crate::frontend::parse::Result<()> {
+        let current = self.tokens.last_mut().unwrap();
+        if current.kind == Operator(DoubleRightAngle) {
+            current.kind = Operator(RightAngle);
+            return Ok(());
+        }
+        self.consume_operator(RightAngle)?;
+        Ok(())
+    }
+
+    // (Number, String)
+    pub(crate) fn parse_tuple_type(&mut self, token: Token) -> crate::frontend::parse::Result<TypeTupleNode> {
+        let mut types = vec![];
+        loop {
+            if self.current()?.is_operator(CloseParen) {
+                self.consume_operator(CloseParen)?;
+                break;
+            }
+            types.push(self.parse_type()?);
+            self.consume_if(Separator(Comma))?;
+        }
+        Ok(TypeTupleNode { token, types })
+    }
+
+    // (x: Number, y: Number)
+    pub(crate) fn parse_record_type(&mut self, token: Token) -> crate::frontend::parse::Result<TypeRecordNode> {
+        let mut fields = vec![];
+        loop {
+            if self.current()?.is_operator(CloseParen) {
+                self.consume_operator(CloseParen)?;
+                break;
+            }
+            let identifier = self.parse_identifier()?;
+            self.consume_operator(Colon)?;
+            let r#type = self.parse_type()?;
+            fields.push(TypeRecordFieldNode { identifier, r#type });
+            self.consume_if(Separator(Comma))?;
+        }
+        Ok(TypeRecordNode { token, fields })
+    }
+
+    // &Point - only external functions take pointers
+    pub(crate) fn parse_pointer_type(
+        &mut self,
+    ) -> crate::frontend::parse::Result<TypePointerNode> {
+        let token = self.consume_operator(Ampersand)?;
+        let r#type = Box::new(self.parse_type()?);
+        Ok(TypePointerNode { token, r#type })
+    }
+
     pub(crate) fn parse_function_type(
         &mut self,
     ) -> crate::frontend::parse::Result<TypeFunctionNode> {
@@ -110,6 +250,35 @@ mod tests {
         assert_eq!(ctx.str_get(token.value()), "Point");
     }
 
+    #[test]
+    fn optional_type() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "Point?").unwrap();
+        let mut parser = Parser::new(&mut ctx, tokens);
+        let result = parser.parse_type().unwrap();
+        let TypeNode::Optional(node) = result else {
+            panic!()
+        };
+        let TypeNode::Type(token) = *node.r#type else {
+            panic!()
+        };
+        assert_eq!(ctx.str_get(token.value()), "Point");
+    }
+
+    #[test]
+    fn optional_function_result_type() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "function() -> Number?").unwrap();
+        let mut parser = Parser::new(&mut ctx, tokens);
+        let result = parser.parse_type().unwrap();
+        let TypeNode::Function(node) = result else {
+            panic!()
+        };
+        let Some(TypeNode::Optional(_)) = node.return_type.as_deref() else {
+            panic!()
+        };
+    }
+
     #[test]
     fn type_boolean() {
         let mut ctx = Context::testing();
@@ -349,4 +518,83 @@ mod tests {
             panic!()
         };
     }
+
+    #[test]
+    fn parameterized_type() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "Map<String, List<Number>>").unwrap();
+        let mut parser = Parser::new(&mut ctx, tokens);
+        let result = parser.parse_type().unwrap();
+
+        let TypeNode::Parameterized(node) = result else {
+            panic!()
+        };
+        assert_eq!(ctx.str_get(node.token.value()), "Map");
+        assert_eq!(node.arguments.len(), 2);
+        let TypeNode::String(_) = &node.arguments[0] else {
+            panic!()
+        };
+
+        let TypeNode::Parameterized(inner) = &node.arguments[1] else {
+            panic!()
+        };
+        assert_eq!(ctx.str_get(inner.token.value()), "List");
+        let [TypeNode::Number(_)] = inner.arguments.as_slice() else {
+            panic!()
+        };
+    }
+
+    #[test]
+    fn tuple_type() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "(Number, (String, Bool))").unwrap();
+        let mut parser = Parser::new(&mut ctx, tokens);
+        let result = parser.parse_type().unwrap();
+
+        let TypeNode::Tuple(node) = result else {
+            panic!()
+        };
+        let [TypeNode::Number(_), TypeNode::Tuple(inner)] = node.types.as_slice() else {
+            panic!()
+        };
+        let [TypeNode::String(_), TypeNode::Boolean(_)] = inner.types.as_slice() else {
+            panic!()
+        };
+    }
+
+    #[test]
+    fn record_type() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "(x: Number, label: String)").unwrap();
+        let mut parser = Parser::new(&mut ctx, tokens);
+        let result = parser.parse_type().unwrap();
+
+        let TypeNode::Record(node) = result else {
+            panic!()
+        };
+        assert_eq!(node.fields.len(), 2);
+        assert_eq!(ctx.str_get(node.fields[0].identifier.value()), "x");
+        let TypeNode::Number(_) = node.fields[0].r#type else { panic!() };
+        assert_eq!(ctx.str_get(node.fields[1].identifier.value()), "label");
+        let TypeNode::String(_) = node.fields[1].r#type else { panic!() };
+    }
+
+    #[test]
+    fn type_of_package() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "std::collection::list::List<Number>").unwrap();
+        let mut parser = Parser::new(&mut ctx, tokens);
+        let result = parser.parse_type().unwrap();
+        let TypeNode::Package(node) = result else {
+            panic!()
+        };
+        let package = node.package.iter().map(|p| ctx.str_get(p.value())).collect::<Vec<_>>();
+        assert_eq!(package, vec!["std", "collection", "list"]);
+
+        let TypeNode::Parameterized(r#type) = *node.r#type else {
+            panic!()
+        };
+        assert_eq!(ctx.str_get(r#type.token.value()), "List");
+        assert_eq!(r#type.arguments.len(), 1);
+    }
 }
@@ -1,6 +1,9 @@
 use crate::frontend::lex::token::KeywordToken::Type;
+use crate::frontend::lex::token::OperatorToken::{Equal, OpenParen, Pipe};
+use crate::frontend::lex::token::SeparatorToken::NewLine;
+use crate::frontend::lex::token::TokenKind::Operator;
 use crate::frontend::modifier::Modifiers;
-use crate::frontend::parse::{Parser, TypeDeclarationNode};
+use crate::frontend::parse::{Parser, TupleNode, TypeDeclarationNode, TypeVariantNode};
 
 impl<'a> Parser<'a> {
     pub(crate) fn parse_type_declaration(
@@ -15,14 +18,59 @@ impl<'a> Parser<'a> {
     ) -> crate::frontend::parse::Result<TypeDeclarationNode> {
         let token = self.consume_keyword(Type)?;
         let identifier = self.parse_type_identifier()?;
+        let type_parameters = self.parse_type_parameters()?;
+
+        if let Some(equal) = self.consume_if(Operator(Equal))? {
+            return Ok(TypeDeclarationNode {
+                token,
+                identifier,
+                type_parameters,
+                properties: TupleNode { token: equal, nodes: vec![] },
+                variants: self.parse_type_variants()?,
+                modifiers,
+            });
+        }
+
         let properties = self.parse_tuple()?;
         Ok(TypeDeclarationNode {
             token,
             identifier,
+            type_parameters,
             properties,
+            variants: vec![],
             modifiers,
         })
     }
+
+    // Circle(radius: Number) | Square(side: Number) | Empty, every variant can start a new line with its |
+    fn parse_type_variants(&mut self) -> crate::frontend::parse::Result<Vec<TypeVariantNode>> {
+        let mut result = vec![];
+        self.skip_new_line()?;
+        self.consume_if(Operator(Pipe))?;
+
+        loop {
+            self.skip_new_line()?;
+            let identifier = self.parse_type_identifier()?;
+            let properties = if !self.is_eof() && self.current()?.is_operator(OpenParen) {
+                self.parse_tuple()?
+            } else {
+                TupleNode { token: identifier.0.clone(), nodes: vec![] }
+            };
+            result.push(TypeVariantNode { identifier, properties });
+
+            if !self.is_pipe_ahead() {
+                return Ok(result);
+            }
+            self.skip_new_line()?;
+            self.consume_operator(Pipe)?;
+        }
+    }
+
+    fn is_pipe_ahead(&self) -> bool {
+        self.tokens.iter().rev()
+            .find(|token| !token.is_separator(NewLine))
+            .is_some_and(|token| token.is_operator(Pipe))
+    }
 }
 
 #[cfg(test)]
@@ -133,4 +181,66 @@ mod tests {
 
         assert!(decl.modifiers.is_exported());
     }
+
+    #[test]
+    fn parse_type_declaration_with_type_parameters() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "type Pair<L, R>(left: L, right: R)").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let decl = result[0].as_type_declaration();
+        assert_eq!(ctx.str_get(decl.identifier.value()), "Pair");
+        assert_eq!(decl.type_parameters.len(), 2);
+        assert_eq!(ctx.str_get(decl.type_parameters[0].value()), "L");
+        assert_eq!(ctx.str_get(decl.type_parameters[1].value()), "R");
+        assert_eq!(decl.properties.nodes.len(), 2);
+    }
+
+    #[test]
+    fn parse_type_declaration_with_variants() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "type Shape = Circle(radius: Number) | Square(side: Number) | Empty").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let decl = result[0].as_type_declaration();
+        assert_eq!(ctx.str_get(decl.identifier.value()), "Shape");
+        assert_eq!(decl.properties.nodes, vec![]);
+        assert_eq!(decl.variants.len(), 3);
+
+        assert_eq!(ctx.str_get(decl.variants[0].identifier.value()), "Circle");
+        let prop = decl.variants[0].properties.nodes[0].as_infix();
+        assert_eq!(ctx.str_get(prop.left.as_identifier().value()), "radius");
+        assert!(matches!(prop.right.as_type(), TypeNode::Number(_)));
+
+        assert_eq!(ctx.str_get(decl.variants[1].identifier.value()), "Square");
+        assert_eq!(decl.variants[1].properties.nodes.len(), 1);
+
+        assert_eq!(ctx.str_get(decl.variants[2].identifier.value()), "Empty");
+        assert_eq!(decl.variants[2].properties.nodes, vec![]);
+    }
+
+    #[test]
+    fn parse_multiline_type_declaration_with_variants() {
+        let mut ctx = Context::testing();
+        let tokens = lex(
+            &mut ctx,
+            r#"type Color_Channel =
+            | Red
+            | Green
+            | Blue
+        let x = 1"#,
+        )
+        .unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 2);
+
+        let decl = result[0].as_type_declaration();
+        assert_eq!(decl.variants.len(), 3);
+        assert_eq!(ctx.str_get(decl.variants[0].identifier.value()), "Red");
+        assert_eq!(ctx.str_get(decl.variants[1].identifier.value()), "Green");
+        assert_eq!(ctx.str_get(decl.variants[2].identifier.value()), "Blue");
+        assert!(result[1].is_declare_variable());
+    }
 }
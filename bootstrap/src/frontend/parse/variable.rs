@@ -1,9 +1,12 @@
 use std::rc::Rc;
 
-use KeywordToken::Let;
+use KeywordToken::{Const, Let};
 
 use crate::frontend::lex::token::{KeywordToken, OperatorToken};
-use crate::frontend::parse::node::VariableDeclarationNode;
+use crate::frontend::lex::token::SeparatorToken::Comma;
+use crate::frontend::lex::token::TokenKind::Separator;
+use crate::frontend::modifier::Modifiers;
+use crate::frontend::parse::node::{TupleDeclarationNode, VariableDeclarationNode};
 use crate::frontend::parse::precedence::Precedence;
 use crate::frontend::parse::Parser;
 
@@ -11,14 +14,19 @@ impl<'a> Parser<'a> {
     pub(crate) fn parse_variable_declaration(
         &mut self,
     ) -> crate::frontend::parse::Result<VariableDeclarationNode> {
-        let token = self.consume_keyword(Let)?;
+        let token = if self.current()?.is_keyword(Const) {
+            self.consume_keyword(Const)?
+        } else {
+            self.consume_keyword(Let)?
+        };
         let identifier = self.parse_identifier()?;
 
-        let r#type = if self.current()?.is_operator(OperatorToken::Colon) {
+        let (r#type, modifiers) = if self.current()?.is_operator(OperatorToken::Colon) {
             self.advance()?;
-            Some(self.parse_type()?)
+            let modifiers = self.parse_readonly_modifier()?;
+            (Some(self.parse_type()?), modifiers)
         } else {
-            None
+            (None, Modifiers(vec![]))
         };
 
         self.consume_operator(OperatorToken::Equal)?;
@@ -29,6 +37,33 @@ impl<'a> Parser<'a> {
             identifier,
             node: value,
             r#type,
+            modifiers,
+        })
+    }
+
+    pub(crate) fn parse_tuple_declaration(
+        &mut self,
+    ) -> crate::frontend::parse::Result<TupleDeclarationNode> {
+        let token = self.consume_keyword(Let)?;
+        self.consume_operator(OperatorToken::OpenParen)?;
+
+        let mut identifiers = vec![];
+        loop {
+            if self.current()?.is_operator(OperatorToken::CloseParen) {
+                self.consume_operator(OperatorToken::CloseParen)?;
+                break;
+            }
+            identifiers.push(self.parse_identifier()?);
+            self.consume_if(Separator(Comma))?;
+        }
+
+        self.consume_operator(OperatorToken::Equal)?;
+        let value = Rc::new(self.parse_node(Precedence::None)?);
+
+        Ok(TupleDeclarationNode {
+            token,
+            identifiers,
+            node: value,
         })
     }
 }
@@ -39,8 +74,9 @@ mod tests {
 
     use crate::common::Context;
     use crate::frontend::lex::lex;
-    use crate::frontend::parse::node::Node::Literal;
-    use crate::frontend::parse::node::{LiteralNode, TypeNode};
+    use crate::frontend::lex::token::KeywordToken;
+    use crate::frontend::parse::node::Node::{Infix, Literal};
+    use crate::frontend::parse::node::{InfixNode, InfixOperator, LiteralNode, TypeNode};
     use crate::frontend::parse::parse;
 
     #[test]
@@ -61,6 +97,22 @@ mod tests {
         assert_eq!(ctx.str_get(result.value()), "Elodie");
     }
 
+    #[test]
+    fn const_without_type_number() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "const value = 9924").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_declare_variable();
+        assert!(node.token.is_keyword(KeywordToken::Const));
+        assert_eq!(ctx.str_get(node.identifier.value()), "value");
+
+        let Literal(LiteralNode::Number(_)) = &node.node.deref() else {
+            panic!()
+        };
+    }
+
     #[test]
     fn let_with_type_string() {
         let mut ctx = Context::testing();
@@ -81,6 +133,22 @@ mod tests {
         assert_eq!(ctx.str_get(result.value()), "Elodie");
     }
 
+    #[test]
+    fn let_with_readonly_type() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "let config: readonly Config = load()").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_declare_variable();
+        assert_eq!(ctx.str_get(node.identifier.value()), "config");
+        assert!(node.modifiers.is_readonly());
+
+        let Some(TypeNode::Type(_)) = node.r#type else {
+            panic!()
+        };
+    }
+
     #[test]
     fn let_without_type_number() {
         let mut ctx = Context::testing();
@@ -115,4 +183,21 @@ mod tests {
         };
         assert_eq!(result.value(), false);
     }
+
+    #[test]
+    fn let_tuple() {
+        let mut ctx = Context::testing();
+        let tokens = lex(&mut ctx, "let (number, name) = pair()").unwrap();
+        let result = parse(&mut ctx, tokens).unwrap();
+        assert_eq!(result.len(), 1);
+
+        let node = result[0].as_tuple_declaration();
+        assert_eq!(node.identifiers.len(), 2);
+        assert_eq!(ctx.str_get(node.identifiers[0].value()), "number");
+        assert_eq!(ctx.str_get(node.identifiers[1].value()), "name");
+
+        let Infix(InfixNode { operator: InfixOperator::Call(_), .. }) = node.node.deref() else {
+            panic!()
+        };
+    }
 }
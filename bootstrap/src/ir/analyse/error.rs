@@ -2,10 +2,72 @@ use crate::common::Span;
 
 #[derive(Debug, PartialEq)]
 pub enum Error {
+    Argument(Box<ArgumentError>),
+    Assign(Box<AssignError>),
+    Callback(CallbackError),
+    Defer(DeferError),
+    External(Box<ExternalError>),
     InvalidLiteral(InvalidLiteralError),
+    Match(MatchError),
+    Operator(Box<OperatorError>),
+    Optional(OptionalError),
+    Range(RangeError),
+    Readonly(Box<ReadonlyError>),
+    Record(Box<RecordError>),
+    Result(ResultError),
+    Trait(Box<TraitError>),
+    Tuple(TupleError),
     TypeMissMatch(TypeMissMatchError),
     Undefined(UndefinedError),
+    Visibility(Box<VisibilityError>),
+}
+
+// arguments of a call which do not line up with the arguments the function declares
+#[derive(Debug, PartialEq)]
+pub enum ArgumentError {
+    // a default value is the same at every call, so it is made of literals only
+    DefaultNotConstant { function: String, argument: String, span: Span },
+    // the arguments after one with a default value need one as well
+    DefaultMissing { function: String, argument: String, span: Span },
+    Duplicate { function: String, argument: String, span: Span },
+    // neither passed nor declared with a default value
+    Missing { function: String, argument: String, span: Span },
+    // a named function takes all of its arguments by name
+    Positional { function: String, span: Span },
+    Unknown { function: String, argument: String, span: Span },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AssignError {
+    // a lambda only holds a copy of the variables it captures
+    CapturedVariable { variable: String, span: Span },
+    // declaration points at the const, argument or match binding
+    ImmutableVariable { variable: String, declaration: Span, span: Span },
+    // expected is the type the variable got declared or inferred with
+    TypeMissMatch { variable: String, expected: String, got: String, span: Span },
+}
+
+// a function passed to an external function which can not be turned into a plain c function pointer
+#[derive(Debug, PartialEq)]
+pub enum CallbackError {
+    CapturingClosure { variable: String, span: Span },
+}
+
+// a break, continue or return which would leave a defer block before it is done
+#[derive(Debug, PartialEq)]
+pub enum DeferError {
+    Break { span: Span },
+    Continue { span: Span },
+    // value? returns from the function as well
+    Return { span: Span },
+}
 
+// a value which crosses to c but has no c representation
+#[derive(Debug, PartialEq)]
+pub enum ExternalError {
+    Argument { function: String, argument: String, r#type: String, span: Span },
+    Return { function: String, r#type: String, span: Span },
+    Variable { r#type: String, variable: String, span: Span },
 }
 
 #[derive(Debug, PartialEq)]
@@ -24,12 +86,128 @@ pub enum InvalidLiteralError {
     Uint16 { got: String, span: Span },
 }
 
+// a match whose arms do not fit the variants of the sum type it matches on
+#[derive(Debug, PartialEq)]
+pub enum MatchError {
+    BindingCountMissMatch { variant: String, expected: usize, got: usize, span: Span },
+    // the variants without an arm, in the order of their declaration
+    NonExhaustive { r#type: String, missing: Vec<String>, span: Span },
+    NotASumType { r#type: String, span: Span },
+    // the variant is already taken by an earlier arm or by otherwise
+    UnreachableArm { variant: String, span: Span },
+    UnknownVariant { r#type: String, variant: String, span: Span },
+}
+
+// an operator function which no operator calls, or an operator used on a type which does not define it
+#[derive(Debug, PartialEq)]
+pub enum OperatorError {
+    // binary operators and index take the other value as their only argument
+    ArgumentCountMissMatch { function: String, expected: usize, got: usize, span: Span },
+    // operator functions only exist inside of a define
+    OutsideOfDefine { function: String, span: Span },
+    // comparisons return Bool
+    ReturnTypeMissMatch { function: String, expected: String, got: String, span: Span },
+    Undefined { r#type: String, operator: String, span: Span },
+    UnknownOperator { function: String, span: Span },
+}
+
+// a value which may be none, used where a value is required
+// comparing the variable against none first narrows it to its value
+#[derive(Debug, PartialEq)]
+pub enum OptionalError {
+    Access { variable: String, span: Span },
+    // passed to a parameter which does not take none
+    Argument { function: String, got: String, span: Span },
+    Calculation { got: String, span: Span },
+}
+
+// a for loop over bounds which do not count in one number type
+#[derive(Debug, PartialEq)]
+pub enum RangeError {
+    // start, end and step have to share the type of the first bound which is not a literal
+    BoundTypeMissMatch { expected: String, got: String, span: Span },
+    NotANumber { got: String, span: Span },
+    ZeroStep { span: Span },
+}
+
+// a change of a value reached through a readonly path, or the value leaving it to where it could be changed
+#[derive(Debug, PartialEq)]
+pub enum ReadonlyError {
+    AssignedField { variable: String, field: String, span: Span },
+    // the variable can only keep values which are not readonly
+    AssignedToMutable { variable: String, span: Span },
+    // only methods of a readonly define can be called
+    CalledMutatingMethod { variable: String, method: String, span: Span },
+    PassedAsMutable { function: String, span: Span },
+    Returned { span: Span },
+}
+
+// a record used as a record or type with other fields
+#[derive(Debug, PartialEq)]
+pub enum RecordError {
+    DuplicateField { field: String, span: Span },
+    FieldTypeMissMatch { field: String, expected: String, got: String, span: Span },
+    MissingField { field: String, r#type: String, span: Span },
+    UnknownField { field: String, r#type: String, span: Span },
+}
+
+// a value? which can not return the error of the value from the function it is used in
+#[derive(Debug, PartialEq)]
+pub enum ResultError {
+    NotAResult { got: String, span: Span },
+    OutsideOfFunction { span: Span },
+    // the function has to return a result with the same error type
+    ReturnTypeMissMatch { expected: String, got: String, span: Span },
+}
+
+// a type which does not provide what a trait it gets used as requires
+#[derive(Debug, PartialEq)]
+pub enum TraitError {
+    // the trait has no default for the method, so the type has to define it
+    MissingMethod { r#trait: String, r#type: String, method: String, span: Span },
+    MethodSignatureMissMatch { method: String, expected: String, got: String, span: Span },
+    NotATrait { r#type: String, span: Span },
+    NotImplemented { r#trait: String, r#type: String, span: Span },
+}
+
+// a tuple used with a different number of elements than it has
+#[derive(Debug, PartialEq)]
+pub enum TupleError {
+    // let (a, b) = value takes exactly one variable for each element
+    ElementCountMissMatch { expected: usize, got: usize, span: Span },
+    IndexOutOfBounds { index: usize, size: usize, span: Span },
+    NotATuple { got: String, span: Span },
+}
+
 #[derive(Debug, PartialEq)]
 pub enum TypeMissMatchError {
-    DeclaredTypeMissMatch { expected: String, got: String, span: Span }
+    DeclaredTypeMissMatch { expected: String, got: String, span: Span },
+    // an if, loop or match bound to a variable which does not end with a value of one type
+    NoValue { span: Span },
+    // the type parameter got bound to `expected` by an earlier argument of the same call
+    TypeArgumentMissMatch { parameter: String, expected: String, got: String, span: Span },
+    // a generic value like the result of empty() which no argument bound, the variable has to declare its type
+    TypeArgumentMissing { r#type: String, span: Span },
 }
 
 #[derive(Debug, PartialEq)]
 pub enum UndefinedError {
-    UndefinedVariable { variable: String, span: Span }
+    UndefinedFunction { function: String, span: Span },
+    UndefinedLabel { label: String, span: Span },
+    // a break or continue without a loop around it
+    UndefinedLoop { span: Span },
+    UndefinedMethod { r#type: String, method: String, span: Span },
+    UndefinedPackage { package: String, span: Span },
+    UndefinedType { r#type: String, span: Span },
+    UndefinedTypeVariable { r#type: String, variable: String, span: Span },
+    UndefinedVariable { variable: String, span: Span },
+}
+
+// access to a package member which was not marked with `export`
+// `span` points at the use, `declared_at` at the declaration of the member
+#[derive(Debug, PartialEq)]
+pub enum VisibilityError {
+    FunctionNotExported { function: String, span: Span, declared_at: Span },
+    PackageNotExported { package: String, span: Span, declared_at: Span },
+    TypeNotExported { r#type: String, span: Span, declared_at: Span },
 }
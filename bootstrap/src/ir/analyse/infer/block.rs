@@ -6,11 +6,14 @@ impl<'a> Inferrer<'a> {
     pub(crate) fn block(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
         if let Node::Block(block) = &mut node.node {
             // self.scope.enter();
+            let narrowed = self.narrowed.len();
 
             for node in &mut block.nodes {
                 self.node(node)?;
             }
 
+            // narrowing ends with the block it happened in
+            self.narrowed.truncate(narrowed);
             // self.scope.leave();
             Ok(())
         } else {
@@ -20,11 +23,13 @@ impl<'a> Inferrer<'a> {
 
     pub(crate) fn block_node(&mut self, node: &mut TypeBlockNode) -> crate::ir::analyse::Result<()> {
         // self.scope.enter();
+        let narrowed = self.narrowed.len();
 
         for node in &mut node.nodes {
             self.node(node)?;
         }
 
+        self.narrowed.truncate(narrowed);
         // self.scope.leave();
         Ok(())
     }
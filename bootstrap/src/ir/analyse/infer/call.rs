@@ -1,9 +1,52 @@
+use crate::common::node::Node;
 use crate::ir::analyse::infer::Inferrer;
 use crate::ir::analyse::TypedTreeNode;
 
 impl<'a> Inferrer<'a> {
+    pub(crate) fn call_function(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::CallFunction(call) = &mut node.node {
+            for argument in &mut call.arguments {
+                self.node(argument)?;
+            }
+            self.value_required_arguments(call.function, 0, &call.arguments)
+        } else {
+            panic!("not call function")
+        }
+    }
+
+    pub(crate) fn call_function_with_lambda(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::CallFunctionWithLambda(call) = &mut node.node {
+            for argument in &mut call.arguments {
+                self.node(argument)?;
+            }
+            self.value_required_arguments(call.function, 0, &call.arguments)?;
+            self.node(&mut call.lambda)
+        } else {
+            panic!("not call function with lambda")
+        }
+    }
+
+    pub(crate) fn call_function_of_object(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::CallFunctionOfObject(call) = &mut node.node {
+            self.value_required(call.object, &node.span)?;
+            for argument in &mut call.arguments {
+                self.node(argument)?;
+            }
+            // the object is the first argument of the method
+            self.value_required_arguments(call.function, 1, &call.arguments)
+        } else {
+            panic!("not call function of object")
+        }
+    }
+
     pub(crate) fn call_function_of_package(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
-        // FIXME TODO
-        Ok(())
+        if let Node::CallFunctionOfPackage(call) = &mut node.node {
+            for argument in &mut call.arguments {
+                self.node(argument)?;
+            }
+            self.value_required_arguments(call.function, 0, &call.arguments)
+        } else {
+            panic!("not call function of package")
+        }
     }
 }
\ No newline at end of file
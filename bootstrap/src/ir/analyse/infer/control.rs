@@ -1,6 +1,8 @@
 use crate::common::Inferred;
-use crate::common::node::Node;
+use crate::common::node::{CompareOperator, Node};
 use crate::ir::analyse::infer::Inferrer;
+use crate::ir::analyse::infer::optional::narrowing;
+use crate::ir::analyse::pre::Pre;
 use crate::ir::analyse::TypedTreeNode;
 
 impl<'a> Inferrer<'a> {
@@ -16,32 +18,126 @@ impl<'a> Inferrer<'a> {
         }
     }
 
+    pub(crate) fn defer(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::Defer(defer) = &mut node.node {
+            self.block_node(defer.nodes.get_mut())
+        } else {
+            panic!("not defer")
+        }
+    }
+
     pub(crate) fn r#loop(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
         if let Node::Loop(r#loop) = &mut node.node {
             self.block_node(&mut r#loop.nodes.get_mut())?;
 
-            node.inferred = r#loop.nodes.borrow().nodes.last().map(|n| n.inferred.clone()).unwrap_or(Inferred::Unit);
+            node.inferred = Pre::loop_inferred(&r#loop.nodes.borrow(), r#loop.label);
             Ok(())
         } else {
             panic!("not loop")
         }
     }
 
+    pub(crate) fn range_loop(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::RangeLoop(range_loop) = &mut node.node {
+            self.node(&mut range_loop.start)?;
+            self.node(&mut range_loop.end)?;
+            if let Some(step) = &mut range_loop.step {
+                self.node(step)?;
+            }
+
+            if let Some(type_id) = self.symbol_table.variable(range_loop.variable).inferred.type_id() {
+                self.symbol_table[range_loop.variable].set_type_id(type_id);
+            }
+            self.block_node(range_loop.nodes.get_mut())?;
+            Ok(())
+        } else {
+            panic!("not range loop")
+        }
+    }
+
+    pub(crate) fn iterator_loop(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::IteratorLoop(iterator_loop) = &mut node.node {
+            self.node(&mut iterator_loop.value)?;
+            if let Some(iter) = &mut iterator_loop.iter {
+                self.node(iter)?;
+            }
+            if let Some(next) = &mut iterator_loop.next {
+                self.node(next)?;
+            }
+
+            for variable in [iterator_loop.iterable, iterator_loop.iterator, iterator_loop.variable] {
+                if let Some(type_id) = self.symbol_table.variable(variable).inferred.type_id() {
+                    self.symbol_table[variable].set_type_id(type_id);
+                }
+            }
+            self.block_node(iterator_loop.nodes.get_mut())?;
+            Ok(())
+        } else {
+            panic!("not iterator loop")
+        }
+    }
+
+    pub(crate) fn r#match(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::Match(r#match) = &mut node.node {
+            self.node(&mut r#match.node)?;
+
+            for arm in r#match.arms.iter_mut() {
+                for binding in arm.bindings.iter() {
+                    if let Some(type_id) = self.symbol_table.variable(*binding).inferred.type_id() {
+                        self.symbol_table[*binding].set_type_id(type_id);
+                    }
+                }
+                self.block_node(arm.nodes.get_mut())?;
+            }
+
+            node.inferred = Pre::match_inferred(&r#match.arms);
+            Ok(())
+        } else {
+            panic!("not match")
+        }
+    }
+
+    pub(crate) fn r#try(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::Try(r#try) = &mut node.node {
+            self.node(&mut r#try.node)?;
+
+            // the value of Ok is the first type argument of the result
+            if let Inferred::Parameterized(_, arguments) = &r#try.node.inferred {
+                node.inferred = arguments[0].clone();
+            }
+            Ok(())
+        } else {
+            panic!("not try")
+        }
+    }
+
     pub(crate) fn r#if(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
         if let Node::If(r#if) = &mut node.node {
-            self.block_node(&mut r#if.then.get_mut())?;
-
-            let inferred = r#if.then.borrow().nodes.last().map(|n| n.inferred.clone()).unwrap_or(Inferred::Unit);
+            let narrowing = narrowing(self.symbol_table, &r#if.condition);
 
-            node.inferred = inferred;
+            // x != none holds a value in then, x == none in otherwise
+            let mark = self.narrowed.len();
+            if let Some((variable, CompareOperator::NotEqual)) = narrowing {
+                self.narrowed.push(variable);
+            }
+            self.block_node(&mut r#if.then.get_mut())?;
+            self.narrowed.truncate(mark);
 
+            if let Some((variable, CompareOperator::Equal)) = narrowing {
+                self.narrowed.push(variable);
+            }
             if let Some(cell) = &mut r#if.otherwise {
                 self.block_node(cell.get_mut())?;
+                self.narrowed.truncate(mark);
+            } else if !Pre::returns(&r#if.then.borrow()) {
+                // only a then which returns keeps the narrowing for the rest of the enclosing block
+                self.narrowed.truncate(mark);
             }
 
+            node.inferred = Pre::if_inferred(r#if);
             Ok(())
         } else {
             panic!("not if")
         }
     }
-}
\ No newline at end of file
+}
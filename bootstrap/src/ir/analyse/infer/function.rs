@@ -0,0 +1,94 @@
+use crate::common::node::Node;
+use crate::ir::analyse::infer::Inferrer;
+use crate::ir::analyse::TypedTreeNode;
+
+impl<'a> Inferrer<'a> {
+    pub(crate) fn declare_function(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::DeclareFunction(function) = &mut node.node {
+            for argument in &function.arguments {
+                if let Some(type_id) = self.symbol_table.variable(*argument).inferred.type_id() {
+                    self.symbol_table[*argument].set_type_id(type_id);
+                }
+            }
+
+            let narrowed = self.narrowed.len();
+            for node in &mut function.nodes {
+                self.node(node)?;
+            }
+            self.narrowed.truncate(narrowed);
+            Ok(())
+        } else {
+            panic!("not declare function")
+        }
+    }
+
+    pub(crate) fn declare_lambda(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::DeclareLambda(lambda) = &mut node.node {
+            for argument in &lambda.arguments {
+                if let Some(type_id) = self.symbol_table.variable(*argument).inferred.type_id() {
+                    self.symbol_table[*argument].set_type_id(type_id);
+                }
+            }
+
+            let narrowed = self.narrowed.len();
+            for node in &mut lambda.nodes {
+                self.node(node)?;
+            }
+            self.narrowed.truncate(narrowed);
+            Ok(())
+        } else {
+            panic!("not declare lambda")
+        }
+    }
+
+    pub(crate) fn declare_package(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::DeclarePackage(package) = &mut node.node {
+            for node in &mut package.packages {
+                self.node(node)?;
+            }
+            for node in &mut package.functions {
+                self.node(node)?;
+            }
+            for node in &mut package.definitions {
+                self.node(node)?;
+            }
+            Ok(())
+        } else {
+            panic!("not declare package")
+        }
+    }
+
+    pub(crate) fn define_type(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::DefineType(define) = &mut node.node {
+            for node in &mut define.functions {
+                self.node(node)?;
+            }
+            Ok(())
+        } else {
+            panic!("not define type")
+        }
+    }
+
+    pub(crate) fn instantiate_type(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::InstantiateType(instantiate) = &mut node.node {
+            for argument in &mut instantiate.arguments {
+                self.node(&mut argument.value)?;
+            }
+            Ok(())
+        } else {
+            panic!("not instantiate type")
+        }
+    }
+
+    pub(crate) fn r#return(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::ReturnFromFunction(r#return) = &mut node.node {
+            if let Some(value) = &mut r#return.node {
+                self.node(value)?;
+                node.inferred = value.inferred.clone();
+            }
+            Ok(())
+        } else {
+            panic!("not return")
+        }
+    }
+}
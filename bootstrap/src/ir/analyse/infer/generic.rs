@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use crate::common::{Inferred, StringTableId};
+
+// a type parameter which got bound to two different types at the same call site
+#[derive(Debug, PartialEq)]
+pub(crate) struct TypeArgumentConflict {
+    // the position of the argument which got bound to the other type
+    pub(crate) argument: usize,
+    pub(crate) parameter: StringTableId,
+    pub(crate) expected: Inferred,
+    pub(crate) got: Inferred,
+}
+
+// binds the type parameters of the parameters to the types of the arguments at a call site,
+// the first argument which binds a type parameter decides its type argument
+pub(crate) fn infer_type_arguments(parameters: &[Inferred], arguments: &[Inferred]) -> Result<HashMap<StringTableId, Inferred>, TypeArgumentConflict> {
+    let mut result = HashMap::new();
+    for (idx, (parameter, argument)) in parameters.iter().zip(arguments).enumerate() {
+        bind(parameter, argument, &mut result).map_err(|conflict| TypeArgumentConflict { argument: idx, ..conflict })?;
+    }
+    Ok(result)
+}
+
+fn bind(parameter: &Inferred, argument: &Inferred, result: &mut HashMap<StringTableId, Inferred>) -> Result<(), TypeArgumentConflict> {
+    match (parameter, argument) {
+        // a value which is still generic itself, like the result of empty() for List<T>
+        (Inferred::Parameter(parameter), Inferred::Parameter(argument)) if parameter == argument => Ok(()),
+        (Inferred::Parameter(_), Inferred::Unknown) => Ok(()),
+        (Inferred::Parameter(parameter), argument) => match result.get(parameter) {
+            Some(expected) if expected == argument || argument.is_parameterized() => Ok(()),
+            Some(expected) if !expected.is_parameterized() => Err(TypeArgumentConflict {
+                argument: 0,
+                parameter: *parameter,
+                expected: expected.clone(),
+                got: argument.clone(),
+            }),
+            _ => {
+                result.insert(*parameter, argument.clone());
+                Ok(())
+            }
+        },
+        (Inferred::Parameterized(parameter, parameters), Inferred::Parameterized(argument, arguments)) if parameter == argument => {
+            for (parameter, argument) in parameters.iter().zip(arguments.iter()) {
+                bind(parameter, argument, result)?;
+            }
+            Ok(())
+        }
+        (Inferred::Function(parameters, parameter), Inferred::Function(arguments, argument)) => {
+            for (parameter, argument) in parameters.iter().zip(arguments.iter()) {
+                bind(parameter, argument, result)?;
+            }
+            bind(parameter, argument, result)
+        }
+        // none tells nothing about the type parameter
+        (Inferred::Optional(_), argument) if argument.is_none() => Ok(()),
+        (Inferred::Optional(parameter), Inferred::Optional(argument)) => bind(parameter, argument, result),
+        // a value can be passed where the value or none is expected
+        (Inferred::Optional(parameter), argument) => bind(parameter, argument, result),
+        (Inferred::Pointer(parameter), Inferred::Pointer(argument)) => bind(parameter, argument, result),
+        (Inferred::Tuple(parameters), Inferred::Tuple(arguments)) => {
+            for (parameter, argument) in parameters.iter().zip(arguments.iter()) {
+                bind(parameter, argument, result)?;
+            }
+            Ok(())
+        }
+        _ => Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Inferred, StringTableId, TypeId};
+    use crate::ir::analyse::infer::generic::{infer_type_arguments, TypeArgumentConflict};
+
+    const T: StringTableId = StringTableId(1);
+
+    #[test]
+    fn bind_parameter_of_parameterized_type() {
+        let list = TypeId::Custom(0);
+        let result = infer_type_arguments(
+            &[Inferred::Parameterized(list, Box::new([Inferred::Parameter(T)]))],
+            &[Inferred::Parameterized(list, Box::new([Inferred::Number]))],
+        ).unwrap();
+        assert_eq!(result[&T], Inferred::Number);
+    }
+
+    #[test]
+    fn parameter_bound_twice() {
+        let result = infer_type_arguments(
+            &[Inferred::Parameter(T), Inferred::Parameter(T)],
+            &[Inferred::Number, Inferred::String],
+        );
+        assert_eq!(result, Err(TypeArgumentConflict {
+            argument: 1,
+            parameter: T,
+            expected: Inferred::Number,
+            got: Inferred::String,
+        }));
+    }
+
+    #[test]
+    fn unbound_argument_does_not_conflict() {
+        let list = TypeId::Custom(0);
+        let result = infer_type_arguments(
+            &[Inferred::Parameter(T), Inferred::Parameterized(list, Box::new([Inferred::Parameter(T)]))],
+            &[Inferred::String, Inferred::Parameterized(list, Box::new([Inferred::Parameter(StringTableId(2))]))],
+        ).unwrap();
+        assert_eq!(result[&T], Inferred::String);
+    }
+
+    #[test]
+    fn bind_parameter_of_optional() {
+        let result = infer_type_arguments(
+            &[Inferred::Optional(Box::new(Inferred::Parameter(T))), Inferred::Optional(Box::new(Inferred::Parameter(T)))],
+            &[Inferred::Optional(Box::new(Inferred::Unknown)), Inferred::Number],
+        ).unwrap();
+        assert_eq!(result[&T], Inferred::Number);
+    }
+}
@@ -1,6 +1,6 @@
 use std::ops::Deref;
 
-use crate::common::{StringTable, SymbolTable, TypeTable, WithSpan};
+use crate::common::{StringTable, SymbolId, SymbolTable, TypeTable, WithSpan};
 use crate::common::Context;
 use crate::ir::analyse::{TypedTreeNode, TypeNode};
 use crate::ir::analyse::scope::Scope;
@@ -9,12 +9,18 @@ mod variable;
 mod call;
 mod block;
 mod control;
+mod function;
+pub(crate) mod generic;
+pub(crate) mod optional;
+mod tuple;
 
 pub(crate) struct Inferrer<'a> {
     string_table: &'a mut StringTable,
     symbol_table: &'a mut SymbolTable,
     type_table: &'a mut TypeTable,
     scope: Scope,
+    // variables of optional type which got compared against none and hold a value
+    narrowed: Vec<SymbolId>,
 }
 
 
@@ -25,6 +31,7 @@ impl<'a> Inferrer<'a> {
             symbol_table: &mut ctx.symbol_table,
             type_table: &mut ctx.type_table,
             scope: Scope::new(),
+            narrowed: vec![],
         }
     }
 
@@ -40,13 +47,34 @@ impl<'a> Inferrer<'a> {
 
     pub(crate) fn node(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
         match node.node() {
+            TypeNode::AccessElementOfTuple(_) => self.access_element_of_tuple(node),
+            TypeNode::AccessVariable(_) => self.access_variable(node),
+            TypeNode::AccessVariableOfObject(_) => self.access_variable_of_object(node),
+            TypeNode::AssignVariable(_) => self.assign_variable(node),
+            TypeNode::AssignVariableOfObject(_) => self.assign_variable_of_object(node),
             TypeNode::Block(_) => self.block(node),
             TypeNode::BreakLoop(_) => self.r#break(node),
-            TypeNode::Calculate(_) => Ok({}),
+            TypeNode::Calculate(_) => self.calculate(node),
+            TypeNode::CallFunction(_) => self.call_function(node),
+            TypeNode::CallFunctionOfObject(_) => self.call_function_of_object(node),
             TypeNode::CallFunctionOfPackage(_) => self.call_function_of_package(node),
+            TypeNode::CallFunctionWithLambda(_) => self.call_function_with_lambda(node),
             TypeNode::Compare(_) => Ok({}),
+            TypeNode::ContinueLoop(_) => Ok(()),
+            TypeNode::DeclareExternalFunction(_) => Ok(()),
+            TypeNode::DeclareFunction(_) => self.declare_function(node),
+            TypeNode::DeclareLambda(_) => self.declare_lambda(node),
+            TypeNode::DeclarePackage(_) => self.declare_package(node),
+            TypeNode::DeclareTrait(_) => Ok(()),
+            TypeNode::DeclareType(_) => Ok(()),
             TypeNode::DeclareVariable(_) => self.declare_variable(node),
+            TypeNode::Defer(_) => self.defer(node),
+            TypeNode::DefineType(_) => self.define_type(node),
+            TypeNode::DestructureTuple(_) => self.destructure_tuple(node),
             TypeNode::If(_) => self.r#if(node),
+            TypeNode::InstantiateTuple(_) => self.instantiate_tuple(node),
+            TypeNode::InstantiateType(_) => self.instantiate_type(node),
+            TypeNode::InterpolateString(_) => Ok(()),
             TypeNode::LiteralBoolean(_) |
             TypeNode::LiteralFloat4(_) |
             TypeNode::LiteralFloat8(_) |
@@ -60,9 +88,15 @@ impl<'a> Inferrer<'a> {
             TypeNode::LiteralUint4(_) |
             TypeNode::LiteralUint8(_) |
             TypeNode::LiteralUint16(_) |
+            TypeNode::LiteralNone(_) |
             TypeNode::LiteralNumber(_) |
             TypeNode::LiteralString(_) => { Ok(()) }
             TypeNode::Loop(_) => self.r#loop(node),
+            TypeNode::Match(_) => self.r#match(node),
+            TypeNode::RangeLoop(_) => self.range_loop(node),
+            TypeNode::IteratorLoop(_) => self.iterator_loop(node),
+            TypeNode::ReturnFromFunction(_) => self.r#return(node),
+            TypeNode::Try(_) => self.r#try(node),
             _ => unimplemented!("{node:#?}")
         }
     }
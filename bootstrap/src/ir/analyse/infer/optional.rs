@@ -0,0 +1,305 @@
+use crate::common::{Inferred, Span, SymbolId, SymbolTable};
+use crate::common::node::{CompareOperator, Node};
+use crate::ir::analyse::{Error, OptionalError, TypedTreeNode};
+use crate::ir::analyse::infer::Inferrer;
+
+impl<'a> Inferrer<'a> {
+    // a narrowed variable holds a value, it can only be none outside of the narrowing
+    pub(crate) fn access_variable(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::AccessVariable(access) = &node.node {
+            if let Inferred::Optional(inner) = &node.inferred {
+                if self.narrowed.contains(&access.variable) {
+                    node.inferred = inner.as_ref().clone();
+                }
+            }
+            Ok(())
+        } else {
+            panic!("not access variable")
+        }
+    }
+
+    pub(crate) fn access_variable_of_object(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::AccessVariableOfObject(access) = &node.node {
+            self.value_required(access.object, &node.span)
+        } else {
+            panic!("not access variable of object")
+        }
+    }
+
+    pub(crate) fn calculate(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::Calculate(calculate) = &mut node.node {
+            self.node(&mut calculate.left)?;
+            self.node(&mut calculate.right)?;
+
+            for operand in [&calculate.left, &calculate.right] {
+                if operand.inferred.is_optional() {
+                    return Err(Error::Optional(OptionalError::Calculation {
+                        got: operand.inferred.to_string(self.string_table, self.type_table),
+                        span: operand.span.clone(),
+                    }));
+                }
+            }
+
+            node.inferred = calculate.left.inferred.clone();
+            Ok(())
+        } else {
+            panic!("not calculate")
+        }
+    }
+
+    // members of a variable which may be none can only be used after comparing it against none
+    pub(crate) fn value_required(&self, variable: SymbolId, span: &Span) -> crate::ir::analyse::Result<()> {
+        let symbol = self.symbol_table.variable(variable);
+        if symbol.inferred.is_optional() && !self.narrowed.contains(&variable) {
+            return Err(Error::Optional(OptionalError::Access {
+                variable: self.string_table.get(symbol.name.0).to_string(),
+                span: span.clone(),
+            }));
+        }
+        Ok(())
+    }
+
+    // a value which may be none can only be passed to a parameter taking none, a type parameter takes it as it is
+    pub(crate) fn value_required_arguments(&self, function: SymbolId, skip: usize, arguments: &[TypedTreeNode]) -> crate::ir::analyse::Result<()> {
+        let Inferred::Function(parameters, _) = self.symbol_table.signature(function) else {
+            return Ok(());
+        };
+
+        for (parameter, argument) in parameters.iter().skip(skip).zip(arguments) {
+            if argument.inferred.is_optional() && !parameter.is_optional() && !matches!(parameter, Inferred::Parameter(_)) {
+                return Err(Error::Optional(OptionalError::Argument {
+                    function: self.string_table.get(self.symbol_table[function].name().0).to_string(),
+                    got: argument.inferred.to_string(self.string_table, self.type_table),
+                    span: argument.span.clone(),
+                }));
+            }
+        }
+        Ok(())
+    }
+}
+
+// x != none and none != x, the operator tells in which branch x holds a value
+pub(crate) fn narrowing(symbol_table: &SymbolTable, condition: &TypedTreeNode) -> Option<(SymbolId, CompareOperator)> {
+    let Node::Compare(compare) = &condition.node else {
+        return None;
+    };
+
+    let variable = match (&compare.left.node, &compare.right.node) {
+        (Node::AccessVariable(access), Node::LiteralNone(_)) | (Node::LiteralNone(_), Node::AccessVariable(access)) => access.variable,
+        _ => return None
+    };
+
+    if !matches!(symbol_table[variable], crate::common::Symbol::Variable(_)) || !symbol_table.variable(variable).inferred.is_optional() {
+        return None;
+    }
+
+    match compare.operator {
+        CompareOperator::Equal | CompareOperator::NotEqual => Some((variable, compare.operator.clone())),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, Inferred};
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{analyse, OptionalError, UndefinedError};
+    use crate::ir::analyse::Error::{Optional, Undefined};
+
+    #[test]
+    fn narrowed_in_then_block() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let x: Number? = 2
+        if x != none {
+            let y = x + x
+        }
+        "#).unwrap();
+        let typed = analyse(&mut ctx, ast).unwrap();
+
+        assert_eq!(typed[0].inferred, Inferred::Optional(Box::new(Inferred::Number)));
+
+        let r#if = typed[1].as_if();
+        let then = r#if.then.borrow();
+        let declared = then.nodes[0].as_declared_variable();
+        assert_eq!(declared.value.inferred, Inferred::Number);
+    }
+
+    #[test]
+    fn narrowed_in_otherwise_block() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let x: Number? = none
+        if x == none {
+        } else {
+            let y = x + x
+        }
+        "#).unwrap();
+        let result = analyse(&mut ctx, ast);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn narrowed_after_early_return() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function double(x: Number?) -> Number {
+            if x == none {
+                return 0
+            }
+            return x + x
+        }
+        "#).unwrap();
+        let result = analyse(&mut ctx, ast);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn not_narrowed_after_assigning_none() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let x: Number? = 2
+        if x != none {
+            x = none
+            let y = x + x
+        }
+        "#).unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Optional(OptionalError::Calculation { got, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(got, "Number?");
+    }
+
+    #[test]
+    fn calculation_with_possibly_none() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let x: Number? = none
+        let y = x + x
+        "#).unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Optional(OptionalError::Calculation { got, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(got, "Number?");
+    }
+
+    #[test]
+    fn not_narrowed_outside_of_then_block() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let x: Number? = 1
+        if x != none {
+        }
+        let y = x + x
+        "#).unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Optional(OptionalError::Calculation { .. }) = result.err().unwrap() else { panic!() };
+    }
+
+    #[test]
+    fn member_access_of_possibly_none() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Point(x: Number)
+        function x_of(p: Point?) -> Number {
+            return p.x
+        }
+        "#).unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Optional(OptionalError::Access { variable, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(variable, "p");
+    }
+
+    #[test]
+    fn declared_optional_takes_none_and_values() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let x: String? = none
+        let y: String? = 'elodie'
+        "#).unwrap();
+        let typed = analyse(&mut ctx, ast).unwrap();
+
+        assert_eq!(typed[0].inferred, Inferred::Optional(Box::new(Inferred::String)));
+        assert_eq!(typed[1].inferred, Inferred::Optional(Box::new(Inferred::String)));
+    }
+
+    #[test]
+    fn argument_of_possibly_none() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        function shout(message: String) {}
+        let message: String? = none
+        shout(message)
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Optional(OptionalError::Argument { function, got, span }) = result.err().unwrap() else { panic!() };
+        assert_eq!(function, "shout");
+        assert_eq!(got, "String?");
+        assert_eq!(span.start.index, source.find("message)").unwrap());
+    }
+
+    #[test]
+    fn narrowed_argument() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function shout(message: String) {}
+        let message: String? = 'hello'
+        if message != none {
+            shout(message)
+        }
+        "#).unwrap();
+        let result = analyse(&mut ctx, ast);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn argument_of_possibly_none_taking_none() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function shout(message: String?) {}
+        let message: String? = none
+        shout(message)
+        "#).unwrap();
+        let result = analyse(&mut ctx, ast);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn method_of_possibly_none() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        type Point(x: Number)
+        define Point {
+            function double() -> Number { return self.x + self.x }
+        }
+        let p: Point? = none
+        p.double()
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Optional(OptionalError::Access { variable, span }) = result.err().unwrap() else { panic!() };
+        assert_eq!(variable, "p");
+        assert_eq!(span.start.index, source.find("p.double").unwrap());
+    }
+
+    #[test]
+    fn method_of_possibly_none_builtin() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        let s: String? = none
+        s.length()
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Undefined(UndefinedError::UndefinedMethod { r#type, method, span }) = result.err().unwrap() else { panic!() };
+        assert_eq!(r#type, "String?");
+        assert_eq!(method, "length");
+        assert_eq!(span.start.index, source.find("s.length").unwrap());
+    }
+}
@@ -0,0 +1,44 @@
+use crate::common::node::Node;
+use crate::ir::analyse::infer::Inferrer;
+use crate::ir::analyse::TypedTreeNode;
+
+impl<'a> Inferrer<'a> {
+    pub(crate) fn instantiate_tuple(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::InstantiateTuple(tuple) = &mut node.node {
+            for node in tuple.nodes.iter_mut() {
+                self.node(node)?;
+            }
+            Ok(())
+        } else {
+            panic!("not instantiate tuple")
+        }
+    }
+
+    pub(crate) fn access_element_of_tuple(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::AccessElementOfTuple(access) = &mut node.node {
+            self.node(&mut access.node)
+        } else {
+            panic!("not access element of tuple")
+        }
+    }
+
+    pub(crate) fn destructure_tuple(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::DestructureTuple(destructure) = &mut node.node {
+            self.node(&mut destructure.value)?;
+
+            for variable in destructure.variables.iter() {
+                let inferred = &self.symbol_table.variable(*variable).inferred;
+                let Some(type_id) = inferred.type_id() else {
+                    unimplemented!("{inferred:#?}")
+                };
+
+                let symbol = &mut self.symbol_table[*variable];
+                self.scope.register_symbol(symbol);
+                symbol.set_type_id(type_id);
+            }
+            Ok(())
+        } else {
+            panic!("not destructure tuple")
+        }
+    }
+}
@@ -1,6 +1,5 @@
 use std::ops::Deref;
 
-use crate::common::{GetString, Inferred, TypeId};
 use crate::common::node::Node;
 use crate::ir::analyse::infer::Inferrer;
 use crate::ir::analyse::TypedTreeNode;
@@ -13,27 +12,17 @@ impl<'a> Inferrer<'a> {
             // result
             self.node(&mut node.value)?;
 
+            let inferred = &self.symbol_table.variable(node.variable).inferred;
+            let declared = inferred.type_id()
+                .filter(|type_id| inferred.is_optional() || self.type_table.is_trait(*type_id));
+
             let symbol = &mut self.symbol_table[node.variable];
 
             self.scope.register_symbol(symbol);
 
-            match &node.value.inferred {
-                Inferred::Boolean => { symbol.set_type_id(TypeId::BOOLEAN) }
-                Inferred::Float4 => { symbol.set_type_id(TypeId::FLOAT4) }
-                Inferred::Float8 => { symbol.set_type_id(TypeId::FLOAT8) }
-                Inferred::Int1 => { symbol.set_type_id(TypeId::INT1) }
-                Inferred::Int2 => { symbol.set_type_id(TypeId::INT2) }
-                Inferred::Int4 => { symbol.set_type_id(TypeId::INT4) }
-                Inferred::Int8 => { symbol.set_type_id(TypeId::INT8) }
-                Inferred::Int16 => { symbol.set_type_id(TypeId::INT16) }
-                Inferred::Number => { symbol.set_type_id(TypeId::NUMBER) }
-                Inferred::String => { symbol.set_type_id(TypeId::STRING) }
-                Inferred::Uint1 => { symbol.set_type_id(TypeId::UINT1) }
-                Inferred::Uint2 => { symbol.set_type_id(TypeId::UINT2) }
-                Inferred::Uint4 => { symbol.set_type_id(TypeId::UINT4) }
-                Inferred::Uint8 => { symbol.set_type_id(TypeId::UINT8) }
-                Inferred::Uint16 => { symbol.set_type_id(TypeId::UINT16) }
-                inferred => unimplemented!("{inferred:#?}")
+            match declared.or(node.value.inferred.type_id()) {
+                Some(type_id) => symbol.set_type_id(type_id),
+                None => unimplemented!("{:#?}", node.value.inferred)
             }
 
             Ok(())
@@ -43,6 +32,28 @@ impl<'a> Inferrer<'a> {
 
         // self.node(inner.value.as_mut())?;
     }
+
+    // a narrowed variable which gets a value that may be none is not narrowed anymore
+    pub(crate) fn assign_variable(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::AssignVariable(node) = &mut node.node {
+            self.node(&mut node.value)?;
+
+            if node.value.inferred.is_none() || node.value.inferred.is_optional() {
+                self.narrowed.retain(|variable| *variable != node.variable);
+            }
+            Ok(())
+        } else {
+            panic!("not assign variable")
+        }
+    }
+
+    pub(crate) fn assign_variable_of_object(&mut self, node: &mut TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if let Node::AssignVariableOfObject(node) = &mut node.node {
+            self.node(&mut node.value)
+        } else {
+            panic!("not assign variable of object")
+        }
+    }
 }
 
 
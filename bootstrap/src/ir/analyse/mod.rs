@@ -44,3 +44,8 @@ pub(crate) fn analyse(ctx: &mut Context, ast: Ast) -> Result<TypedAst> {
     let prepared = prepare(ctx, ast)?;
     infer(ctx, prepared)
 }
+
+pub(crate) fn analyse_program(ctx: &mut Context, library: Vec<Ast>, ast: Ast) -> Result<TypedAst> {
+    let nodes = Pre::new(ctx).process_program(library, ast)?;
+    infer(ctx, TypedAst { nodes })
+}
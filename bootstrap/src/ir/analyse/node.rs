@@ -2,8 +2,8 @@ use std::cell::RefCell;
 
 use bigdecimal::BigDecimal;
 
-use crate::common::{Inferred, Span, StringTableId, SymbolId, WithSpan};
-use crate::common::node::{AccessVariableNode, AccessVariableOfObjectNode, AccessVariableOfSelfNode, BlockNode, BreakLoopNode, CalculateNode, CalculateOperator, CallFunctionNode, CallFunctionOfObjectNode, CallFunctionOfPackageNode, CallFunctionWithLambdaNode, CompareNode, CompareOperator, ContinueLoopNode, DeclareExternalFunctionNode, DeclareFunctionNode, DeclarePackageNode, DeclareTypeNode, DeclareVariableNode, DefineTypeNode, ExportPackageNode, IfNode, InstantiateTypeNode, InterpolateStringNode, LiteralBooleanNode, LiteralFloat4Node, LiteralFloat8Node, LiteralInt16Node, LiteralInt1Node, LiteralInt2Node, LiteralInt4Node, LiteralInt8Node, LiteralNumberNode, LiteralStringNode, LiteralUint16Node, LiteralUint1Node, LiteralUint2Node, LiteralUint4Node, LiteralUint8Node, LoopNode, Node, ReturnFromFunctionNode, Variant};
+use crate::common::{Dispatch, Inferred, Span, StringTableId, SymbolId, TypeId, TypeVariableId, WithSpan};
+use crate::common::node::{AccessElementOfTupleNode, AccessIndexNode, AccessVariableNode, AccessVariableOfObjectNode, AccessVariableOfSelfNode, AssignVariableNode, AssignVariableOfObjectNode, BlockNode, BreakLoopNode, CalculateNode, CalculateOperator, CallFunctionNode, CallFunctionOfObjectNode, CallFunctionOfPackageNode, CallFunctionWithLambdaNode, CompareNode, CompareOperator, ContinueLoopNode, DeclareExternalFunctionNode, DeclareFunctionNode, DeclareLambdaNode, DeclarePackageNode, DeclareTraitNode, DeclareTypeNode, DeclareVariableNode, DeferNode, DefineTypeNode, DestructureTupleNode, ExportPackageNode, IfNode, InstantiateRecordNode, InstantiateTupleNode, InstantiateTypeNode, InterpolateStringNode, IteratorLoopNode, LiteralBooleanNode, LiteralFloat4Node, LiteralFloat8Node, LiteralInt16Node, LiteralInt1Node, LiteralInt2Node, LiteralInt4Node, LiteralInt8Node, LiteralNoneNode, LiteralNumberNode, LiteralStringNode, LiteralUint16Node, LiteralUint1Node, LiteralUint2Node, LiteralUint4Node, LiteralUint8Node, LoopNode, MatchNode, Node, RangeLoopNode, ReturnFromFunctionNode, TryNode, Variant};
 use crate::frontend::ast::AstType;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -13,9 +13,13 @@ impl Variant for TypeVariant {}
 
 pub type TypeNode = crate::common::node::Node<
     TypeVariant,
+    TypeAccessElementOfTupleNode,
+    TypeAccessIndexNode,
     TypeAccessVariableNode,
     TypeAccessVariableOfObjectNode,
     TypeAccessVariableOfSelfNode,
+    TypeAssignVariableNode,
+    TypeAssignVariableOfObjectNode,
     TypeBlockNode,
     TypeBreakLoopNode,
     TypeCalculateNode,
@@ -27,13 +31,20 @@ pub type TypeNode = crate::common::node::Node<
     TypeContinueLoopNode,
     TypeDeclareExternalFunctionNode,
     TypeDeclareFunctionNode,
+    TypeDeclareLambdaNode,
     TypeDeclarePackageNode,
+    TypeDeclareTraitNode,
     TypeDeclareTypeNode,
     TypeDeclareVariableNode,
+    TypeDeferNode,
     TypeDefineTypeNode,
+    TypeDestructureTupleNode,
     TypeExportPackageNode,
     TypeIfNode,
     TypeInterpolateStringNode,
+    TypeIteratorLoopNode,
+    TypeInstantiateRecordNode,
+    TypeInstantiateTupleNode,
     TypeInstantiateTypeNode,
     TypeLiteralBooleanNode,
     TypeLiteralFloat4Node,
@@ -43,6 +54,7 @@ pub type TypeNode = crate::common::node::Node<
     TypeLiteralInt4Node,
     TypeLiteralInt8Node,
     TypeLiteralInt16Node,
+    TypeLiteralNoneNode,
     TypeLiteralNumberNode,
     TypeLiteralStringNode,
     TypeLiteralUint1Node,
@@ -51,7 +63,10 @@ pub type TypeNode = crate::common::node::Node<
     TypeLiteralUint8Node,
     TypeLiteralUint16Node,
     TypeLoopNode,
+    TypeMatchNode,
+    TypeRangeLoopNode,
     TypeReturnFromFunctionNode,
+    TypeTryNode,
 >;
 
 pub(crate) enum Direction {
@@ -75,6 +90,30 @@ impl TypedTreeNode {
         }
     }
 
+    pub fn as_access_variable_of_object(&self) -> &TypeAccessVariableOfObjectNode {
+        if let Node::AccessVariableOfObject(result) = &self.node {
+            result
+        } else {
+            panic!("not access variable of object")
+        }
+    }
+
+    pub fn as_assign_variable(&self) -> &TypeAssignVariableNode {
+        if let Node::AssignVariable(result) = &self.node {
+            result
+        } else {
+            panic!("not assign variable")
+        }
+    }
+
+    pub fn as_assign_variable_of_object(&self) -> &TypeAssignVariableOfObjectNode {
+        if let Node::AssignVariableOfObject(result) = &self.node {
+            result
+        } else {
+            panic!("not assign variable of object")
+        }
+    }
+
     pub fn as_block(&self) -> &TypeBlockNode {
         if let Node::Block(result) = &self.node {
             result
@@ -91,6 +130,86 @@ impl TypedTreeNode {
         }
     }
 
+    pub fn as_declare_external_function(&self) -> &TypeDeclareExternalFunctionNode {
+        if let Node::DeclareExternalFunction(result) = &self.node {
+            result
+        } else {
+            panic!("not declare external function")
+        }
+    }
+
+    pub fn as_declare_function(&self) -> &TypeDeclareFunctionNode {
+        if let Node::DeclareFunction(result) = &self.node {
+            result
+        } else {
+            panic!("not declare function")
+        }
+    }
+
+    pub fn as_declare_lambda(&self) -> &TypeDeclareLambdaNode {
+        if let Node::DeclareLambda(result) = &self.node {
+            result
+        } else {
+            panic!("not declare lambda")
+        }
+    }
+
+    pub fn as_declare_package(&self) -> &TypeDeclarePackageNode {
+        if let Node::DeclarePackage(result) = &self.node {
+            result
+        } else {
+            panic!("not declare package")
+        }
+    }
+
+    pub fn as_call_function(&self) -> &TypeCallFunctionNode {
+        if let Node::CallFunction(result) = &self.node {
+            result
+        } else {
+            panic!("not call function")
+        }
+    }
+
+    pub fn as_call_function_with_lambda(&self) -> &TypeCallFunctionWithLambdaNode {
+        if let Node::CallFunctionWithLambda(result) = &self.node {
+            result
+        } else {
+            panic!("not call function with lambda")
+        }
+    }
+
+    pub fn as_call_function_of_object(&self) -> &TypeCallFunctionOfObjectNode {
+        if let Node::CallFunctionOfObject(result) = &self.node {
+            result
+        } else {
+            panic!("not call function of object")
+        }
+    }
+
+    pub fn as_call_function_of_package(&self) -> &TypeCallFunctionOfPackageNode {
+        if let Node::CallFunctionOfPackage(result) = &self.node {
+            result
+        } else {
+            panic!("not call function of package")
+        }
+    }
+
+    pub fn as_declare_trait(&self) -> &TypeDeclareTraitNode {
+        if let Node::DeclareTrait(result) = &self.node {
+            result
+        } else {
+            panic!("not declare trait")
+        }
+    }
+
+    pub fn as_declare_type(&self) -> &TypeDeclareTypeNode {
+        if let Node::DeclareType(result) = &self.node {
+            result
+        } else {
+            panic!("not declare type")
+        }
+    }
+
     pub fn as_declared_variable(&self) -> &TypeDeclareVariableNode {
         if let Node::DeclareVariable(result) = &self.node {
             result
@@ -99,6 +218,22 @@ impl TypedTreeNode {
         }
     }
 
+    pub fn as_defer(&self) -> &TypeDeferNode {
+        if let Node::Defer(result) = &self.node {
+            result
+        } else {
+            panic!("not defer")
+        }
+    }
+
+    pub fn as_define_type(&self) -> &TypeDefineTypeNode {
+        if let Node::DefineType(result) = &self.node {
+            result
+        } else {
+            panic!("not define type")
+        }
+    }
+
     pub fn as_if(&self) -> &TypeIfNode {
         if let Node::If(result) = &self.node {
             result
@@ -107,6 +242,78 @@ impl TypedTreeNode {
         }
     }
 
+    pub fn as_loop(&self) -> &TypeLoopNode {
+        if let Node::Loop(result) = &self.node {
+            result
+        } else {
+            panic!("not loop")
+        }
+    }
+
+    pub fn as_iterator_loop(&self) -> &TypeIteratorLoopNode {
+        if let Node::IteratorLoop(result) = &self.node {
+            result
+        } else {
+            panic!("not iterator loop")
+        }
+    }
+
+    pub fn as_range_loop(&self) -> &TypeRangeLoopNode {
+        if let Node::RangeLoop(result) = &self.node {
+            result
+        } else {
+            panic!("not range loop")
+        }
+    }
+
+    pub fn as_access_element_of_tuple(&self) -> &TypeAccessElementOfTupleNode {
+        if let Node::AccessElementOfTuple(result) = &self.node {
+            result
+        } else {
+            panic!("not access element of tuple")
+        }
+    }
+
+    pub fn as_destructure_tuple(&self) -> &TypeDestructureTupleNode {
+        if let Node::DestructureTuple(result) = &self.node {
+            result
+        } else {
+            panic!("not destructure tuple")
+        }
+    }
+
+    pub fn as_instantiate_tuple(&self) -> &TypeInstantiateTupleNode {
+        if let Node::InstantiateTuple(result) = &self.node {
+            result
+        } else {
+            panic!("not instantiate tuple")
+        }
+    }
+
+    pub fn as_try(&self) -> &TypeTryNode {
+        if let Node::Try(result) = &self.node {
+            result
+        } else {
+            panic!("not try")
+        }
+    }
+
+    pub fn as_match(&self) -> &TypeMatchNode {
+        if let Node::Match(result) = &self.node {
+            result
+        } else {
+            panic!("not match")
+        }
+    }
+
+    pub fn as_instantiate_type(&self) -> &TypeInstantiateTypeNode {
+        if let Node::InstantiateType(result) = &self.node {
+            result
+        } else {
+            panic!("not instantiate type")
+        }
+    }
+
     pub fn as_interpolate_string(&self) -> &TypeInterpolateStringNode {
         if let Node::InterpolateString(result) = &self.node {
             result
@@ -234,6 +441,14 @@ impl TypedTreeNode {
             panic!("not literal uint16")
         }
     }
+
+    pub fn as_return_from_function(&self) -> &TypeReturnFromFunctionNode {
+        if let Node::ReturnFromFunction(result) = &self.node {
+            result
+        } else {
+            panic!("not return from function")
+        }
+    }
 }
 
 impl TypedTreeNode {
@@ -264,6 +479,20 @@ impl WithSpan for TypedTreeNode {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAccessElementOfTupleNode {
+    pub node: Box<TypedTreeNode>,
+    pub index: usize,
+}
+
+impl AccessElementOfTupleNode<TypeVariant> for TypeAccessElementOfTupleNode {}
+
+// lowered to the call of the index operator function
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAccessIndexNode {}
+
+impl AccessIndexNode<TypeVariant> for TypeAccessIndexNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TypeAccessVariableNode {
     pub variable: SymbolId,
@@ -272,7 +501,10 @@ pub struct TypeAccessVariableNode {
 impl AccessVariableNode<TypeVariant> for TypeAccessVariableNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct TypeAccessVariableOfObjectNode {}
+pub struct TypeAccessVariableOfObjectNode {
+    pub object: SymbolId,
+    pub variable: TypeVariableId,
+}
 
 impl AccessVariableOfObjectNode<TypeVariant> for TypeAccessVariableOfObjectNode {}
 
@@ -281,6 +513,23 @@ pub struct TypeAccessVariableOfSelfNode {}
 
 impl AccessVariableOfSelfNode<TypeVariant> for TypeAccessVariableOfSelfNode {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAssignVariableNode {
+    pub variable: SymbolId,
+    pub value: Box<TypedTreeNode>,
+}
+
+impl AssignVariableNode<TypeVariant> for TypeAssignVariableNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAssignVariableOfObjectNode {
+    pub object: SymbolId,
+    pub variable: TypeVariableId,
+    pub value: Box<TypedTreeNode>,
+}
+
+impl AssignVariableOfObjectNode<TypeVariant> for TypeAssignVariableOfObjectNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TypeBlockNode {
     pub nodes: Box<[TypedTreeNode]>,
@@ -290,6 +539,7 @@ impl BlockNode<TypeVariant> for TypeBlockNode {}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct TypeBreakLoopNode {
+    pub label: Option<StringTableId>,
     pub node: Option<Box<TypedTreeNode>>,
 }
 
@@ -305,17 +555,30 @@ pub struct TypeCalculateNode {
 impl CalculateNode<TypeVariant> for TypeCalculateNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct TypeCallFunctionNode {}
+pub struct TypeCallFunctionNode {
+    pub function: SymbolId,
+    pub arguments: Box<[TypedTreeNode]>,
+}
 
 impl CallFunctionNode<TypeVariant> for TypeCallFunctionNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct TypeCallFunctionWithLambdaNode {}
+pub struct TypeCallFunctionWithLambdaNode {
+    pub function: SymbolId,
+    pub arguments: Box<[TypedTreeNode]>,
+    // the trailing block, passed as last argument
+    pub lambda: Box<TypedTreeNode>,
+}
 
 impl CallFunctionWithLambdaNode<TypeVariant> for TypeCallFunctionWithLambdaNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct TypeCallFunctionOfObjectNode {}
+pub struct TypeCallFunctionOfObjectNode {
+    pub object: SymbolId,
+    pub function: SymbolId,
+    pub arguments: Box<[TypedTreeNode]>,
+    pub dispatch: Dispatch,
+}
 
 impl CallFunctionOfObjectNode<TypeVariant> for TypeCallFunctionOfObjectNode {}
 
@@ -338,35 +601,87 @@ pub struct TypeCompareNode {
 impl CompareNode<TypeVariant> for TypeCompareNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct TypeContinueLoopNode {}
+pub struct TypeContinueLoopNode {
+    pub label: Option<StringTableId>,
+}
 
 impl ContinueLoopNode<TypeVariant> for TypeContinueLoopNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct TypeDeclareExternalFunctionNode {}
+pub struct TypeDeclareExternalFunctionNode {
+    pub function: SymbolId,
+}
 
 impl DeclareExternalFunctionNode<TypeVariant> for TypeDeclareExternalFunctionNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct TypeDeclareFunctionNode {}
+pub struct TypeDeclareFunctionNode {
+    pub function: SymbolId,
+    pub arguments: Box<[SymbolId]>,
+    pub nodes: Box<[TypedTreeNode]>,
+}
 
 impl DeclareFunctionNode<TypeVariant> for TypeDeclareFunctionNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct TypeDeclarePackageNode {}
+pub struct TypeDeclareLambdaNode {
+    pub function: SymbolId,
+    pub arguments: Box<[SymbolId]>,
+    // variables of enclosing scopes the lambda accesses
+    pub captures: Box<[SymbolId]>,
+    pub nodes: Box<[TypedTreeNode]>,
+}
+
+impl DeclareLambdaNode<TypeVariant> for TypeDeclareLambdaNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDeclarePackageNode {
+    pub package: SymbolId,
+    pub definitions: Box<[TypedTreeNode]>,
+    pub functions: Box<[TypedTreeNode]>,
+    pub packages: Box<[TypedTreeNode]>,
+    pub types: Box<[TypedTreeNode]>,
+}
 
 impl DeclarePackageNode<TypeVariant> for TypeDeclarePackageNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct TypeDeclareTypeNode {}
+pub struct TypeDeclareTraitNode {
+    pub r#trait: SymbolId,
+}
+
+impl DeclareTraitNode<TypeVariant> for TypeDeclareTraitNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDeclareTypeNode {
+    pub r#type: SymbolId,
+}
 
 impl DeclareTypeNode<TypeVariant> for TypeDeclareTypeNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct TypeDefineTypeNode {}
+pub struct TypeDeferNode {
+    pub nodes: RefCell<TypeBlockNode>,
+}
+
+impl DeferNode<TypeVariant> for TypeDeferNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDefineTypeNode {
+    pub r#type: SymbolId,
+    pub functions: Box<[TypedTreeNode]>,
+}
 
 impl DefineTypeNode<TypeVariant> for TypeDefineTypeNode {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDestructureTupleNode {
+    pub variables: Box<[SymbolId]>,
+    pub value: Box<TypedTreeNode>,
+}
+
+impl DestructureTupleNode<TypeVariant> for TypeDestructureTupleNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TypeDeclareVariableNode {
     pub variable: SymbolId,
@@ -396,8 +711,30 @@ pub struct TypeInterpolateStringNode {
 
 impl InterpolateStringNode<TypeVariant> for TypeInterpolateStringNode {}
 
+// records become tuples of their values, ordered by field name
 #[derive(Debug, Clone, PartialEq)]
-pub struct TypeInstantiateTypeNode {}
+pub struct TypeInstantiateRecordNode {}
+
+impl InstantiateRecordNode<TypeVariant> for TypeInstantiateRecordNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeInstantiateTupleNode {
+    pub nodes: Box<[TypedTreeNode]>,
+}
+
+impl InstantiateTupleNode<TypeVariant> for TypeInstantiateTupleNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeInstantiateTypeNode {
+    pub r#type: SymbolId,
+    pub arguments: Box<[TypeNamedArgument]>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeNamedArgument {
+    pub argument: StringTableId,
+    pub value: TypedTreeNode,
+}
 
 impl InstantiateTypeNode<TypeVariant> for TypeInstantiateTypeNode {}
 
@@ -465,6 +802,11 @@ pub struct TypeLiteralInt16Node {
 
 impl LiteralInt16Node<TypeVariant> for TypeLiteralInt16Node {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeLiteralNoneNode {}
+
+impl LiteralNoneNode<TypeVariant> for TypeLiteralNoneNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TypeLiteralNumberNode {
     pub value: BigDecimal,
@@ -523,12 +865,71 @@ impl LiteralUint16Node<TypeVariant> for TypeLiteralUint16Node {}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct TypeLoopNode {
+    pub label: Option<StringTableId>,
     pub nodes: RefCell<TypeBlockNode>,
 }
 
 impl LoopNode<TypeVariant> for TypeLoopNode {}
 
+// iterable holds the value, iterator what next() gets called on - the value itself or what iter() returned on it
+// a string has no next(), the loop walks its characters
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeIteratorLoopNode {
+    pub label: Option<StringTableId>,
+    pub variable: SymbolId,
+    pub iterable: SymbolId,
+    pub value: Box<TypedTreeNode>,
+    pub iterator: SymbolId,
+    pub iter: Option<Box<TypedTreeNode>>,
+    pub next: Option<Box<TypedTreeNode>>,
+    pub nodes: RefCell<TypeBlockNode>,
+}
+
+impl IteratorLoopNode<TypeVariant> for TypeIteratorLoopNode {}
+
+// start, end and step share the type of the variable
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeRangeLoopNode {
+    pub label: Option<StringTableId>,
+    pub variable: SymbolId,
+    pub start: Box<TypedTreeNode>,
+    pub end: Box<TypedTreeNode>,
+    pub inclusive: bool,
+    pub step: Option<Box<TypedTreeNode>>,
+    pub nodes: RefCell<TypeBlockNode>,
+}
+
+impl RangeLoopNode<TypeVariant> for TypeRangeLoopNode {}
+
 #[derive(Debug, Clone, PartialEq)]
-pub struct TypeReturnFromFunctionNode {}
+pub struct TypeMatchNode {
+    pub node: Box<TypedTreeNode>,
+    pub arms: Box<[TypeMatchArm]>,
+}
+
+impl MatchNode<TypeVariant> for TypeMatchNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeMatchArm {
+    // none for otherwise
+    pub variant: Option<TypeId>,
+    // the variables which get the values of the variables of the variant
+    pub bindings: Box<[SymbolId]>,
+    pub nodes: RefCell<TypeBlockNode>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeReturnFromFunctionNode {
+    pub node: Option<Box<TypedTreeNode>>,
+}
 
 impl ReturnFromFunctionNode<TypeVariant> for TypeReturnFromFunctionNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeTryNode {
+    pub node: Box<TypedTreeNode>,
+    // the variant which holds the error and gets returned
+    pub error: TypeId,
+}
+
+impl TryNode<TypeVariant> for TypeTryNode {}
@@ -1,7 +1,7 @@
-use crate::common::node::Node::AccessVariable;
-use crate::common::SymbolName;
-use crate::frontend::ast::AstAccessVariableNode;
-use crate::ir::analyse::{TypeAccessVariableNode, TypedTreeNode};
+use crate::common::node::Node::{AccessVariable, AccessVariableOfObject};
+use crate::common::{Inferred, SymbolName};
+use crate::frontend::ast::{AstAccessVariableNode, AstAccessVariableOfObjectNode, AstAccessVariableOfSelfNode, AstIdentifier};
+use crate::ir::analyse::{TypeAccessVariableNode, TypeAccessVariableOfObjectNode, TypedTreeNode};
 use crate::ir::analyse::pre::Pre;
 
 impl<'a> Pre<'a> {
@@ -9,16 +9,72 @@ impl<'a> Pre<'a> {
         &mut self,
         node: &AstAccessVariableNode,
     ) -> crate::ir::analyse::Result<TypedTreeNode> {
-        let variable = self.variable_get(SymbolName::from(&node.variable))?;
+        let name = SymbolName::from(&node.variable);
+
+        // a function passed by its name, e.g. as callback
+        if self.scope.variable(name).is_none() {
+            if let Some(function) = self.scope.function(name) {
+                self.library_use(function);
+                return Ok(TypedTreeNode::new(
+                    AccessVariable(TypeAccessVariableNode { variable: function }),
+                    self.span(),
+                    self.symbol_table.function(function).inferred.clone(),
+                ));
+            }
+        }
+
+        let variable = self.variable_get(name)?;
+        let (id, inferred) = (variable.id, variable.inferred.clone());
+        self.lambda_capture(name, id);
+
+        // a narrowed variable holds a value
+        let inferred = match inferred {
+            Inferred::Optional(inner) if self.scope.narrowed(id) => *inner,
+            inferred => inferred
+        };
 
         Ok(TypedTreeNode::new(
             AccessVariable(TypeAccessVariableNode {
-                variable: variable.id.clone()
+                variable: id
             }),
             self.span(),
-            variable.inferred.clone(),
+            inferred,
         ))
     }
+
+    pub(crate) fn access_variable_of_object(
+        &mut self,
+        node: &AstAccessVariableOfObjectNode,
+    ) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+
+        let name = SymbolName::from(&node.object);
+        let object = self.variable_get(name)?;
+        let (object, inferred) = (object.id, object.inferred.clone());
+        self.lambda_capture(name, object);
+
+        if let Inferred::Record(_) = &inferred {
+            let record = TypedTreeNode::new(AccessVariable(TypeAccessVariableNode { variable: object }), span, inferred);
+            return self.access_field_of_record(record, node.variable.0);
+        }
+
+        let (variable, inferred) = self.type_variable_get(&inferred, node.variable.0)?;
+
+        Ok(TypedTreeNode::new(
+            AccessVariableOfObject(TypeAccessVariableOfObjectNode { object, variable }),
+            span,
+            inferred,
+        ))
+    }
+
+    // self is the first argument of every method
+    pub(crate) fn access_variable_of_self(
+        &mut self,
+        node: &AstAccessVariableOfSelfNode,
+    ) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let object = AstIdentifier(self.string_table.push_str("self"));
+        self.access_variable_of_object(&AstAccessVariableOfObjectNode { object, variable: node.variable.clone() })
+    }
 }
 
 #[cfg(test)]
@@ -61,4 +117,37 @@ mod tests {
 
         assert_eq!(variable, "value")
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn access_variable_of_external_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            external type Point(x: Int4, y: Float8)
+            let p = Point(x = 1, y = 2.0)
+            p.y
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+        assert_eq!(typed.nodes.len(), 3);
+
+        let result = &typed[2];
+        let inner = result.as_access_variable_of_object();
+        assert_eq!(inner.variable.0, 1);
+
+        assert_eq!(result.inferred, Inferred::Float8);
+    }
+
+    #[test]
+    fn type_variable_does_not_exists() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            external type Point(x: Int4, y: Int4)
+            let p = Point(x = 1, y = 2)
+            p.z
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(Undefined(UndefinedError::UndefinedTypeVariable { r#type, variable, .. })) = result else { panic!() };
+        assert_eq!(r#type, "Point");
+        assert_eq!(variable, "z")
+    }
+}
@@ -0,0 +1,266 @@
+use crate::common::{Symbol, SymbolId, WithSpan};
+use crate::common::node::Node::{InstantiateRecord, InstantiateTuple, InstantiateType, LiteralBoolean, LiteralNone, LiteralNumber, LiteralString};
+use crate::frontend::ast::{AstDeclareFunctionNode, AstFunctionArgument, AstNamedArgument, AstTreeNode};
+use crate::ir::analyse::{ArgumentError, Error};
+use crate::ir::analyse::pre::Pre;
+
+impl<'a> Pre<'a> {
+    pub(crate) fn argument_defaults_register(&mut self, function: SymbolId, arguments: &[AstFunctionArgument]) {
+        if arguments.iter().any(|a| a.default.is_some()) {
+            self.argument_defaults.insert(function, arguments.iter().map(|a| a.default.clone()).collect());
+        }
+    }
+
+    pub(crate) fn argument_defaults_check(&self, node: &AstDeclareFunctionNode) -> crate::ir::analyse::Result<()> {
+        let function = || self.string_table.get(node.function.0).to_string();
+        let mut defaulted = false;
+        for argument in &node.arguments {
+            let error = match &argument.default {
+                Some(default) if !is_constant(default) => ArgumentError::DefaultNotConstant {
+                    function: function(),
+                    argument: self.string_table.get(argument.argument.0).to_string(),
+                    span: default.span(),
+                },
+                None if defaulted => ArgumentError::DefaultMissing {
+                    function: function(),
+                    argument: self.string_table.get(argument.argument.0).to_string(),
+                    span: node.span.clone(),
+                },
+                default => {
+                    defaulted |= default.is_some();
+                    continue;
+                }
+            };
+            return Err(Error::Argument(Box::new(error)));
+        }
+        Ok(())
+    }
+
+    // puts the named arguments at the position of their name and fills the left out ones with their default,
+    // before are the arguments already given like the object of a method, after the ones still to come like a trailing lambda
+    pub(crate) fn arguments_place(
+        &self,
+        function: SymbolId,
+        before: usize,
+        arguments: &[AstTreeNode],
+        named: &[AstNamedArgument],
+        after: usize,
+    ) -> crate::ir::analyse::Result<Vec<AstTreeNode>> {
+        let name = self.string_table.get(self.symbol_table[function].name().0).to_string();
+
+        // a variable holding a function knows nothing about the names of its arguments
+        let Symbol::Function(symbol) = &self.symbol_table[function] else {
+            return match named.first() {
+                Some(argument) => Err(self.argument_unknown(name, argument)),
+                None => Ok(arguments.to_vec())
+            };
+        };
+
+        if symbol.named && !arguments.is_empty() {
+            return Err(Error::Argument(Box::new(ArgumentError::Positional { function: name, span: self.span() })));
+        }
+
+        let defaults = self.argument_defaults.get(&function);
+        let names = &symbol.arguments;
+        // too many arguments are left to the check of the signature
+        if (named.is_empty() && defaults.is_none()) || before + arguments.len() + after > names.len() {
+            return Ok(arguments.to_vec());
+        }
+
+        let end = names.len() - after;
+        let mut result: Vec<Option<AstTreeNode>> = vec![None; end - before];
+        for (index, argument) in arguments.iter().enumerate() {
+            result[index] = Some(argument.clone());
+        }
+
+        for argument in named {
+            let Some(index) = (before..names.len()).find(|index| names[*index] == argument.argument.0) else {
+                return Err(self.argument_unknown(name, argument));
+            };
+            if index >= end || result[index - before].is_some() {
+                return Err(Error::Argument(Box::new(ArgumentError::Duplicate {
+                    function: name,
+                    argument: self.string_table.get(argument.argument.0).to_string(),
+                    span: self.span(),
+                })));
+            }
+            result[index - before] = Some(argument.value.clone());
+        }
+
+        result.into_iter().enumerate()
+            .map(|(index, argument)| {
+                argument
+                    .or_else(|| defaults.and_then(|defaults| defaults[before + index].clone()))
+                    .ok_or_else(|| Error::Argument(Box::new(ArgumentError::Missing {
+                        function: name.clone(),
+                        argument: self.string_table.get(names[before + index]).to_string(),
+                        span: self.span(),
+                    })))
+            })
+            .collect()
+    }
+
+    fn argument_unknown(&self, function: String, argument: &AstNamedArgument) -> Error {
+        Error::Argument(Box::new(ArgumentError::Unknown {
+            function,
+            argument: self.string_table.get(argument.argument.0).to_string(),
+            span: self.span(),
+        }))
+    }
+}
+
+// literals and the tuples, records and types made of them, a variable would be the one of the caller
+fn is_constant(node: &AstTreeNode) -> bool {
+    match node.node() {
+        LiteralBoolean(_) | LiteralNone(_) | LiteralNumber(_) | LiteralString(_) => true,
+        InstantiateRecord(record) => record.fields.iter().all(|field| is_constant(&field.value)),
+        InstantiateTuple(tuple) => tuple.nodes.iter().all(is_constant),
+        InstantiateType(r#type) => r#type.arguments.iter().all(|argument| is_constant(&argument.value)),
+        _ => false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::node::Node;
+    use crate::common::Context;
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{prepare, ArgumentError};
+    use crate::ir::analyse::Error::Argument;
+
+    #[test]
+    fn named_arguments_in_declared_order() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function connect(host: String, port: Number) {}
+        connect(port = 80, host = 'localhost')
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let call = typed[1].as_call_function();
+        assert!(matches!(call.arguments[0].node(), Node::LiteralString(_)));
+        assert!(matches!(call.arguments[1].node(), Node::LiteralNumber(_)));
+    }
+
+    #[test]
+    fn default_of_left_out_argument() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function connect(host: String, port: Number = 80, secure: Bool = false) {}
+        connect('localhost', secure = true)
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let call = typed[1].as_call_function();
+        assert_eq!(call.arguments.len(), 3);
+        assert!(matches!(call.arguments[1].node(), Node::LiteralNumber(_)));
+        assert!(matches!(call.arguments[2].node(), Node::LiteralBoolean(_)));
+    }
+
+    #[test]
+    fn named_argument_of_method() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Server(host: String)
+        define Server {
+            function listen(port: Number = 80, backlog: Number = 16) {}
+        }
+        let server = Server(host = 'localhost')
+        server.listen(backlog = 32)
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let call = typed[3].as_call_function_of_object();
+        assert_eq!(call.arguments.len(), 2);
+    }
+
+    #[test]
+    fn missing_argument() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function connect(host: String, port: Number) {}
+        connect(port = 80)
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Argument(error) = result.err().unwrap() else { panic!() };
+        let ArgumentError::Missing { function, argument, .. } = *error else { panic!() };
+        assert_eq!(function, "connect");
+        assert_eq!(argument, "host");
+    }
+
+    #[test]
+    fn duplicate_argument() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function connect(host: String, port: Number) {}
+        connect('localhost', host = 'remote', port = 80)
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Argument(error) = result.err().unwrap() else { panic!() };
+        let ArgumentError::Duplicate { argument, .. } = *error else { panic!() };
+        assert_eq!(argument, "host");
+    }
+
+    #[test]
+    fn unknown_argument() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function connect(host: String) {}
+        connect(host = 'localhost', timeout = 10)
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Argument(error) = result.err().unwrap() else { panic!() };
+        let ArgumentError::Unknown { function, argument, .. } = *error else { panic!() };
+        assert_eq!(function, "connect");
+        assert_eq!(argument, "timeout");
+    }
+
+    #[test]
+    fn positional_argument_of_named_function() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        named function connect(host: String, port: Number) {}
+        connect(host = 'localhost', port = 80)
+        connect('localhost', port = 80)
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Argument(error) = result.err().unwrap() else { panic!() };
+        let ArgumentError::Positional { function, .. } = *error else { panic!() };
+        assert_eq!(function, "connect");
+    }
+
+    #[test]
+    fn default_of_variable() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        let base = 10
+        function add(x: Number, by: Number = base) -> Number { return x + by }
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Argument(error) = result.err().unwrap() else { panic!() };
+        let ArgumentError::DefaultNotConstant { function, argument, span } = *error else { panic!() };
+        assert_eq!(function, "add");
+        assert_eq!(argument, "by");
+        assert_eq!(span.start.index, source.rfind("base").unwrap());
+    }
+
+    #[test]
+    fn argument_without_default_after_default() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function connect(port: Number = 80, host: String) {}
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Argument(error) = result.err().unwrap() else { panic!() };
+        let ArgumentError::DefaultMissing { function, argument, .. } = *error else { panic!() };
+        assert_eq!(function, "connect");
+        assert_eq!(argument, "host");
+    }
+}
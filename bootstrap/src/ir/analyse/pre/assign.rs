@@ -0,0 +1,284 @@
+use crate::common::{Inferred, SymbolName};
+use crate::common::node::Node::{AssignVariable, AssignVariableOfObject, LiteralNumber};
+use crate::frontend::ast::{AstAssignVariableNode, AstAssignVariableOfObjectNode, AstLiteralNumberNode};
+use crate::ir::analyse::{AssignError, Error, ReadonlyError, TypeAssignVariableNode, TypeAssignVariableOfObjectNode, TypedTreeNode};
+use crate::ir::analyse::pre::Pre;
+
+impl<'a> Pre<'a> {
+    // the value has to fit the type the variable got declared or inferred with
+    pub(crate) fn assign_variable(
+        &mut self,
+        node: &AstAssignVariableNode,
+    ) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+
+        let name = SymbolName::from(&node.variable);
+        let symbol = self.variable_get(name)?;
+        let (variable, expected, symbol_readonly) = (symbol.id, symbol.inferred.clone(), symbol.readonly);
+
+        if !symbol.mutable {
+            return Err(Error::Assign(Box::new(AssignError::ImmutableVariable {
+                variable: self.string_table.get(name.0).to_string(),
+                declaration: symbol.span.clone(),
+                span,
+            })));
+        }
+
+        if self.lambda_captures(name) {
+            return Err(Error::Assign(Box::new(AssignError::CapturedVariable {
+                variable: self.string_table.get(name.0).to_string(),
+                span,
+            })));
+        }
+
+        let value = match node.value.node() {
+            LiteralNumber(literal) => self.literal_number_as(literal, &expected)?,
+            _ => self.node(&node.value)?
+        };
+        let value = self.record_into(value, &expected)?;
+
+        if self.readonly(&value) && !symbol_readonly {
+            return Err(Error::Readonly(Box::new(ReadonlyError::AssignedToMutable {
+                variable: self.string_table.get(name.0).to_string(),
+                span,
+            })));
+        }
+
+        let matches = match &expected {
+            Inferred::Type(r#trait) if self.type_table.is_trait(*r#trait) => self.trait_implemented(&value.inferred, *r#trait),
+            Inferred::Optional(inner) => value.inferred.is_none() || value.inferred == **inner || value.inferred == expected,
+            expected => *expected == value.inferred
+        };
+
+        if !matches {
+            return Err(Error::Assign(Box::new(AssignError::TypeMissMatch {
+                variable: self.string_table.get(name.0).to_string(),
+                expected: expected.to_string(self.string_table, self.type_table),
+                got: value.inferred.to_string(self.string_table, self.type_table),
+                span,
+            })));
+        }
+
+        // a narrowed variable which gets a value that may be none is not narrowed anymore
+        if value.inferred.is_optional() {
+            self.scope.widen(variable);
+        }
+
+        Ok(TypedTreeNode::new(
+            AssignVariable(TypeAssignVariableNode { variable, value: Box::new(value) }),
+            span,
+            Inferred::Unit,
+        ))
+    }
+
+    // a number literal takes the type of the variable it gets assigned to
+    pub(crate) fn literal_number_as(&mut self, node: &AstLiteralNumberNode, inferred: &Inferred) -> crate::ir::analyse::Result<TypedTreeNode> {
+        match inferred {
+            Inferred::Optional(inner) => self.literal_number_as(node, inner),
+            Inferred::Float4 => self.literal_number_float4(node),
+            Inferred::Float8 => self.literal_number_float8(node),
+            Inferred::Int1 => self.literal_number_int1(node),
+            Inferred::Int2 => self.literal_number_int2(node),
+            Inferred::Int4 => self.literal_number_int4(node),
+            Inferred::Int8 => self.literal_number_int8(node),
+            Inferred::Int16 => self.literal_number_int16(node),
+            Inferred::Uint1 => self.literal_number_uint1(node),
+            Inferred::Uint2 => self.literal_number_uint2(node),
+            Inferred::Uint4 => self.literal_number_uint4(node),
+            Inferred::Uint8 => self.literal_number_uint8(node),
+            Inferred::Uint16 => self.literal_number_uint16(node),
+            _ => self.literal_number(node)
+        }
+    }
+
+    pub(crate) fn assign_variable_of_object(
+        &mut self,
+        node: &AstAssignVariableOfObjectNode,
+    ) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+
+        let name = SymbolName::from(&node.object);
+        let object = self.variable_get(name)?;
+        let (object, inferred) = (object.id, object.inferred.clone());
+        self.lambda_capture(name, object);
+        self.readonly_field_check(object, node.variable.0)?;
+
+        let (variable, _) = self.type_variable_get(&inferred, node.variable.0)?;
+        let value = Box::new(self.node(&node.value)?);
+
+        Ok(TypedTreeNode::new(
+            AssignVariableOfObject(TypeAssignVariableOfObjectNode { object, variable, value }),
+            span,
+            Inferred::Unit,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, Inferred};
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{AssignError, prepare, TypeMissMatchError};
+    use crate::ir::analyse::Error::{Assign, TypeMissMatch};
+
+    #[test]
+    fn assign_variable() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            let counter = 1
+            counter = 2
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let result = &typed[1];
+        let inner = result.as_assign_variable();
+        assert_eq!(inner.variable, typed[0].as_declared_variable().variable);
+        assert_eq!(inner.value.inferred, Inferred::Number);
+
+        assert_eq!(result.inferred, Inferred::Unit);
+    }
+
+    #[test]
+    fn assign_literal_takes_declared_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            let counter: Int4 = 1
+            counter = 2
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        assert_eq!(typed[1].as_assign_variable().value.inferred, Inferred::Int4);
+    }
+
+    #[test]
+    fn assign_none_to_optional() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            let value: Number? = 1
+            value = none
+            value = 2
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn assign_narrowed_optional_variable() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            function value(n: Number?) -> Number {
+                let result = 0
+                if n != none {
+                    result = n
+                    result = n + 1
+                }
+                return result
+            }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn assign_optional_to_narrowed_variable() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            function value(n: Number?, m: Number?) -> Number {
+                let current = n
+                if current != none {
+                    current = m
+                    let inner: Number = current
+                    return inner
+                }
+                return 0
+            }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+        let Err(TypeMissMatch(TypeMissMatchError::DeclaredTypeMissMatch { expected, got, .. })) = result else { panic!() };
+        assert_eq!(expected, "Number");
+        assert_eq!(got, "Number?");
+    }
+
+    #[test]
+    fn assign_value_of_other_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            let counter = 1
+            counter = 'Elodie'
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Assign(error) = result.err().unwrap() else { panic!() };
+        let AssignError::TypeMissMatch { variable, expected, got, .. } = *error else { panic!() };
+        assert_eq!(variable, "counter");
+        assert_eq!(expected, "Number");
+        assert_eq!(got, "String");
+    }
+
+    #[test]
+    fn assign_const() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            const limit = 1
+            limit = 2
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Assign(error) = result.err().unwrap() else { panic!() };
+        let AssignError::ImmutableVariable { variable, declaration, span } = *error else { panic!() };
+        assert_eq!(variable, "limit");
+        assert_eq!(declaration.start.index, 13);
+        assert_eq!(span.start.index, 41);
+    }
+
+    #[test]
+    fn assign_argument() {
+        let mut ctx = Context::testing();
+        let source = r#"
+            function next(value: Number) -> Number {
+                value = 2
+                return value
+            }
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Assign(error) = result.err().unwrap() else { panic!() };
+        let AssignError::ImmutableVariable { variable, declaration, .. } = *error else { panic!() };
+        assert_eq!(variable, "value");
+        assert_eq!(declaration.start.index, source.find("value").unwrap());
+    }
+
+    #[test]
+    fn assign_captured_variable() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            let counter = 1
+            let increment = { () -> counter = 2 }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Assign(error) = result.err().unwrap() else { panic!() };
+        let AssignError::CapturedVariable { variable, .. } = *error else { panic!() };
+        assert_eq!(variable, "counter");
+    }
+
+    #[test]
+    fn assign_variable_of_external_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            external type Point(x: Int4, y: Int4)
+            let p = Point(x = 1, y = 2)
+            p.y = 3
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+        assert_eq!(typed.nodes.len(), 3);
+
+        let result = &typed[2];
+        let inner = result.as_assign_variable_of_object();
+        assert_eq!(inner.variable.0, 1);
+        assert_eq!(inner.value.inferred, Inferred::Number);
+
+        assert_eq!(result.inferred, Inferred::Unit);
+    }
+}
@@ -1,6 +1,8 @@
+use crate::common::Inferred;
 use crate::common::node::Node;
 use crate::frontend::ast::AstCalculateNode;
 use crate::ir::analyse::{TypeCalculateNode, TypedTreeNode};
+use crate::ir::analyse::pre::operator::calculate_operator;
 use crate::ir::analyse::pre::Pre;
 
 impl<'a> Pre<'a> {
@@ -8,6 +10,21 @@ impl<'a> Pre<'a> {
         let left = self.node(node.left.as_ref())?;
         let right = self.node(node.right.as_ref())?;
 
+        if let Some(type_id) = self.operator_type(&left.inferred) {
+            let operator = calculate_operator(&node.operator);
+            let Some(function) = self.operator_get(type_id, operator) else {
+                return Err(self.operator_undefined(&left.inferred, operator));
+            };
+            return self.operator_call(function, vec![left, right]);
+        }
+
+        // a value of a type parameter may be of any type, it takes part in no calculation
+        for operand in [&left, &right] {
+            if matches!(operand.inferred, Inferred::Parameter(_)) {
+                return Err(self.operator_undefined(&operand.inferred, calculate_operator(&node.operator)));
+            }
+        }
+
         let inferred = left.inferred.clone();
 
         Ok(
@@ -1,31 +1,504 @@
-use crate::common::{Inferred, Span, SymbolId};
+use crate::common::{Inferred, Symbol, SymbolId, SymbolName, WithSpan};
 use crate::common::node::Node;
-use crate::frontend::ast::AstCallFunctionOfPackageNode;
-use crate::ir::analyse::{TypeCallFunctionOfPackageNode, TypedTreeNode};
+use crate::frontend::ast::{AStCallFunctionNode, AstCallFunctionOfObjectNode, AstCallFunctionOfPackageNode, AstCallFunctionWithLambdaNode, AstDeclareLambdaNode, AstTreeNode};
+use crate::ir::analyse::{CallbackError, Error, TypeAccessVariableNode, TypeCallFunctionNode, TypeCallFunctionOfObjectNode, TypeCallFunctionOfPackageNode, TypeCallFunctionWithLambdaNode, TypedTreeNode, UndefinedError};
 use crate::ir::analyse::pre::Pre;
 
 impl<'a> Pre<'a> {
-    pub(crate) fn call_function_of_package(&mut self, node: &AstCallFunctionOfPackageNode) -> crate::ir::analyse::Result<TypedTreeNode> {
-        // get package
-        // get function from package
+    pub(crate) fn call_function(&mut self, node: &AStCallFunctionNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
 
-        let mut arguments = vec![];
-        for arg in &node.arguments {
-            arguments.push(
-                self.node(arg)?
-            )
+        if let Some(result) = self.call_operator(node)? {
+            return Ok(result);
         }
 
+        let function = self.callee_get(SymbolName::from(&node.function))?;
+        let arguments = self.arguments_place(function, 0, &node.arguments, &node.named, 0)?;
+        let arguments = self.call_arguments(function, &arguments)?;
+        let inferred = self.call_result(function, arguments.iter())?;
+
+        Ok(TypedTreeNode::new(
+            Node::CallFunction(
+                TypeCallFunctionNode {
+                    function,
+                    arguments,
+                }
+            ),
+            span,
+            inferred,
+        ))
+    }
+
+    // the trailing block becomes a lambda without arguments, passed as last argument
+    pub(crate) fn call_function_with_lambda(&mut self, node: &AstCallFunctionWithLambdaNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+
+        let function = self.callee_get(SymbolName::from(&node.function))?;
+        let arguments = self.arguments_place(function, 0, &node.arguments, &node.named, 1)?;
+        let arguments = self.call_arguments(function, &arguments)?;
+
+        let expected = self.call_argument_expected(function, &arguments)?;
+
+        let lambda = AstDeclareLambdaNode { span: span.clone(), arguments: vec![], nodes: node.lambda.clone() };
+        let lambda = self.declare_lambda(&lambda, expected.as_ref())?;
+        if self.is_external(function) {
+            self.call_arguments_callbacks(std::slice::from_ref(&lambda))?;
+        }
+
+        let inferred = self.call_result(function, arguments.iter().chain(std::iter::once(&lambda)))?;
+
+        Ok(TypedTreeNode::new(
+            Node::CallFunctionWithLambda(
+                TypeCallFunctionWithLambdaNode {
+                    function,
+                    arguments,
+                    lambda: Box::new(lambda),
+                }
+            ),
+            span,
+            inferred,
+        ))
+    }
+
+    pub(crate) fn call_function_of_package(&mut self, node: &AstCallFunctionOfPackageNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+
+        let package = self.package_get(&node.package)?;
+        let function = self.package_function_get(package, SymbolName::from(&node.function))?;
+        self.library_use(function);
+
+        let arguments = self.arguments_place(function, 0, &node.arguments, &node.named, 0)?;
+        let arguments = self.call_arguments(function, &arguments)?;
+        let inferred = self.call_result(function, arguments.iter())?;
+
         Ok(TypedTreeNode::new(
             Node::CallFunctionOfPackage(
                 TypeCallFunctionOfPackageNode {
-                    package: SymbolId(2),
-                    function: SymbolId(3),
+                    package,
+                    function,
+                    arguments,
+                }
+            ),
+            span,
+            inferred,
+        ))
+    }
+
+    // the object becomes the first argument of the method
+    pub(crate) fn call_function_of_object(&mut self, node: &AstCallFunctionOfObjectNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+
+        let name = SymbolName::from(&node.object);
+        let object = self.variable_get(name)?;
+        let (object, object_inferred) = (object.id, object.inferred.clone());
+        self.lambda_capture(name, object);
+
+        let (function, dispatch) = self.method_get(&object_inferred, node.function.0)?;
+        self.readonly_method_check(object, function)?;
+        self.library_use(function);
+
+        let receiver = TypedTreeNode::new(
+            Node::AccessVariable(TypeAccessVariableNode { variable: object }),
+            span.clone(),
+            object_inferred,
+        );
+        let arguments = self.arguments_place(function, 1, &node.arguments, &node.named, 0)?;
+        let mut arguments = self.call_arguments_after(function, vec![receiver], &arguments)?;
+        let inferred = self.call_result(function, arguments.iter())?;
+        arguments.remove(0);
+
+        Ok(TypedTreeNode::new(
+            Node::CallFunctionOfObject(
+                TypeCallFunctionOfObjectNode {
+                    object,
+                    function,
                     arguments: arguments.into_boxed_slice(),
+                    dispatch,
                 }
             ),
-            self.span(),
-            Inferred::Unknown,
+            span,
+            inferred,
         ))
     }
-}
\ No newline at end of file
+
+    // calling a value of a custom type calls its call operator function, with the value as first argument
+    fn call_operator(&mut self, node: &AStCallFunctionNode) -> crate::ir::analyse::Result<Option<TypedTreeNode>> {
+        let name = SymbolName::from(&node.function);
+        let Some(variable) = self.scope.variable(name) else {
+            return Ok(None);
+        };
+        let inferred = self.symbol_table.variable(variable).inferred.clone();
+        let Some(type_id) = self.operator_type(&inferred) else {
+            return Ok(None);
+        };
+        let Some(function) = self.operator_get(type_id, "call") else {
+            return Err(self.operator_undefined(&inferred, "call"));
+        };
+        self.lambda_capture(name, variable);
+
+        let receiver = TypedTreeNode::new(
+            Node::AccessVariable(TypeAccessVariableNode { variable }),
+            self.span(),
+            inferred,
+        );
+        self.readonly_argument_check(function, 0, &receiver)?;
+        let arguments = self.arguments_place(function, 1, &node.arguments, &node.named, 0)?;
+        let arguments = self.call_arguments_after(function, vec![receiver], &arguments)?;
+        let inferred = self.call_result(function, arguments.iter())?;
+
+        Ok(Some(TypedTreeNode::new(
+            Node::CallFunction(TypeCallFunctionNode { function, arguments: arguments.into_boxed_slice() }),
+            self.span(),
+            inferred,
+        )))
+    }
+
+    fn call_arguments(&mut self, function: SymbolId, arguments: &[AstTreeNode]) -> crate::ir::analyse::Result<Box<[TypedTreeNode]>> {
+        Ok(self.call_arguments_after(function, Vec::with_capacity(arguments.len()), arguments)?.into_boxed_slice())
+    }
+
+    // lambdas take their signature from the parameter they get passed as
+    fn call_arguments_after(&mut self, function: SymbolId, mut result: Vec<TypedTreeNode>, arguments: &[AstTreeNode]) -> crate::ir::analyse::Result<Vec<TypedTreeNode>> {
+        for argument in arguments {
+            let argument = if let Node::DeclareLambda(lambda) = argument.node() {
+                self.scope.span_set(argument.span());
+                let expected = self.call_argument_expected(function, &result)?;
+                self.declare_lambda(lambda, expected.as_ref())?
+            } else {
+                // arguments of type parameters are checked against each other by the type arguments they bind
+                let parameterized = match self.symbol_table.signature(function) {
+                    Inferred::Function(parameters, _) => parameters.get(result.len()).is_some_and(Inferred::is_parameterized),
+                    _ => false
+                };
+                match self.call_argument_expected(function, &result)? {
+                    Some(expected) if !parameterized => self.declared_value(argument, &expected)?,
+                    Some(expected) => {
+                        let argument = self.node(argument)?;
+                        self.record_into(argument, &expected)?
+                    }
+                    None => self.node(argument)?
+                }
+            };
+            self.readonly_argument_check(function, result.len(), &argument)?;
+            result.push(argument)
+        }
+
+        if self.is_external(function) {
+            self.call_arguments_callbacks(&result)?;
+        }
+
+        Ok(result)
+    }
+
+    // the type of the next argument, with the type arguments the arguments before it bound
+    fn call_argument_expected(&self, function: SymbolId, arguments: &[TypedTreeNode]) -> crate::ir::analyse::Result<Option<Inferred>> {
+        let Inferred::Function(parameters, _) = self.symbol_table.signature(function) else {
+            return Ok(None);
+        };
+        let Some(expected) = parameters.get(arguments.len()) else {
+            return Ok(None);
+        };
+
+        let type_arguments = self.type_arguments(parameters, &arguments.iter().collect::<Vec<_>>())?;
+        Ok(Some(expected.substitute(&type_arguments)))
+    }
+
+    // the result of a generic function is the return type with the type arguments inferred from the arguments
+    pub(crate) fn call_result<'n>(&self, function: SymbolId, arguments: impl Iterator<Item=&'n TypedTreeNode>) -> crate::ir::analyse::Result<Inferred> {
+        let Inferred::Function(parameters, result) = self.symbol_table.signature(function) else {
+            return Ok(self.function_return(function));
+        };
+
+        let arguments = arguments.collect::<Vec<_>>();
+        let type_arguments = self.type_arguments(parameters, &arguments)?;
+        let arguments = arguments.iter().map(|a| a.inferred.clone()).collect::<Vec<_>>();
+
+        // values passed as trait or as bound type parameter have to implement the trait
+        for (parameter, argument) in parameters.iter().zip(&arguments) {
+            if let Some(r#trait) = parameter.type_id().filter(|type_id| self.type_table.is_trait(*type_id)) {
+                self.trait_check(argument, r#trait)?;
+            }
+        }
+        if let Symbol::Function(symbol) = &self.symbol_table[function] {
+            for (parameter, r#trait) in &symbol.bounds {
+                if let Some(argument) = type_arguments.get(parameter) {
+                    self.trait_check(argument, *r#trait)?;
+                }
+            }
+        }
+
+        Ok(result.substitute(&type_arguments))
+    }
+
+    // a c function pointer can not carry the variables a lambda captures
+    fn call_arguments_callbacks(&self, arguments: &[TypedTreeNode]) -> crate::ir::analyse::Result<()> {
+        for argument in arguments {
+            if let Node::DeclareLambda(lambda) = argument.node() {
+                if let Some(variable) = lambda.captures.first() {
+                    return Err(Error::Callback(CallbackError::CapturingClosure {
+                        variable: self.string_table.get(self.symbol_table.variable(*variable).name.0).to_string(),
+                        span: argument.span.clone(),
+                    }));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // a variable which holds a function shadows functions of the same name
+    fn callee_get(&mut self, name: SymbolName) -> crate::ir::analyse::Result<SymbolId> {
+        if let Some(variable) = self.scope.variable(name) {
+            if let Inferred::Function(..) = self.symbol_table.variable(variable).inferred {
+                self.lambda_capture(name, variable);
+                return Ok(variable);
+            }
+        }
+
+        let function = self.function_get(name)?;
+        self.library_use(function);
+        Ok(function)
+    }
+
+    fn is_external(&self, function: SymbolId) -> bool {
+        matches!(&self.symbol_table[function], Symbol::Function(symbol) if symbol.external)
+    }
+
+    // functions of the current package and the packages it is nested in shadow functions of the scope
+    fn function_get(&self, name: SymbolName) -> crate::ir::analyse::Result<SymbolId> {
+        let mut current = self.package;
+        while let Some(package) = current {
+            if let Some(result) = self.package_table[package].functions.get(&name) {
+                return Ok(*result);
+            }
+            current = self.package_table[package].parent;
+        }
+
+        self.scope.function(name)
+            .ok_or(Error::Undefined(UndefinedError::UndefinedFunction {
+                function: self.string_table.get(name.0).to_string(),
+                span: self.span(),
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, Inferred, SymbolId};
+    use crate::common::node::Node;
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{prepare, TypeMissMatchError, UndefinedError};
+    use crate::ir::analyse::Error::{TypeMissMatch, Undefined};
+
+    #[test]
+    fn call_function_of_same_package() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        package io {
+            function write(s: String) {}
+            export function print(s: String) { write(s) }
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let package = typed[0].as_declare_package();
+        let print = package.functions[1].as_declare_function();
+        let call = print.nodes[0].as_call_function();
+        assert_eq!(ctx.symbol_name(call.function), "write");
+        assert_eq!(call.arguments.len(), 1);
+    }
+
+    #[test]
+    fn call_function_infers_return_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function one() -> Number { return 1 }
+        one()
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let call = typed[1].as_call_function();
+        assert_eq!(call.function, SymbolId(1));
+        assert_eq!(typed[1].inferred, Inferred::Number);
+    }
+
+    #[test]
+    fn call_undefined_function() {
+        let mut ctx = Context::testing();
+        let source = "does_not_exist()";
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(Undefined(UndefinedError::UndefinedFunction { function, .. })) = result else { panic!() };
+        assert_eq!(function, "does_not_exist");
+    }
+
+    #[test]
+    fn call_function_held_by_variable() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let offset: Int4 = 1
+        let add: function(Int4) -> Int4 = { (x) -> offset }
+        add(offset)
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let declared = typed[1].as_declared_variable();
+        let lambda = declared.value.as_declare_lambda();
+        assert_eq!(ctx.symbol_table.variable(lambda.arguments[0]).inferred, Inferred::Int4);
+        assert_eq!(lambda.captures.len(), 1);
+        assert_eq!(ctx.symbol_name(lambda.captures[0]), "offset");
+
+        let call = typed[2].as_call_function();
+        assert_eq!(call.function, declared.variable);
+        assert_eq!(typed[2].inferred, Inferred::Int4);
+    }
+
+    #[test]
+    fn call_function_with_trailing_lambda() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function check(description: String, body: function() -> Bool) {
+            let passed = body()
+        }
+        let expected = true
+        check('captures') { expected }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let call = typed[2].as_call_function_with_lambda();
+        assert_eq!(ctx.symbol_name(call.function), "check");
+        assert_eq!(call.arguments.len(), 1);
+
+        let lambda = call.lambda.as_declare_lambda();
+        assert_eq!(lambda.arguments.len(), 0);
+        assert_eq!(lambda.captures.len(), 1);
+        assert_eq!(call.lambda.inferred, Inferred::Function(Box::new([]), Box::new(Inferred::Boolean)));
+        assert_eq!(lambda.nodes[0].as_return_from_function().node.as_ref().unwrap().inferred, Inferred::Boolean);
+
+        let check = typed[0].as_declare_function();
+        let passed = check.nodes[0].as_declared_variable();
+        assert_eq!(passed.value.inferred, Inferred::Boolean);
+    }
+
+    #[test]
+    fn call_generic_function_infers_type_argument() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type List<T>()
+        function first<T>(l: List<T>) -> T { }
+        let numbers: List<Number> = List()
+        first(numbers)
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        assert_eq!(typed[3].inferred, Inferred::Number);
+    }
+
+    #[test]
+    fn call_generic_function_with_conflicting_type_arguments() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        function same<T>(left: T, right: T) -> T { return left }
+        same(1, 'two')
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(TypeMissMatch(TypeMissMatchError::TypeArgumentMissMatch { parameter, expected, got, span })) = result else { panic!() };
+        assert_eq!(parameter, "T");
+        assert_eq!(expected, "Number");
+        assert_eq!(got, "String");
+        assert_eq!(span.start.index, source.find("'two'").unwrap());
+    }
+
+    #[test]
+    fn declared_type_names_substituted_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function identity<T>(value: T) -> T { return value }
+        let value: String = identity(23)
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(TypeMissMatch(TypeMissMatchError::DeclaredTypeMissMatch { expected, got, .. })) = result else { panic!() };
+        assert_eq!(expected, "String");
+        assert_eq!(got, "Number");
+    }
+
+    #[test]
+    fn lambda_takes_inferred_type_arguments() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function apply<T, R>(value: T, f: function(T) -> R) -> R { return f(value) }
+        apply('Elodie', { (s) -> true })
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let call = typed[1].as_call_function();
+        assert_eq!(call.arguments[1].inferred, Inferred::Function(Box::new([Inferred::String]), Box::new(Inferred::Boolean)));
+        assert_eq!(typed[1].inferred, Inferred::Boolean);
+    }
+
+    #[test]
+    fn call_argument_literal_as_declared_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function twice(x: Int4) -> Int4 { return x + x }
+        twice(2)
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let call = typed[1].as_call_function();
+        assert!(matches!(call.arguments[0].node(), Node::LiteralInt4(_)));
+    }
+
+    #[test]
+    fn call_argument_different_type_then_declared() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        function one(x: Int4) -> Int4 { return x }
+        one('hello')
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(TypeMissMatch(TypeMissMatchError::DeclaredTypeMissMatch { expected, got, span })) = result else { panic!() };
+        assert_eq!(expected, "Int4");
+        assert_eq!(got, "String");
+        assert_eq!(span.start.index, source.find("'hello'").unwrap());
+    }
+
+    #[test]
+    fn call_argument_different_type_arguments_then_declared() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        type Box<T>(value: T)
+        function take(b: Box<String>) {}
+        let b = Box(value = 5)
+        take(b)
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(TypeMissMatch(TypeMissMatchError::DeclaredTypeMissMatch { expected, got, span })) = result else { panic!() };
+        assert_eq!(expected, "Box<String>");
+        assert_eq!(got, "Box<Number>");
+        assert_eq!(span.start.index, source.find("b)").unwrap());
+    }
+
+    #[test]
+    fn call_argument_tuple_of_different_types_then_declared() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        function take(p: (Number, String)) {}
+        take(('a', 1))
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(TypeMissMatch(TypeMissMatchError::DeclaredTypeMissMatch { expected, got, span })) = result else { panic!() };
+        assert_eq!(expected, "(Number, String)");
+        assert_eq!(got, "(String, Number)");
+        assert_eq!(span.start.index, source.find("('a'").unwrap());
+    }
+}
@@ -2,12 +2,25 @@ use crate::common::Inferred;
 use crate::common::node::Node;
 use crate::frontend::ast::AstCompareNode;
 use crate::ir::analyse::{TypeCompareNode, TypedTreeNode};
+use crate::ir::analyse::pre::operator::compare_operator;
 use crate::ir::analyse::pre::Pre;
 
 impl<'a> Pre<'a> {
     pub(crate) fn compare(&mut self, node: &AstCompareNode) -> crate::ir::analyse::Result<TypedTreeNode> {
         let left = self.node(node.left.as_ref())?;
         let right = self.node(node.right.as_ref())?;
+
+        // values of custom types compare by their operator functions, only against none they compare the builtin way
+        let operator = compare_operator(&node.operator);
+        if let Some(type_id) = self.operator_type(&left.inferred) {
+            if let Some(function) = self.operator_get(type_id, operator) {
+                return self.operator_call(function, vec![left, right]);
+            }
+            if !left.inferred.is_none() && !right.inferred.is_none() {
+                return Err(self.operator_undefined(&left.inferred, operator));
+            }
+        }
+
         Ok(
             TypedTreeNode::new(
                 Node::Compare(TypeCompareNode {
@@ -1,14 +1,20 @@
 use std::cell::RefCell;
 use std::ops::Deref;
+use std::str::FromStr;
 
-use crate::common::Inferred;
-use crate::common::node::Node::{BreakLoop, If, Loop};
-use crate::frontend::ast::{AstBreakLoopNode, AstIfNode, AstLoopNode};
-use crate::ir::analyse::{TypeBlockNode, TypeBreakLoopNode, TypedTreeNode, TypeIfNode, TypeLoopNode};
+use bigdecimal::{BigDecimal, Zero};
+
+use crate::common::{Inferred, Span, StringTableId, WithSpan};
+use crate::common::node::CompareOperator;
+use crate::common::node::Node::{Block, BreakLoop, ContinueLoop, Defer, If, IteratorLoop, LiteralNumber, Loop, Match, RangeLoop, ReturnFromFunction};
+use crate::frontend::ast::{AstBreakLoopNode, AstContinueLoopNode, AstDeferNode, AstIdentifier, AstIfNode, AstLoopNode, AstRangeLoopNode, AstTreeNode};
+use crate::ir::analyse::{DeferError, Error, RangeError, TypeBlockNode, TypeBreakLoopNode, TypeContinueLoopNode, TypeDeferNode, TypedTreeNode, TypeIfNode, TypeLoopNode, TypeRangeLoopNode, UndefinedError};
+use crate::ir::analyse::infer::optional::narrowing;
 use crate::ir::analyse::pre::Pre;
 
 impl<'a> Pre<'a> {
     pub(crate) fn r#break(&mut self, node: &AstBreakLoopNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let label = self.label_check(node.label.as_ref(), |span| DeferError::Break { span })?;
         let node = if let Some(node) = &node.node {
             Some(Box::new(self.node(node)?))
         } else {
@@ -18,37 +24,209 @@ impl<'a> Pre<'a> {
         let inferred = node.clone().map(|n| n.inferred).unwrap_or(Inferred::Unit);
 
         Ok(TypedTreeNode::new(
-            BreakLoop(TypeBreakLoopNode { node }),
+            BreakLoop(TypeBreakLoopNode { label, node }),
             self.span(),
             inferred,
         ))
     }
 
+    pub(crate) fn r#continue(&mut self, node: &AstContinueLoopNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let label = self.label_check(node.label.as_ref(), |span| DeferError::Continue { span })?;
+        Ok(TypedTreeNode::new(
+            ContinueLoop(TypeContinueLoopNode { label }),
+            self.span(),
+            Inferred::Unit,
+        ))
+    }
+
     pub(crate) fn r#loop(&mut self, node: &AstLoopNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
         let mut nodes = vec![];
 
+        let label = self.loop_enter(node.label.as_ref());
         self.scope.enter();
         for node in &node.nodes {
             nodes.push(self.node(node.deref())?)
         }
         self.scope.leave();
+        self.loop_leave(label);
 
-        // FIXME this is not correct
-        let inferred = nodes.last().clone().map(|n| n.inferred.clone()).unwrap_or(Inferred::Unit);
+        let nodes = TypeBlockNode { nodes: nodes.into_boxed_slice() };
+        let inferred = Self::loop_inferred(&nodes, label);
 
         Ok(TypedTreeNode::new(
             Loop(TypeLoopNode {
-                nodes: RefCell::new(TypeBlockNode { nodes: nodes.into_boxed_slice() })
+                label,
+                nodes: RefCell::new(nodes)
             }),
-            self.span(),
+            span,
             inferred,
         ))
     }
 
+    pub(crate) fn range_loop(&mut self, node: &AstRangeLoopNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+
+        // the counter counts in the type of the first bound which is not a literal
+        let mut bounds = vec![node.start.deref(), node.end.deref()];
+        bounds.extend(node.step.as_deref());
+
+        // a step of 0 never gets to the end
+        if let Some(step) = node.step.as_deref() {
+            if let LiteralNumber(literal) = step.node() {
+                if BigDecimal::from_str(self.string_table.get(literal.0.value())).is_ok_and(|value| value.is_zero()) {
+                    return Err(Error::Range(RangeError::ZeroStep { span: step.span() }));
+                }
+            }
+        }
+
+        let mut typed = Vec::with_capacity(bounds.len());
+        for bound in &bounds {
+            typed.push(match bound.node() {
+                LiteralNumber(_) => None,
+                _ => Some(self.node(bound)?)
+            });
+        }
+
+        let inferred = typed.iter().flatten().next().map(|n| n.inferred.clone()).unwrap_or(Inferred::Number);
+        if !matches!(inferred, Inferred::Number | Inferred::Int1 | Inferred::Int2 | Inferred::Int4 | Inferred::Int8 | Inferred::Int16
+            | Inferred::Uint1 | Inferred::Uint2 | Inferred::Uint4 | Inferred::Uint8 | Inferred::Uint16) {
+            return Err(Error::Range(RangeError::NotANumber {
+                got: inferred.to_string(self.string_table, self.type_table),
+                span: typed.iter().flatten().next().unwrap().span.clone(),
+            }));
+        }
+
+        let mut bounds = bounds.into_iter().zip(typed).map(|(bound, typed)| self.range_bound(bound, typed, &inferred));
+        let start = Box::new(bounds.next().unwrap()?);
+        let end = Box::new(bounds.next().unwrap()?);
+        let step = bounds.next().transpose()?.map(Box::new);
+
+        let label = self.loop_enter(node.label.as_ref());
+        self.scope.enter();
+        let variable = self.variable_declare(&node.variable, inferred, false, false, span.clone());
+        let mut nodes = vec![];
+        for node in &node.nodes {
+            nodes.push(self.node(node)?)
+        }
+        self.scope.leave();
+        self.loop_leave(label);
+
+        Ok(TypedTreeNode::new(
+            RangeLoop(TypeRangeLoopNode {
+                label,
+                variable,
+                start,
+                end,
+                inclusive: node.inclusive,
+                step,
+                nodes: RefCell::new(TypeBlockNode { nodes: nodes.into_boxed_slice() }),
+            }),
+            span,
+            Inferred::Unit,
+        ))
+    }
+
+    pub(crate) fn loop_enter(&mut self, label: Option<&AstIdentifier>) -> Option<StringTableId> {
+        let label = label.map(|label| label.0);
+        self.flow.labels.extend(label);
+        self.flow.loops += 1;
+        label
+    }
+
+    pub(crate) fn loop_leave(&mut self, label: Option<StringTableId>) {
+        if label.is_some() {
+            self.flow.labels.pop();
+        }
+        self.flow.loops -= 1;
+    }
+
+    // break and continue can only be used in a loop and only refer to the label of a loop they are in,
+    // and only to a loop inside of the defer block they are in
+    fn label_check(&mut self, label: Option<&AstIdentifier>, error: fn(Span) -> DeferError) -> crate::ir::analyse::Result<Option<StringTableId>> {
+        let position = match label {
+            Some(label) => match self.flow.labels.iter().rposition(|l| *l == label.0) {
+                Some(position) => Some(position),
+                None => return Err(Error::Undefined(UndefinedError::UndefinedLabel {
+                    label: self.string_table.get(label.0).to_string(),
+                    span: self.span(),
+                }))
+            },
+            None if self.flow.loops == 0 => return Err(Error::Undefined(UndefinedError::UndefinedLoop { span: self.span() })),
+            None => None
+        };
+
+        if let Some((loops, labels)) = self.flow.defer {
+            let leaves_defer = match position {
+                Some(position) => position < labels,
+                None => self.flow.loops == loops
+            };
+            if leaves_defer {
+                return Err(Error::Defer(error(self.span())));
+            }
+        }
+
+        Ok(label.map(|label| label.0))
+    }
+
+    // return and value? can not leave a defer block, it runs while the function already gets left
+    pub(crate) fn defer_check(&self, error: fn(Span) -> DeferError) -> crate::ir::analyse::Result<()> {
+        match self.flow.defer {
+            Some(_) => Err(Error::Defer(error(self.span()))),
+            None => Ok(())
+        }
+    }
+
+    pub(crate) fn defer(&mut self, node: &AstDeferNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+        let outer = self.flow.defer.replace((self.flow.loops, self.flow.labels.len()));
+
+        self.scope.enter();
+        let mut nodes = vec![];
+        for node in &node.nodes {
+            nodes.push(self.node(node)?)
+        }
+        self.scope.leave();
+        self.flow.defer = outer;
+
+        Ok(TypedTreeNode::new(
+            Defer(TypeDeferNode {
+                nodes: RefCell::new(TypeBlockNode { nodes: nodes.into_boxed_slice() }),
+            }),
+            span,
+            Inferred::Unit,
+        ))
+    }
+
+    fn range_bound(&mut self, bound: &AstTreeNode, typed: Option<TypedTreeNode>, inferred: &Inferred) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let typed = match (bound.node(), typed) {
+            (LiteralNumber(literal), None) => {
+                self.scope.span_set(bound.span());
+                self.literal_number_as(literal, inferred)?
+            }
+            (_, typed) => typed.unwrap()
+        };
+
+        if &typed.inferred != inferred {
+            return Err(Error::Range(RangeError::BoundTypeMissMatch {
+                expected: inferred.to_string(self.string_table, self.type_table),
+                got: typed.inferred.to_string(self.string_table, self.type_table),
+                span: typed.span.clone(),
+            }));
+        }
+        Ok(typed)
+    }
+
     pub(crate) fn r#if(&mut self, node: &AstIfNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
         let condition = Box::new(self.node(node.condition.deref())?);
+        let narrowing = narrowing(self.symbol_table, &condition);
 
+        // x != none holds a value in then, x == none in otherwise
         self.scope.enter();
+        if let Some((variable, CompareOperator::NotEqual)) = narrowing {
+            self.scope.narrow(variable);
+        }
         let mut then_body = vec![];
         for node in &node.then.nodes {
             then_body.push(self.node(node.deref())?)
@@ -56,6 +234,9 @@ impl<'a> Pre<'a> {
         self.scope.leave();
 
         self.scope.enter();
+        if let Some((variable, CompareOperator::Equal)) = narrowing {
+            self.scope.narrow(variable);
+        }
         let otherwise = if node.otherwise.is_some() {
             let mut otherwise_body = vec![];
             for node in &node.otherwise.as_ref().unwrap().nodes {
@@ -69,16 +250,100 @@ impl<'a> Pre<'a> {
         };
         self.scope.leave();
 
+        let then = TypeBlockNode { nodes: then_body.into_boxed_slice() };
+        // only a then which returns keeps the narrowing for the rest of the enclosing block
+        if let Some((variable, CompareOperator::Equal)) = narrowing {
+            if otherwise.is_none() && Self::returns(&then) {
+                self.scope.narrow(variable);
+            }
+        }
+
+        let r#if = TypeIfNode {
+            condition,
+            then: RefCell::new(then),
+            otherwise,
+        };
+        let inferred = Self::if_inferred(&r#if);
+
         Ok(TypedTreeNode::new(
-            If(TypeIfNode {
-                condition,
-                then: RefCell::new(TypeBlockNode { nodes: then_body.into_boxed_slice() }),
-                otherwise,
-            }),
-            self.span(),
-            Inferred::Unit, // FIXME
+            If(r#if),
+            span,
+            inferred,
         ))
     }
+
+    // the value of a block is its last node
+    pub(crate) fn returns(block: &TypeBlockNode) -> bool {
+        matches!(block.nodes.last().map(|n| &n.node), Some(ReturnFromFunction(_)))
+    }
+
+    pub(crate) fn block_inferred(block: &TypeBlockNode) -> Inferred {
+        block.nodes.last().map(|n| n.inferred.clone()).unwrap_or(Inferred::Unit)
+    }
+
+    // branches which all end with a value of the same type have a value, otherwise there is none
+    pub(crate) fn branch_inferred(branches: impl IntoIterator<Item=Inferred>) -> Inferred {
+        let mut result: Option<Inferred> = None;
+        for inferred in branches {
+            match &result {
+                None => result = Some(inferred),
+                Some(result) if *result == inferred => {}
+                Some(_) => return Inferred::Unit
+            }
+        }
+        result.unwrap_or(Inferred::Unit)
+    }
+
+    // an if only has a value if it has an otherwise
+    pub(crate) fn if_inferred(node: &TypeIfNode) -> Inferred {
+        match &node.otherwise {
+            Some(otherwise) => Self::branch_inferred([
+                Self::block_inferred(&node.then.borrow()),
+                Self::block_inferred(&otherwise.borrow()),
+            ]),
+            None => Inferred::Unit
+        }
+    }
+
+    // a loop has the value its breaks hand out
+    pub(crate) fn loop_inferred(block: &TypeBlockNode, label: Option<StringTableId>) -> Inferred {
+        let mut breaks = vec![];
+        Self::loop_breaks(block, label, false, &mut breaks);
+        Self::branch_inferred(breaks)
+    }
+
+    // a break within a nested loop belongs to that loop, unless it names the label of this one
+    fn loop_breaks(block: &TypeBlockNode, label: Option<StringTableId>, nested: bool, result: &mut Vec<Inferred>) {
+        for node in block.nodes.iter() {
+            match &node.node {
+                BreakLoop(r#break) => {
+                    let belongs = match r#break.label {
+                        Some(_) => r#break.label == label,
+                        None => !nested
+                    };
+                    if belongs {
+                        result.push(r#break.node.as_ref().map(|n| n.inferred.clone()).unwrap_or(Inferred::Unit))
+                    }
+                }
+                Block(block) => Self::loop_breaks(block, label, nested, result),
+                If(r#if) => {
+                    Self::loop_breaks(&r#if.then.borrow(), label, nested, result);
+                    if let Some(otherwise) = &r#if.otherwise {
+                        Self::loop_breaks(&otherwise.borrow(), label, nested, result);
+                    }
+                }
+                Match(r#match) => {
+                    for arm in r#match.arms.iter() {
+                        Self::loop_breaks(&arm.nodes.borrow(), label, nested, result);
+                    }
+                }
+                Loop(r#loop) if label.is_some() => Self::loop_breaks(&r#loop.nodes.borrow(), label, true, result),
+                RangeLoop(r#loop) if label.is_some() => Self::loop_breaks(&r#loop.nodes.borrow(), label, true, result),
+                IteratorLoop(r#loop) if label.is_some() => Self::loop_breaks(&r#loop.nodes.borrow(), label, true, result),
+                _ => {}
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -87,8 +352,10 @@ mod tests {
 
     use crate::common::{Context, Inferred, SymbolId};
     use crate::common::node::CompareOperator;
+    use crate::common::node::Node::ContinueLoop;
     use crate::frontend::ast_from_str;
-    use crate::ir::analyse::prepare;
+    use crate::ir::analyse::{prepare, DeferError, RangeError, TypeMissMatchError, UndefinedError};
+    use crate::ir::analyse::Error::{Defer, Range, TypeMissMatch, Undefined};
 
     #[test]
     fn nested_if() {
@@ -130,4 +397,421 @@ mod tests {
 
         assert_eq!(result.inferred, Inferred::Unit);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn range_of_literals() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        for i in 0..10 {
+            i
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let result = typed[0].as_range_loop();
+        assert!(!result.inclusive);
+        assert!(result.step.is_none());
+        assert_eq!(result.start.inferred, Inferred::Number);
+        assert_eq!(result.end.inferred, Inferred::Number);
+
+        let nodes = result.nodes.borrow();
+        let access = nodes.nodes[0].as_access_variable();
+        assert_eq!(access.variable, result.variable);
+        assert_eq!(nodes.nodes[0].inferred, Inferred::Number);
+    }
+
+    #[test]
+    fn range_takes_type_of_bound() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function count(end: Uint2) {
+            for i in 1..=end step 2 {
+                i
+            }
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let function = typed[0].as_declare_function();
+        let result = function.nodes[0].as_range_loop();
+        assert!(result.inclusive);
+        assert_eq!(result.start.as_literal_uint2().value, 1);
+        assert_eq!(result.step.as_ref().unwrap().as_literal_uint2().value, 2);
+
+        let nodes = result.nodes.borrow();
+        assert_eq!(nodes.nodes[0].inferred, Inferred::Uint2);
+    }
+
+    #[test]
+    fn range_bound_type_miss_match() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function count(start: Int4, end: Int8) {
+            for i in start..end { }
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Range(RangeError::BoundTypeMissMatch { expected, got, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(expected, "Int4");
+        assert_eq!(got, "Int8");
+    }
+
+    #[test]
+    fn range_with_step_of_zero() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        for i in 0..10 step 0 {
+            i
+        }
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Range(RangeError::ZeroStep { span }) = result.err().unwrap() else { panic!() };
+        assert_eq!(span.start.index, source.find("0 {").unwrap());
+    }
+
+    #[test]
+    fn range_of_strings() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        for i in 'a'..'z' { }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Range(RangeError::NotANumber { got, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(got, "String");
+    }
+
+    #[test]
+    fn range_variable_only_lives_in_loop() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        for i in 0..10 { }
+        i
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Undefined(UndefinedError::UndefinedVariable { variable, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(variable, "i");
+    }
+
+    #[test]
+    fn continue_in_nested_if() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let x = true
+        loop {
+            if x == true {
+                continue
+            }
+            break
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let looped = typed[1].as_loop();
+        let nodes = looped.nodes.borrow();
+        let then = nodes.nodes[0].as_if().then.borrow();
+        let ContinueLoop(_) = &then.nodes[0].node else { panic!() };
+        assert_eq!(then.nodes[0].inferred, Inferred::Unit);
+    }
+
+    #[test]
+    fn if_with_otherwise_has_value() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let c = true
+        let s = if c { 'a' } else { 'b' }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let declared = typed[1].as_declared_variable();
+        assert_eq!(declared.value.inferred, Inferred::String);
+    }
+
+    #[test]
+    fn if_with_different_branches_has_no_value() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let c = true
+        if c { 'a' } else { 1 }
+        if c { 'a' }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        assert_eq!(typed[1].inferred, Inferred::Unit);
+        assert_eq!(typed[2].inferred, Inferred::Unit);
+    }
+
+    #[test]
+    fn loop_has_value_of_break() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let c = true
+        let v = loop {
+            loop {
+                break 'inner'
+            }
+            if c {
+                break 42
+            }
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let declared = typed[1].as_declared_variable();
+        assert_eq!(declared.value.inferred, Inferred::Number);
+    }
+
+    #[test]
+    fn declare_if_without_otherwise() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        let c = true
+        let s = if c { 'a' }
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let TypeMissMatch(TypeMissMatchError::NoValue { span }) = result.err().unwrap() else { panic!() };
+        assert_eq!(span.start.index, source.find("if c").unwrap());
+    }
+
+    #[test]
+    fn declare_if_with_different_branches() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let c = true
+        let u = if c { 1 } else { 'x' }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let TypeMissMatch(TypeMissMatchError::NoValue { .. }) = result.err().unwrap() else { panic!() };
+    }
+
+    #[test]
+    fn declare_loop_without_value() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        let u = loop { break }
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let TypeMissMatch(TypeMissMatchError::NoValue { span }) = result.err().unwrap() else { panic!() };
+        assert_eq!(span.start.index, source.find("loop").unwrap());
+    }
+
+    #[test]
+    fn declare_loop_of_other_type() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        let x: Number = loop { break 'no' }
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let TypeMissMatch(TypeMissMatchError::DeclaredTypeMissMatch { expected, got, span }) = result.err().unwrap() else { panic!() };
+        assert_eq!(expected, "Number");
+        assert_eq!(got, "String");
+        assert_eq!(span.start.index, source.find("loop").unwrap());
+    }
+
+    #[test]
+    fn labeled_break_of_outer_loop() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let v = 'outer: loop {
+            for i in 0..10 {
+                loop {
+                    break 'outer 'found'
+                }
+            }
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let declared = typed[0].as_declared_variable();
+        assert_eq!(declared.value.inferred, Inferred::String);
+    }
+
+    #[test]
+    fn labeled_continue_of_outer_loop() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        'rows: for row in 0..3 {
+            for column in 0..3 {
+                continue 'rows
+            }
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let outer = typed[0].as_range_loop();
+        let nodes = outer.nodes.borrow();
+        let inner = nodes.deref().nodes[0].as_range_loop();
+        let inner_nodes = inner.nodes.borrow();
+        let ContinueLoop(node) = &inner_nodes.deref().nodes[0].node else { panic!() };
+        assert_eq!(node.label, outer.label);
+    }
+
+    #[test]
+    fn undefined_label() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        'outer: loop {
+            break 'inner
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Undefined(UndefinedError::UndefinedLabel { label, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(label, "inner");
+    }
+
+    #[test]
+    fn break_outside_of_loop() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        let c = true
+        if c {
+            break
+        }
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Undefined(UndefinedError::UndefinedLoop { span }) = result.err().unwrap() else { panic!() };
+        assert_eq!(span.start.index, source.find("break").unwrap());
+    }
+
+    #[test]
+    fn continue_in_function_inside_of_loop() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        loop {
+            function skip() {
+                continue
+            }
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Undefined(UndefinedError::UndefinedLoop { .. }) = result.err().unwrap() else { panic!() };
+    }
+
+    #[test]
+    fn label_of_loop_around_function_is_undefined() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        'outer: loop {
+            function leave() {
+                loop {
+                    continue 'outer
+                }
+            }
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Undefined(UndefinedError::UndefinedLabel { label, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(label, "outer");
+    }
+
+    #[test]
+    fn defer() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let value = 1
+        defer {
+            let other = value
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        assert_eq!(typed[1].inferred, Inferred::Unit);
+        let defer = typed[1].as_defer();
+        assert_eq!(defer.nodes.borrow().nodes.len(), 1);
+    }
+
+    #[test]
+    fn break_out_of_defer() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        loop {
+            defer {
+                break
+            }
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        assert!(matches!(result.err().unwrap(), Defer(DeferError::Break { .. })));
+    }
+
+    #[test]
+    fn labeled_continue_out_of_defer() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        'outer: loop {
+            defer {
+                loop {
+                    continue 'outer
+                }
+            }
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        assert!(matches!(result.err().unwrap(), Defer(DeferError::Continue { .. })));
+    }
+
+    #[test]
+    fn break_of_loop_inside_defer() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        'outer: loop {
+            defer {
+                'inner: loop {
+                    loop {
+                        break 'inner
+                    }
+                    break
+                }
+            }
+            break
+        }
+        "#).unwrap();
+        assert!(prepare(&mut ctx, ast).is_ok());
+    }
+
+    #[test]
+    fn return_out_of_defer() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function close() {
+            defer {
+                return
+            }
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        assert!(matches!(result.err().unwrap(), Defer(DeferError::Return { .. })));
+    }
+
+    #[test]
+    fn return_of_function_inside_defer() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function close() {
+            defer {
+                function inner() -> Number { return 1 }
+            }
+        }
+        "#).unwrap();
+        assert!(prepare(&mut ctx, ast).is_ok());
+    }
+}
@@ -0,0 +1,395 @@
+use crate::common::{Inferred, SymbolId, SymbolName, WithSpan};
+use crate::common::node::Node::{DeclareExternalFunction, DeclareFunction, LiteralNumber, ReturnFromFunction};
+use crate::frontend::ast::{AstDeclareExternalFunctionNode, AstDeclareFunctionNode, AstFunctionArgument, AstReturnFromFunctionNode, AstTreeNode, AstType};
+use crate::ir::analyse::{DeferError, Error, ExternalError, TypeDeclareExternalFunctionNode, TypeDeclareFunctionNode, TypedTreeNode, TypeMissMatchError, TypeReturnFromFunctionNode};
+use crate::ir::analyse::pre::Pre;
+
+impl<'a> Pre<'a> {
+    pub(crate) fn declare_function(&mut self, node: &AstDeclareFunctionNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        self.operator_check(node, false)?;
+        let function = self.function_register(node);
+        let symbol = self.symbol_table[function].clone();
+        self.scope.register_symbol(&symbol);
+
+        self.function_body(function, node)
+    }
+
+    pub(crate) fn declare_external_function(&mut self, node: &AstDeclareExternalFunctionNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let function = self.external_function_register(node);
+        self.external_function_check(function, node)?;
+        let symbol = self.symbol_table[function].clone();
+        self.scope.register_symbol(&symbol);
+
+        Ok(TypedTreeNode::new(
+            DeclareExternalFunction(TypeDeclareExternalFunctionNode { function }),
+            node.span.clone(),
+            Inferred::Unit,
+        ))
+    }
+
+    pub(crate) fn function_register(&mut self, node: &AstDeclareFunctionNode) -> SymbolId {
+        let outer = self.type_parameters_enter(&node.type_parameters);
+        let inferred = self.function_signature(&node.arguments, node.return_type.as_ref());
+        let bounds = self.type_parameters[outer.len()..].iter()
+            .filter_map(|(parameter, bound)| bound.map(|bound| (*parameter, bound)))
+            .collect();
+        self.type_parameters = outer;
+
+        let result = self.symbol_table.register_function(SymbolName::from(&node.function));
+        let symbol = self.symbol_table.function_mut(result);
+        symbol.inferred = inferred;
+        symbol.package_id = self.package;
+        symbol.bounds = bounds;
+        symbol.exported = node.modifiers.is_exported();
+        symbol.readonly = node.arguments.iter().map(|a| a.readonly).collect();
+        symbol.arguments = node.arguments.iter().map(|a| a.argument.0).collect();
+        symbol.named = node.modifiers.is_named();
+        symbol.span = node.span.clone();
+        self.argument_defaults_register(result, &node.arguments);
+        result
+    }
+
+    pub(crate) fn external_function_register(&mut self, node: &AstDeclareExternalFunctionNode) -> SymbolId {
+        let outer = self.type_parameters_enter(&node.type_parameters);
+        let inferred = self.function_signature(&node.arguments, node.return_type.as_ref());
+        self.type_parameters = outer;
+
+        let result = self.symbol_table.register_function(SymbolName::from(&node.function));
+        let symbol = self.symbol_table.function_mut(result);
+        symbol.inferred = inferred;
+        symbol.package_id = self.package;
+        symbol.external = true;
+        symbol.exported = node.modifiers.is_exported();
+        symbol.readonly = node.arguments.iter().map(|a| a.readonly).collect();
+        symbol.arguments = node.arguments.iter().map(|a| a.argument.0).collect();
+        symbol.span = node.span.clone();
+        self.argument_defaults_register(result, &node.arguments);
+        result
+    }
+
+    // the c function only knows numbers, booleans, strings, external types and functions of them,
+    // external functions of sysroot work with vals instead
+    pub(crate) fn external_function_check(&self, function: SymbolId, node: &AstDeclareExternalFunctionNode) -> crate::ir::analyse::Result<()> {
+        let sysroot = self.package
+            .and_then(|package| self.package_table.path(package).first().copied())
+            .is_some_and(|package| self.string_table.get(self.symbol_table[self.package_table[package].symbol].name().0) == "sysroot");
+        if sysroot {
+            return Ok(());
+        }
+        let Inferred::Function(arguments, result) = self.symbol_table.signature(function) else {
+            return Ok(());
+        };
+
+        let name = self.string_table.get(node.function.0).to_string();
+        for (inferred, argument) in arguments.iter().zip(&node.arguments) {
+            if !self.external_supported(inferred) {
+                return Err(Error::External(Box::new(ExternalError::Argument {
+                    function: name,
+                    argument: self.string_table.get(argument.argument.0).to_string(),
+                    r#type: inferred.to_string(self.string_table, self.type_table),
+                    span: node.span.clone(),
+                })));
+            }
+        }
+        if !self.external_supported(result) {
+            return Err(Error::External(Box::new(ExternalError::Return {
+                function: name,
+                r#type: result.to_string(self.string_table, self.type_table),
+                span: node.span.clone(),
+            })));
+        }
+        Ok(())
+    }
+
+    fn external_supported(&self, inferred: &Inferred) -> bool {
+        match inferred {
+            Inferred::Function(arguments, result) => arguments.iter().all(|a| self.external_supported(a)) && self.external_supported(result),
+            Inferred::Pointer(inner) => self.external_supported(inner),
+            Inferred::Type(type_id) => self.type_table.is_external(*type_id),
+            Inferred::Unknown | Inferred::Optional(_) | Inferred::Parameter(_) | Inferred::Parameterized(..)
+            | Inferred::Record(_) | Inferred::Tuple(_) | Inferred::OneOf(_) | Inferred::AllOf(_) => false,
+            _ => true
+        }
+    }
+
+    pub(crate) fn function_signature(&self, arguments: &[AstFunctionArgument], return_type: Option<&AstType>) -> Inferred {
+        Inferred::Function(
+            arguments.iter()
+                .map(|a| a.argument_type.as_ref().map(|t| self.inferred_from_ast_type(t)).unwrap_or(Inferred::Unknown))
+                .collect(),
+            Box::new(return_type.map(|r| self.inferred_from_ast_type(r)).unwrap_or(Inferred::Unit)),
+        )
+    }
+
+    // the inferred return type of a call to the given function
+    pub(crate) fn function_return(&self, function: SymbolId) -> Inferred {
+        match self.symbol_table.signature(function) {
+            Inferred::Function(_, result) => result.as_ref().clone(),
+            _ => Inferred::Unknown
+        }
+    }
+
+    pub(crate) fn function_body(&mut self, function: SymbolId, node: &AstDeclareFunctionNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        self.argument_defaults_check(node)?;
+        for bound in node.type_parameters.iter().filter_map(|p| p.bound.as_ref()) {
+            self.trait_get(bound, &node.span)?;
+        }
+
+        let outer = self.type_parameters_enter(&node.type_parameters);
+        let outer_function = self.function.replace(function);
+        // break, continue and return can not leave the function towards a loop or defer around its declaration
+        let outer_flow = std::mem::take(&mut self.flow);
+        self.scope.enter();
+
+        let mut arguments = Vec::with_capacity(node.arguments.len());
+        for argument in &node.arguments {
+            let inferred = argument.argument_type.as_ref()
+                .map(|t| self.inferred_from_ast_type(t))
+                .unwrap_or(Inferred::Unknown);
+
+            let variable = self.variable_register(SymbolName::from(&argument.argument), inferred);
+            let symbol = self.symbol_table.variable_mut(variable);
+            symbol.readonly = argument.readonly;
+            symbol.span = argument.span.clone();
+            arguments.push(variable);
+        }
+
+        let mut nodes = Vec::with_capacity(node.nodes.nodes.len());
+        for node in &node.nodes.nodes {
+            nodes.push(self.node(node)?)
+        }
+
+        self.scope.leave();
+        self.function = outer_function;
+        self.flow = outer_flow;
+        self.type_parameters = outer;
+
+        Ok(TypedTreeNode::new(
+            DeclareFunction(TypeDeclareFunctionNode {
+                function,
+                arguments: arguments.into_boxed_slice(),
+                nodes: nodes.into_boxed_slice(),
+            }),
+            node.span.clone(),
+            Inferred::Unit,
+        ))
+    }
+
+    pub(crate) fn r#return(&mut self, node: &AstReturnFromFunctionNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        self.defer_check(|span| DeferError::Return { span })?;
+        let node = if let Some(node) = &node.node {
+            let expected = self.function.map(|function| self.function_return(function)).unwrap_or(Inferred::Unknown);
+            let node = self.declared_value(node, &expected)?;
+            self.readonly_return_check(&node)?;
+            Some(Box::new(node))
+        } else {
+            None
+        };
+
+        let inferred = node.as_ref().map(|n| n.inferred.clone()).unwrap_or(Inferred::Unit);
+
+        Ok(TypedTreeNode::new(
+            ReturnFromFunction(TypeReturnFromFunctionNode { node }),
+            self.span(),
+            inferred,
+        ))
+    }
+
+    // a value handed over as a declared type, a number literal takes that type and a record becomes it
+    pub(crate) fn declared_value(&mut self, node: &AstTreeNode, expected: &Inferred) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let value = match node.node() {
+            LiteralNumber(literal) => self.literal_number_as(literal, expected)?,
+            _ => self.node(node)?
+        };
+        let value = self.record_into(value, expected)?;
+
+        if !self.declared_type_matches(&value.inferred, expected) {
+            return Err(Error::TypeMissMatch(TypeMissMatchError::DeclaredTypeMissMatch {
+                expected: expected.to_string(self.string_table, self.type_table),
+                got: value.inferred.to_string(self.string_table, self.type_table),
+                span: node.span(),
+            }));
+        }
+        Ok(value)
+    }
+
+    // builtin types, custom types and tuples get compared, type parameters, traits and lambdas are checked elsewhere
+    fn declared_type_matches(&self, value: &Inferred, expected: &Inferred) -> bool {
+        let concrete = |inferred: &Inferred| match inferred {
+            Inferred::Type(type_id) | Inferred::Parameterized(type_id, _) => !self.type_table.is_trait(*type_id),
+            Inferred::Boolean | Inferred::Number | Inferred::String
+            | Inferred::Float4 | Inferred::Float8
+            | Inferred::Int1 | Inferred::Int2 | Inferred::Int4 | Inferred::Int8 | Inferred::Int16
+            | Inferred::Uint1 | Inferred::Uint2 | Inferred::Uint4 | Inferred::Uint8 | Inferred::Uint16 => true,
+            _ => false
+        };
+
+        match (value, expected) {
+            (_, Inferred::Optional(inner)) => value.is_none() || value == expected || self.declared_type_matches(value, inner),
+            (Inferred::Optional(inner), _) => self.declared_type_matches(inner, expected),
+            (Inferred::Tuple(values), Inferred::Tuple(expected)) => {
+                values.len() == expected.len() && values.iter().zip(expected.iter()).all(|(value, expected)| self.declared_type_matches(value, expected))
+            }
+            _ if !concrete(value) || !concrete(expected) => true,
+            (Inferred::Parameterized(value_type, value_arguments), Inferred::Parameterized(expected_type, expected_arguments)) => {
+                value_type == expected_type && value_arguments.len() == expected_arguments.len()
+                    && value_arguments.iter().zip(expected_arguments.iter()).all(|(value, expected)| self.declared_type_matches(value, expected))
+            }
+            _ => value.type_id() == expected.type_id()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, Inferred, SymbolId};
+    use crate::common::node::Node;
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{prepare, ExternalError};
+    use crate::ir::analyse::Error::{External, TypeMissMatch};
+    use crate::ir::analyse::TypeMissMatchError::DeclaredTypeMissMatch;
+
+    #[test]
+    fn declare_function() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function add(left: Number, right: Number) -> Number {
+            return left + right
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+        assert_eq!(typed.nodes.len(), 1);
+
+        let result = typed[0].as_declare_function();
+        assert_eq!(result.function, SymbolId(1));
+        assert_eq!(result.arguments.as_ref(), &[SymbolId(2), SymbolId(3)]);
+        assert_eq!(result.nodes.len(), 1);
+
+        assert_eq!(ctx.symbol_name(SymbolId(1)), "add");
+        assert_eq!(ctx.symbol_table.variable(SymbolId(2)).inferred, Inferred::Number);
+    }
+
+    #[test]
+    fn declare_external_function() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        external function puts(s: String) -> Int4
+        puts('Elodie')
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+        assert_eq!(typed.nodes.len(), 2);
+
+        let declared = typed[0].as_declare_external_function();
+        assert!(ctx.symbol_table.function(declared.function).external);
+
+        let call = typed[1].as_call_function();
+        assert_eq!(call.function, declared.function);
+        assert_eq!(typed[1].inferred, Inferred::Int4);
+    }
+
+    #[test]
+    fn return_literal_as_declared_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function one() -> Int4 { return 1 }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let function = typed[0].as_declare_function();
+        let r#return = function.nodes[0].as_return_from_function();
+        let value = r#return.node.as_ref().unwrap();
+        assert!(matches!(value.node(), Node::LiteralInt4(_)));
+        assert_eq!(value.inferred, Inferred::Int4);
+    }
+
+    #[test]
+    fn return_different_type_then_declared() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        function one() -> Int4 { return 'x' }
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(TypeMissMatch(DeclaredTypeMissMatch { expected, got, span })) = result else { panic!() };
+        assert_eq!(expected, "Int4");
+        assert_eq!(got, "String");
+        assert_eq!(span.start.index, source.find("'x'").unwrap());
+    }
+
+    #[test]
+    fn return_tuple_of_different_types_then_declared() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        function pair() -> (Number, String) { return ('a', 1) }
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(TypeMissMatch(DeclaredTypeMissMatch { expected, got, .. })) = result else { panic!() };
+        assert_eq!(expected, "(Number, String)");
+        assert_eq!(got, "(String, Number)");
+    }
+
+    #[test]
+    fn external_function_with_argument_of_custom_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Point(x: Int4)
+        external function draw(p: Point)
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(External(error)) = result else { panic!() };
+        let ExternalError::Argument { function, argument, r#type, .. } = *error else { panic!() };
+        assert_eq!(function, "draw");
+        assert_eq!(argument, "p");
+        assert_eq!(r#type, "Point");
+    }
+
+    #[test]
+    fn external_function_of_package_returning_custom_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        package geometry {
+            type Point(x: Int4)
+            external function origin() -> Point
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(External(error)) = result else { panic!() };
+        let ExternalError::Return { function, r#type, .. } = *error else { panic!() };
+        assert_eq!(function, "origin");
+        assert_eq!(r#type, "Point");
+    }
+
+    #[test]
+    fn external_function_with_type_parameter() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        external function first<T>(value: T) -> T
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(External(error)) = result else { panic!() };
+        let ExternalError::Argument { function, argument, r#type, .. } = *error else { panic!() };
+        assert_eq!(function, "first");
+        assert_eq!(argument, "value");
+        assert_eq!(r#type, "T");
+    }
+
+    #[test]
+    fn sysroot_external_function_with_type_parameter() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        package sysroot {
+            export external function first<T>(value: T) -> T
+        }
+        sysroot::first(1)
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        assert_eq!(typed[1].inferred, Inferred::Number);
+    }
+}
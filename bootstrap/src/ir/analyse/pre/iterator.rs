@@ -0,0 +1,237 @@
+use std::cell::RefCell;
+
+use crate::common::{Inferred, Span, SymbolId, SymbolName, TypeId};
+use crate::common::node::Node::{AccessVariable, CallFunctionOfObject, IteratorLoop};
+use crate::frontend::ast::AstIteratorLoopNode;
+use crate::ir::analyse::{Error, TypeAccessVariableNode, TypeBlockNode, TypeCallFunctionOfObjectNode, TypedTreeNode, TypeIteratorLoopNode, UndefinedError};
+use crate::ir::analyse::pre::Pre;
+
+impl<'a> Pre<'a> {
+    // for x in value calls next() until it returns none - on the value itself if it is an Iterator, otherwise on what value.iter() returns
+    pub(crate) fn iterator_loop(&mut self, node: &AstIteratorLoopNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+
+        let value = self.node(&node.iterable)?;
+        self.scope.span_set(span.clone());
+
+        let readonly = self.readonly(&value);
+        let iterable = self.iterator_variable("iterable", value.inferred.clone(), readonly);
+
+        let (iterator, iter, next, inferred) = if value.inferred == Inferred::String {
+            (iterable, None, None, Inferred::String)
+        } else {
+            let r#trait = self.iterator_trait()?;
+            let (iterator, iter) = if self.trait_implemented(&value.inferred, r#trait) {
+                (iterable, None)
+            } else if self.iterator_method(&value.inferred, "iter") {
+                let iter = self.iterator_call(iterable, "iter", &span)?;
+                self.trait_check(&iter.inferred, r#trait)?;
+                let iterator = self.iterator_variable("iterator", iter.inferred.clone(), false);
+                (iterator, Some(Box::new(iter)))
+            } else {
+                return Err(self.trait_check(&value.inferred, r#trait).unwrap_err());
+            };
+
+            let next = self.iterator_call(iterator, "next", &span)?;
+            let inferred = match &next.inferred {
+                Inferred::Optional(inner) => inner.as_ref().clone(),
+                inferred => inferred.clone()
+            };
+            (iterator, iter, Some(Box::new(next)), inferred)
+        };
+
+        let label = self.loop_enter(node.label.as_ref());
+        self.scope.enter();
+        let variable = self.variable_declare(&node.variable, inferred, false, readonly, span.clone());
+        let mut nodes = vec![];
+        for node in &node.nodes {
+            nodes.push(self.node(node)?)
+        }
+        self.scope.leave();
+        self.loop_leave(label);
+
+        Ok(TypedTreeNode::new(
+            IteratorLoop(TypeIteratorLoopNode {
+                label,
+                variable,
+                iterable,
+                value: Box::new(value),
+                iterator,
+                iter,
+                next,
+                nodes: RefCell::new(TypeBlockNode { nodes: nodes.into_boxed_slice() }),
+            }),
+            span,
+            Inferred::Unit,
+        ))
+    }
+
+    fn iterator_trait(&mut self) -> crate::ir::analyse::Result<TypeId> {
+        let name = self.string_table.push_str("Iterator");
+        self.type_get(SymbolName(name))
+            .and_then(|symbol| self.symbol_table[symbol].type_id())
+            .filter(|type_id| self.type_table.is_trait(*type_id))
+            .ok_or(Error::Undefined(UndefinedError::UndefinedType {
+                r#type: "Iterator".to_string(),
+                span: self.span(),
+            }))
+    }
+
+    // the loop holds the iterable and the iterator in variables of its own, which can not be referred to by name
+    fn iterator_variable(&mut self, name: &str, inferred: Inferred, readonly: bool) -> SymbolId {
+        let name = SymbolName(self.string_table.push_str(name));
+        let result = self.symbol_table.register_variable(name, inferred);
+        let span = self.span();
+        let symbol = self.symbol_table.variable_mut(result);
+        symbol.readonly = readonly;
+        symbol.span = span;
+        result
+    }
+
+    fn iterator_method(&mut self, inferred: &Inferred, method: &str) -> bool {
+        let method = self.string_table.push_str(method);
+        self.method_get(inferred, method).is_ok()
+    }
+
+    fn iterator_call(&mut self, object: SymbolId, method: &str, span: &Span) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let inferred = self.symbol_table.variable(object).inferred.clone();
+        let method = self.string_table.push_str(method);
+        let (function, dispatch) = self.method_get(&inferred, method)?;
+        self.readonly_method_check(object, function)?;
+        self.library_use(function);
+
+        let receiver = TypedTreeNode::new(
+            AccessVariable(TypeAccessVariableNode { variable: object }),
+            span.clone(),
+            inferred,
+        );
+        let inferred = self.call_result(function, std::iter::once(&receiver))?;
+
+        Ok(TypedTreeNode::new(
+            CallFunctionOfObject(TypeCallFunctionOfObjectNode {
+                object,
+                function,
+                arguments: Box::new([]),
+                dispatch,
+            }),
+            span.clone(),
+            inferred,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Deref;
+
+    use crate::common::{Context, Inferred};
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{prepare, TraitError, UndefinedError};
+    use crate::ir::analyse::Error::{Trait, Undefined};
+
+    #[test]
+    fn iterate_iterator() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        trait Iterator<T> {
+            function next() -> T?
+        }
+        type Counter(count: Number)
+        define Counter: Iterator<Number> {
+            function next() -> Number? { return self.count }
+        }
+        let counter = Counter(count = 1)
+        for x in counter { x }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let looped = typed[4].as_iterator_loop();
+        let nodes = looped.nodes.borrow();
+        assert_eq!(nodes.deref().nodes[0].inferred, Inferred::Number);
+    }
+
+    #[test]
+    fn iterate_what_iter_returns() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        trait Iterator<T> {
+            function next() -> T?
+        }
+        type Counter(count: Number)
+        define Counter: Iterator<Number> {
+            function next() -> Number? { return self.count }
+        }
+        type Bag(size: Number)
+        define Bag {
+            function iter() -> Counter { return Counter(count = self.size) }
+        }
+        let bag = Bag(size = 3)
+        for x in bag { x }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let looped = typed[6].as_iterator_loop();
+        assert!(looped.iter.is_some());
+        assert_ne!(looped.iterable, looped.iterator);
+        let nodes = looped.nodes.borrow();
+        assert_eq!(nodes.deref().nodes[0].inferred, Inferred::Number);
+    }
+
+    #[test]
+    fn iterate_string() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        for c in 'elodie' { c }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let looped = typed[0].as_iterator_loop();
+        assert!(looped.next.is_none());
+        let nodes = looped.nodes.borrow();
+        assert_eq!(nodes.deref().nodes[0].inferred, Inferred::String);
+    }
+
+    #[test]
+    fn iterate_not_iterable() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        trait Iterator<T> {
+            function next() -> T?
+        }
+        type Point(x: Number)
+        for p in Point(x = 1) { }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Trait(error) = result.err().unwrap() else { panic!() };
+        let TraitError::NotImplemented { r#trait, r#type, .. } = *error else { panic!() };
+        assert_eq!(r#trait, "Iterator");
+        assert_eq!(r#type, "Point");
+    }
+
+    #[test]
+    fn iterate_without_iterator_trait() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Point(x: Number)
+        for p in Point(x = 1) { }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Undefined(UndefinedError::UndefinedType { r#type, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(r#type, "Iterator");
+    }
+
+    #[test]
+    fn iterate_variable_only_lives_in_loop() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        for c in 'elodie' { }
+        c
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Undefined(UndefinedError::UndefinedVariable { variable, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(variable, "c");
+    }
+}
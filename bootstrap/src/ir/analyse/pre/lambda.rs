@@ -0,0 +1,206 @@
+use crate::common::{Inferred, SymbolId, SymbolName};
+use crate::common::node::Node::{DeclareLambda, DeclareVariable, DestructureTuple, ReturnFromFunction};
+use crate::frontend::ast::AstDeclareLambdaNode;
+use crate::ir::analyse::{TypeDeclareLambdaNode, TypedTreeNode, TypeReturnFromFunctionNode};
+use crate::ir::analyse::pre::Pre;
+
+// a lambda which is currently analysed
+pub(crate) struct Lambda {
+    // index of the scope frame the arguments of the lambda live in
+    frame: usize,
+    captures: Vec<SymbolId>,
+}
+
+impl<'a> Pre<'a> {
+    // arguments without type take the type of the function type the lambda gets passed as
+    pub(crate) fn declare_lambda(&mut self, node: &AstDeclareLambdaNode, expected: Option<&Inferred>) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+
+        let (expected_arguments, expected_result) = match expected {
+            Some(Inferred::Function(arguments, result)) => (arguments.clone(), result.as_ref().clone()),
+            _ => (Box::default(), Inferred::Unit)
+        };
+
+        let signature: Vec<Inferred> = node.arguments.iter()
+            .enumerate()
+            .map(|(idx, argument)| argument.argument_type.as_ref()
+                .map(|t| self.inferred_from_ast_type(t))
+                .or_else(|| expected_arguments.get(idx).cloned())
+                .unwrap_or(Inferred::Unknown))
+            .collect();
+
+        let function = self.symbol_table.register_function(SymbolName(self.string_table.push_str("lambda")));
+        let symbol = self.symbol_table.function_mut(function);
+        symbol.inferred = Inferred::Function(signature.clone().into_boxed_slice(), Box::new(expected_result.clone()));
+        symbol.package_id = self.package;
+        symbol.span = node.span.clone();
+
+        self.scope.enter();
+        self.lambdas.push(Lambda { frame: self.scope.depth() - 1, captures: vec![] });
+        let outer_function = self.function.replace(function);
+        let outer_flow = std::mem::take(&mut self.flow);
+
+        let mut arguments = Vec::with_capacity(node.arguments.len());
+        for (argument, inferred) in node.arguments.iter().zip(signature) {
+            let variable = self.variable_register(SymbolName::from(&argument.argument), inferred);
+            self.symbol_table.variable_mut(variable).span = argument.span.clone();
+            arguments.push(variable);
+        }
+
+        let mut nodes = Vec::with_capacity(node.nodes.nodes.len());
+        for node in &node.nodes.nodes {
+            nodes.push(self.node(node)?)
+        }
+
+        // a lambda which is not passed as function type returns what its body returns
+        let inferred_result = !matches!(expected, Some(Inferred::Function(..)));
+
+        // the last expression of the lambda is its result
+        if expected_result != Inferred::Unit || inferred_result {
+            if let Some(last) = nodes.pop() {
+                let expression = !matches!(last.node, DeclareVariable(_) | DestructureTuple(_)) && last.inferred != Inferred::Unit;
+                if matches!(last.node, ReturnFromFunction(_)) || (expected_result == Inferred::Unit && !expression) {
+                    nodes.push(last)
+                } else {
+                    let (span, inferred) = (last.span.clone(), last.inferred.clone());
+                    nodes.push(TypedTreeNode::new(
+                        ReturnFromFunction(TypeReturnFromFunctionNode { node: Some(Box::new(last)) }),
+                        span,
+                        inferred,
+                    ))
+                }
+            }
+        }
+
+        // a result of a type parameter takes the type of the result of the lambda
+        if expected_result.is_parameterized() || inferred_result {
+            if let Some(last) = nodes.last().filter(|last| matches!(last.node, ReturnFromFunction(_))) {
+                if let Inferred::Function(_, result) = &mut self.symbol_table.function_mut(function).inferred {
+                    **result = last.inferred.clone();
+                }
+            }
+        }
+
+        self.function = outer_function;
+        self.flow = outer_flow;
+        let lambda = self.lambdas.pop().unwrap();
+        self.scope.leave();
+
+        Ok(TypedTreeNode::new(
+            DeclareLambda(TypeDeclareLambdaNode {
+                function,
+                arguments: arguments.into_boxed_slice(),
+                captures: lambda.captures.into_boxed_slice(),
+                nodes: nodes.into_boxed_slice(),
+            }),
+            span,
+            self.symbol_table.function(function).inferred.clone(),
+        ))
+    }
+
+    // every lambda declared in a deeper frame than the variable captures it
+    // the variable lives outside of the lambda which is declared right now
+    pub(crate) fn lambda_captures(&self, name: SymbolName) -> bool {
+        match (self.scope.variable_depth(name), self.lambdas.last()) {
+            (Some(frame), Some(lambda)) => frame < lambda.frame,
+            _ => false
+        }
+    }
+
+    pub(crate) fn lambda_capture(&mut self, name: SymbolName, variable: SymbolId) {
+        let Some(frame) = self.scope.variable_depth(name) else {
+            return;
+        };
+
+        for lambda in self.lambdas.iter_mut().filter(|lambda| frame < lambda.frame) {
+            if !lambda.captures.contains(&variable) {
+                lambda.captures.push(variable)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, Inferred};
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{CallbackError, prepare};
+    use crate::ir::analyse::Error::Callback;
+
+    #[test]
+    fn lambda_takes_argument_types_of_external_function() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        external function apply(f: function(Int4) -> Int4, x: Int4) -> Int4
+        apply({ (x) -> x }, 2)
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let call = typed[1].as_call_function();
+        let lambda = call.arguments[0].as_declare_lambda();
+        assert_eq!(lambda.arguments.len(), 1);
+        assert_eq!(lambda.captures.len(), 0);
+        assert_eq!(ctx.symbol_table.variable(lambda.arguments[0]).inferred, Inferred::Int4);
+        assert_eq!(call.arguments[0].inferred, Inferred::Function(Box::new([Inferred::Int4]), Box::new(Inferred::Int4)));
+
+        let result = lambda.nodes[0].as_return_from_function();
+        assert_eq!(result.node.as_ref().unwrap().inferred, Inferred::Int4);
+    }
+
+    #[test]
+    fn function_passed_by_name() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        external function apply(f: function(Int4) -> Int4, x: Int4) -> Int4
+        function double(x: Int4) -> Int4 { return x + x }
+        apply(double, 2)
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let call = typed[2].as_call_function();
+        let argument = call.arguments[0].as_access_variable();
+        assert_eq!(ctx.symbol_name(argument.variable), "double");
+        assert_eq!(call.arguments[0].inferred, Inferred::Function(Box::new([Inferred::Int4]), Box::new(Inferred::Int4)));
+    }
+
+    #[test]
+    fn capturing_lambda_can_not_be_passed_to_c() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        external function apply(f: function(Int4) -> Int4, x: Int4) -> Int4
+        let offset = 1
+        apply({ (x) -> offset }, 2)
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(Callback(CallbackError::CapturingClosure { variable, .. })) = result else { panic!() };
+        assert_eq!(variable, "offset");
+    }
+
+    #[test]
+    fn lambda_infers_result_of_return() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let start = 'elodie'
+        let c = { () -> return start }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        assert_eq!(typed[1].inferred, Inferred::Function(Box::new([]), Box::new(Inferred::String)));
+    }
+
+    #[test]
+    fn lambda_returns_its_last_expression() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let start = 'elodie'
+        let c = { () -> start }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let lambda = typed[1].as_declared_variable().value.as_declare_lambda();
+        let result = lambda.nodes[0].as_return_from_function();
+        assert_eq!(result.node.as_ref().unwrap().inferred, Inferred::String);
+        assert_eq!(typed[1].inferred, Inferred::Function(Box::new([]), Box::new(Inferred::String)));
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::mem;
+
+use crate::common::{PackageId, SymbolId, SymbolName};
+use crate::common::node::Node::{DeclarePackage, DeclareTrait, DeclareType};
+use crate::frontend::Ast;
+use crate::frontend::ast::{AstDeclareFunctionNode, AstDeclarePackageNode, AstDefineTypeNode};
+use crate::ir::analyse::pre::Pre;
+use crate::ir::analyse::pre::r#trait::Defined;
+use crate::ir::analyse::scope::Scope;
+use crate::ir::analyse::TypedTreeNode;
+
+// core, std, rt and sysroot are registered upfront, but the body of a library function only gets analysed
+// once the program uses it - so that only what is used ends up in the program
+#[derive(Default)]
+pub(crate) struct Library {
+    packages: Vec<SymbolId>,
+    // types and traits declared outside of a package
+    types: Vec<SymbolId>,
+    functions: HashMap<SymbolId, (PackageId, AstDeclareFunctionNode)>,
+    used: Vec<(SymbolId, PackageId, AstDeclareFunctionNode)>,
+    // a define block gets analysed as a whole once one of its methods is used, so that its vtables are complete
+    definitions: Vec<(PackageId, AstDefineTypeNode, Option<Defined>)>,
+    methods: HashMap<SymbolId, usize>,
+    defined: Vec<usize>,
+}
+
+impl<'a> Pre<'a> {
+    // types and traits declared outside of a package, like Result and Iterator, are available to every program without a prefix
+    pub(crate) fn library_register(&mut self, library: Vec<Ast>) -> crate::ir::analyse::Result<()> {
+        for ast in library {
+            for node in ast.nodes {
+                match node.node() {
+                    DeclarePackage(node) => {
+                        let package = self.package_register(node);
+                        let symbol = self.symbol_table[package].clone();
+                        self.scope.register_symbol(&symbol);
+
+                        self.library.packages.push(package);
+                        self.library_collect(package, node);
+                    }
+                    DeclareType(node) => {
+                        let r#type = self.declare_type(node)?.as_declare_type().r#type;
+                        self.library.types.push(r#type);
+                    }
+                    DeclareTrait(node) => {
+                        let r#trait = self.declare_trait(node)?.as_declare_trait().r#trait;
+                        self.library.types.push(r#trait);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        self.library_define()
+    }
+
+    // methods of define blocks are registered once all library types and traits are known
+    fn library_define(&mut self) -> crate::ir::analyse::Result<()> {
+        for idx in 0..self.library.definitions.len() {
+            let (package, node, _) = &self.library.definitions[idx];
+            let (package, node) = (*package, node.clone());
+
+            let outer = self.library_enter(package);
+            let defined = self.define_register(&node);
+            self.library_leave(outer);
+            let defined = defined?;
+
+            for method in defined.methods() {
+                self.library.methods.insert(method, idx);
+            }
+            self.library.definitions[idx].2 = Some(defined);
+        }
+        Ok(())
+    }
+
+    fn library_collect(&mut self, package: SymbolId, node: &AstDeclarePackageNode) {
+        let package_id = self.package_id(package);
+
+        for function in &node.functions {
+            let symbol = self.package_table[package_id].functions[&SymbolName::from(&function.function)];
+            self.library.functions.insert(symbol, (package_id, function.clone()));
+        }
+
+        for definition in &node.definitions {
+            self.library.definitions.push((package_id, definition.clone(), None));
+        }
+
+        for nested in &node.packages {
+            let symbol = self.package_table[package_id].packages[&SymbolName::from(&nested.package)];
+            self.library_collect(symbol, nested);
+        }
+    }
+
+    pub(crate) fn library_use(&mut self, function: SymbolId) {
+        if let Some((package, node)) = self.library.functions.remove(&function) {
+            self.library.used.push((function, package, node));
+        }
+        if let Some(idx) = self.library.methods.get(&function) {
+            if self.library.definitions[*idx].2.is_some() && !self.library.defined.contains(idx) {
+                self.library.defined.push(*idx);
+            }
+        }
+    }
+
+    // analyses the bodies of all used library functions and define blocks, including the ones only used by other library code
+    pub(crate) fn library_body(&mut self) -> crate::ir::analyse::Result<Vec<TypedTreeNode>> {
+        let mut functions = vec![];
+        let mut definitions = vec![];
+
+        let mut next = 0;
+        loop {
+            if let Some((function, package, node)) = self.library.used.pop() {
+                let outer = self.library_enter(package);
+                let body = self.function_body(function, &node);
+                self.library_leave(outer);
+
+                functions.push(body?);
+            } else if let Some(idx) = self.library.defined.get(next).copied() {
+                next += 1;
+                let (package, node, defined) = &mut self.library.definitions[idx];
+                let (package, node, defined) = (*package, node.clone(), defined.take().unwrap());
+
+                let outer = self.library_enter(package);
+                let body = self.define_body(&node, defined);
+                self.library_leave(outer);
+
+                definitions.push(body?);
+            } else {
+                break;
+            }
+        }
+
+        functions.sort_by_key(|node| node.as_declare_function().function.0);
+        functions.extend(definitions);
+        Ok(functions)
+    }
+
+    // library code only sees the library packages, not what the program declares
+    fn library_enter(&mut self, package: PackageId) -> (Scope, Option<PackageId>) {
+        let mut scope = Scope::new();
+        for symbol in self.library.packages.iter().chain(&self.library.types) {
+            scope.register_symbol(&self.symbol_table[*symbol]);
+        }
+
+        (mem::replace(&mut self.scope, scope), self.package.replace(package))
+    }
+
+    fn library_leave(&mut self, (scope, package): (Scope, Option<PackageId>)) {
+        self.scope = scope;
+        self.package = package;
+    }
+}
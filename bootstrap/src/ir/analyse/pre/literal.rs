@@ -3,10 +3,11 @@ use std::str::FromStr;
 
 use bigdecimal::BigDecimal;
 
+use crate::common::Inferred;
 use crate::common::Inferred::{Boolean, Float4, Float8, Int1, Int16, Int2, Int4, Int8, Number, String, Uint1, Uint16, Uint2, Uint4, Uint8};
-use crate::common::node::Node::{LiteralBoolean, LiteralFloat4, LiteralFloat8, LiteralInt1, LiteralInt16, LiteralInt2, LiteralInt4, LiteralInt8, LiteralNumber, LiteralString, LiteralUint1, LiteralUint16, LiteralUint2, LiteralUint4, LiteralUint8};
-use crate::frontend::ast::{AstLiteralBooleanNode, AstLiteralFloat4Node, AstLiteralNumberNode, AstLiteralStringNode, AstType};
-use crate::ir::analyse::{Error, InvalidLiteralError, TypedTreeNode, TypeLiteralBooleanNode, TypeLiteralFloat4Node, TypeLiteralFloat8Node, TypeLiteralInt16Node, TypeLiteralInt1Node, TypeLiteralInt2Node, TypeLiteralInt4Node, TypeLiteralInt8Node, TypeLiteralNumberNode, TypeLiteralStringNode, TypeLiteralUint16Node, TypeLiteralUint1Node, TypeLiteralUint2Node, TypeLiteralUint4Node, TypeLiteralUint8Node};
+use crate::common::node::Node::{LiteralBoolean, LiteralFloat4, LiteralFloat8, LiteralInt1, LiteralInt16, LiteralInt2, LiteralInt4, LiteralInt8, LiteralNone, LiteralNumber, LiteralString, LiteralUint1, LiteralUint16, LiteralUint2, LiteralUint4, LiteralUint8};
+use crate::frontend::ast::{AstLiteralBooleanNode, AstLiteralFloat4Node, AstLiteralNoneNode, AstLiteralNumberNode, AstLiteralStringNode, AstType};
+use crate::ir::analyse::{Error, InvalidLiteralError, TypedTreeNode, TypeLiteralBooleanNode, TypeLiteralFloat4Node, TypeLiteralFloat8Node, TypeLiteralInt16Node, TypeLiteralInt1Node, TypeLiteralInt2Node, TypeLiteralInt4Node, TypeLiteralInt8Node, TypeLiteralNoneNode, TypeLiteralNumberNode, TypeLiteralStringNode, TypeLiteralUint16Node, TypeLiteralUint1Node, TypeLiteralUint2Node, TypeLiteralUint4Node, TypeLiteralUint8Node};
 use crate::ir::analyse::pre::Pre;
 
 // FIXME no unwrap
@@ -26,6 +27,15 @@ impl<'a> Pre<'a> {
         ))
     }
 
+    // none gets the type of the optional it is assigned to
+    pub(crate) fn literal_none(&mut self, _node: &AstLiteralNoneNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        Ok(TypedTreeNode::new(
+            LiteralNone(TypeLiteralNoneNode {}),
+            self.span(),
+            Inferred::Optional(Box::new(Inferred::Unknown)),
+        ))
+    }
+
     pub(crate) fn literal_float4(
         &mut self,
         node: &AstLiteralFloat4Node,
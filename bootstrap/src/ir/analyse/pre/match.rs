@@ -0,0 +1,307 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::common::{Inferred, SymbolName, TypeId};
+use crate::common::node::Node::Match;
+use crate::frontend::ast::{AstMatchNode, AstMatchPattern};
+use crate::ir::analyse::{Error, MatchError, TypeBlockNode, TypedTreeNode, TypeMatchArm, TypeMatchNode};
+use crate::ir::analyse::pre::Pre;
+
+impl<'a> Pre<'a> {
+    pub(crate) fn r#match(&mut self, node: &AstMatchNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+        let value = Box::new(self.node(&node.node)?);
+
+        // a value of a variant gets matched against the variants of its sum type
+        let sum = value.inferred.type_id()
+            .filter(|type_id| matches!(type_id, TypeId::Custom(_)))
+            .map(|type_id| self.type_table[type_id].variant_of.unwrap_or(type_id))
+            .filter(|type_id| self.type_table.is_sum(*type_id));
+
+        let Some(sum) = sum else {
+            return Err(Error::Match(MatchError::NotASumType {
+                r#type: value.inferred.to_string(self.string_table, self.type_table),
+                span: value.span.clone(),
+            }));
+        };
+
+        // Option<Number> turns the T of the variables of its variants into Number
+        let type_arguments: HashMap<_, _> = match &value.inferred {
+            Inferred::Parameterized(_, arguments) => self.type_table[sum].parameters.iter().cloned().zip(arguments.iter().cloned()).collect(),
+            _ => HashMap::new()
+        };
+
+        // the bindings of a readonly value are readonly as well
+        let readonly = self.readonly(&value);
+
+        let mut arms = Vec::with_capacity(node.arms.len());
+        let mut taken: Vec<TypeId> = vec![];
+        let mut otherwise = false;
+
+        for arm in &node.arms {
+            self.scope.span_set(arm.span.clone());
+
+            let (variant, bindings) = match &arm.pattern {
+                AstMatchPattern::Variant { variant, bindings } => {
+                    let name = self.string_table.get(variant.0).to_string();
+                    let Some(variant) = self.type_table.variant(sum, variant.0) else {
+                        return Err(Error::Match(MatchError::UnknownVariant {
+                            r#type: self.string_table.get(self.type_table[sum].name.0).to_string(),
+                            variant: name,
+                            span: self.span(),
+                        }));
+                    };
+
+                    if otherwise || taken.contains(&variant) {
+                        return Err(Error::Match(MatchError::UnreachableArm { variant: name, span: self.span() }));
+                    }
+
+                    let expected = self.type_table[variant].variables.len();
+                    if expected != bindings.len() {
+                        return Err(Error::Match(MatchError::BindingCountMissMatch {
+                            variant: name,
+                            expected,
+                            got: bindings.len(),
+                            span: self.span(),
+                        }));
+                    }
+
+                    taken.push(variant);
+                    (Some(variant), bindings.as_slice())
+                }
+                AstMatchPattern::Otherwise => {
+                    if otherwise || taken.len() == self.type_table[sum].variants.len() {
+                        return Err(Error::Match(MatchError::UnreachableArm { variant: "otherwise".to_string(), span: self.span() }));
+                    }
+                    otherwise = true;
+                    (None, [].as_slice())
+                }
+            };
+
+            self.scope.enter();
+
+            let mut symbols = Vec::with_capacity(bindings.len());
+            for (idx, binding) in bindings.iter().enumerate() {
+                let inferred = self.type_table[variant.unwrap()].variables[idx].inferred.substitute(&type_arguments);
+                let symbol = self.variable_register(SymbolName::from(binding), inferred);
+                self.symbol_table.variable_mut(symbol).readonly = readonly;
+                symbols.push(symbol);
+            }
+
+            let mut nodes = Vec::with_capacity(arm.nodes.nodes.len());
+            for node in &arm.nodes.nodes {
+                nodes.push(self.node(node)?)
+            }
+
+            self.scope.leave();
+
+            arms.push(TypeMatchArm {
+                variant,
+                bindings: symbols.into_boxed_slice(),
+                nodes: RefCell::new(TypeBlockNode { nodes: nodes.into_boxed_slice() }),
+            })
+        }
+
+        if !otherwise {
+            let missing: Vec<String> = self.type_table[sum].variants.iter()
+                .filter(|variant| !taken.contains(variant))
+                .map(|variant| self.string_table.get(self.type_table[*variant].name.0).to_string())
+                .collect();
+
+            if !missing.is_empty() {
+                return Err(Error::Match(MatchError::NonExhaustive {
+                    r#type: self.string_table.get(self.type_table[sum].name.0).to_string(),
+                    missing,
+                    span,
+                }));
+            }
+        }
+
+        let inferred = Self::match_inferred(&arms);
+
+        Ok(TypedTreeNode::new(
+            Match(TypeMatchNode {
+                node: value,
+                arms: arms.into_boxed_slice(),
+            }),
+            span,
+            inferred,
+        ))
+    }
+
+    // the match has the value of its arms, if all of them end with a value of the same type
+    pub(crate) fn match_inferred(arms: &[TypeMatchArm]) -> Inferred {
+        Self::branch_inferred(arms.iter().map(|arm| Self::block_inferred(&arm.nodes.borrow())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, Inferred};
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{MatchError, prepare, RecordError};
+    use crate::ir::analyse::Error::{Match, Record};
+
+    #[test]
+    fn match_all_variants() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Shape = Circle(radius: Number) | Square(side: Number) | Empty
+        let shape = Circle(radius = 2)
+        let area = match shape {
+            Circle(r) => r * r * 3
+            Square(s) => s * s
+            Empty => 0
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let declared = typed[0].as_declare_type();
+        let sum = ctx.symbol_table[declared.r#type].type_id().unwrap();
+        assert!(ctx.type_table.is_sum(sum));
+        assert_eq!(ctx.type_table[sum].variants.len(), 3);
+        assert_eq!(typed[1].inferred, Inferred::Type(sum));
+
+        let area = typed[2].as_declared_variable();
+        let r#match = area.value.as_match();
+        assert_eq!(r#match.arms.len(), 3);
+        assert_eq!(r#match.arms[0].variant, Some(ctx.type_table[sum].variants[0]));
+        assert_eq!(r#match.arms[0].bindings.len(), 1);
+        assert_eq!(r#match.arms[2].bindings.len(), 0);
+        assert_eq!(area.value.inferred, Inferred::Number);
+    }
+
+    #[test]
+    fn match_with_otherwise() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Shape = Circle(radius: Number) | Square(side: Number) | Empty
+        let shape = Empty()
+        match shape {
+            Circle(r) => r
+            otherwise => 0
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let r#match = typed[2].as_match();
+        assert_eq!(r#match.arms.len(), 2);
+        assert_eq!(r#match.arms[1].variant, None);
+    }
+
+    #[test]
+    fn variant_without_variables_named_on_its_own() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Shape = Circle(radius: Number) | Empty
+        let shape = Empty
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let declared = typed[0].as_declare_type();
+        let sum = ctx.symbol_table[declared.r#type].type_id().unwrap();
+        assert_eq!(typed[1].as_declared_variable().value.inferred, Inferred::Type(sum));
+    }
+
+    #[test]
+    fn variant_with_variables_named_on_its_own() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Shape = Circle(radius: Number) | Empty
+        let shape = Circle
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Record(error) = result.err().unwrap() else { panic!() };
+        let RecordError::MissingField { field, r#type, .. } = *error else { panic!() };
+        assert_eq!(field, "radius");
+        assert_eq!(r#type, "Circle");
+    }
+
+    #[test]
+    fn match_not_exhaustive() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Shape = Circle(radius: Number) | Square(side: Number) | Empty
+        let shape = Empty()
+        match shape {
+            Square(s) => s
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Match(MatchError::NonExhaustive { r#type, missing, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(r#type, "Shape");
+        assert_eq!(missing, vec!["Circle".to_string(), "Empty".to_string()]);
+    }
+
+    #[test]
+    fn match_unknown_variant() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Shape = Circle(radius: Number) | Empty
+        let shape = Empty()
+        match shape {
+            Triangle => 1
+            otherwise => 0
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Match(MatchError::UnknownVariant { r#type, variant, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(r#type, "Shape");
+        assert_eq!(variant, "Triangle");
+    }
+
+    #[test]
+    fn match_binding_count_miss_match() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Shape = Circle(radius: Number) | Empty
+        let shape = Empty()
+        match shape {
+            Circle(r, x) => r
+            Empty => 0
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Match(MatchError::BindingCountMissMatch { variant, expected, got, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(variant, "Circle");
+        assert_eq!(expected, 1);
+        assert_eq!(got, 2);
+    }
+
+    #[test]
+    fn match_unreachable_arm() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Shape = Circle(radius: Number) | Empty
+        let shape = Empty()
+        match shape {
+            Empty => 0
+            Empty => 1
+            otherwise => 2
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Match(MatchError::UnreachableArm { variant, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(variant, "Empty");
+    }
+
+    #[test]
+    fn match_on_non_sum_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let value = 42
+        match value {
+            otherwise => 0
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Match(MatchError::NotASumType { r#type, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(r#type, "Number");
+    }
+}
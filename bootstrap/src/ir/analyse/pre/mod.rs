@@ -1,16 +1,20 @@
+use std::collections::HashMap;
 use std::ops::Deref;
 
 use Node::{Block, BreakLoop, Calculate, If, LiteralFloat4};
 
-use crate::common::{GetString, Inferred, Span, StringTable, SymbolId, SymbolName, SymbolTable, TypeTable, VariableSymbol, WithSpan};
+use crate::common::{GetString, Inferred, PackageId, PackageTable, Span, StringTable, StringTableId, SymbolId, SymbolName, SymbolTable, TypeId, TypeTable, VariableSymbol, WithSpan};
 use crate::common::Context;
 use crate::common::node::Node;
-use crate::common::node::Node::{AccessVariable, CallFunctionOfPackage, Compare, DeclareVariable, InterpolateString, LiteralBoolean, LiteralNumber, LiteralString, Loop};
+use crate::common::node::Node::{AccessElementOfTuple, AccessIndex, AccessVariable, AccessVariableOfObject, AccessVariableOfSelf, AssignVariable, AssignVariableOfObject, CallFunction, CallFunctionOfObject, CallFunctionOfPackage, CallFunctionWithLambda, Compare, ContinueLoop, DeclareExternalFunction, DeclareFunction, DeclareLambda, DeclarePackage, DeclareTrait, DeclareType, DeclareVariable, Defer, DefineType, DestructureTuple, InstantiateRecord, InstantiateTuple, InstantiateType, InterpolateString, IteratorLoop, LiteralBoolean, LiteralNone, LiteralNumber, LiteralString, Loop, Match, RangeLoop, ReturnFromFunction, Try};
 use crate::frontend;
-use crate::frontend::ast::AstTreeNode;
+use crate::frontend::ast::{AstTraitFunction, AstTreeNode};
 use crate::ir::analyse::{Error, TypedTreeNode, UndefinedError};
+use crate::ir::analyse::pre::lambda::Lambda;
+use crate::ir::analyse::pre::library::Library;
 use crate::ir::analyse::scope::Scope;
 
+mod argument;
 mod variable;
 mod literal;
 mod call;
@@ -20,21 +24,70 @@ mod control;
 mod block;
 mod compare;
 mod calculate;
+mod function;
+mod package;
+mod r#type;
+mod library;
+mod lambda;
+mod assign;
+mod r#trait;
+mod r#match;
+mod r#try;
+mod tuple;
+mod record;
+mod readonly;
+mod iterator;
+mod operator;
 
 pub(crate) struct Pre<'a> {
+    package_table: &'a mut PackageTable,
     string_table: &'a mut StringTable,
     symbol_table: &'a mut SymbolTable,
     type_table: &'a mut TypeTable,
     scope: Scope,
+    package: Option<PackageId>,
+    library: Library,
+    lambdas: Vec<Lambda>,
+    // type parameters of the generic functions and types which are currently analysed, with the trait they are bound to
+    type_parameters: Vec<(StringTableId, Option<TypeId>)>,
+    // what Self refers to inside of a trait or a definition
+    self_type: Option<Inferred>,
+    // default implementations of trait methods, analysed for every type which uses them
+    trait_defaults: HashMap<SymbolId, AstTraitFunction>,
+    // default values of the arguments of functions which declare any, analysed at every call which leaves them out
+    argument_defaults: HashMap<SymbolId, Vec<Option<AstTreeNode>>>,
+    // the function whose body is currently analysed, value? returns from it
+    function: Option<SymbolId>,
+    // loops and defer blocks of the function which is currently analysed
+    flow: Flow,
+}
+
+#[derive(Default)]
+pub(crate) struct Flow {
+    // labels of the loops, the innermost one last
+    labels: Vec<StringTableId>,
+    loops: usize,
+    // loops and labels at the start of the innermost defer block, break and continue can not leave it
+    defer: Option<(usize, usize)>,
 }
 
 impl<'a> Pre<'a> {
     pub(crate) fn new(ctx: &'a mut Context) -> Self {
         Self {
+            package_table: &mut ctx.package_table,
             string_table: &mut ctx.string_table,
             symbol_table: &mut ctx.symbol_table,
             type_table: &mut ctx.type_table,
             scope: Scope::new(),
+            package: None,
+            library: Library::default(),
+            lambdas: vec![],
+            type_parameters: vec![],
+            self_type: None,
+            trait_defaults: HashMap::new(),
+            argument_defaults: HashMap::new(),
+            function: None,
+            flow: Flow::default(),
         }
     }
 
@@ -49,24 +102,66 @@ impl<'a> Pre<'a> {
         Ok(nodes)
     }
 
+    // analyses a program together with the library it gets linked against, used library functions come first
+    pub(crate) fn process_program(
+        &mut self,
+        library: Vec<frontend::Ast>,
+        ast: frontend::Ast,
+    ) -> crate::ir::analyse::Result<Vec<TypedTreeNode>> {
+        self.library_register(library)?;
+        let nodes = self.process(ast)?;
+
+        let mut result = self.library_body()?;
+        result.extend(nodes);
+        Ok(result)
+    }
+
     fn node(&mut self, ast: &AstTreeNode) -> crate::ir::analyse::Result<TypedTreeNode> {
         self.scope.span_set(ast.span());
 
         match ast.node() {
+            AccessElementOfTuple(node) => self.access_element_of_tuple(node),
+            AccessIndex(node) => self.access_index(node),
             AccessVariable(node) => self.access_variable(node),
+            AccessVariableOfObject(node) => self.access_variable_of_object(node),
+            AccessVariableOfSelf(node) => self.access_variable_of_self(node),
+            AssignVariable(node) => self.assign_variable(node),
+            AssignVariableOfObject(node) => self.assign_variable_of_object(node),
             Block(node) => self.block(node),
             BreakLoop(node) => self.r#break(node),
             Calculate(node) => self.calculate(node),
+            CallFunction(node) => self.call_function(node),
+            CallFunctionOfObject(node) => self.call_function_of_object(node),
             CallFunctionOfPackage(node) => self.call_function_of_package(node),
+            CallFunctionWithLambda(node) => self.call_function_with_lambda(node),
             Compare(node) => self.compare(node),
+            ContinueLoop(node) => self.r#continue(node),
+            DeclareExternalFunction(node) => self.declare_external_function(node),
+            DeclareFunction(node) => self.declare_function(node),
+            DeclareLambda(node) => self.declare_lambda(node, None),
+            DeclarePackage(node) => self.declare_package(node),
+            DeclareTrait(node) => self.declare_trait(node),
+            DeclareType(node) => self.declare_type(node),
             DeclareVariable(node) => self.declare_variable(node),
+            Defer(node) => self.defer(node),
+            DefineType(node) => self.define_type(node),
+            DestructureTuple(node) => self.destructure_tuple(node),
             If(node) => self.r#if(node),
+            InstantiateRecord(node) => self.instantiate_record(node),
+            InstantiateTuple(node) => self.instantiate_tuple(node),
+            InstantiateType(node) => self.instantiate_type(node),
             InterpolateString(node) => self.interpolate_string(node),
+            IteratorLoop(node) => self.iterator_loop(node),
             LiteralBoolean(node) => self.literal_boolean(node),
             LiteralFloat4(node) => self.literal_float4(node),
+            LiteralNone(node) => self.literal_none(node),
             LiteralNumber(node) => self.literal_number(node),
             LiteralString(node) => self.literal_string(node),
             Loop(node) => self.r#loop(node),
+            Match(node) => self.r#match(node),
+            RangeLoop(node) => self.range_loop(node),
+            ReturnFromFunction(node) => self.r#return(node),
+            Try(node) => self.r#try(node),
             _ => unimplemented!("{ast:#?}"),
         }
     }
@@ -88,6 +183,7 @@ impl<'a> Pre<'a> {
 
     fn variable_register(&mut self, name: SymbolName, inferred: Inferred) -> SymbolId {
         let result = self.symbol_table.register_variable(name, inferred);
+        self.symbol_table.variable_mut(result).span = self.span();
         let symbol = &mut self.symbol_table[result];
         self.scope.register_symbol(&symbol);
         result
@@ -0,0 +1,296 @@
+use crate::common::{Inferred, SymbolId, TypeId};
+use crate::common::node::{CalculateOperator, CompareOperator, Node};
+use crate::frontend::ast::{AstAccessIndexNode, AstDeclareFunctionNode, AstType};
+use crate::ir::analyse::{Error, OperatorError, TypeCallFunctionNode, TypedTreeNode};
+use crate::ir::analyse::pre::Pre;
+
+// the names of the operator functions, value[index] calls index and value(..) calls call
+const OPERATORS: [&str; 13] = [
+    "add", "subtract", "multiply", "divide", "modulo",
+    "equal", "not_equal", "greater_than", "greater_than_equal", "less_than", "less_than_equal",
+    "index", "call",
+];
+
+const COMPARISONS: [&str; 6] = ["equal", "not_equal", "greater_than", "greater_than_equal", "less_than", "less_than_equal"];
+
+pub(crate) fn calculate_operator(operator: &CalculateOperator) -> &'static str {
+    match operator {
+        CalculateOperator::Add => "add",
+        CalculateOperator::Divide => "divide",
+        CalculateOperator::Modulo => "modulo",
+        CalculateOperator::Multiply => "multiply",
+        CalculateOperator::Subtract => "subtract",
+    }
+}
+
+pub(crate) fn compare_operator(operator: &CompareOperator) -> &'static str {
+    match operator {
+        CompareOperator::Equal => "equal",
+        CompareOperator::NotEqual => "not_equal",
+        CompareOperator::GreaterThan => "greater_than",
+        CompareOperator::GreaterThanEqual => "greater_than_equal",
+        CompareOperator::LessThan => "less_than",
+        CompareOperator::LessThanEqual => "less_than_equal",
+    }
+}
+
+impl<'a> Pre<'a> {
+    pub(crate) fn operator_check(&self, function: &AstDeclareFunctionNode, define: bool) -> crate::ir::analyse::Result<()> {
+        if !function.modifiers.is_operator() {
+            return Ok(());
+        }
+
+        let name = self.string_table.get(function.function.0).to_string();
+        if !define {
+            return Err(Error::Operator(Box::new(OperatorError::OutsideOfDefine { function: name, span: function.span.clone() })));
+        }
+        if !OPERATORS.contains(&name.as_str()) {
+            return Err(Error::Operator(Box::new(OperatorError::UnknownOperator { function: name, span: function.span.clone() })));
+        }
+
+        // call takes as many arguments as it likes
+        if name != "call" && function.arguments.len() != 1 {
+            return Err(Error::Operator(Box::new(OperatorError::ArgumentCountMissMatch {
+                function: name,
+                expected: 1,
+                got: function.arguments.len(),
+                span: function.span.clone(),
+            })));
+        }
+        if COMPARISONS.contains(&name.as_str()) && function.return_type != Some(AstType::Boolean) {
+            return Err(Error::Operator(Box::new(OperatorError::ReturnTypeMissMatch {
+                function: name,
+                expected: AstType::Boolean.to_string(self.string_table),
+                got: function.return_type.as_ref().map_or("Unit".to_string(), |r#type| r#type.to_string(self.string_table)),
+                span: function.span.clone(),
+            })));
+        }
+        Ok(())
+    }
+
+    // values of custom types have the operators their define declares, values of other types the builtin ones
+    pub(crate) fn operator_type(&self, inferred: &Inferred) -> Option<TypeId> {
+        let type_id = match inferred {
+            Inferred::Type(type_id) | Inferred::Parameterized(type_id, _) => *type_id,
+            _ => return None
+        };
+        if !matches!(type_id, TypeId::Custom(_)) || self.type_table.is_trait(type_id) || self.type_table.is_external(type_id) {
+            return None;
+        }
+        Some(type_id)
+    }
+
+    pub(crate) fn operator_get(&mut self, type_id: TypeId, operator: &str) -> Option<SymbolId> {
+        let name = self.string_table.push_str(operator);
+        self.type_table.operator(type_id, name).map(|method| method.function)
+    }
+
+    pub(crate) fn operator_undefined(&self, inferred: &Inferred, operator: &str) -> Error {
+        Error::Operator(Box::new(OperatorError::Undefined {
+            r#type: inferred.to_string(self.string_table, self.type_table),
+            operator: operator.to_string(),
+            span: self.span(),
+        }))
+    }
+
+    // the value the operator is used on becomes the first argument, like the object of a method
+    pub(crate) fn operator_call(&mut self, function: SymbolId, arguments: Vec<TypedTreeNode>) -> crate::ir::analyse::Result<TypedTreeNode> {
+        self.library_use(function);
+        for (index, argument) in arguments.iter().enumerate() {
+            self.readonly_argument_check(function, index, argument)?;
+        }
+        let inferred = self.call_result(function, arguments.iter())?;
+
+        Ok(TypedTreeNode::new(
+            Node::CallFunction(TypeCallFunctionNode { function, arguments: arguments.into_boxed_slice() }),
+            self.span(),
+            inferred,
+        ))
+    }
+
+    pub(crate) fn access_index(&mut self, node: &AstAccessIndexNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let value = self.node(node.node.as_ref())?;
+        let index = self.node(node.index.as_ref())?;
+
+        let function = self.operator_type(&value.inferred).and_then(|type_id| self.operator_get(type_id, "index"));
+        let Some(function) = function else {
+            return Err(self.operator_undefined(&value.inferred, "index"));
+        };
+        self.operator_call(function, vec![value, index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::Context;
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{prepare, OperatorError};
+    use crate::ir::analyse::Error::Operator;
+
+    #[test]
+    fn calculate_calls_operator_function() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Meter(value: Number)
+        define Meter {
+            operator function add(other: Meter) -> Meter { return other }
+        }
+        let one = Meter(value = 1)
+        one + one
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let defined = typed[1].as_define_type();
+        let call = typed[3].as_call_function();
+        assert_eq!(call.function, defined.functions[0].as_declare_function().function);
+        assert_eq!(call.arguments.len(), 2);
+        assert_eq!(typed[3].inferred.to_string(&ctx.string_table, &ctx.type_table), "Meter");
+    }
+
+    #[test]
+    fn compare_without_operator_function() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Meter(value: Number)
+        define Meter {
+            operator function equal(other: Meter) -> Bool { return true }
+        }
+        let one = Meter(value = 1)
+        one != one
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Operator(error) = result.err().unwrap() else { panic!() };
+        let OperatorError::Undefined { r#type, operator, .. } = *error else { panic!() };
+        assert_eq!(r#type, "Meter");
+        assert_eq!(operator, "not_equal");
+    }
+
+    #[test]
+    fn compare_against_none() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Meter(value: Number)
+        let one = Meter(value = 1)
+        one == none
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+        typed[2].as_compare();
+    }
+
+    #[test]
+    fn index_and_call() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Lookup(name: String)
+        define Lookup {
+            operator function index(position: Number) -> String { return 'index' }
+            operator function call(text: String) -> String { return text }
+        }
+        let lookup = Lookup(name = 'lookup')
+        lookup[1]
+        lookup('text')
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let functions = &typed[1].as_define_type().functions;
+        assert_eq!(typed[3].as_call_function().function, functions[0].as_declare_function().function);
+        assert_eq!(typed[4].as_call_function().function, functions[1].as_declare_function().function);
+        assert_eq!(typed[4].as_call_function().arguments.len(), 2);
+    }
+
+    #[test]
+    fn operator_undefined() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Meter(value: Number)
+        let one = Meter(value = 1)
+        one * one
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Operator(error) = result.err().unwrap() else { panic!() };
+        let OperatorError::Undefined { r#type, operator, .. } = *error else { panic!() };
+        assert_eq!(r#type, "Meter");
+        assert_eq!(operator, "multiply");
+    }
+
+    #[test]
+    fn unknown_operator() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Meter(value: Number)
+        define Meter {
+            operator function power(other: Meter) -> Meter { return other }
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Operator(error) = result.err().unwrap() else { panic!() };
+        let OperatorError::UnknownOperator { function, .. } = *error else { panic!() };
+        assert_eq!(function, "power");
+    }
+
+    #[test]
+    fn operator_with_two_arguments() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Meter(value: Number)
+        define Meter {
+            operator function add(a: Meter, b: Meter) -> Meter { return a }
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Operator(error) = result.err().unwrap() else { panic!() };
+        let OperatorError::ArgumentCountMissMatch { function, expected, got, .. } = *error else { panic!() };
+        assert_eq!(function, "add");
+        assert_eq!(expected, 1);
+        assert_eq!(got, 2);
+    }
+
+    #[test]
+    fn comparison_not_returning_bool() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Meter(value: Number)
+        define Meter {
+            operator function less_than(other: Meter) -> Number { return 1 }
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Operator(error) = result.err().unwrap() else { panic!() };
+        let OperatorError::ReturnTypeMissMatch { function, expected, got, .. } = *error else { panic!() };
+        assert_eq!(function, "less_than");
+        assert_eq!(expected, "Boolean");
+        assert_eq!(got, "Number");
+    }
+
+    #[test]
+    fn operator_outside_of_define() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        operator function add(other: Number) -> Number { return other }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Operator(error) = result.err().unwrap() else { panic!() };
+        let OperatorError::OutsideOfDefine { function, .. } = *error else { panic!() };
+        assert_eq!(function, "add");
+    }
+
+    #[test]
+    fn operator_on_value_of_type_parameter() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function add<T>(a: T, b: T) -> T { return a + b }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Operator(error) = result.err().unwrap() else { panic!() };
+        let OperatorError::Undefined { r#type, operator, .. } = *error else { panic!() };
+        assert_eq!(r#type, "T");
+        assert_eq!(operator, "add");
+    }
+}
@@ -0,0 +1,419 @@
+use crate::common::{Inferred, PackageId, PackagePath, Symbol, SymbolId, SymbolName};
+use crate::common::node::Node::{DeclarePackage, DeclareType};
+use crate::frontend::ast::AstDeclarePackageNode;
+use crate::ir::analyse::{Error, TypeDeclarePackageNode, TypeDeclareTypeNode, TypedTreeNode, UndefinedError, VisibilityError};
+use crate::ir::analyse::pre::Pre;
+
+impl<'a> Pre<'a> {
+    pub(crate) fn declare_package(&mut self, node: &AstDeclarePackageNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let package = self.package_register(node);
+        let symbol = self.symbol_table[package].clone();
+        self.scope.register_symbol(&symbol);
+
+        self.package_body(package, node)
+    }
+
+    // registers the package and all of its members upfront, so that members can be referenced before they are declared
+    pub(crate) fn package_register(&mut self, node: &AstDeclarePackageNode) -> SymbolId {
+        let result = self.symbol_table.register_package(SymbolName::from(&node.package));
+        let package_id = self.package_table.register(result, self.package);
+
+        let symbol = &mut self.symbol_table[result];
+        symbol.set_package_id(package_id);
+        symbol.set_exported(node.modifiers.is_exported());
+        symbol.set_span(node.span.clone());
+
+        self.package_table[package_id].links = node.links.clone();
+
+        let parent = self.package.replace(package_id);
+
+        for r#type in &node.types {
+            let symbol = self.type_register(r#type);
+            self.package_table[package_id].types.insert(SymbolName::from(&r#type.r#type), symbol);
+            for (variant, symbol) in r#type.variants.iter().zip(self.variants_register(symbol, r#type)) {
+                self.package_table[package_id].types.insert(SymbolName::from(&variant.r#type), symbol);
+            }
+        }
+
+        for function in &node.functions {
+            let symbol = self.function_register(function);
+            self.package_table[package_id].functions.insert(SymbolName::from(&function.function), symbol);
+        }
+
+        for function in &node.external_functions {
+            let symbol = self.external_function_register(function);
+            self.package_table[package_id].functions.insert(SymbolName::from(&function.function), symbol);
+        }
+
+        for package in &node.packages {
+            let symbol = self.package_register(package);
+            self.package_table[package_id].packages.insert(SymbolName::from(&package.package), symbol);
+        }
+
+        self.package = parent;
+        result
+    }
+
+    fn package_body(&mut self, package: SymbolId, node: &AstDeclarePackageNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let package_id = self.symbol_table.package(package).package_id.unwrap();
+        let parent = self.package.replace(package_id);
+
+        let mut types = Vec::with_capacity(node.types.len());
+        for r#type in &node.types {
+            let symbol = self.package_table[package_id].types[&SymbolName::from(&r#type.r#type)];
+            self.external_type_check(symbol, r#type)?;
+
+            types.push(TypedTreeNode::new(
+                DeclareType(TypeDeclareTypeNode { r#type: symbol }),
+                r#type.span.clone(),
+                Inferred::Unit,
+            ));
+        }
+
+        let mut packages = Vec::with_capacity(node.packages.len());
+        for nested in &node.packages {
+            let symbol = self.package_table[package_id].packages[&SymbolName::from(&nested.package)];
+            packages.push(self.package_body(symbol, nested)?);
+        }
+
+        // methods of all define blocks are known before any body gets analysed
+        let mut defined = Vec::with_capacity(node.definitions.len());
+        for definition in &node.definitions {
+            defined.push(self.define_register(definition)?);
+        }
+
+        let mut functions = Vec::with_capacity(node.functions.len());
+        for function in &node.functions {
+            let symbol = self.package_table[package_id].functions[&SymbolName::from(&function.function)];
+            functions.push(self.function_body(symbol, function)?);
+        }
+
+        let mut definitions = Vec::with_capacity(node.definitions.len());
+        for (definition, defined) in node.definitions.iter().zip(defined) {
+            definitions.push(self.define_body(definition, defined)?);
+        }
+
+        for function in &node.external_functions {
+            let symbol = self.package_table[package_id].functions[&SymbolName::from(&function.function)];
+            self.external_function_check(symbol, function)?;
+        }
+
+        self.package = parent;
+
+        Ok(TypedTreeNode::new(
+            DeclarePackage(TypeDeclarePackageNode {
+                package,
+                definitions: definitions.into_boxed_slice(),
+                functions: functions.into_boxed_slice(),
+                packages: packages.into_boxed_slice(),
+                types: types.into_boxed_slice(),
+            }),
+            node.span.clone(),
+            Inferred::Unit,
+        ))
+    }
+
+    pub(crate) fn package_get(&self, path: &PackagePath) -> crate::ir::analyse::Result<SymbolId> {
+        let mut segments = path.segments.iter();
+
+        let root = segments.next().expect("package path must not be empty");
+        let mut result = self.scope.package(SymbolName(*root))
+            .ok_or(Error::Undefined(UndefinedError::UndefinedPackage {
+                package: self.string_table.get(*root).to_string(),
+                span: self.span(),
+            }))?;
+
+        for segment in segments {
+            let package_id = self.package_id(result);
+            let nested = self.package_table[package_id].packages.get(&SymbolName(*segment)).cloned()
+                .ok_or(Error::Undefined(UndefinedError::UndefinedPackage {
+                    package: self.string_table.get(*segment).to_string(),
+                    span: self.span(),
+                }))?;
+
+            self.member_visible(package_id, nested)?;
+            result = nested;
+        }
+
+        Ok(result)
+    }
+
+    pub(crate) fn package_function_get(&self, package: SymbolId, name: SymbolName) -> crate::ir::analyse::Result<SymbolId> {
+        let package_id = self.package_id(package);
+        let result = self.package_table[package_id].functions.get(&name).cloned()
+            .ok_or(Error::Undefined(UndefinedError::UndefinedFunction {
+                function: self.string_table.get(name.0).to_string(),
+                span: self.span(),
+            }))?;
+
+        self.member_visible(package_id, result)?;
+        Ok(result)
+    }
+
+    pub(crate) fn package_type_get(&self, package: SymbolId, name: SymbolName) -> crate::ir::analyse::Result<SymbolId> {
+        let package_id = self.package_id(package);
+        let result = self.package_table[package_id].types.get(&name).cloned()
+            .ok_or(Error::Undefined(UndefinedError::UndefinedType {
+                r#type: self.string_table.get(name.0).to_string(),
+                span: self.span(),
+            }))?;
+
+        self.member_visible(package_id, result)?;
+        Ok(result)
+    }
+
+    pub(crate) fn package_id(&self, package: SymbolId) -> PackageId {
+        self.symbol_table.package(package).package_id.unwrap()
+    }
+
+    // members which are not exported are only visible within the declaring package and the packages nested in it
+    fn member_visible(&self, owner: PackageId, member: SymbolId) -> crate::ir::analyse::Result<()> {
+        let symbol = &self.symbol_table[member];
+        if symbol.is_exported() {
+            return Ok(());
+        }
+
+        if let Some(current) = self.package {
+            if self.package_table[current].is_within(self.package_table, owner) {
+                return Ok(());
+            }
+        }
+
+        let name = symbol.name_str(self.string_table).to_string();
+        let span = self.span();
+        let declared_at = symbol.span();
+
+        Err(Error::Visibility(Box::new(match symbol {
+            Symbol::Function(_) => VisibilityError::FunctionNotExported { function: name, span, declared_at },
+            Symbol::Package(_) => VisibilityError::PackageNotExported { package: name, span, declared_at },
+            Symbol::Type(_) => VisibilityError::TypeNotExported { r#type: name, span, declared_at },
+            Symbol::Argument(_) | Symbol::Variable(_) => unreachable!()
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, Inferred, SymbolId};
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{prepare, UndefinedError, VisibilityError};
+    use crate::ir::analyse::Error::{Undefined, Visibility};
+
+    #[test]
+    fn call_exported_function() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        package math {
+            export function one() -> Number { return 1 }
+        }
+        math::one()
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+        assert_eq!(typed.nodes.len(), 2);
+
+        let package = typed[0].as_declare_package();
+        assert_eq!(package.package, SymbolId(1));
+        assert_eq!(package.functions.len(), 1);
+
+        let call = typed[1].as_call_function_of_package();
+        assert_eq!(call.package, SymbolId(1));
+        assert_eq!(call.function, SymbolId(2));
+    }
+
+    #[test]
+    fn call_function_not_exported() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        package math {
+            function one() -> Number { return 1 }
+        }
+        math::one()
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Visibility(error) = result.err().unwrap() else { panic!() };
+        let VisibilityError::FunctionNotExported { function, span, declared_at } = *error else { panic!() };
+        assert_eq!(function, "one");
+        assert_eq!(span.start.index, source.rfind("one").unwrap());
+        assert_eq!(declared_at.start.index, source.find("one").unwrap());
+    }
+
+    #[test]
+    fn call_function_not_exported_within_package() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        package math {
+            function one() -> Number { return 1 }
+            export function two() -> Number { return math::one() + math::one() }
+        }
+        math::two()
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+        assert_eq!(typed.nodes.len(), 2);
+    }
+
+    #[test]
+    fn call_function_of_nested_package_not_exported() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        package std {
+            package math {
+                export function one() -> Number { return 1 }
+            }
+        }
+        std::math::one()
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Visibility(error) = result.err().unwrap() else { panic!() };
+        let VisibilityError::PackageNotExported { package, span, declared_at } = *error else { panic!() };
+        assert_eq!(package, "math");
+        assert_eq!(span.start.index, source.rfind("one").unwrap());
+        assert_eq!(declared_at.start.index, source.find("math").unwrap());
+    }
+
+    #[test]
+    fn call_function_of_nested_package_from_parent() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        package std {
+            package math {
+                function one() -> Number { return 1 }
+            }
+            export function one() -> Number { return std::math::one() }
+        }
+        std::one()
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        // the parent package does not see into its nested packages
+        let Visibility(error) = result.err().unwrap() else { panic!() };
+        let VisibilityError::FunctionNotExported { function, .. } = *error else { panic!() };
+        assert_eq!(function, "one");
+    }
+
+    #[test]
+    fn call_function_of_parent_from_nested_package() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        package std {
+            function one() -> Number { return 1 }
+            export package math {
+                export function one() -> Number { return std::one() }
+            }
+        }
+        std::math::one()
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+        assert_eq!(typed.nodes.len(), 2);
+    }
+
+    #[test]
+    fn instantiate_type_not_exported() {
+        let mut ctx = Context::testing();
+        let source = r#"
+        package geometry {
+            type Point(x: Number, y: Number)
+        }
+        geometry::Point(x = 1, y = 2)
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Visibility(error) = result.err().unwrap() else { panic!() };
+        let VisibilityError::TypeNotExported { r#type, span, declared_at } = *error else { panic!() };
+        assert_eq!(r#type, "Point");
+        assert_eq!(span.start.index, source.rfind("Point").unwrap());
+        assert_eq!(declared_at.start.index, source.find("Point").unwrap());
+    }
+
+    #[test]
+    fn instantiate_exported_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        package geometry {
+            export type Point(x: Number, y: Number)
+        }
+        geometry::Point(x = 1, y = 2)
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let result = typed[1].as_instantiate_type();
+        assert_eq!(result.r#type, SymbolId(2));
+        assert_eq!(result.arguments.len(), 2);
+    }
+
+    #[test]
+    fn function_does_not_exist() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        package math { }
+        math::one()
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Undefined(UndefinedError::UndefinedFunction { function, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(function, "one");
+    }
+
+    #[test]
+    fn package_declares_link() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        package math {
+            external link 'm'
+            export external function cos(x: Float8) -> Float8
+        }
+        math::cos(1)
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let call = typed[1].as_call_function_of_package();
+        let package = ctx.symbol_table.package(call.package).package_id.unwrap();
+        let links = &ctx.package_table[package].links;
+        assert_eq!(links.len(), 1);
+        assert_eq!(ctx.str_get(links[0]), "m");
+    }
+
+    #[test]
+    fn package_defines_method() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        package shapes {
+            export type Square(side: Number)
+            export function area(square: Square) -> Number { return square.area() }
+            define Square {
+                function area() -> Number { return self.side }
+            }
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let package = typed[0].as_declare_package();
+        assert_eq!(package.functions.len(), 1);
+        assert_eq!(package.definitions.len(), 1);
+
+        let defined = package.definitions[0].as_define_type();
+        assert_eq!(defined.functions.len(), 1);
+        let r#type = ctx.symbol_table[defined.r#type].type_id().unwrap();
+        assert!(ctx.type_table.method(r#type, ctx.string_table.push_str("area")).is_some());
+    }
+
+    #[test]
+    fn declare_variable_of_exported_generic_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        package bags {
+            export type Bag<T>()
+            export function empty<T>() -> Bag<T> { return Bag() }
+        }
+        let bag: bags::Bag<Number> = bags::empty()
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let Inferred::Parameterized(_, arguments) = &typed[1].inferred else { panic!() };
+        assert_eq!(arguments.as_ref(), &[Inferred::Number]);
+    }
+}
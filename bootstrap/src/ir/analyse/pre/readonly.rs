@@ -0,0 +1,286 @@
+use crate::common::{Inferred, StringTableId, Symbol, SymbolId, TypeId};
+use crate::common::node::Node::{AccessElementOfTuple, AccessVariable, AccessVariableOfObject, InstantiateTuple, InstantiateType};
+use crate::ir::analyse::{Error, ReadonlyError, TypedTreeNode};
+use crate::ir::analyse::pre::Pre;
+
+impl<'a> Pre<'a> {
+    // a value reached through a readonly variable, or holding such a value, is readonly all the way down
+    pub(crate) fn readonly(&self, node: &TypedTreeNode) -> bool {
+        let readonly = match node.node() {
+            AccessVariable(access) => self.variable_readonly(access.variable),
+            AccessVariableOfObject(access) => self.variable_readonly(access.object),
+            AccessElementOfTuple(access) => self.readonly(&access.node),
+            InstantiateTuple(tuple) => tuple.nodes.iter().any(|node| self.readonly(node)),
+            InstantiateType(instantiate) => instantiate.arguments.iter().any(|argument| self.readonly(&argument.value)),
+            _ => false
+        };
+        readonly && self.changeable(&node.inferred)
+    }
+
+    pub(crate) fn readonly_field_check(&self, object: SymbolId, field: StringTableId) -> crate::ir::analyse::Result<()> {
+        if !self.variable_readonly(object) {
+            return Ok(());
+        }
+        Err(Error::Readonly(Box::new(ReadonlyError::AssignedField {
+            variable: self.symbol_table[object].name_str(self.string_table).to_string(),
+            field: self.string_table.get(field).to_string(),
+            span: self.span(),
+        })))
+    }
+
+    pub(crate) fn readonly_method_check(&self, object: SymbolId, function: SymbolId) -> crate::ir::analyse::Result<()> {
+        let symbol = self.symbol_table.variable(object);
+        if !symbol.readonly || !self.changeable(&symbol.inferred) || self.argument_readonly(function, 0) {
+            return Ok(());
+        }
+        Err(Error::Readonly(Box::new(ReadonlyError::CalledMutatingMethod {
+            variable: self.string_table.get(symbol.name.0).to_string(),
+            method: self.symbol_table[function].name_str(self.string_table).to_string(),
+            span: self.span(),
+        })))
+    }
+
+    pub(crate) fn readonly_argument_check(&self, function: SymbolId, index: usize, argument: &TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if !self.readonly(argument) || self.argument_readonly(function, index) {
+            return Ok(());
+        }
+        Err(Error::Readonly(Box::new(ReadonlyError::PassedAsMutable {
+            function: self.symbol_table[function].name_str(self.string_table).to_string(),
+            span: argument.span.clone(),
+        })))
+    }
+
+    pub(crate) fn readonly_return_check(&self, node: &TypedTreeNode) -> crate::ir::analyse::Result<()> {
+        if !self.readonly(node) {
+            return Ok(());
+        }
+        Err(Error::Readonly(Box::new(ReadonlyError::Returned { span: node.span.clone() })))
+    }
+
+    fn variable_readonly(&self, variable: SymbolId) -> bool {
+        matches!(&self.symbol_table[variable], Symbol::Variable(symbol) if symbol.readonly)
+    }
+
+    // lambdas held by variables have no readonly arguments
+    fn argument_readonly(&self, function: SymbolId, index: usize) -> bool {
+        matches!(&self.symbol_table[function], Symbol::Function(symbol) if symbol.readonly.get(index) == Some(&true))
+    }
+
+    // strings, numbers and booleans can not be changed anyway
+    fn changeable(&self, inferred: &Inferred) -> bool {
+        match inferred {
+            Inferred::Type(type_id) | Inferred::Parameterized(type_id, _) => matches!(type_id, TypeId::Custom(_)),
+            Inferred::Optional(inner) => self.changeable(inner),
+            Inferred::Parameter(_) => true,
+            Inferred::Record(fields) => fields.iter().any(|(_, inferred)| self.changeable(inferred)),
+            Inferred::Tuple(elements) => elements.iter().any(|inferred| self.changeable(inferred)),
+            _ => false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::Context;
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{prepare, ReadonlyError};
+    use crate::ir::analyse::Error::Readonly;
+
+    #[test]
+    fn assign_field_of_readonly_argument() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Config(port: Number)
+        function reset(config: readonly Config) {
+            config.port = 80
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Readonly(error) = result.err().unwrap() else { panic!() };
+        let ReadonlyError::AssignedField { variable, field, .. } = *error else { panic!() };
+        assert_eq!(variable, "config");
+        assert_eq!(field, "port");
+    }
+
+    #[test]
+    fn assign_field_of_readonly_variable() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Config(port: Number)
+        let config: readonly Config = Config(port = 8080)
+        config.port = 80
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Readonly(error) = result.err().unwrap() else { panic!() };
+        let ReadonlyError::AssignedField { variable, .. } = *error else { panic!() };
+        assert_eq!(variable, "config");
+    }
+
+    #[test]
+    fn assign_field_of_child() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Database(port: Number)
+        type Config(database: Database)
+        function reset(config: readonly Config) {
+            let database = config.database
+            database.port = 80
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Readonly(error) = result.err().unwrap() else { panic!() };
+        let ReadonlyError::AssignedField { variable, .. } = *error else { panic!() };
+        assert_eq!(variable, "database");
+    }
+
+    #[test]
+    fn assign_field_of_mutable_argument() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Config(port: Number)
+        function reset(config: Config) {
+            config.port = 80
+        }
+        "#).unwrap();
+        assert!(prepare(&mut ctx, ast).is_ok());
+    }
+
+    #[test]
+    fn call_mutating_method() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Config(port: Number)
+        define Config {
+            function reset() { self.port = 80 }
+        }
+        function reset(config: readonly Config) {
+            config.reset()
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Readonly(error) = result.err().unwrap() else { panic!() };
+        let ReadonlyError::CalledMutatingMethod { variable, method, .. } = *error else { panic!() };
+        assert_eq!(variable, "config");
+        assert_eq!(method, "reset");
+    }
+
+    #[test]
+    fn call_readonly_method() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Config(port: Number)
+        readonly define Config {
+            function port() -> Number { return self.port }
+        }
+        function port(config: readonly Config) -> Number {
+            return config.port()
+        }
+        "#).unwrap();
+        assert!(prepare(&mut ctx, ast).is_ok());
+    }
+
+    #[test]
+    fn readonly_define_assigns_field() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        trait Reset {
+            function reset()
+        }
+        type Config(port: Number)
+        readonly define Config: Reset {
+            function reset() { self.port = 80 }
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Readonly(error) = result.err().unwrap() else { panic!() };
+        let ReadonlyError::AssignedField { variable, .. } = *error else { panic!() };
+        assert_eq!(variable, "self");
+    }
+
+    #[test]
+    fn pass_readonly_as_mutable() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Config(port: Number)
+        function reset(config: Config) { }
+        function check(config: readonly Config) {
+            reset(config)
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Readonly(error) = result.err().unwrap() else { panic!() };
+        let ReadonlyError::PassedAsMutable { function, .. } = *error else { panic!() };
+        assert_eq!(function, "reset");
+    }
+
+    #[test]
+    fn pass_readonly_as_readonly() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Config(name: String)
+        function show(config: readonly Config) { }
+        function greet(name: String) { }
+        function check(config: readonly Config) {
+            show(config)
+            greet(config.name)
+        }
+        "#).unwrap();
+        assert!(prepare(&mut ctx, ast).is_ok());
+    }
+
+    #[test]
+    fn return_readonly() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Database(port: Number)
+        type Config(database: Database)
+        function database(config: readonly Config) -> Database {
+            return config.database
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Readonly(error) = result.err().unwrap() else { panic!() };
+        let ReadonlyError::Returned { .. } = *error else { panic!() };
+    }
+
+    #[test]
+    fn assign_readonly_to_mutable_variable() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Config(port: Number)
+        function check(config: readonly Config) {
+            let current = Config(port = 80)
+            current = config
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Readonly(error) = result.err().unwrap() else { panic!() };
+        let ReadonlyError::AssignedToMutable { variable, .. } = *error else { panic!() };
+        assert_eq!(variable, "current");
+    }
+
+    #[test]
+    fn readonly_inside_of_new_value() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Config(port: Number)
+        type Service(config: Config)
+        function reset(service: Service) { }
+        function start(config: readonly Config) {
+            reset(Service(config = config))
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Readonly(error) = result.err().unwrap() else { panic!() };
+        let ReadonlyError::PassedAsMutable { .. } = *error else { panic!() };
+    }
+}
@@ -0,0 +1,298 @@
+use std::collections::HashSet;
+
+use crate::common::{Inferred, StringTableId, Symbol, SymbolId, TypeId};
+use crate::common::node::Node::{AccessElementOfTuple, InstantiateTuple, InstantiateType};
+use crate::frontend::ast::AstInstantiateRecordNode;
+use crate::ir::analyse::{Error, RecordError, TypeAccessElementOfTupleNode, TypedTreeNode, TypeInstantiateTupleNode, TypeInstantiateTypeNode, TypeNamedArgument};
+use crate::ir::analyse::pre::Pre;
+
+impl<'a> Pre<'a> {
+    // a record is the tuple of its values ordered by field name, so (x = 1, y = 2) and (y = 2, x = 1) are the same
+    pub(crate) fn instantiate_record(&mut self, node: &AstInstantiateRecordNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+
+        let mut seen = HashSet::with_capacity(node.fields.len());
+        let mut fields = Vec::with_capacity(node.fields.len());
+        for field in &node.fields {
+            if !seen.insert(field.argument.0) {
+                return Err(Error::Record(Box::new(RecordError::DuplicateField {
+                    field: self.string_table.get(field.argument.0).to_string(),
+                    span,
+                })));
+            }
+            fields.push((field.argument.0, self.node(&field.value)?));
+        }
+        fields.sort_by(|(a, _), (b, _)| self.string_table.get(*a).cmp(self.string_table.get(*b)));
+
+        let inferred = Inferred::Record(fields.iter().map(|(name, node)| (*name, node.inferred.clone())).collect());
+        Ok(TypedTreeNode::new(
+            InstantiateTuple(TypeInstantiateTupleNode { nodes: fields.into_iter().map(|(_, node)| node).collect() }),
+            span,
+            inferred,
+        ))
+    }
+
+    pub(crate) fn record_inferred(&self, mut fields: Vec<(StringTableId, Inferred)>) -> Inferred {
+        fields.sort_by(|(a, _), (b, _)| self.string_table.get(*a).cmp(self.string_table.get(*b)));
+        Inferred::Record(fields.into_boxed_slice())
+    }
+
+    pub(crate) fn access_field_of_record(&self, record: TypedTreeNode, field: StringTableId) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+        let Inferred::Record(fields) = &record.inferred else { unreachable!() };
+
+        let Some(index) = fields.iter().position(|(name, _)| *name == field) else {
+            return Err(Error::Record(Box::new(RecordError::UnknownField {
+                field: self.string_table.get(field).to_string(),
+                r#type: record.inferred.to_string(self.string_table, self.type_table),
+                span,
+            })));
+        };
+
+        let inferred = fields[index].1.clone();
+        Ok(TypedTreeNode::new(
+            AccessElementOfTuple(TypeAccessElementOfTupleNode { node: Box::new(record), index }),
+            span,
+            inferred,
+        ))
+    }
+
+    // a record fits every record or plain type with the same fields, a value of a type gets instantiated from it
+    pub(crate) fn record_into(&self, value: TypedTreeNode, expected: &Inferred) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let Inferred::Record(fields) = &value.inferred else { return Ok(value) };
+
+        let expected_fields: Box<[(StringTableId, Inferred)]> = match expected {
+            Inferred::Record(expected) => expected.clone(),
+            Inferred::Type(type_id @ TypeId::Custom(_)) if self.record_compatible_type(*type_id) => self.type_table[*type_id].variables.iter()
+                .map(|variable| (variable.name.0, variable.inferred.clone()))
+                .collect(),
+            _ => return Ok(value)
+        };
+
+        let r#type = expected.to_string(self.string_table, self.type_table);
+        for (name, inferred) in expected_fields.iter() {
+            let Some((_, got)) = fields.iter().find(|(field, _)| field == name) else {
+                return Err(Error::Record(Box::new(RecordError::MissingField {
+                    field: self.string_table.get(*name).to_string(),
+                    r#type,
+                    span: value.span.clone(),
+                })));
+            };
+            if got != inferred {
+                return Err(Error::Record(Box::new(RecordError::FieldTypeMissMatch {
+                    field: self.string_table.get(*name).to_string(),
+                    expected: inferred.to_string(self.string_table, self.type_table),
+                    got: got.to_string(self.string_table, self.type_table),
+                    span: value.span.clone(),
+                })));
+            }
+        }
+        if let Some((name, _)) = fields.iter().find(|(field, _)| !expected_fields.iter().any(|(name, _)| name == field)) {
+            return Err(Error::Record(Box::new(RecordError::UnknownField {
+                field: self.string_table.get(*name).to_string(),
+                r#type,
+                span: value.span.clone(),
+            })));
+        }
+
+        let Inferred::Type(type_id) = expected else { return Ok(value) };
+        let Some(symbol) = self.type_symbol(*type_id) else { return Ok(value) };
+
+        let span = value.span.clone();
+        let arguments = fields.iter().enumerate()
+            .map(|(index, (name, inferred))| TypeNamedArgument {
+                argument: *name,
+                value: match value.node() {
+                    InstantiateTuple(tuple) => tuple.nodes[index].clone(),
+                    _ => TypedTreeNode::new(
+                        AccessElementOfTuple(TypeAccessElementOfTupleNode { node: Box::new(value.clone()), index }),
+                        span.clone(),
+                        inferred.clone(),
+                    )
+                },
+            })
+            .collect();
+
+        Ok(TypedTreeNode::new(
+            InstantiateType(TypeInstantiateTypeNode { r#type: symbol, arguments }),
+            span,
+            expected.clone(),
+        ))
+    }
+
+    // traits, sum types and generic types have no plain set of fields a record could provide
+    fn record_compatible_type(&self, type_id: TypeId) -> bool {
+        let r#type = &self.type_table[type_id];
+        !r#type.r#trait && !r#type.external && r#type.parameters.is_empty() && r#type.variants.is_empty()
+    }
+
+    fn type_symbol(&self, type_id: TypeId) -> Option<SymbolId> {
+        (1..=self.symbol_table.len())
+            .map(SymbolId)
+            .find(|id| matches!(self.symbol_table[*id], Symbol::Type(_)) && self.symbol_table[*id].type_id() == Some(type_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, Inferred};
+    use crate::common::node::Node::InstantiateType;
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{analyse, RecordError, TypeMissMatchError};
+    use crate::ir::analyse::Error::{Record, TypeMissMatch};
+
+    #[test]
+    fn record_value() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, "let point = (y = 'two', x = 1)").unwrap();
+        let typed = analyse(&mut ctx, ast).unwrap();
+
+        let Inferred::Record(fields) = &typed[0].inferred else { panic!() };
+        assert_eq!(ctx.str_get(fields[0].0), "x");
+        assert_eq!(fields[0].1, Inferred::Number);
+        assert_eq!(ctx.str_get(fields[1].0), "y");
+        assert_eq!(fields[1].1, Inferred::String);
+
+        let declared = typed[0].as_declared_variable();
+        let tuple = declared.value.as_instantiate_tuple();
+        assert_eq!(tuple.nodes[0].inferred, Inferred::Number);
+    }
+
+    #[test]
+    fn record_with_duplicate_field() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, "let point = (x = 1, x = 2)").unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Record(error) = result.err().unwrap() else { panic!() };
+        let RecordError::DuplicateField { field, .. } = *error else { panic!() };
+        assert_eq!(field, "x");
+    }
+
+    #[test]
+    fn access_field() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let person = (name = 'Elodie', age = 7)
+        let name = person.name
+        "#).unwrap();
+        let typed = analyse(&mut ctx, ast).unwrap();
+
+        let declared = typed[1].as_declared_variable();
+        assert_eq!(declared.value.inferred, Inferred::String);
+
+        let access = declared.value.as_access_element_of_tuple();
+        assert_eq!(access.index, 1);
+    }
+
+    #[test]
+    fn access_unknown_field() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let person = (name = 'Elodie', age = 7)
+        let height = person.height
+        "#).unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Record(error) = result.err().unwrap() else { panic!() };
+        let RecordError::UnknownField { field, r#type, .. } = *error else { panic!() };
+        assert_eq!(field, "height");
+        assert_eq!(r#type, "(age: Number, name: String)");
+    }
+
+    #[test]
+    fn declared_record_type_ignores_field_order() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, "let point: (y: Number, x: Number) = (x = 1, y = 2)").unwrap();
+        let typed = analyse(&mut ctx, ast).unwrap();
+
+        let Inferred::Record(fields) = &typed[0].inferred else { panic!() };
+        assert_eq!(fields.len(), 2);
+    }
+
+    #[test]
+    fn declared_record_type_with_mismatching_field() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, "let point: (x: Number, y: String) = (x = 1, y = 2)").unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Record(error) = result.err().unwrap() else { panic!() };
+        let RecordError::FieldTypeMissMatch { field, expected, got, .. } = *error else { panic!() };
+        assert_eq!(field, "y");
+        assert_eq!(expected, "String");
+        assert_eq!(got, "Number");
+    }
+
+    #[test]
+    fn record_is_no_tuple() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, "let point: (Number, Number) = (x = 1, y = 2)").unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let TypeMissMatch(TypeMissMatchError::DeclaredTypeMissMatch { expected, got, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(expected, "(Number, Number)");
+        assert_eq!(got, "(x: Number, y: Number)");
+    }
+
+    #[test]
+    fn record_returned_as_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Point(x: Number, y: Number)
+        function return_object() -> (x: Number, y: Number) {
+            return (x = 1, y = 2)
+        }
+        let point: Point = return_object()
+        "#).unwrap();
+        let typed = analyse(&mut ctx, ast).unwrap();
+
+        let declared = typed[2].as_declared_variable();
+        let InstantiateType(instantiate) = declared.value.node() else { panic!() };
+        assert_eq!(instantiate.arguments.len(), 2);
+        assert_eq!(declared.value.inferred.to_string(&ctx.string_table, &ctx.type_table), "Point");
+    }
+
+    #[test]
+    fn record_literal_passed_as_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Point(x: Number, y: Number)
+        function origin(point: Point) -> Point {
+            return point
+        }
+        origin((y = 0, x = 0))
+        "#).unwrap();
+        let typed = analyse(&mut ctx, ast).unwrap();
+
+        assert_eq!(typed[2].inferred.to_string(&ctx.string_table, &ctx.type_table), "Point");
+    }
+
+    #[test]
+    fn record_with_missing_field_of_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Point(x: Number, y: Number)
+        let point: Point = (x = 1)
+        "#).unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Record(error) = result.err().unwrap() else { panic!() };
+        let RecordError::MissingField { field, r#type, .. } = *error else { panic!() };
+        assert_eq!(field, "y");
+        assert_eq!(r#type, "Point");
+    }
+
+    #[test]
+    fn record_with_unknown_field_of_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Point(x: Number, y: Number)
+        let point: Point = (x = 1, y = 2, z = 3)
+        "#).unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Record(error) = result.err().unwrap() else { panic!() };
+        let RecordError::UnknownField { field, .. } = *error else { panic!() };
+        assert_eq!(field, "z");
+    }
+}
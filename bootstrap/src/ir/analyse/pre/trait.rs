@@ -0,0 +1,459 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::common::{Dispatch, Inferred, Span, StringTableId, SymbolId, SymbolName, TypeId, TypeName};
+use crate::common::node::Node::{DeclareTrait, DefineType};
+use crate::frontend::ast::{AstDeclareFunctionNode, AstDeclareTraitNode, AstDefineTypeNode, AstFunctionArgument, AstIdentifier, AstType, AstTypeParameter};
+use crate::frontend::modifier::Modifiers;
+use crate::ir::analyse::{Error, TraitError, TypeDeclareTraitNode, TypeDefineTypeNode, TypedTreeNode, UndefinedError};
+use crate::ir::analyse::pre::Pre;
+
+// the type a define block is for and its methods, registered but not yet analysed
+pub(crate) struct Defined {
+    r#type: SymbolId,
+    methods: Vec<(SymbolId, AstDeclareFunctionNode)>,
+}
+
+impl Defined {
+    pub(crate) fn methods(&self) -> impl Iterator<Item=SymbolId> + '_ {
+        self.methods.iter().map(|(symbol, _)| *symbol)
+    }
+}
+
+impl<'a> Pre<'a> {
+    pub(crate) fn declare_trait(&mut self, node: &AstDeclareTraitNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let type_id = self.type_table.register_trait(TypeName(node.r#trait.0));
+
+        let outer = self.type_parameters_enter(&node.type_parameters);
+        self.type_table[type_id].parameters = node.type_parameters.iter().map(|p| p.parameter.0).collect();
+
+        // Self stays open, it becomes the type of the object a method gets called on
+        let outer_self = self.self_type.replace(Inferred::Parameter(self.string_table.push_str("Self")));
+
+        for function in &node.functions {
+            let mut arguments = vec![self.self_argument(false, function.span.clone())];
+            arguments.extend(function.arguments.iter().cloned());
+            let inferred = self.function_signature(&arguments, function.return_type.as_ref());
+
+            let method = self.symbol_table.register_function(SymbolName::from(&function.function));
+            let symbol = self.symbol_table.function_mut(method);
+            symbol.inferred = inferred;
+            symbol.package_id = self.package;
+            symbol.readonly = arguments.iter().map(|a| a.readonly).collect();
+            symbol.span = function.span.clone();
+
+            self.type_table.register_method(type_id, function.function.0, method, function.nodes.is_some());
+            if function.nodes.is_some() {
+                self.trait_defaults.insert(method, function.clone());
+            }
+        }
+
+        self.self_type = outer_self;
+        self.type_parameters = outer;
+
+        let r#trait = self.symbol_table.register_type(SymbolName::from(&node.r#trait));
+        let symbol = &mut self.symbol_table[r#trait];
+        symbol.set_type_id(type_id);
+        symbol.set_exported(node.modifiers.is_exported());
+        symbol.set_span(node.span.clone());
+
+        let symbol = self.symbol_table[r#trait].clone();
+        self.scope.register_symbol(&symbol);
+
+        Ok(TypedTreeNode::new(
+            DeclareTrait(TypeDeclareTraitNode { r#trait }),
+            node.span.clone(),
+            Inferred::Unit,
+        ))
+    }
+
+    pub(crate) fn define_type(&mut self, node: &AstDefineTypeNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let defined = self.define_register(node)?;
+        self.define_body(node, defined)
+    }
+
+    // registers the methods of the type, so that they can be called before the bodies get analysed
+    pub(crate) fn define_register(&mut self, node: &AstDefineTypeNode) -> crate::ir::analyse::Result<Defined> {
+        let r#type = self.type_get(SymbolName::from(&node.r#type))
+            .ok_or(Error::Undefined(UndefinedError::UndefinedType {
+                r#type: self.string_table.get(node.r#type.0).to_string(),
+                span: node.span.clone(),
+            }))?;
+        let type_id = self.symbol_table[r#type].type_id().unwrap();
+
+        let (outer, outer_self) = self.define_enter(node, type_id);
+
+        let mut methods = Vec::with_capacity(node.functions.len());
+        for function in &node.functions {
+            self.operator_check(function, true)?;
+            let method = self.method_node(function, node.modifiers.is_readonly());
+            let symbol = self.function_register(&method);
+            if function.modifiers.is_operator() {
+                self.type_table.register_operator(type_id, function.function.0, symbol);
+            } else {
+                self.type_table.register_method(type_id, function.function.0, symbol, false);
+            }
+            methods.push((symbol, method));
+        }
+
+        for r#trait in &node.traits {
+            let (trait_id, trait_inferred) = self.trait_get(r#trait, &node.span)?;
+            let self_type = self.self_type.clone().unwrap();
+            methods.extend(self.define_trait(type_id, &self_type, trait_id, &trait_inferred, node)?);
+            self.type_table[type_id].traits.push(trait_id);
+        }
+
+        self.self_type = outer_self;
+        self.type_parameters = outer;
+
+        Ok(Defined { r#type, methods })
+    }
+
+    pub(crate) fn define_body(&mut self, node: &AstDefineTypeNode, defined: Defined) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let type_id = self.symbol_table[defined.r#type].type_id().unwrap();
+        let (outer, outer_self) = self.define_enter(node, type_id);
+
+        let mut functions = Vec::with_capacity(defined.methods.len());
+        for (symbol, method) in &defined.methods {
+            functions.push(self.function_body(*symbol, method)?);
+        }
+
+        self.self_type = outer_self;
+        self.type_parameters = outer;
+
+        Ok(TypedTreeNode::new(
+            DefineType(TypeDefineTypeNode { r#type: defined.r#type, functions: functions.into_boxed_slice() }),
+            node.span.clone(),
+            Inferred::Unit,
+        ))
+    }
+
+    // the type parameters of the define are in scope and Self is the defined type, returns what was there before
+    fn define_enter(&mut self, node: &AstDefineTypeNode, type_id: TypeId) -> (Vec<(StringTableId, Option<TypeId>)>, Option<Inferred>) {
+        let outer = self.type_parameters_enter(&node.type_parameters);
+        let self_type = if node.type_parameters.is_empty() {
+            Inferred::Type(type_id)
+        } else {
+            Inferred::Parameterized(type_id, node.type_parameters.iter().map(|p| Inferred::Parameter(p.parameter.0)).collect())
+        };
+        (outer, self.self_type.replace(self_type))
+    }
+
+    // checks the methods of the type against the trait, methods it does not define come from the defaults of the trait
+    fn define_trait(
+        &mut self,
+        type_id: TypeId,
+        self_type: &Inferred,
+        trait_id: TypeId,
+        trait_inferred: &Inferred,
+        node: &AstDefineTypeNode,
+    ) -> crate::ir::analyse::Result<Vec<(SymbolId, AstDeclareFunctionNode)>> {
+        let r#trait = &self.type_table[trait_id];
+        let mut type_arguments: HashMap<StringTableId, Inferred> = match trait_inferred {
+            Inferred::Parameterized(_, arguments) => r#trait.parameters.iter().cloned().zip(arguments.iter().cloned()).collect(),
+            _ => HashMap::new()
+        };
+        type_arguments.insert(self.string_table.push_str("Self"), self_type.clone());
+
+        let mut result = vec![];
+        for method in self.type_table[trait_id].methods.clone() {
+            let expected = self.symbol_table.signature(method.function).substitute(&type_arguments);
+
+            if let Some(defined) = self.type_table.method(type_id, method.name) {
+                let got = self.symbol_table.signature(defined.function);
+                if got != &expected {
+                    return Err(Error::Trait(Box::new(TraitError::MethodSignatureMissMatch {
+                        method: self.string_table.get(method.name).to_string(),
+                        expected: expected.to_string(self.string_table, self.type_table),
+                        got: got.to_string(self.string_table, self.type_table),
+                        span: self.symbol_table.function(defined.function).span.clone(),
+                    })));
+                }
+                continue;
+            }
+
+            if !method.default {
+                return Err(Error::Trait(Box::new(TraitError::MissingMethod {
+                    r#trait: self.string_table.get(self.type_table[trait_id].name.0).to_string(),
+                    r#type: self.string_table.get(self.type_table[type_id].name.0).to_string(),
+                    method: self.string_table.get(method.name).to_string(),
+                    span: node.span.clone(),
+                })));
+            }
+
+            // the default gets analysed for every type, so that it calls the methods of the type directly
+            let default = self.trait_defaults[&method.function].clone();
+            let function = self.method_node(&AstDeclareFunctionNode {
+                function: default.function.clone(),
+                type_parameters: self.type_table[trait_id].parameters.iter()
+                    .map(|p| AstTypeParameter { parameter: AstIdentifier(*p), bound: None })
+                    .collect(),
+                modifiers: Modifiers(vec![]),
+                span: default.span.clone(),
+                arguments: default.arguments.clone(),
+                return_type: default.return_type.clone(),
+                nodes: Rc::clone(default.nodes.as_ref().unwrap()),
+            }, node.modifiers.is_readonly());
+            let symbol = self.function_register(&function);
+            self.type_table.register_method(type_id, method.name, symbol, false);
+            result.push((symbol, function));
+        }
+        Ok(result)
+    }
+
+    // a method is a function which takes the object it gets called on as first argument
+    // the methods of a readonly define can not change that object
+    fn method_node(&mut self, function: &AstDeclareFunctionNode, readonly: bool) -> AstDeclareFunctionNode {
+        let mut result = function.clone();
+        result.arguments.insert(0, self.self_argument(readonly, function.span.clone()));
+        result
+    }
+
+    fn self_argument(&mut self, readonly: bool, span: Span) -> AstFunctionArgument {
+        AstFunctionArgument {
+            argument: AstIdentifier(self.string_table.push_str("self")),
+            argument_type: Some(AstType::Type { package: None, r#type: AstIdentifier(self.string_table.push_str("Self")), arguments: vec![] }),
+            readonly,
+            default: None,
+            span,
+        }
+    }
+
+    pub(crate) fn trait_get(&self, r#type: &AstType, span: &Span) -> crate::ir::analyse::Result<(TypeId, Inferred)> {
+        let inferred = self.inferred_from_ast_type(r#type);
+        match inferred.type_id() {
+            Some(type_id) if self.type_table.is_trait(type_id) => Ok((type_id, inferred)),
+            _ => Err(Error::Trait(Box::new(TraitError::NotATrait {
+                r#type: r#type.to_string(self.string_table),
+                span: span.clone(),
+            })))
+        }
+    }
+
+    // methods of known types get called directly, trait values and bound type parameters go through the vtable
+    pub(crate) fn method_get(&self, object: &Inferred, method: StringTableId) -> crate::ir::analyse::Result<(SymbolId, Dispatch)> {
+        let r#trait = match object {
+            Inferred::Parameter(parameter) => self.type_parameter_bound(*parameter),
+            _ => object.type_id().filter(|type_id| self.type_table.is_trait(*type_id))
+        };
+
+        if let Some(r#trait) = r#trait {
+            let methods = &self.type_table[r#trait].methods;
+            if let Some(idx) = methods.iter().position(|m| m.name == method) {
+                return Ok((methods[idx].function, Dispatch::Vtable(idx)));
+            }
+        } else if let Some(type_id @ TypeId::Custom(_)) = object.type_id() {
+            if let Some(result) = self.type_table.method(type_id, method) {
+                return Ok((result.function, Dispatch::Static));
+            }
+        }
+
+        Err(Error::Undefined(UndefinedError::UndefinedMethod {
+            r#type: object.to_string(self.string_table, self.type_table),
+            method: self.string_table.get(method).to_string(),
+            span: self.span(),
+        }))
+    }
+
+    // a value of a type parameter satisfies the trait its parameter is bound to
+    pub(crate) fn trait_implemented(&self, inferred: &Inferred, r#trait: TypeId) -> bool {
+        match inferred {
+            Inferred::Parameter(parameter) => self.type_parameter_bound(*parameter) == Some(r#trait),
+            _ => inferred.type_id().is_some_and(|type_id| self.type_table.implements(type_id, r#trait))
+        }
+    }
+
+    pub(crate) fn trait_check(&self, inferred: &Inferred, r#trait: TypeId) -> crate::ir::analyse::Result<()> {
+        if self.trait_implemented(inferred, r#trait) {
+            return Ok(());
+        }
+
+        Err(Error::Trait(Box::new(TraitError::NotImplemented {
+            r#trait: self.string_table.get(self.type_table[r#trait].name.0).to_string(),
+            r#type: inferred.to_string(self.string_table, self.type_table),
+            span: self.span(),
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, Dispatch, Inferred};
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{prepare, TraitError};
+    use crate::ir::analyse::Error::Trait;
+
+    #[test]
+    fn declare_trait() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        trait Shape {
+            function area() -> Number
+            function scaled(factor: Number) -> Number { return factor }
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let declared = typed[0].as_declare_trait();
+        let type_id = ctx.symbol_table[declared.r#trait].type_id().unwrap();
+        assert!(ctx.type_table.is_trait(type_id));
+
+        let methods = &ctx.type_table[type_id].methods;
+        assert_eq!(methods.len(), 2);
+        assert!(!methods[0].default);
+        assert!(methods[1].default);
+
+        let Inferred::Function(arguments, result) = ctx.symbol_table.signature(methods[1].function) else { panic!() };
+        assert_eq!(arguments.len(), 2);
+        assert_eq!(result.as_ref(), &Inferred::Number);
+    }
+
+    #[test]
+    fn define_type_implements_trait() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        trait Shape {
+            function area() -> Number
+        }
+        type Square(side: Number)
+        define Square: Shape {
+            function area() -> Number { return 4 }
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let r#trait = ctx.symbol_table[typed[0].as_declare_trait().r#trait].type_id().unwrap();
+        let defined = typed[2].as_define_type();
+        let r#type = ctx.symbol_table[defined.r#type].type_id().unwrap();
+        assert_eq!(defined.functions.len(), 1);
+        assert!(ctx.type_table.implements(r#type, r#trait));
+    }
+
+    #[test]
+    fn define_type_without_required_method() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        trait Shape {
+            function area() -> Number
+        }
+        type Square(side: Number)
+        define Square: Shape {}
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Trait(error) = result.err().unwrap() else { panic!() };
+        let TraitError::MissingMethod { r#trait, r#type, method, .. } = *error else { panic!() };
+        assert_eq!(r#trait, "Shape");
+        assert_eq!(r#type, "Square");
+        assert_eq!(method, "area");
+    }
+
+    #[test]
+    fn define_type_with_other_signature() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        trait Shape {
+            function area() -> Number
+        }
+        type Square(side: Number)
+        define Square: Shape {
+            function area() -> String { return 'four' }
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Trait(error) = result.err().unwrap() else { panic!() };
+        let TraitError::MethodSignatureMissMatch { method, expected, got, .. } = *error else { panic!() };
+        assert_eq!(method, "area");
+        assert_eq!(expected, "function(Square) -> Number");
+        assert_eq!(got, "function(Square) -> String");
+    }
+
+    #[test]
+    fn define_type_takes_default_method() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        trait Shape {
+            function area() -> Number
+            function doubled() -> Number { return self.area() }
+        }
+        type Square(side: Number)
+        define Square: Shape {
+            function area() -> Number { return 4 }
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let defined = typed[2].as_define_type();
+        assert_eq!(defined.functions.len(), 2);
+
+        // the default calls the method of the type directly
+        let doubled = defined.functions[1].as_declare_function();
+        let call = doubled.nodes[0].as_return_from_function().node.as_ref().unwrap().as_call_function_of_object();
+        assert_eq!(call.function, defined.functions[0].as_declare_function().function);
+        assert_eq!(call.dispatch, Dispatch::Static);
+    }
+
+    #[test]
+    fn call_method_of_trait_value() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        trait Shape {
+            function name() -> String
+            function area() -> Number
+        }
+        function area_of(shape: Shape) -> Number {
+            return shape.area()
+        }
+        function area_of_any<T: Shape>(shape: T) -> Number {
+            return shape.area()
+        }
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        for function in [&typed[1], &typed[2]] {
+            let function = function.as_declare_function();
+            let call = function.nodes[0].as_return_from_function().node.as_ref().unwrap();
+            assert_eq!(call.as_call_function_of_object().dispatch, Dispatch::Vtable(1));
+            assert_eq!(call.inferred, Inferred::Number);
+        }
+    }
+
+    #[test]
+    fn call_with_type_not_implementing_bound() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        trait Shape {
+            function area() -> Number
+        }
+        type Point(x: Number)
+        function area_of<T: Shape>(shape: T) -> Number {
+            return shape.area()
+        }
+        let p = Point(x = 1)
+        area_of(p)
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Trait(error) = result.err().unwrap() else { panic!() };
+        let TraitError::NotImplemented { r#trait, r#type, .. } = *error else { panic!() };
+        assert_eq!(r#trait, "Shape");
+        assert_eq!(r#type, "Point");
+    }
+
+    #[test]
+    fn bound_which_is_not_a_trait() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        type Point(x: Number)
+        function first<T: Point>(value: T) -> T {
+            return value
+        }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Trait(error) = result.err().unwrap() else { panic!() };
+        let TraitError::NotATrait { r#type, .. } = *error else { panic!() };
+        assert_eq!(r#type, "Point");
+    }
+}
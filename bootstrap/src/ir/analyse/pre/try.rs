@@ -0,0 +1,170 @@
+use crate::common::{Inferred, TypeId};
+use crate::common::node::Node::Try;
+use crate::frontend::ast::AstTryNode;
+use crate::ir::analyse::{DeferError, Error, ResultError, TypedTreeNode, TypeTryNode};
+use crate::ir::analyse::pre::Pre;
+
+impl<'a> Pre<'a> {
+    // value? is the value of Ok, an Err gets returned as it is - so the function has to return a result with the same error type
+    pub(crate) fn r#try(&mut self, node: &AstTryNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+        self.defer_check(|span| DeferError::Return { span })?;
+        let value = Box::new(self.node(&node.node)?);
+
+        let Some((sum, arguments)) = self.result_of(&value.inferred) else {
+            return Err(Error::Result(ResultError::NotAResult {
+                got: value.inferred.to_string(self.string_table, self.type_table),
+                span: value.span.clone(),
+            }));
+        };
+
+        let Some(function) = self.function else {
+            return Err(Error::Result(ResultError::OutsideOfFunction { span }));
+        };
+
+        let expected = self.function_return(function);
+        let compatible = match &expected {
+            Inferred::Parameterized(type_id, expected) if *type_id == sum => {
+                expected[1] == arguments[1] || expected[1].is_parameterized() || arguments[1].is_parameterized()
+            }
+            _ => false
+        };
+
+        if !compatible {
+            return Err(Error::Result(ResultError::ReturnTypeMissMatch {
+                expected: expected.to_string(self.string_table, self.type_table),
+                got: value.inferred.to_string(self.string_table, self.type_table),
+                span,
+            }));
+        }
+
+        let error = self.result_variant(sum, "Err").unwrap();
+        let inferred = arguments[0].clone();
+
+        Ok(TypedTreeNode::new(
+            Try(TypeTryNode { node: value, error }),
+            span,
+            inferred,
+        ))
+    }
+
+    // a result is a sum type of Ok(value) and Err(error) like the Result of core, its type arguments are the types of both
+    fn result_of(&self, inferred: &Inferred) -> Option<(TypeId, Box<[Inferred]>)> {
+        let Inferred::Parameterized(type_id, arguments) = inferred else {
+            return None;
+        };
+
+        if !self.type_table.is_sum(*type_id) || arguments.len() != 2 || self.type_table[*type_id].variants.len() != 2 {
+            return None;
+        }
+
+        for name in ["Ok", "Err"] {
+            let variant = self.result_variant(*type_id, name)?;
+            if self.type_table[variant].variables.len() != 1 {
+                return None;
+            }
+        }
+
+        Some((*type_id, arguments.clone()))
+    }
+
+    fn result_variant(&self, sum: TypeId, name: &str) -> Option<TypeId> {
+        self.type_table[sum].variants.iter()
+            .find(|variant| self.string_table.get(self.type_table[**variant].name.0) == name)
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, Inferred};
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{prepare, ResultError};
+    use crate::ir::analyse::Error::Result;
+
+    const RESULT: &str = r#"
+        type Result<T, E> = Ok(value: T) | Err(error: E)
+        function read(fail: Bool) -> Result<Number, String> {
+            if fail == true {
+                return Err(error = 'failed')
+            }
+            return Ok(value = 42)
+        }
+    "#;
+
+    #[test]
+    fn try_takes_value_of_ok() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, &format!(r#"{RESULT}
+        function twice() -> Result<Number, String> {{
+            let value = read(false)?
+            return Ok(value = value * 2)
+        }}
+        "#)).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let twice = typed[2].as_declare_function();
+        let declared = twice.nodes[0].as_declared_variable();
+        assert_eq!(declared.value.inferred, Inferred::Number);
+
+        let r#try = declared.value.as_try();
+        assert_eq!(ctx.str_get(ctx.type_table[r#try.error].name.0), "Err");
+    }
+
+    #[test]
+    fn try_outside_of_function() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, &format!(r#"{RESULT}
+        let value = read(false)?
+        "#)).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Result(ResultError::OutsideOfFunction { .. }) = result.err().unwrap() else { panic!() };
+    }
+
+    #[test]
+    fn try_on_value_which_is_no_result() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, &format!(r#"{RESULT}
+        function twice(value: Number) -> Result<Number, String> {{
+            let result = value?
+            return Ok(value = result)
+        }}
+        "#)).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Result(ResultError::NotAResult { got, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(got, "Number");
+    }
+
+    #[test]
+    fn try_in_function_which_returns_no_result() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, &format!(r#"{RESULT}
+        function twice() -> Number {{
+            let value = read(false)?
+            return value * 2
+        }}
+        "#)).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Result(ResultError::ReturnTypeMissMatch { expected, got, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(expected, "Number");
+        assert_eq!(got, "Result<Number, String>");
+    }
+
+    #[test]
+    fn try_with_other_error_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, &format!(r#"{RESULT}
+        function twice() -> Result<Number, Number> {{
+            let value = read(false)?
+            return Ok(value = value * 2)
+        }}
+        "#)).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Result(ResultError::ReturnTypeMissMatch { expected, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(expected, "Result<Number, Number>");
+    }
+}
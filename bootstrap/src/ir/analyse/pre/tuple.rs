@@ -0,0 +1,221 @@
+use crate::common::{Inferred, SymbolName};
+use crate::common::node::Node::{AccessElementOfTuple, DestructureTuple, InstantiateTuple};
+use crate::frontend::ast::{AstAccessElementOfTupleNode, AstDestructureTupleNode, AstInstantiateTupleNode};
+use crate::ir::analyse::{Error, TupleError, TypeAccessElementOfTupleNode, TypeDestructureTupleNode, TypedTreeNode, TypeInstantiateTupleNode};
+use crate::ir::analyse::pre::Pre;
+
+impl<'a> Pre<'a> {
+    pub(crate) fn instantiate_tuple(&mut self, node: &AstInstantiateTupleNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+
+        let mut nodes = Vec::with_capacity(node.nodes.len());
+        for node in &node.nodes {
+            nodes.push(self.node(node)?);
+        }
+
+        let inferred = Inferred::Tuple(nodes.iter().map(|n| n.inferred.clone()).collect());
+        Ok(TypedTreeNode::new(
+            InstantiateTuple(TypeInstantiateTupleNode { nodes: nodes.into_boxed_slice() }),
+            span,
+            inferred,
+        ))
+    }
+
+    pub(crate) fn access_element_of_tuple(&mut self, node: &AstAccessElementOfTupleNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+        let tuple = self.node(&node.node)?;
+
+        let Inferred::Tuple(elements) = &tuple.inferred else {
+            return Err(Error::Tuple(TupleError::NotATuple {
+                got: tuple.inferred.to_string(self.string_table, self.type_table),
+                span,
+            }));
+        };
+
+        let Some(inferred) = elements.get(node.index).cloned() else {
+            return Err(Error::Tuple(TupleError::IndexOutOfBounds {
+                index: node.index,
+                size: elements.len(),
+                span,
+            }));
+        };
+
+        Ok(TypedTreeNode::new(
+            AccessElementOfTuple(TypeAccessElementOfTupleNode { node: Box::new(tuple), index: node.index }),
+            span,
+            inferred,
+        ))
+    }
+
+    // every variable takes the type of the element at its position
+    pub(crate) fn destructure_tuple(&mut self, node: &AstDestructureTupleNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+        let value = self.node(&node.value)?;
+
+        let Inferred::Tuple(elements) = &value.inferred else {
+            return Err(Error::Tuple(TupleError::NotATuple {
+                got: value.inferred.to_string(self.string_table, self.type_table),
+                span,
+            }));
+        };
+
+        if elements.len() != node.variables.len() {
+            return Err(Error::Tuple(TupleError::ElementCountMissMatch {
+                expected: elements.len(),
+                got: node.variables.len(),
+                span,
+            }));
+        }
+
+        // every variable retains its element, so it can be reassigned like any other let
+        let variables = node.variables.iter().zip(elements.iter())
+            .map(|(variable, inferred)| self.variable_register(SymbolName::from(variable), inferred.clone()))
+            .collect::<Box<[_]>>();
+        let readonly = self.readonly(&value);
+        for variable in &variables {
+            let symbol = self.symbol_table.variable_mut(*variable);
+            symbol.mutable = true;
+            symbol.readonly = readonly;
+        }
+
+        let inferred = value.inferred.clone();
+        Ok(TypedTreeNode::new(
+            DestructureTuple(TypeDestructureTupleNode { variables, value: Box::new(value) }),
+            span,
+            inferred,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, Inferred};
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{analyse, TupleError, TypeMissMatchError};
+    use crate::ir::analyse::Error::{Tuple, TypeMissMatch};
+
+    #[test]
+    fn tuple_value() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, "let pair = (1, 'Elodie')").unwrap();
+        let typed = analyse(&mut ctx, ast).unwrap();
+
+        assert_eq!(typed[0].inferred, Inferred::Tuple(Box::new([Inferred::Number, Inferred::String])));
+
+        let declared = typed[0].as_declared_variable();
+        let tuple = declared.value.as_instantiate_tuple();
+        assert_eq!(tuple.nodes.len(), 2);
+    }
+
+    #[test]
+    fn parens_around_single_value_are_no_tuple() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, "let value = (1)").unwrap();
+        let typed = analyse(&mut ctx, ast).unwrap();
+
+        assert_eq!(typed[0].inferred, Inferred::Number);
+    }
+
+    #[test]
+    fn declared_tuple_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, "let pair: (Number, String) = (1, 'Elodie')").unwrap();
+        let typed = analyse(&mut ctx, ast).unwrap();
+
+        assert_eq!(typed[0].inferred, Inferred::Tuple(Box::new([Inferred::Number, Inferred::String])));
+    }
+
+    #[test]
+    fn declared_tuple_type_miss_match() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, "let pair: (String, Number) = (1, 'Elodie')").unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let TypeMissMatch(TypeMissMatchError::DeclaredTypeMissMatch { expected, got, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(expected, "(String, Number)");
+        assert_eq!(got, "(Number, String)");
+    }
+
+    #[test]
+    fn access_element() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let pair = (1, 'Elodie')
+        let name = pair.1
+        "#).unwrap();
+        let typed = analyse(&mut ctx, ast).unwrap();
+
+        let declared = typed[1].as_declared_variable();
+        assert_eq!(declared.value.inferred, Inferred::String);
+
+        let access = declared.value.as_access_element_of_tuple();
+        assert_eq!(access.index, 1);
+    }
+
+    #[test]
+    fn access_element_of_nested_tuple() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let nested = (1, ('Elodie', true))
+        let flag = nested.1.1
+        "#).unwrap();
+        let typed = analyse(&mut ctx, ast).unwrap();
+
+        assert_eq!(typed[1].inferred, Inferred::Boolean);
+    }
+
+    #[test]
+    fn access_element_out_of_bounds() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let pair = (1, 'Elodie')
+        let name = pair.2
+        "#).unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Tuple(TupleError::IndexOutOfBounds { index, size, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(index, 2);
+        assert_eq!(size, 2);
+    }
+
+    #[test]
+    fn access_element_of_no_tuple() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        let value = 1
+        let name = value.0
+        "#).unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Tuple(TupleError::NotATuple { got, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(got, "Number");
+    }
+
+    #[test]
+    fn destructure_tuple_returned_by_function() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+        function pair() -> (Number, String) {
+            return (1, 'Elodie')
+        }
+        let (number, name) = pair()
+        "#).unwrap();
+        let typed = analyse(&mut ctx, ast).unwrap();
+
+        let destructured = typed[1].as_destructure_tuple();
+        assert_eq!(destructured.variables.len(), 2);
+        assert_eq!(ctx.symbol_table.variable(destructured.variables[0]).inferred, Inferred::Number);
+        assert_eq!(ctx.symbol_table.variable(destructured.variables[1]).inferred, Inferred::String);
+    }
+
+    #[test]
+    fn destructure_with_too_many_variables() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, "let (a, b, c) = (1, 'Elodie')").unwrap();
+        let result = analyse(&mut ctx, ast);
+
+        let Tuple(TupleError::ElementCountMissMatch { expected, got, .. }) = result.err().unwrap() else { panic!() };
+        assert_eq!(expected, 2);
+        assert_eq!(got, 3);
+    }
+}
@@ -0,0 +1,416 @@
+use std::collections::HashMap;
+
+use crate::common::{Inferred, StringTableId, WithSpan, SymbolId, SymbolName, TypeId, TypeName, TypeVariableId, TypeVariableName};
+use crate::common::node::Node::{DeclareType, InstantiateType};
+use crate::frontend::ast::{AstDeclareTypeNode, AstInstantiateTypeNode, AstType, AstTypeParameter};
+use crate::ir::analyse::{Error, ExternalError, RecordError, TypeDeclareTypeNode, TypedTreeNode, TypeInstantiateTypeNode, TypeMissMatchError, TypeNamedArgument, UndefinedError};
+use crate::ir::analyse::infer::generic::infer_type_arguments;
+use crate::ir::analyse::pre::Pre;
+
+impl<'a> Pre<'a> {
+    pub(crate) fn declare_type(&mut self, node: &AstDeclareTypeNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let r#type = self.type_register(node);
+        let symbol = self.symbol_table[r#type].clone();
+        self.scope.register_symbol(&symbol);
+        for variant in self.variants_register(r#type, node) {
+            let symbol = self.symbol_table[variant].clone();
+            self.scope.register_symbol(&symbol);
+        }
+        self.external_type_check(r#type, node)?;
+
+        Ok(TypedTreeNode::new(
+            DeclareType(TypeDeclareTypeNode { r#type }),
+            node.span.clone(),
+            Inferred::Unit,
+        ))
+    }
+
+    pub(crate) fn type_register(&mut self, node: &AstDeclareTypeNode) -> SymbolId {
+        let type_id = if node.external {
+            self.type_table.register_external(TypeName(node.r#type.0))
+        } else {
+            self.type_table.register(TypeName(node.r#type.0))
+        };
+
+        let outer = self.type_parameters_enter(&node.type_parameters);
+        self.type_table[type_id].parameters = node.type_parameters.iter().map(|p| p.parameter.0).collect();
+        for variable in &node.variables {
+            let inferred = self.inferred_from_ast_type(&variable.r#type);
+            self.type_table.register_variable(type_id, TypeVariableName(variable.variable.0), inferred);
+        }
+        self.type_parameters = outer;
+
+        let result = self.symbol_table.register_type(SymbolName::from(&node.r#type));
+        let symbol = &mut self.symbol_table[result];
+        symbol.set_type_id(type_id);
+        symbol.set_exported(node.modifiers.is_exported());
+        symbol.set_span(node.span.clone());
+        result
+    }
+
+    // every variant of a sum type is a type of its own, which can be instantiated by its name
+    pub(crate) fn variants_register(&mut self, r#type: SymbolId, node: &AstDeclareTypeNode) -> Vec<SymbolId> {
+        let type_id = self.symbol_table[r#type].type_id().unwrap();
+        let outer = self.type_parameters_enter(&node.type_parameters);
+
+        let mut result = Vec::with_capacity(node.variants.len());
+        for variant in &node.variants {
+            let variant_id = self.type_table.register_variant(type_id, TypeName(variant.r#type.0));
+            for variable in &variant.variables {
+                let inferred = self.inferred_from_ast_type(&variable.r#type);
+                self.type_table.register_variable(variant_id, TypeVariableName(variable.variable.0), inferred);
+            }
+
+            let symbol_id = self.symbol_table.register_type(SymbolName::from(&variant.r#type));
+            let symbol = &mut self.symbol_table[symbol_id];
+            symbol.set_type_id(variant_id);
+            symbol.set_exported(node.modifiers.is_exported());
+            symbol.set_span(variant.span.clone());
+            result.push(symbol_id);
+        }
+
+        self.type_parameters = outer;
+        result
+    }
+
+    // c structs can only hold numbers, booleans and other c structs
+    pub(crate) fn external_type_check(&self, r#type: SymbolId, node: &AstDeclareTypeNode) -> crate::ir::analyse::Result<()> {
+        let type_id = self.symbol_table[r#type].type_id().unwrap();
+        if !self.type_table.is_external(type_id) {
+            return Ok(());
+        }
+
+        for (variable, declared) in self.type_table[type_id].variables.iter().zip(&node.variables) {
+            let supported = match variable.type_id {
+                TypeId::STRING | TypeId::UNIT | TypeId::UNKNOWN => false,
+                TypeId::Builtin(_) => true,
+                type_id => self.type_table.is_external(type_id)
+            };
+
+            if !supported {
+                return Err(Error::External(Box::new(ExternalError::Variable {
+                    r#type: self.string_table.get(node.r#type.0).to_string(),
+                    variable: self.string_table.get(declared.variable.0).to_string(),
+                    span: node.span.clone(),
+                })));
+            }
+        }
+        Ok(())
+    }
+
+    // the variable of the type the object is an instance of
+    pub(crate) fn type_variable_get(&self, object: &Inferred, variable: StringTableId) -> crate::ir::analyse::Result<(TypeVariableId, Inferred)> {
+        let result = object.type_id()
+            .filter(|type_id| matches!(type_id, TypeId::Custom(_)))
+            .and_then(|type_id| self.type_table.variable(type_id, variable));
+
+        let Some(result) = result else {
+            return Err(Error::Undefined(UndefinedError::UndefinedTypeVariable {
+                r#type: object.type_id()
+                    .filter(|type_id| matches!(type_id, TypeId::Custom(_)))
+                    .map(|type_id| self.string_table.get(self.type_table[type_id].name.0).to_string())
+                    .unwrap_or_default(),
+                variable: self.string_table.get(variable).to_string(),
+                span: self.span(),
+            }));
+        };
+
+        // List<Number> turns the T of its variables into Number
+        let r#type = &self.type_table[object.type_id().unwrap()];
+        let inferred = match object {
+            Inferred::Parameterized(_, arguments) => result.inferred.substitute(&r#type.parameters.iter().cloned().zip(arguments.iter().cloned()).collect()),
+            _ => result.inferred.clone()
+        };
+
+        Ok((result.variable_id, inferred))
+    }
+
+    pub(crate) fn instantiate_type(&mut self, node: &AstInstantiateTypeNode) -> crate::ir::analyse::Result<TypedTreeNode> {
+        let span = self.span();
+
+        let r#type = if let Some(package) = &node.package {
+            let package = self.package_get(package)?;
+            self.package_type_get(package, SymbolName::from(&node.r#type))?
+        } else {
+            self.type_get(SymbolName::from(&node.r#type))
+                .ok_or(Error::Undefined(UndefinedError::UndefinedType {
+                    r#type: self.string_table.get(node.r#type.0).to_string(),
+                    span: self.span(),
+                }))?
+        };
+
+        // a variable of a type parameter takes any value, the other ones take values of their declared type
+        let type_id = self.symbol_table[r#type].type_id();
+        let mut arguments = Vec::with_capacity(node.arguments.len());
+        for argument in &node.arguments {
+            let declared = type_id
+                .and_then(|type_id| self.type_table.variable(type_id, argument.argument.0))
+                .map(|variable| variable.inferred.clone())
+                .filter(|inferred| !inferred.is_parameterized());
+            arguments.push(TypeNamedArgument {
+                argument: argument.argument.0,
+                value: match declared {
+                    Some(declared) => self.declared_value(&argument.value, &declared)?,
+                    None => self.node(&argument.value)?
+                },
+            })
+        }
+
+        // a variant without variables can be named on its own, every other value gets all of its variables
+        if let Some(type_id) = type_id.filter(|type_id| !self.type_table.is_external(*type_id)) {
+            let missing = self.type_table[type_id].variables.iter()
+                .find(|variable| !arguments.iter().any(|argument| argument.argument == variable.name.0));
+            if let Some(missing) = missing {
+                return Err(Error::Record(Box::new(RecordError::MissingField {
+                    field: self.string_table.get(missing.name.0).to_string(),
+                    r#type: self.string_table.get(node.r#type.0).to_string(),
+                    span: span.clone(),
+                })));
+            }
+        }
+
+        let inferred = match self.symbol_table[r#type].type_id() {
+            Some(type_id) => self.type_instantiated(type_id, &arguments)?,
+            None => Inferred::Unknown
+        };
+
+        // the value of a variant is a value of its sum type, the variant is only known by its tag
+        let inferred = match inferred {
+            Inferred::Type(type_id) if self.type_table.is_variant(type_id) => Inferred::Type(self.type_table[type_id].variant_of.unwrap()),
+            Inferred::Parameterized(type_id, arguments) if self.type_table.is_variant(type_id) => Inferred::Parameterized(self.type_table[type_id].variant_of.unwrap(), arguments),
+            inferred => inferred
+        };
+
+        Ok(TypedTreeNode::new(
+            InstantiateType(TypeInstantiateTypeNode {
+                r#type,
+                arguments: arguments.into_boxed_slice(),
+            }),
+            span,
+            inferred,
+        ))
+    }
+
+    // the type arguments of a generic type get inferred from the values of its variables,
+    // the ones which can not be inferred stay type parameters
+    fn type_instantiated(&self, type_id: TypeId, arguments: &[TypeNamedArgument]) -> crate::ir::analyse::Result<Inferred> {
+        let r#type = &self.type_table[type_id];
+        if r#type.parameters.is_empty() {
+            return Ok(Inferred::Type(type_id));
+        }
+
+        let mut parameters = Vec::with_capacity(arguments.len());
+        let mut values = Vec::with_capacity(arguments.len());
+        for argument in arguments {
+            if let Some(variable) = self.type_table.variable(type_id, argument.argument) {
+                parameters.push(variable.inferred.clone());
+                values.push(&argument.value);
+            }
+        }
+
+        let type_arguments = self.type_arguments(&parameters, &values)?;
+        Ok(Inferred::Parameterized(
+            type_id,
+            r#type.parameters.iter().map(|p| type_arguments.get(p).cloned().unwrap_or(Inferred::Parameter(*p))).collect(),
+        ))
+    }
+
+    // a conflict points at the argument which got bound to the other type
+    pub(crate) fn type_arguments(&self, parameters: &[Inferred], arguments: &[&TypedTreeNode]) -> crate::ir::analyse::Result<HashMap<StringTableId, Inferred>> {
+        let inferred = arguments.iter().map(|a| a.inferred.clone()).collect::<Vec<_>>();
+        infer_type_arguments(parameters, &inferred).map_err(|conflict| Error::TypeMissMatch(TypeMissMatchError::TypeArgumentMissMatch {
+            parameter: self.string_table.get(conflict.parameter).to_string(),
+            expected: conflict.expected.to_string(self.string_table, self.type_table),
+            got: conflict.got.to_string(self.string_table, self.type_table),
+            span: arguments[conflict.argument].span(),
+        }))
+    }
+
+    // the type parameters of a generic function or type shadow types of the same name while it gets analysed
+    pub(crate) fn type_parameters_enter(&mut self, parameters: &[AstTypeParameter]) -> Vec<(StringTableId, Option<TypeId>)> {
+        let result = self.type_parameters.clone();
+        self.type_parameters.extend(parameters.iter().map(|p| (p.parameter.0, None)));
+
+        // bounds are resolved once all parameters are known, as they may refer to each other
+        for (idx, parameter) in parameters.iter().enumerate() {
+            let bound = parameter.bound.as_ref()
+                .and_then(|b| self.inferred_from_ast_type(b).type_id())
+                .filter(|type_id| self.type_table.is_trait(*type_id));
+            self.type_parameters[result.len() + idx].1 = bound;
+        }
+        result
+    }
+
+    // the trait a type parameter is bound to, the innermost parameter of a name wins
+    pub(crate) fn type_parameter_bound(&self, parameter: StringTableId) -> Option<TypeId> {
+        self.type_parameters.iter().rev()
+            .find(|(name, _)| *name == parameter)
+            .and_then(|(_, bound)| *bound)
+    }
+
+    // types of the current package and the packages it is nested in shadow types of the scope
+    pub(crate) fn type_get(&self, name: SymbolName) -> Option<SymbolId> {
+        let mut current = self.package;
+        while let Some(package) = current {
+            if let Some(result) = self.package_table[package].types.get(&name) {
+                return Some(*result);
+            }
+            current = self.package_table[package].parent;
+        }
+
+        self.scope.r#type(name)
+    }
+
+    pub(crate) fn inferred_from_ast_type(&self, r#type: &AstType) -> Inferred {
+        match r#type {
+            AstType::Boolean => Inferred::Boolean,
+            AstType::Function { arguments, return_type, .. } => Inferred::Function(
+                arguments.iter().map(|a| self.inferred_from_ast_type(a)).collect(),
+                Box::new(return_type.as_ref().map(|r| self.inferred_from_ast_type(r)).unwrap_or(Inferred::Unit)),
+            ),
+            AstType::Float4 => Inferred::Float4,
+            AstType::Float8 => Inferred::Float8,
+            AstType::Int1 => Inferred::Int1,
+            AstType::Int2 => Inferred::Int2,
+            AstType::Int4 => Inferred::Int4,
+            AstType::Int8 => Inferred::Int8,
+            AstType::Int16 => Inferred::Int16,
+            AstType::Number => Inferred::Number,
+            AstType::Optional(r#type) => Inferred::Optional(Box::new(self.inferred_from_ast_type(r#type))),
+            AstType::Pointer(r#type) => Inferred::Pointer(Box::new(self.inferred_from_ast_type(r#type))),
+            AstType::Record(fields) => self.record_inferred(fields.iter().map(|(name, t)| (name.0, self.inferred_from_ast_type(t))).collect()),
+            AstType::String => Inferred::String,
+            AstType::Tuple(types) => Inferred::Tuple(types.iter().map(|t| self.inferred_from_ast_type(t)).collect()),
+            AstType::Type { package: None, r#type, arguments } if arguments.is_empty() && self.self_type.is_some() && self.string_table.get(r#type.0) == "Self" => self.self_type.clone().unwrap(),
+            AstType::Type { package: None, r#type, arguments } if arguments.is_empty() && self.type_parameters.iter().any(|(p, _)| *p == r#type.0) => Inferred::Parameter(r#type.0),
+            AstType::Type { package, r#type, arguments } => match package {
+                Some(package) => self.package_get(package).and_then(|package| self.package_type_get(package, SymbolName::from(r#type))).ok(),
+                None => self.type_get(SymbolName::from(r#type))
+            }
+                .and_then(|id| self.symbol_table[id].type_id())
+                .map(|type_id| if arguments.is_empty() {
+                    Inferred::Type(type_id)
+                } else {
+                    Inferred::Parameterized(type_id, arguments.iter().map(|a| self.inferred_from_ast_type(a)).collect())
+                })
+                .unwrap_or(Inferred::Unknown),
+            AstType::Uint1 => Inferred::Uint1,
+            AstType::Uint2 => Inferred::Uint2,
+            AstType::Uint4 => Inferred::Uint4,
+            AstType::Uint8 => Inferred::Uint8,
+            AstType::Uint16 => Inferred::Uint16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, Inferred};
+    use crate::frontend::ast_from_str;
+    use crate::ir::analyse::{prepare, ExternalError};
+    use crate::ir::analyse::Error::{External, TypeMissMatch};
+    use crate::ir::analyse::TypeMissMatchError::{DeclaredTypeMissMatch, TypeArgumentMissMatch};
+
+    #[test]
+    fn external_type_with_string_variable() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            external type Person(name: String)
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(External(error)) = result else { panic!() };
+        let ExternalError::Variable { r#type, variable, .. } = *error else { panic!() };
+        assert_eq!(r#type, "Person");
+        assert_eq!(variable, "name")
+    }
+
+    #[test]
+    fn instantiate_generic_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            type Box<T>(value: T)
+            let boxed = Box(value = 'Elodie')
+            let value = boxed.value
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let Inferred::Parameterized(_, arguments) = &typed[1].inferred else { panic!() };
+        assert_eq!(arguments.as_ref(), &[Inferred::String]);
+        assert_eq!(typed[2].inferred, Inferred::String);
+    }
+
+    #[test]
+    fn generic_value_takes_declared_type_arguments() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            type List<T>()
+            let numbers: List<Number> = List()
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let Inferred::Parameterized(_, arguments) = &typed[1].inferred else { panic!() };
+        assert_eq!(arguments.as_ref(), &[Inferred::Number]);
+    }
+
+    #[test]
+    fn generic_value_with_other_declared_type_arguments() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            type Box<T>(value: T)
+            let boxed: Box<Number> = Box(value = 'Elodie')
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(TypeMissMatch(DeclaredTypeMissMatch { expected, got, .. })) = result else { panic!() };
+        assert_eq!(expected, "Box<Number>");
+        assert_eq!(got, "Box<String>");
+    }
+
+    #[test]
+    fn instantiate_generic_type_with_conflicting_type_arguments() {
+        let mut ctx = Context::testing();
+        let source = r#"
+            type Pair<T>(left: T, right: T)
+            let pair = Pair(left = 'one', right = true)
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(TypeMissMatch(TypeArgumentMissMatch { parameter, expected, got, span })) = result else { panic!() };
+        assert_eq!(parameter, "T");
+        assert_eq!(expected, "String");
+        assert_eq!(got, "Boolean");
+        assert_eq!(span.start.index, source.find("true").unwrap());
+    }
+
+    #[test]
+    fn instantiate_type_with_literal_as_declared_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            type Point(x: Int4)
+            let point = Point(x = 1)
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let declared = typed[1].as_declared_variable();
+        let instantiate = declared.value.as_instantiate_type();
+        assert_eq!(instantiate.arguments[0].value.inferred, Inferred::Int4);
+    }
+
+    #[test]
+    fn instantiate_type_with_different_type_then_declared() {
+        let mut ctx = Context::testing();
+        let source = r#"
+            type Point(x: Int4)
+            let point = Point(x = 'one')
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(TypeMissMatch(DeclaredTypeMissMatch { expected, got, span })) = result else { panic!() };
+        assert_eq!(expected, "Int4");
+        assert_eq!(got, "String");
+        assert_eq!(span.start.index, source.find("'one'").unwrap());
+    }
+}
@@ -1,10 +1,13 @@
-use crate::common::{Inferred, SymbolName};
-use crate::common::node::Node::{DeclareVariable, LiteralNumber};
-use crate::frontend::ast::{AstDeclareVariableNode, AstType};
+use std::slice;
+
+use crate::common::{Inferred, Span, SymbolId, SymbolName, WithSpan};
+use crate::common::node::Node::{DeclareLambda, DeclareVariable, If, LiteralNumber, Loop, Match};
+use crate::frontend::ast::{AstDeclareVariableNode, AstIdentifier, AstType};
 use crate::ir::analyse::{TypeDeclareVariableNode, TypedTreeNode};
 use crate::ir::analyse::Error::TypeMissMatch;
+use crate::ir::analyse::infer::generic::infer_type_arguments;
 use crate::ir::analyse::pre::Pre;
-use crate::ir::analyse::TypeMissMatchError::DeclaredTypeMissMatch;
+use crate::ir::analyse::TypeMissMatchError::{DeclaredTypeMissMatch, NoValue, TypeArgumentMissing};
 
 impl<'a> Pre<'a> {
     pub(crate) fn declare_variable(
@@ -12,10 +15,18 @@ impl<'a> Pre<'a> {
         node: &AstDeclareVariableNode,
     ) -> crate::ir::analyse::Result<TypedTreeNode> {
         let variable = &node.variable;
+        let (mutable, node_readonly, span) = (node.mutable, node.readonly, self.span());
 
         if let Some(expected) = &node.value_type {
+            let value_span = node.value.span();
             if let LiteralNumber(node) = &node.value.node() {
-                let value = match expected {
+                // a number declared as Int4? is an Int4 which may become none
+                let literal_type = match expected {
+                    AstType::Optional(inner) => inner.as_ref(),
+                    expected => expected
+                };
+
+                let value = match literal_type {
                     AstType::Float4 => self.literal_number_float4(node)?,
                     AstType::Float8 => self.literal_number_float8(node)?,
                     AstType::Int1 => self.literal_number_int1(node)?,
@@ -33,13 +44,16 @@ impl<'a> Pre<'a> {
                         return Err(TypeMissMatch(DeclaredTypeMissMatch {
                             expected: expected.to_string(&self.string_table),
                             got: "Number".to_string(),
-                            span: self.span(),
+                            span: value_span,
                         }));
                     }
                 };
 
-                let value_inferred = value.inferred.clone();
-                let variable = self.variable_register(SymbolName::from(&variable.clone()), value_inferred.clone());
+                let value_inferred = match expected {
+                    AstType::Optional(_) => Inferred::Optional(Box::new(value.inferred.clone())),
+                    _ => value.inferred.clone()
+                };
+                let variable = self.variable_declare(variable, value_inferred.clone(), mutable, node_readonly, span);
 
                 return Ok(TypedTreeNode::new(
                     DeclareVariable(TypeDeclareVariableNode { variable, value: Box::new(value) }),
@@ -50,15 +64,64 @@ impl<'a> Pre<'a> {
         }
 
 
-        let value = Box::new(self.node(&node.value)?);
-        let value_inferred = value.inferred.clone();
+        // a lambda takes the argument types of the declared function type
+        let value = match (&node.value_type, node.value.node()) {
+            (Some(expected @ AstType::Function { .. }), DeclareLambda(lambda)) => {
+                let expected = self.inferred_from_ast_type(expected);
+                self.scope.span_set(node.value.span());
+                Box::new(self.declare_lambda(lambda, Some(&expected))?)
+            }
+            _ => Box::new(self.node(&node.value)?)
+        };
+
+        // a record declared as a type with the same fields becomes a value of that type
+        let value = match &node.value_type {
+            Some(expected) => Box::new(self.record_into(*value, &self.inferred_from_ast_type(expected))?),
+            None => value
+        };
+
+        // a generic value like List() takes the type arguments of the declared type
+        let value_inferred = match &node.value_type {
+            Some(expected @ AstType::Type { .. }) if value.inferred.is_parameterized() => {
+                let expected = self.inferred_from_ast_type(expected);
+                let type_arguments = infer_type_arguments(slice::from_ref(&value.inferred), slice::from_ref(&expected)).unwrap_or_default();
+                value.inferred.substitute(&type_arguments)
+            }
+            _ => value.inferred.clone()
+        };
 
-        let variable = self.variable_register(SymbolName::from(&variable.clone()), value_inferred.clone());
+        // an if without otherwise, branches of different types or breaks without a value leave nothing to bind
+        if value_inferred == Inferred::Unit && matches!(value.node, If(_) | Loop(_) | Match(_)) {
+            return Err(TypeMissMatch(NoValue { span: node.value.span() }));
+        }
+
+        // the type parameters of the enclosing function or define are bound by its callers
+        if node.value_type.is_none() {
+            let enclosing = self.type_parameters.iter().map(|(parameter, _)| (*parameter, Inferred::Unknown)).collect();
+            if value_inferred.substitute(&enclosing).is_parameterized() {
+                return Err(TypeMissMatch(TypeArgumentMissing {
+                    r#type: value_inferred.to_string(self.string_table, self.type_table),
+                    span: node.value.span(),
+                }));
+            }
+        }
+
+        // a variable declared as trait holds the value together with the vtable of its type
+        let variable_inferred = match node.value_type.as_ref().map(|t| self.inferred_from_ast_type(t)) {
+            Some(expected @ Inferred::Type(r#trait)) if self.type_table.is_trait(r#trait) => expected,
+            Some(expected @ Inferred::Optional(_)) => expected,
+            _ => value_inferred.clone()
+        };
+        // a variable taking a readonly value is readonly as well
+        let readonly = node_readonly || self.readonly(&value);
+        let variable = self.variable_declare(variable, variable_inferred.clone(), mutable, readonly, span);
 
         if let Some(expected) = &node.value_type {
             let matches = match (expected, &value_inferred) {
                 (&AstType::Boolean, &Inferred::Boolean) => true,
                 (&AstType::Boolean, _) => false,
+                (&AstType::Function { .. }, &Inferred::Function(..)) => self.inferred_from_ast_type(expected) == value_inferred,
+                (&AstType::Function { .. }, _) => false,
                 (&AstType::Float4, &Inferred::Float4) => true,
                 (&AstType::Float4, _) => false,
                 (&AstType::Float8, &Inferred::Float8) => true,
@@ -82,9 +145,26 @@ impl<'a> Pre<'a> {
                 (&AstType::Number, &Inferred::Number) => true,
                 (&AstType::Number, _) => false,
 
+                // none or a value of the inner type
+                (&AstType::Optional(_), _) => match self.inferred_from_ast_type(expected) {
+                    _ if value_inferred.is_none() => true,
+                    Inferred::Optional(inner) if *inner == value_inferred => true,
+                    expected => expected == value_inferred
+                },
+
+                (&AstType::Record(_), _) => self.inferred_from_ast_type(expected) == value_inferred,
+
                 (&AstType::String, &Inferred::String) => true,
                 (&AstType::String, _) => false,
 
+                (&AstType::Tuple(_), _) => self.inferred_from_ast_type(expected) == value_inferred,
+
+                (&AstType::Type { .. }, _) => match self.inferred_from_ast_type(expected) {
+                    // the value gets wrapped together with the vtable of its type
+                    Inferred::Type(r#trait) if self.type_table.is_trait(r#trait) => self.trait_implemented(&value_inferred, r#trait),
+                    expected => expected == value_inferred
+                },
+
                 (&AstType::Uint1, &Inferred::Uint1) => true,
                 (&AstType::Uint1, _) => false,
 
@@ -105,8 +185,8 @@ impl<'a> Pre<'a> {
             if !matches {
                 return Err(TypeMissMatch(DeclaredTypeMissMatch {
                     expected: expected.to_string(&self.string_table),
-                    got: value_inferred.to_string(&self.string_table),
-                    span: self.span(),
+                    got: value_inferred.to_string(self.string_table, self.type_table),
+                    span: node.value.span(),
                 }));
             }
         }
@@ -114,9 +194,19 @@ impl<'a> Pre<'a> {
         Ok(TypedTreeNode::new(
             DeclareVariable(TypeDeclareVariableNode { variable, value }),
             self.span(),
-            value_inferred,
+            variable_inferred,
         ))
     }
+
+    // a let or const binding remembers where it got declared
+    pub(crate) fn variable_declare(&mut self, variable: &AstIdentifier, inferred: Inferred, mutable: bool, readonly: bool, span: Span) -> SymbolId {
+        let result = self.variable_register(SymbolName::from(variable), inferred);
+        let symbol = self.symbol_table.variable_mut(result);
+        symbol.mutable = mutable;
+        symbol.readonly = readonly;
+        symbol.span = span;
+        result
+    }
 }
 
 #[cfg(test)]
@@ -403,4 +493,81 @@ mod tests {
         assert_eq!(inner.variable, SymbolId(3));
         assert_eq!(ctx.str_get(inner.value.as_literal_string().value), "Elodie");
     }
+
+    #[test]
+    fn declare_generic_value_without_type() {
+        let mut ctx = Context::testing();
+        let source = r#"
+            type Bag<T>()
+            function empty<T>() -> Bag<T> { return Bag() }
+            let bag = empty()
+        "#;
+        let ast = ast_from_str(&mut ctx, source).unwrap();
+        let result = prepare(&mut ctx, ast);
+
+        let Err(TypeMissMatch(TypeMissMatchError::TypeArgumentMissing { r#type, span })) = result else { panic!() };
+        assert_eq!(r#type, "Bag<T>");
+        assert_eq!(span.start.index, source.rfind("empty()").unwrap());
+    }
+
+    #[test]
+    fn declare_generic_value_with_type() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            type Bag<T>()
+            function empty<T>() -> Bag<T> { return Bag() }
+            let bag: Bag<Number> = empty()
+        "#).unwrap();
+        let typed = prepare(&mut ctx, ast).unwrap();
+
+        let Inferred::Parameterized(_, arguments) = &typed[2].inferred else { panic!() };
+        assert_eq!(arguments.as_ref(), &[Inferred::Number]);
+    }
+
+    #[test]
+    fn declare_narrowed_optional_variable() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            function value(n: Number?) -> Number {
+                if n != none {
+                    let inner: Number = n
+                    return inner
+                }
+                return 0
+            }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn declare_optional_variable_after_early_return() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            function value(n: Number?) -> Number {
+                if n == none {
+                    return 0
+                }
+                let inner: Number = n
+                return inner
+            }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn declare_optional_variable_without_narrowing() {
+        let mut ctx = Context::testing();
+        let ast = ast_from_str(&mut ctx, r#"
+            function value(n: Number?) -> Number {
+                let inner: Number = n
+                return inner
+            }
+        "#).unwrap();
+        let result = prepare(&mut ctx, ast);
+        let Err(TypeMissMatch(DeclaredTypeMissMatch { expected, got, .. })) = result else { panic!() };
+        assert_eq!(expected, "Number");
+        assert_eq!(got, "Number?");
+    }
 }
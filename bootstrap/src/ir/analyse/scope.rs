@@ -4,14 +4,23 @@ use crate::common::{Span, Symbol, SymbolId, SymbolName};
 
 struct Frame {
     span: Span,
+    functions: HashMap<SymbolName, SymbolId>,
+    packages: HashMap<SymbolName, SymbolId>,
+    types: HashMap<SymbolName, SymbolId>,
     variables: HashMap<SymbolName, SymbolId>,
+    // variables which may be none, but hold a value within this frame
+    narrowed: Vec<SymbolId>,
 }
 
 impl Frame {
     fn new() -> Self {
         Self {
             span: Span::default(),
+            functions: HashMap::new(),
+            packages: HashMap::new(),
+            types: HashMap::new(),
             variables: HashMap::new(),
+            narrowed: vec![],
         }
     }
 }
@@ -38,6 +47,15 @@ impl Scope {
     pub(crate) fn register_symbol(&mut self, symbol: &Symbol) {
         let frame = self.frames.last_mut().unwrap();
         match symbol {
+            Symbol::Function(s) => {
+                frame.functions.insert(s.name, s.id);
+            }
+            Symbol::Package(s) => {
+                frame.packages.insert(s.name, s.id);
+            }
+            Symbol::Type(s) => {
+                frame.types.insert(s.name, s.id);
+            }
             Symbol::Variable(s) => {
                 frame.variables.insert(s.name.clone(), s.id.clone());
             }
@@ -45,6 +63,33 @@ impl Scope {
         }
     }
 
+    pub(crate) fn function(&self, name: impl AsRef<SymbolName>) -> Option<SymbolId> {
+        for frame in self.frames.iter().rev() {
+            if let Some(value) = frame.functions.get(name.as_ref()).cloned() {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    pub(crate) fn package(&self, name: impl AsRef<SymbolName>) -> Option<SymbolId> {
+        for frame in self.frames.iter().rev() {
+            if let Some(value) = frame.packages.get(name.as_ref()).cloned() {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    pub(crate) fn r#type(&self, name: impl AsRef<SymbolName>) -> Option<SymbolId> {
+        for frame in self.frames.iter().rev() {
+            if let Some(value) = frame.types.get(name.as_ref()).cloned() {
+                return Some(value);
+            }
+        }
+        None
+    }
+
     pub(crate) fn variable(&self, name: impl AsRef<SymbolName>) -> Option<SymbolId> {
         for frame in self.frames.iter().rev() {
             if let Some(value) = frame.variables.get(name.as_ref()).cloned() {
@@ -54,6 +99,30 @@ impl Scope {
         None
     }
 
+    // index of the frame which declares the variable, outer frames have lower indices
+    pub(crate) fn variable_depth(&self, name: impl AsRef<SymbolName>) -> Option<usize> {
+        self.frames.iter().rposition(|frame| frame.variables.contains_key(name.as_ref()))
+    }
+
+    pub(crate) fn narrow(&mut self, variable: SymbolId) {
+        self.frames.last_mut().unwrap().narrowed.push(variable)
+    }
+
+    pub(crate) fn narrowed(&self, variable: SymbolId) -> bool {
+        self.frames.iter().any(|frame| frame.narrowed.contains(&variable))
+    }
+
+    // the variable got a value which may be none again
+    pub(crate) fn widen(&mut self, variable: SymbolId) {
+        for frame in self.frames.iter_mut() {
+            frame.narrowed.retain(|narrowed| *narrowed != variable);
+        }
+    }
+
+    pub(crate) fn depth(&self) -> usize {
+        self.frames.len()
+    }
+
     pub(crate) fn enter(&mut self) {
         self.frames.push(Frame::new())
     }
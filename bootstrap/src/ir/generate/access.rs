@@ -1,19 +1,65 @@
-use crate::common::node::Node::AccessVariable;
-use crate::common::Span;
-use crate::ir::analyse::TypeAccessVariableNode;
+use crate::common::node::Node::{AccessVariable, AccessVariableOfObject};
+use crate::common::{Inferred, Span, Symbol, SymbolId, TypeId, TypeVariableId};
+use crate::ir::analyse::{TypeAccessVariableNode, TypeAccessVariableOfObjectNode};
 use crate::ir::generate::Generator;
-use crate::ir::{IrAccessVariableNode, IrTreeNode};
+use crate::ir::{IrAccessVariableNode, IrAccessVariableOfObjectNode, IrTreeNode};
 
 impl<'a> Generator<'a> {
     pub(crate) fn access_variable(&mut self, node: &TypeAccessVariableNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
-        let variable = self.symbol_table.variable(node.variable);
+        // functions passed by name have no type of their own
+        let type_id = match &self.symbol_table[node.variable] {
+            Symbol::Function(_) => TypeId::UNKNOWN,
+            _ => self.symbol_table.variable(node.variable).type_id.unwrap()
+        };
 
         Ok(IrTreeNode::new(
             AccessVariable(IrAccessVariableNode{
                 variable: node.variable
             }),
             span,
-            variable.type_id.unwrap()
+            type_id
         ))
     }
-}
\ No newline at end of file
+
+    // a variable of a type parameter has the type it got instantiated with, e.g. String for value of Box<String>
+    pub(crate) fn access_variable_of_object(&mut self, node: &TypeAccessVariableOfObjectNode, span: Span, inferred: &Inferred) -> crate::ir::generate::Result<IrTreeNode> {
+        let type_id = inferred.type_id().unwrap_or_else(|| self.type_variable_type_id(node.object, node.variable));
+
+        Ok(IrTreeNode::new(
+            AccessVariableOfObject(IrAccessVariableOfObjectNode {
+                object: node.object,
+                variable: node.variable,
+            }),
+            span,
+            type_id,
+        ))
+    }
+
+    // the type of a variable of the type the object is an instance of
+    pub(crate) fn type_variable_type_id(&self, object: SymbolId, variable: TypeVariableId) -> TypeId {
+        let type_id = self.symbol_table.variable(object).type_id.unwrap();
+        self.type_table[type_id].variables[variable.0].type_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, TypeId};
+    use crate::ir::ir_from_str;
+
+    #[test]
+    fn access_variable_of_generic_type() {
+        let mut ctx = Context::testing();
+        let ir = ir_from_str(&mut ctx, r#"
+            type Box<T>(value: T)
+            let boxed = Box(value = 'Elodie')
+            boxed.value
+        "#).unwrap();
+        assert_eq!(ir.len(), 3);
+
+        let result = &ir[2];
+        let inner = result.as_access_variable_of_object();
+        assert_eq!(inner.variable.0, 0);
+        assert_eq!(result.type_id, TypeId::STRING);
+    }
+}
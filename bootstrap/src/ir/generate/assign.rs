@@ -0,0 +1,36 @@
+use std::rc::Rc;
+
+use crate::common::node::Node::{AssignVariable, AssignVariableOfObject};
+use crate::common::{Span, TypeId};
+use crate::ir::analyse::{TypeAssignVariableNode, TypeAssignVariableOfObjectNode};
+use crate::ir::generate::Generator;
+use crate::ir::{IrAssignVariableNode, IrAssignVariableOfObjectNode, IrTreeNode};
+
+impl<'a> Generator<'a> {
+    pub(crate) fn assign_variable(&mut self, node: &TypeAssignVariableNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        let value = Rc::new(self.node(node.value.as_ref())?);
+
+        Ok(IrTreeNode::new(
+            AssignVariable(IrAssignVariableNode {
+                variable: node.variable,
+                value,
+            }),
+            span,
+            TypeId::UNIT,
+        ))
+    }
+
+    pub(crate) fn assign_variable_of_object(&mut self, node: &TypeAssignVariableOfObjectNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        let value = Rc::new(self.node(node.value.as_ref())?);
+
+        Ok(IrTreeNode::new(
+            AssignVariableOfObject(IrAssignVariableOfObjectNode {
+                object: node.object,
+                variable: node.variable,
+                value,
+            }),
+            span,
+            TypeId::UNIT,
+        ))
+    }
+}
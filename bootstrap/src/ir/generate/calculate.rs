@@ -1,13 +1,13 @@
 use std::rc::Rc;
 
-use crate::common::{Span, TypeId};
+use crate::common::{Inferred, Span, TypeId};
 use crate::common::node::Node;
 use crate::ir::{IrCalculateNode, IrTreeNode};
 use crate::ir::analyse::TypeCalculateNode;
 use crate::ir::generate::Generator;
 
 impl<'a> Generator<'a> {
-    pub(crate) fn calculate(&mut self, node: &TypeCalculateNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+    pub(crate) fn calculate(&mut self, node: &TypeCalculateNode, span: Span, inferred: &Inferred) -> crate::ir::generate::Result<IrTreeNode> {
         let left = self.node(node.left.as_ref())?;
         let right = self.node(node.right.as_ref())?;
 
@@ -19,7 +19,7 @@ impl<'a> Generator<'a> {
                     right: Rc::new(right),
                 }),
                 span.clone(),
-                TypeId::BOOLEAN,
+                inferred.type_id().unwrap_or(TypeId::UNKNOWN),
             )
         )
     }
@@ -1,28 +1,74 @@
 use std::rc::Rc;
 
-use crate::common::{Span, SymbolId, TypeId};
-use crate::common::node::Node::CallFunctionOfPackage;
-use crate::ir::analyse::TypeCallFunctionOfPackageNode;
+use crate::common::{Inferred, Span, TypeId};
+use crate::common::node::Node::{CallFunction, CallFunctionOfObject, CallFunctionOfPackage, CallFunctionWithLambda};
+use crate::ir::analyse::{TypeCallFunctionNode, TypeCallFunctionOfObjectNode, TypeCallFunctionOfPackageNode, TypeCallFunctionWithLambdaNode, TypedTreeNode};
 use crate::ir::generate::Generator;
-use crate::ir::node::{IrCallFunctionOfPackageNode, IrTreeNode};
+use crate::ir::node::{IrCallFunctionNode, IrCallFunctionOfObjectNode, IrCallFunctionOfPackageNode, IrCallFunctionWithLambdaNode, IrTreeNode};
 
 impl<'a> Generator<'a> {
-    pub(crate) fn call_function_of_package(&mut self, node: &TypeCallFunctionOfPackageNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
-        let mut arguments = vec![];
-        for arg in &node.arguments {
-            arguments.push(Rc::new(self.node(arg)?))
-        }
+    pub(crate) fn call_function(&mut self, node: &TypeCallFunctionNode, span: Span, inferred: &Inferred) -> crate::ir::generate::Result<IrTreeNode> {
+        let arguments = self.call_arguments(&node.arguments)?;
+
+        Ok(IrTreeNode::new(
+            CallFunction(IrCallFunctionNode {
+                function: node.function,
+                arguments,
+            }),
+            span,
+            inferred.type_id().unwrap_or(TypeId::UNKNOWN),
+        ))
+    }
+
+    pub(crate) fn call_function_with_lambda(&mut self, node: &TypeCallFunctionWithLambdaNode, span: Span, inferred: &Inferred) -> crate::ir::generate::Result<IrTreeNode> {
+        let arguments = self.call_arguments(&node.arguments)?;
+        let lambda = Rc::new(self.node(&node.lambda)?);
+
+        Ok(IrTreeNode::new(
+            CallFunctionWithLambda(IrCallFunctionWithLambdaNode {
+                function: node.function,
+                arguments,
+                lambda,
+            }),
+            span,
+            inferred.type_id().unwrap_or(TypeId::UNKNOWN),
+        ))
+    }
+
+    pub(crate) fn call_function_of_object(&mut self, node: &TypeCallFunctionOfObjectNode, span: Span, inferred: &Inferred) -> crate::ir::generate::Result<IrTreeNode> {
+        let arguments = self.call_arguments(&node.arguments)?;
+
+        Ok(IrTreeNode::new(
+            CallFunctionOfObject(IrCallFunctionOfObjectNode {
+                object: node.object,
+                function: node.function,
+                arguments,
+                dispatch: node.dispatch,
+            }),
+            span,
+            inferred.type_id().unwrap_or(TypeId::UNKNOWN),
+        ))
+    }
 
-        let result_type = TypeId::UNIT; // FIXME derive
+    pub(crate) fn call_function_of_package(&mut self, node: &TypeCallFunctionOfPackageNode, span: Span, inferred: &Inferred) -> crate::ir::generate::Result<IrTreeNode> {
+        let arguments = self.call_arguments(&node.arguments)?;
 
         Ok(IrTreeNode::new(
             CallFunctionOfPackage(IrCallFunctionOfPackageNode {
-                package: SymbolId(1),
-                function: SymbolId(2),
-                arguments: arguments.into_boxed_slice(),
+                package: node.package,
+                function: node.function,
+                arguments,
             }),
             span,
-            result_type,
+            inferred.type_id().unwrap_or(TypeId::UNKNOWN),
         ))
     }
-}
\ No newline at end of file
+
+    fn call_arguments(&mut self, arguments: &[TypedTreeNode]) -> crate::ir::generate::Result<Box<[Rc<IrTreeNode>]>> {
+        let mut result = Vec::with_capacity(arguments.len());
+        for argument in arguments {
+            result.push(Rc::new(self.node(argument)?))
+        }
+        Ok(result.into_boxed_slice())
+    }
+}
@@ -1,15 +1,16 @@
 use std::ops::Deref;
 use std::rc::Rc;
 
-use crate::common::{Span, TypeId};
-use crate::common::node::Node::{BreakLoop, If, Loop};
-use crate::ir::{IrBlockNode, IrBreakLoopNode, IrIfNode, IrLoopNode, IrTreeNode};
-use crate::ir::analyse::{TypeBreakLoopNode, TypeIfNode, TypeLoopNode};
+use crate::common::{Inferred, Span, TypeId};
+use crate::common::node::Node::{BreakLoop, ContinueLoop, Defer, If, IteratorLoop, Loop, Match, RangeLoop, Try};
+use crate::ir::{IrBlockNode, IrBreakLoopNode, IrContinueLoopNode, IrDeferNode, IrIfNode, IrIteratorLoopNode, IrLoopNode, IrMatchArm, IrMatchNode, IrRangeLoopNode, IrTreeNode, IrTryNode};
+use crate::ir::analyse::{TypeBreakLoopNode, TypeContinueLoopNode, TypeDeferNode, TypeIfNode, TypeIteratorLoopNode, TypeLoopNode, TypeMatchNode, TypeRangeLoopNode, TypeTryNode};
 use crate::ir::generate::Generator;
 
 impl<'a> Generator<'a> {
 
-    pub(crate) fn r#break(&mut self, node: &TypeBreakLoopNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+    pub(crate) fn r#break(&mut self, node: &TypeBreakLoopNode, span: Span, inferred: &Inferred) -> crate::ir::generate::Result<IrTreeNode> {
+        let label = node.label;
         let node = if let Some(node) = &node.node {
             Some(Rc::new(self.node(node)?))
         } else {
@@ -18,27 +19,148 @@ impl<'a> Generator<'a> {
 
         Ok(IrTreeNode::new(
             BreakLoop(IrBreakLoopNode {
+                label,
                 node
             }),
             span,
-            TypeId::NUMBER,
+            inferred.type_id().unwrap_or(TypeId::UNIT),
         ))
     }
 
-    pub(crate) fn r#loop(&mut self, node: &TypeLoopNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+    pub(crate) fn r#continue(&mut self, node: &TypeContinueLoopNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        Ok(IrTreeNode::new(
+            ContinueLoop(IrContinueLoopNode { label: node.label }),
+            span,
+            TypeId::UNIT,
+        ))
+    }
+
+    pub(crate) fn defer(&mut self, node: &TypeDeferNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        let mut nodes = vec![];
+        for node in &node.nodes.borrow().nodes {
+            nodes.push(Rc::new(self.node(node)?))
+        }
+
+        Ok(IrTreeNode::new(
+            Defer(IrDeferNode { block: Rc::new(IrBlockNode { nodes: nodes.into_boxed_slice() }) }),
+            span,
+            TypeId::UNIT,
+        ))
+    }
+
+    pub(crate) fn r#loop(&mut self, node: &TypeLoopNode, span: Span, inferred: &Inferred) -> crate::ir::generate::Result<IrTreeNode> {
         let mut nodes = vec![];
         for node in &node.nodes.borrow().nodes {
             nodes.push(Rc::new(self.node(node.deref())?))
         }
 
         Ok(IrTreeNode::new(
-            Loop(IrLoopNode { block: Rc::new(IrBlockNode { nodes: nodes.into_boxed_slice() }) }),
+            Loop(IrLoopNode { label: node.label, block: Rc::new(IrBlockNode { nodes: nodes.into_boxed_slice() }) }),
             span,
-            TypeId::NUMBER,
+            inferred.type_id().unwrap_or(TypeId::UNIT),
         ))
     }
 
-    pub(crate) fn r#if(&mut self, node: &TypeIfNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+    pub(crate) fn range_loop(&mut self, node: &TypeRangeLoopNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        let start = Rc::new(self.node(node.start.deref())?);
+        let end = Rc::new(self.node(node.end.deref())?);
+        let step = match &node.step {
+            Some(step) => Some(Rc::new(self.node(step.deref())?)),
+            None => None
+        };
+
+        let mut nodes = vec![];
+        for node in &node.nodes.borrow().nodes {
+            nodes.push(Rc::new(self.node(node)?))
+        }
+
+        Ok(IrTreeNode::new(
+            RangeLoop(IrRangeLoopNode {
+                label: node.label,
+                variable: node.variable,
+                start,
+                end,
+                inclusive: node.inclusive,
+                step,
+                block: Rc::new(IrBlockNode { nodes: nodes.into_boxed_slice() }),
+            }),
+            span,
+            TypeId::UNIT,
+        ))
+    }
+
+    pub(crate) fn iterator_loop(&mut self, node: &TypeIteratorLoopNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        let value = Rc::new(self.node(node.value.deref())?);
+        let iter = match &node.iter {
+            Some(iter) => Some(Rc::new(self.node(iter.deref())?)),
+            None => None
+        };
+        let next = match &node.next {
+            Some(next) => Some(Rc::new(self.node(next.deref())?)),
+            None => None
+        };
+
+        let mut nodes = vec![];
+        for node in &node.nodes.borrow().nodes {
+            nodes.push(Rc::new(self.node(node)?))
+        }
+
+        Ok(IrTreeNode::new(
+            IteratorLoop(IrIteratorLoopNode {
+                label: node.label,
+                variable: node.variable,
+                iterable: node.iterable,
+                value,
+                iterator: node.iterator,
+                iter,
+                next,
+                block: Rc::new(IrBlockNode { nodes: nodes.into_boxed_slice() }),
+            }),
+            span,
+            TypeId::UNIT,
+        ))
+    }
+
+    pub(crate) fn r#match(&mut self, node: &TypeMatchNode, span: Span, inferred: &Inferred) -> crate::ir::generate::Result<IrTreeNode> {
+        let value = Rc::new(self.node(node.node.deref())?);
+
+        let mut arms = Vec::with_capacity(node.arms.len());
+        for arm in node.arms.iter() {
+            let mut nodes = vec![];
+            for node in &arm.nodes.borrow().nodes {
+                nodes.push(Rc::new(self.node(node)?))
+            }
+            arms.push(IrMatchArm {
+                variant: arm.variant,
+                bindings: arm.bindings.clone(),
+                block: Rc::new(IrBlockNode { nodes: nodes.into_boxed_slice() }),
+            })
+        }
+
+        Ok(IrTreeNode::new(
+            Match(IrMatchNode {
+                node: value,
+                arms: arms.into_boxed_slice(),
+            }),
+            span,
+            inferred.type_id().unwrap_or(TypeId::UNIT),
+        ))
+    }
+
+    pub(crate) fn r#try(&mut self, node: &TypeTryNode, span: Span, inferred: &Inferred) -> crate::ir::generate::Result<IrTreeNode> {
+        let value = Rc::new(self.node(node.node.deref())?);
+
+        Ok(IrTreeNode::new(
+            Try(IrTryNode {
+                node: value,
+                error: node.error,
+            }),
+            span,
+            inferred.type_id().unwrap_or(TypeId::UNKNOWN),
+        ))
+    }
+
+    pub(crate) fn r#if(&mut self, node: &TypeIfNode, span: Span, inferred: &Inferred) -> crate::ir::generate::Result<IrTreeNode> {
         let condition = Rc::new(self.node(node.condition.deref())?);
 
         let mut then_body = vec![];
@@ -65,7 +187,7 @@ impl<'a> Generator<'a> {
                 otherwise,
             }),
             span,
-            TypeId::UNIT,
+            inferred.type_id().unwrap_or(TypeId::UNIT),
         )
         )
     }
@@ -0,0 +1,93 @@
+use std::rc::Rc;
+
+use crate::common::{Span, TypeId};
+use crate::common::node::Node::{DeclareExternalFunction, DeclareFunction, DeclareLambda, ReturnFromFunction};
+use crate::ir::analyse::{TypeDeclareExternalFunctionNode, TypeDeclareFunctionNode, TypeDeclareLambdaNode, TypeReturnFromFunctionNode};
+use crate::ir::generate::Generator;
+use crate::ir::node::{IrDeclareExternalFunctionNode, IrDeclareFunctionNode, IrDeclareLambdaNode, IrReturnFromFunctionNode, IrTreeNode};
+
+impl<'a> Generator<'a> {
+    pub(crate) fn declare_external_function(&mut self, node: &TypeDeclareExternalFunctionNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        Ok(IrTreeNode::new(
+            DeclareExternalFunction(IrDeclareExternalFunctionNode { function: node.function }),
+            span,
+            TypeId::UNIT,
+        ))
+    }
+
+    pub(crate) fn declare_function(&mut self, node: &TypeDeclareFunctionNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        let mut nodes = Vec::with_capacity(node.nodes.len());
+        for node in &node.nodes {
+            nodes.push(Rc::new(self.node(node)?))
+        }
+
+        Ok(IrTreeNode::new(
+            DeclareFunction(IrDeclareFunctionNode {
+                function: node.function,
+                arguments: node.arguments.clone(),
+                nodes: nodes.into_boxed_slice(),
+            }),
+            span,
+            TypeId::UNIT,
+        ))
+    }
+
+    pub(crate) fn declare_lambda(&mut self, node: &TypeDeclareLambdaNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        let mut nodes = Vec::with_capacity(node.nodes.len());
+        for node in &node.nodes {
+            nodes.push(Rc::new(self.node(node)?))
+        }
+
+        Ok(IrTreeNode::new(
+            DeclareLambda(IrDeclareLambdaNode {
+                function: node.function,
+                arguments: node.arguments.clone(),
+                captures: node.captures.clone(),
+                nodes: nodes.into_boxed_slice(),
+            }),
+            span,
+            TypeId::UNKNOWN,
+        ))
+    }
+
+    pub(crate) fn r#return(&mut self, node: &TypeReturnFromFunctionNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        let node = if let Some(node) = &node.node {
+            Some(Rc::new(self.node(node)?))
+        } else {
+            None
+        };
+
+        let type_id = node.as_ref().map(|n| n.type_id).unwrap_or(TypeId::UNIT);
+
+        Ok(IrTreeNode::new(
+            ReturnFromFunction(IrReturnFromFunctionNode { node }),
+            span,
+            type_id,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, TypeId};
+    use crate::ir::ir_from_str;
+
+    #[test]
+    fn declare_function_and_call_it() {
+        let mut ctx = Context::testing();
+        let ir = ir_from_str(&mut ctx, r#"
+        function greeting() -> String { return 'hi' }
+        let value = greeting()
+        "#).unwrap();
+        assert_eq!(ir.len(), 2);
+
+        let function = ir[0].as_declare_function();
+        assert_eq!(ctx.symbol_name(function.function), "greeting");
+        assert_eq!(function.nodes.len(), 1);
+        assert_eq!(function.nodes[0].type_id, TypeId::STRING);
+
+        let value = ir[1].as_declare_variable();
+        assert_eq!(value.value.as_call_function().function, function.function);
+        assert_eq!(value.value.type_id, TypeId::STRING);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::common::{Span, TypeId};
-use crate::common::node::Node::{LiteralBoolean, LiteralFloat4, LiteralFloat8, LiteralInt1, LiteralInt16, LiteralInt2, LiteralInt4, LiteralInt8, LiteralNumber, LiteralString, LiteralUint1, LiteralUint16, LiteralUint2, LiteralUint4, LiteralUint8};
-use crate::ir::{IrLiteralFloat4Node, IrLiteralFloat8Node, IrLiteralInt16Node, IrLiteralInt1Node, IrLiteralInt2Node, IrLiteralInt4Node, IrLiteralInt8Node, IrLiteralUint16Node, IrLiteralUint1Node, IrLiteralUint2Node, IrLiteralUint4Node, IrLiteralUint8Node};
-use crate::ir::analyse::{TypeLiteralBooleanNode, TypeLiteralFloat4Node, TypeLiteralFloat8Node, TypeLiteralInt16Node, TypeLiteralInt1Node, TypeLiteralInt2Node, TypeLiteralInt4Node, TypeLiteralInt8Node, TypeLiteralNumberNode, TypeLiteralStringNode, TypeLiteralUint16Node, TypeLiteralUint1Node, TypeLiteralUint2Node, TypeLiteralUint4Node, TypeLiteralUint8Node};
+use crate::common::node::Node::{LiteralBoolean, LiteralFloat4, LiteralFloat8, LiteralInt1, LiteralInt16, LiteralInt2, LiteralInt4, LiteralInt8, LiteralNone, LiteralNumber, LiteralString, LiteralUint1, LiteralUint16, LiteralUint2, LiteralUint4, LiteralUint8};
+use crate::ir::{IrLiteralFloat4Node, IrLiteralFloat8Node, IrLiteralInt16Node, IrLiteralInt1Node, IrLiteralInt2Node, IrLiteralInt4Node, IrLiteralInt8Node, IrLiteralNoneNode, IrLiteralUint16Node, IrLiteralUint1Node, IrLiteralUint2Node, IrLiteralUint4Node, IrLiteralUint8Node};
+use crate::ir::analyse::{TypeLiteralBooleanNode, TypeLiteralFloat4Node, TypeLiteralFloat8Node, TypeLiteralInt16Node, TypeLiteralInt1Node, TypeLiteralInt2Node, TypeLiteralInt4Node, TypeLiteralInt8Node, TypeLiteralNoneNode, TypeLiteralNumberNode, TypeLiteralStringNode, TypeLiteralUint16Node, TypeLiteralUint1Node, TypeLiteralUint2Node, TypeLiteralUint4Node, TypeLiteralUint8Node};
 use crate::ir::generate::Generator;
 use crate::ir::node::{IrLiteralBooleanNode, IrLiteralNumberNode, IrLiteralStringNode, IrTreeNode};
 
@@ -70,6 +70,15 @@ impl<'a> Generator<'a> {
         ))
     }
 
+    // none has no type of its own, the variable it gets assigned to decides it
+    pub(crate) fn literal_none(&mut self, _node: &TypeLiteralNoneNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        Ok(IrTreeNode::new(
+            LiteralNone(IrLiteralNoneNode {}),
+            span,
+            TypeId::UNKNOWN,
+        ))
+    }
+
     pub(crate) fn literal_number(&mut self, node: &TypeLiteralNumberNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
         Ok(IrTreeNode::new(
             LiteralNumber(IrLiteralNumberNode { value: node.value.clone() }),
@@ -2,7 +2,7 @@ use std::ops::Index;
 
 use crate::common::{StringTable, SymbolTable, TypeTable, WithSpan};
 use crate::common::Context;
-use crate::common::node::Node::{AccessVariable, Block, BreakLoop, Calculate, CallFunctionOfPackage, Compare, DeclareVariable, If, InterpolateString, LiteralBoolean, LiteralFloat4, LiteralFloat8, LiteralInt1, LiteralInt16, LiteralInt2, LiteralInt4, LiteralInt8, LiteralNumber, LiteralString, LiteralUint1, LiteralUint16, LiteralUint2, LiteralUint4, LiteralUint8, Loop};
+use crate::common::node::Node::{AccessElementOfTuple, AccessVariable, AccessVariableOfObject, AssignVariable, AssignVariableOfObject, Block, BreakLoop, Calculate, CallFunction, CallFunctionOfObject, CallFunctionOfPackage, CallFunctionWithLambda, Compare, ContinueLoop, DeclareExternalFunction, DeclareFunction, DeclareLambda, DeclarePackage, DeclareTrait, DeclareType, DeclareVariable, Defer, DefineType, DestructureTuple, If, InstantiateTuple, InstantiateType, InterpolateString, IteratorLoop, LiteralBoolean, LiteralFloat4, LiteralFloat8, LiteralInt1, LiteralInt16, LiteralInt2, LiteralInt4, LiteralInt8, LiteralNone, LiteralNumber, LiteralString, LiteralUint1, LiteralUint16, LiteralUint2, LiteralUint4, LiteralUint8, Loop, Match, RangeLoop, ReturnFromFunction, Try};
 use crate::ir::analyse::{TypedAst, TypedTreeNode};
 use crate::ir::Ir;
 use crate::ir::node::IrTreeNode;
@@ -16,6 +16,10 @@ mod block;
 mod control;
 mod compare;
 mod calculate;
+mod function;
+mod package;
+mod assign;
+mod tuple;
 
 #[derive(Debug)]
 pub enum Error {}
@@ -47,14 +51,33 @@ impl<'a> Generator<'a> {
 
     pub(crate) fn node(&mut self, node: &TypedTreeNode) -> Result<IrTreeNode> {
         match &node.node {
+            AccessElementOfTuple(inner) => self.access_element_of_tuple(inner, node.span(), &node.inferred),
             AccessVariable(inner) => self.access_variable(inner, node.span()),
+            AccessVariableOfObject(inner) => self.access_variable_of_object(inner, node.span(), &node.inferred),
+            AssignVariable(inner) => self.assign_variable(inner, node.span()),
+            AssignVariableOfObject(inner) => self.assign_variable_of_object(inner, node.span()),
             Block(inner) => self.block(inner, node.span()),
-            BreakLoop(inner) => self.r#break(inner, node.span()),
-            Calculate(inner) => self.calculate(inner, node.span()),
-            CallFunctionOfPackage(inner) => self.call_function_of_package(inner, node.span()),
+            BreakLoop(inner) => self.r#break(inner, node.span(), &node.inferred),
+            Calculate(inner) => self.calculate(inner, node.span(), &node.inferred),
+            CallFunction(inner) => self.call_function(inner, node.span(), &node.inferred),
+            CallFunctionOfObject(inner) => self.call_function_of_object(inner, node.span(), &node.inferred),
+            CallFunctionOfPackage(inner) => self.call_function_of_package(inner, node.span(), &node.inferred),
+            CallFunctionWithLambda(inner) => self.call_function_with_lambda(inner, node.span(), &node.inferred),
             Compare(inner) => self.compare(inner, node.span()),
+            ContinueLoop(inner) => self.r#continue(inner, node.span()),
+            DeclareExternalFunction(inner) => self.declare_external_function(inner, node.span()),
+            DeclareFunction(inner) => self.declare_function(inner, node.span()),
+            DeclareLambda(inner) => self.declare_lambda(inner, node.span()),
+            DeclarePackage(inner) => self.declare_package(inner, node.span()),
+            DeclareTrait(inner) => self.declare_trait(inner, node.span()),
+            DeclareType(inner) => self.declare_type(inner, node.span()),
             DeclareVariable(inner) => self.declare_variable(inner, node.span()),
-            If(inner) => self.r#if(inner, node.span()),
+            Defer(inner) => self.defer(inner, node.span()),
+            DefineType(inner) => self.define_type(inner, node.span()),
+            DestructureTuple(inner) => self.destructure_tuple(inner, node.span()),
+            If(inner) => self.r#if(inner, node.span(), &node.inferred),
+            InstantiateTuple(inner) => self.instantiate_tuple(inner, node.span()),
+            InstantiateType(inner) => self.instantiate_type(inner, node.span()),
             InterpolateString(inner) => self.interpolate_string(inner, node.span()),
             LiteralBoolean(inner) => self.literal_boolean(inner, node.span()),
             LiteralFloat4(inner) => self.literal_float4(inner, node.span()),
@@ -64,6 +87,7 @@ impl<'a> Generator<'a> {
             LiteralInt4(inner) => self.literal_int4(inner, node.span()),
             LiteralInt8(inner) => self.literal_int8(inner, node.span()),
             LiteralInt16(inner) => self.literal_int16(inner, node.span()),
+            LiteralNone(inner) => self.literal_none(inner, node.span()),
             LiteralNumber(inner) => self.literal_number(inner, node.span()),
             LiteralString(inner) => self.literal_string(inner, node.span()),
             LiteralUint1(inner) => self.literal_uint1(inner, node.span()),
@@ -71,7 +95,12 @@ impl<'a> Generator<'a> {
             LiteralUint4(inner) => self.literal_uint4(inner, node.span()),
             LiteralUint8(inner) => self.literal_uint8(inner, node.span()),
             LiteralUint16(inner) => self.literal_uint16(inner, node.span()),
-            Loop(inner) => self.r#loop(inner, node.span()),
+            IteratorLoop(inner) => self.iterator_loop(inner, node.span()),
+            Loop(inner) => self.r#loop(inner, node.span(), &node.inferred),
+            Match(inner) => self.r#match(inner, node.span(), &node.inferred),
+            RangeLoop(inner) => self.range_loop(inner, node.span()),
+            ReturnFromFunction(inner) => self.r#return(inner, node.span()),
+            Try(inner) => self.r#try(inner, node.span(), &node.inferred),
             _ => unimplemented!("{node:#?}")
         }
     }
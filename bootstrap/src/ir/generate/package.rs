@@ -0,0 +1,96 @@
+use std::rc::Rc;
+
+use crate::common::{Span, TypeId};
+use crate::common::node::Node::{DeclarePackage, DeclareTrait, DeclareType, DefineType, InstantiateType};
+use crate::ir::analyse::{TypeDeclarePackageNode, TypeDeclareTraitNode, TypeDeclareTypeNode, TypeDefineTypeNode, TypeInstantiateTypeNode};
+use crate::ir::generate::Generator;
+use crate::ir::node::{IrDeclarePackageNode, IrDeclareTraitNode, IrDeclareTypeNode, IrDefineTypeNode, IrInstantiateTypeNode, IrNamedArgument, IrTreeNode};
+
+impl<'a> Generator<'a> {
+    pub(crate) fn declare_package(&mut self, node: &TypeDeclarePackageNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        let mut functions = Vec::with_capacity(node.functions.len());
+        for function in &node.functions {
+            functions.push(Rc::new(self.node(function)?))
+        }
+
+        let mut packages = Vec::with_capacity(node.packages.len());
+        for package in &node.packages {
+            packages.push(Rc::new(self.node(package)?))
+        }
+
+        let mut types = Vec::with_capacity(node.types.len());
+        for r#type in &node.types {
+            types.push(Rc::new(self.node(r#type)?))
+        }
+
+        let mut definitions = Vec::with_capacity(node.definitions.len());
+        for definition in &node.definitions {
+            definitions.push(Rc::new(self.node(definition)?))
+        }
+
+        Ok(IrTreeNode::new(
+            DeclarePackage(IrDeclarePackageNode {
+                package: node.package,
+                definitions: definitions.into_boxed_slice(),
+                functions: functions.into_boxed_slice(),
+                packages: packages.into_boxed_slice(),
+                types: types.into_boxed_slice(),
+            }),
+            span,
+            TypeId::UNIT,
+        ))
+    }
+
+    pub(crate) fn declare_type(&mut self, node: &TypeDeclareTypeNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        Ok(IrTreeNode::new(
+            DeclareType(IrDeclareTypeNode { r#type: node.r#type }),
+            span,
+            TypeId::UNIT,
+        ))
+    }
+
+    pub(crate) fn declare_trait(&mut self, node: &TypeDeclareTraitNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        Ok(IrTreeNode::new(
+            DeclareTrait(IrDeclareTraitNode { r#trait: node.r#trait }),
+            span,
+            TypeId::UNIT,
+        ))
+    }
+
+    pub(crate) fn define_type(&mut self, node: &TypeDefineTypeNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        let mut functions = Vec::with_capacity(node.functions.len());
+        for function in &node.functions {
+            functions.push(Rc::new(self.node(function)?))
+        }
+
+        Ok(IrTreeNode::new(
+            DefineType(IrDefineTypeNode {
+                r#type: node.r#type,
+                functions: functions.into_boxed_slice(),
+            }),
+            span,
+            TypeId::UNIT,
+        ))
+    }
+
+    pub(crate) fn instantiate_type(&mut self, node: &TypeInstantiateTypeNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        let mut arguments = Vec::with_capacity(node.arguments.len());
+        for argument in &node.arguments {
+            arguments.push(IrNamedArgument {
+                argument: argument.argument,
+                value: Rc::new(self.node(&argument.value)?),
+            })
+        }
+
+        let type_id = self.symbol_table[node.r#type].type_id().unwrap_or(TypeId::UNKNOWN);
+
+        Ok(IrTreeNode::new(
+            InstantiateType(IrInstantiateTypeNode {
+                r#type: node.r#type,
+                arguments: arguments.into_boxed_slice(),
+            }),
+            span,
+            type_id,
+        ))
+    }
+}
@@ -0,0 +1,93 @@
+use std::ops::Deref;
+use std::rc::Rc;
+
+use crate::common::{Inferred, Span, TypeId};
+use crate::common::node::Node::{AccessElementOfTuple, DestructureTuple, InstantiateTuple};
+use crate::ir::{IrAccessElementOfTupleNode, IrDestructureTupleNode, IrInstantiateTupleNode, IrTreeNode};
+use crate::ir::analyse::{TypeAccessElementOfTupleNode, TypeDestructureTupleNode, TypeInstantiateTupleNode};
+use crate::ir::generate::Generator;
+
+impl<'a> Generator<'a> {
+    pub(crate) fn access_element_of_tuple(&mut self, node: &TypeAccessElementOfTupleNode, span: Span, inferred: &Inferred) -> crate::ir::generate::Result<IrTreeNode> {
+        let tuple = Rc::new(self.node(node.node.deref())?);
+
+        Ok(IrTreeNode::new(
+            AccessElementOfTuple(IrAccessElementOfTupleNode {
+                node: tuple,
+                index: node.index,
+            }),
+            span,
+            inferred.type_id().unwrap_or(TypeId::UNKNOWN),
+        ))
+    }
+
+    pub(crate) fn destructure_tuple(&mut self, node: &TypeDestructureTupleNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        let value = Rc::new(self.node(node.value.deref())?);
+
+        Ok(IrTreeNode::new(
+            DestructureTuple(IrDestructureTupleNode {
+                variables: node.variables.clone(),
+                value,
+            }),
+            span,
+            TypeId::TUPLE,
+        ))
+    }
+
+    pub(crate) fn instantiate_tuple(&mut self, node: &TypeInstantiateTupleNode, span: Span) -> crate::ir::generate::Result<IrTreeNode> {
+        let mut nodes = Vec::with_capacity(node.nodes.len());
+        for node in node.nodes.iter() {
+            nodes.push(Rc::new(self.node(node)?));
+        }
+
+        Ok(IrTreeNode::new(
+            InstantiateTuple(IrInstantiateTupleNode { nodes: nodes.into_boxed_slice() }),
+            span,
+            TypeId::TUPLE,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, TypeId};
+    use crate::ir::ir_from_str;
+
+    #[test]
+    fn instantiate_tuple() {
+        let mut ctx = Context::testing();
+        let ir = ir_from_str(&mut ctx, "let pair = (1, 'Elodie')").unwrap();
+
+        let declared = ir[0].as_declare_variable();
+        assert_eq!(declared.value.type_id, TypeId::TUPLE);
+
+        let tuple = declared.value.as_instantiate_tuple();
+        assert_eq!(tuple.nodes[0].type_id, TypeId::NUMBER);
+        assert_eq!(tuple.nodes[1].type_id, TypeId::STRING);
+    }
+
+    #[test]
+    fn access_element_of_tuple() {
+        let mut ctx = Context::testing();
+        let ir = ir_from_str(&mut ctx, r#"
+        let pair = (1, 'Elodie')
+        let name = pair.1
+        "#).unwrap();
+
+        let declared = ir[1].as_declare_variable();
+        assert_eq!(declared.value.type_id, TypeId::STRING);
+
+        let access = declared.value.as_access_element_of_tuple();
+        assert_eq!(access.index, 1);
+    }
+
+    #[test]
+    fn destructure_tuple() {
+        let mut ctx = Context::testing();
+        let ir = ir_from_str(&mut ctx, "let (number, name) = (1, 'Elodie')").unwrap();
+
+        let destructured = ir[0].as_destructure_tuple();
+        assert_eq!(ctx.symbol_name(destructured.variables[0]), "number");
+        assert_eq!(ctx.symbol_name(destructured.variables[1]), "name");
+    }
+}
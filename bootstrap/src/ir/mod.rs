@@ -3,8 +3,9 @@ use std::ops::Index;
 
 use crate::{frontend, ir};
 use crate::common::Context;
-use crate::frontend::ast_from_str;
-use crate::ir::analyse::analyse;
+use crate::frontend::{ast_from_library, ast_from_str};
+use crate::frontend::library::LIBRARIES;
+use crate::ir::analyse::{analyse, analyse_program};
 use crate::ir::generate::generate;
 pub use crate::ir::node::*;
 
@@ -59,3 +60,75 @@ pub fn ir_from_str(ctx: &mut Context, str: &str) -> Result<ir::Ir> {
     let typed = analyse(ctx, ast)?;
     Ok(generate(ctx, typed)?)
 }
+
+// the program gets analysed together with core, std, rt and sysroot, but only used library functions end up in the ir
+pub fn ir_from_program(ctx: &mut Context, str: &str) -> Result<ir::Ir> {
+    let ast = ast_from_str(ctx, str)?;
+
+    let mut library = Vec::with_capacity(LIBRARIES.len());
+    for path in LIBRARIES {
+        library.push(ast_from_library(ctx, path)?);
+    }
+
+    let typed = analyse_program(ctx, library, ast)?;
+    Ok(generate(ctx, typed)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{Context, TypeId};
+    use crate::common::node::Node::DefineType;
+    use crate::ir::ir_from_program;
+
+    #[test]
+    fn program_uses_std() {
+        let mut ctx = Context::testing();
+        let ir = ir_from_program(&mut ctx, "let list: std::collection::list::List<Number> = std::collection::list::empty()").unwrap();
+        assert_eq!(ir.len(), 2);
+
+        let empty = ir[0].as_declare_function();
+        assert_eq!(ctx.symbol_name(empty.function), "empty");
+
+        let list = ir[1].as_declare_variable();
+        assert_eq!(list.value.as_call_function_of_package().function, empty.function);
+        assert!(matches!(list.value.type_id, TypeId::Custom(_)));
+    }
+
+    #[test]
+    fn program_links_only_used_library_functions() {
+        let mut ctx = Context::testing();
+        let ir = ir_from_program(&mut ctx, "rt::io::println('Elodie')").unwrap();
+        assert_eq!(ir.len(), 3);
+
+        assert_eq!(ctx.symbol_name(ir[0].as_declare_function().function), "print");
+        assert_eq!(ctx.symbol_name(ir[1].as_declare_function().function), "println");
+
+        let call = ir[2].as_call_function_of_package();
+        assert_eq!(ctx.symbol_name(call.function), "println");
+    }
+
+    #[test]
+    fn program_without_library_usage() {
+        let mut ctx = Context::testing();
+        let ir = ir_from_program(&mut ctx, "let value = 1").unwrap();
+        assert_eq!(ir.len(), 1);
+    }
+
+    #[test]
+    fn program_iterates_list() {
+        let mut ctx = Context::testing();
+        let ir = ir_from_program(&mut ctx, r#"
+        let list: std::collection::list::List<Number> = std::collection::list::empty()
+        list.append(2)
+        for value in list { }
+        "#).unwrap();
+
+        let defined = ir.nodes.iter()
+            .filter_map(|node| match node.node() {
+                DefineType(define) => Some(ctx.symbol_name(define.r#type)),
+                _ => None
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(defined, vec!["List", "List_Iterator"]);
+    }
+}
@@ -2,8 +2,8 @@ use std::rc::Rc;
 
 use bigdecimal::BigDecimal;
 
-use crate::common::{Span, StringTableId, SymbolId, TypeId, WithSpan};
-use crate::common::node::{AccessVariableNode, AccessVariableOfObjectNode, AccessVariableOfSelfNode, BlockNode, BreakLoopNode, CalculateNode, CalculateOperator, CallFunctionNode, CallFunctionOfObjectNode, CallFunctionOfPackageNode, CallFunctionWithLambdaNode, CompareNode, CompareOperator, ContinueLoopNode, DeclareExternalFunctionNode, DeclareFunctionNode, DeclarePackageNode, DeclareTypeNode, DeclareVariableNode, DefineTypeNode, ExportPackageNode, IfNode, InstantiateTypeNode, InterpolateStringNode, LiteralBooleanNode, LiteralFloat4Node, LiteralFloat8Node, LiteralInt16Node, LiteralInt1Node, LiteralInt2Node, LiteralInt4Node, LiteralInt8Node, LiteralNumberNode, LiteralStringNode, LiteralUint16Node, LiteralUint1Node, LiteralUint2Node, LiteralUint4Node, LiteralUint8Node, LoopNode, Node, ReturnFromFunctionNode, Variant};
+use crate::common::{Dispatch, Span, StringTableId, SymbolId, TypeId, TypeVariableId, WithSpan};
+use crate::common::node::{AccessElementOfTupleNode, AccessIndexNode, AccessVariableNode, AccessVariableOfObjectNode, AccessVariableOfSelfNode, AssignVariableNode, AssignVariableOfObjectNode, BlockNode, BreakLoopNode, CalculateNode, CalculateOperator, CallFunctionNode, CallFunctionOfObjectNode, CallFunctionOfPackageNode, CallFunctionWithLambdaNode, CompareNode, CompareOperator, ContinueLoopNode, DeclareExternalFunctionNode, DeclareFunctionNode, DeclareLambdaNode, DeclarePackageNode, DeclareTraitNode, DeclareTypeNode, DeclareVariableNode, DeferNode, DefineTypeNode, DestructureTupleNode, ExportPackageNode, IfNode, InstantiateRecordNode, InstantiateTupleNode, InstantiateTypeNode, InterpolateStringNode, IteratorLoopNode, LiteralBooleanNode, LiteralFloat4Node, LiteralFloat8Node, LiteralInt16Node, LiteralInt1Node, LiteralInt2Node, LiteralInt4Node, LiteralInt8Node, LiteralNoneNode, LiteralNumberNode, LiteralStringNode, LiteralUint16Node, LiteralUint1Node, LiteralUint2Node, LiteralUint4Node, LiteralUint8Node, LoopNode, MatchNode, Node, RangeLoopNode, ReturnFromFunctionNode, TryNode, Variant};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct IrVariant {}
@@ -12,9 +12,13 @@ impl Variant for IrVariant {}
 
 pub type IrNode = Node<
     IrVariant,
+    IrAccessElementOfTupleNode,
+    IrAccessIndexNode,
     IrAccessVariableNode,
     IrAccessVariableOfObjectNode,
     IrAccessVariableOfSelfNode,
+    IrAssignVariableNode,
+    IrAssignVariableOfObjectNode,
     IrBlockNode,
     IrBreakLoopNode,
     IrCalculateNode,
@@ -26,13 +30,20 @@ pub type IrNode = Node<
     IrContinueLoopNode,
     IrDeclareExternalFunctionNode,
     IrDeclareFunctionNode,
+    IrDeclareLambdaNode,
     IrDeclarePackageNode,
+    IrDeclareTraitNode,
     IrDeclareTypeNode,
     IrDeclareVariableNode,
+    IrDeferNode,
     IrDefineTypeNode,
+    IrDestructureTupleNode,
     IrExportPackageNode,
     IrIfNode,
     IrInterpolateStringNode,
+    IrIteratorLoopNode,
+    IrInstantiateRecordNode,
+    IrInstantiateTupleNode,
     IrInstantiateTypeNode,
     IrLiteralBooleanNode,
     IrLiteralFloat4Node,
@@ -42,6 +53,7 @@ pub type IrNode = Node<
     IrLiteralInt4Node,
     IrLiteralInt8Node,
     IrLiteralInt16Node,
+    IrLiteralNoneNode,
     IrLiteralNumberNode,
     IrLiteralStringNode,
     IrLiteralUint1Node,
@@ -50,7 +62,10 @@ pub type IrNode = Node<
     IrLiteralUint8Node,
     IrLiteralUint16Node,
     IrLoopNode,
+    IrMatchNode,
+    IrRangeLoopNode,
     IrReturnFromFunctionNode,
+    IrTryNode,
 >;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -61,6 +76,14 @@ pub struct IrTreeNode {
 }
 
 impl IrTreeNode {
+    pub fn as_access_element_of_tuple(&self) -> &IrAccessElementOfTupleNode {
+        if let Node::AccessElementOfTuple(result) = &self.node {
+            result
+        } else {
+            panic!("not access element of tuple")
+        }
+    }
+
     pub fn as_access_variable(&self) -> &IrAccessVariableNode {
         if let Node::AccessVariable(result) = &self.node {
             result
@@ -69,6 +92,54 @@ impl IrTreeNode {
         }
     }
 
+    pub fn as_access_variable_of_object(&self) -> &IrAccessVariableOfObjectNode {
+        if let Node::AccessVariableOfObject(result) = &self.node {
+            result
+        } else {
+            panic!("not access variable of object")
+        }
+    }
+
+    pub fn as_call_function(&self) -> &IrCallFunctionNode {
+        if let Node::CallFunction(result) = &self.node {
+            result
+        } else {
+            panic!("not call function")
+        }
+    }
+
+    pub fn as_call_function_of_package(&self) -> &IrCallFunctionOfPackageNode {
+        if let Node::CallFunctionOfPackage(result) = &self.node {
+            result
+        } else {
+            panic!("not call function of package")
+        }
+    }
+
+    pub fn as_declare_external_function(&self) -> &IrDeclareExternalFunctionNode {
+        if let Node::DeclareExternalFunction(result) = &self.node {
+            result
+        } else {
+            panic!("not declare external function")
+        }
+    }
+
+    pub fn as_declare_function(&self) -> &IrDeclareFunctionNode {
+        if let Node::DeclareFunction(result) = &self.node {
+            result
+        } else {
+            panic!("not declare function")
+        }
+    }
+
+    pub fn as_declare_lambda(&self) -> &IrDeclareLambdaNode {
+        if let Node::DeclareLambda(result) = &self.node {
+            result
+        } else {
+            panic!("not declare lambda")
+        }
+    }
+
     pub fn as_declare_variable(&self) -> &IrDeclareVariableNode {
         if let Node::DeclareVariable(result) = &self.node {
             result
@@ -77,6 +148,22 @@ impl IrTreeNode {
         }
     }
 
+    pub fn as_destructure_tuple(&self) -> &IrDestructureTupleNode {
+        if let Node::DestructureTuple(result) = &self.node {
+            result
+        } else {
+            panic!("not destructure tuple")
+        }
+    }
+
+    pub fn as_instantiate_tuple(&self) -> &IrInstantiateTupleNode {
+        if let Node::InstantiateTuple(result) = &self.node {
+            result
+        } else {
+            panic!("not instantiate tuple")
+        }
+    }
+
     pub fn as_literal_boolean(&self) -> &IrLiteralBooleanNode {
         if let Node::LiteralBoolean(result) = &self.node {
             result
@@ -127,6 +214,19 @@ impl WithSpan for IrTreeNode {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrAccessElementOfTupleNode {
+    pub node: Rc<IrTreeNode>,
+    pub index: usize,
+}
+
+impl AccessElementOfTupleNode<IrVariant> for IrAccessElementOfTupleNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrAccessIndexNode {}
+
+impl AccessIndexNode<IrVariant> for IrAccessIndexNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct IrAccessVariableNode {
     pub variable: SymbolId,
@@ -135,7 +235,10 @@ pub struct IrAccessVariableNode {
 impl AccessVariableNode<IrVariant> for IrAccessVariableNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct IrAccessVariableOfObjectNode {}
+pub struct IrAccessVariableOfObjectNode {
+    pub object: SymbolId,
+    pub variable: TypeVariableId,
+}
 
 impl AccessVariableOfObjectNode<IrVariant> for IrAccessVariableOfObjectNode {}
 
@@ -144,6 +247,23 @@ pub struct IrAccessVariableOfSelfNode {}
 
 impl AccessVariableOfSelfNode<IrVariant> for IrAccessVariableOfSelfNode {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrAssignVariableNode {
+    pub variable: SymbolId,
+    pub value: Rc<IrTreeNode>,
+}
+
+impl AssignVariableNode<IrVariant> for IrAssignVariableNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrAssignVariableOfObjectNode {
+    pub object: SymbolId,
+    pub variable: TypeVariableId,
+    pub value: Rc<IrTreeNode>,
+}
+
+impl AssignVariableOfObjectNode<IrVariant> for IrAssignVariableOfObjectNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct IrBlockNode {
     pub nodes: Box<[Rc<IrTreeNode>]>,
@@ -153,6 +273,7 @@ impl BlockNode<IrVariant> for IrBlockNode {}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct IrBreakLoopNode {
+    pub label: Option<StringTableId>,
     pub node: Option<Rc<IrTreeNode>>,
 }
 
@@ -168,17 +289,29 @@ pub struct IrCalculateNode {
 impl CalculateNode<IrVariant> for IrCalculateNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct IrCallFunctionNode {}
+pub struct IrCallFunctionNode {
+    pub function: SymbolId,
+    pub arguments: Box<[Rc<IrTreeNode>]>,
+}
 
 impl CallFunctionNode<IrVariant> for IrCallFunctionNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct IrCallFunctionWithLambdaNode {}
+pub struct IrCallFunctionWithLambdaNode {
+    pub function: SymbolId,
+    pub arguments: Box<[Rc<IrTreeNode>]>,
+    pub lambda: Rc<IrTreeNode>,
+}
 
 impl CallFunctionWithLambdaNode<IrVariant> for IrCallFunctionWithLambdaNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct IrCallFunctionOfObjectNode {}
+pub struct IrCallFunctionOfObjectNode {
+    pub object: SymbolId,
+    pub function: SymbolId,
+    pub arguments: Box<[Rc<IrTreeNode>]>,
+    pub dispatch: Dispatch,
+}
 
 impl CallFunctionOfObjectNode<IrVariant> for IrCallFunctionOfObjectNode {}
 
@@ -201,35 +334,86 @@ pub struct IrCompareNode {
 impl CompareNode<IrVariant> for IrCompareNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct IrContinueLoopNode {}
+pub struct IrContinueLoopNode {
+    pub label: Option<StringTableId>,
+}
 
 impl ContinueLoopNode<IrVariant> for IrContinueLoopNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct IrDeclareExternalFunctionNode {}
+pub struct IrDeclareExternalFunctionNode {
+    pub function: SymbolId,
+}
 
 impl DeclareExternalFunctionNode<IrVariant> for IrDeclareExternalFunctionNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct IrDeclareFunctionNode {}
+pub struct IrDeclareFunctionNode {
+    pub function: SymbolId,
+    pub arguments: Box<[SymbolId]>,
+    pub nodes: Box<[Rc<IrTreeNode>]>,
+}
 
 impl DeclareFunctionNode<IrVariant> for IrDeclareFunctionNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct IrDeclarePackageNode {}
+pub struct IrDeclareLambdaNode {
+    pub function: SymbolId,
+    pub arguments: Box<[SymbolId]>,
+    pub captures: Box<[SymbolId]>,
+    pub nodes: Box<[Rc<IrTreeNode>]>,
+}
+
+impl DeclareLambdaNode<IrVariant> for IrDeclareLambdaNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrDeclarePackageNode {
+    pub package: SymbolId,
+    pub definitions: Box<[Rc<IrTreeNode>]>,
+    pub functions: Box<[Rc<IrTreeNode>]>,
+    pub packages: Box<[Rc<IrTreeNode>]>,
+    pub types: Box<[Rc<IrTreeNode>]>,
+}
 
 impl DeclarePackageNode<IrVariant> for IrDeclarePackageNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct IrDeclareTypeNode {}
+pub struct IrDeclareTraitNode {
+    pub r#trait: SymbolId,
+}
+
+impl DeclareTraitNode<IrVariant> for IrDeclareTraitNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrDeclareTypeNode {
+    pub r#type: SymbolId,
+}
 
 impl DeclareTypeNode<IrVariant> for IrDeclareTypeNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct IrDefineTypeNode {}
+pub struct IrDeferNode {
+    pub block: Rc<IrBlockNode>,
+}
+
+impl DeferNode<IrVariant> for IrDeferNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrDefineTypeNode {
+    pub r#type: SymbolId,
+    pub functions: Box<[Rc<IrTreeNode>]>,
+}
 
 impl DefineTypeNode<IrVariant> for IrDefineTypeNode {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrDestructureTupleNode {
+    pub variables: Box<[SymbolId]>,
+    pub value: Rc<IrTreeNode>,
+}
+
+impl DestructureTupleNode<IrVariant> for IrDestructureTupleNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct IrDeclareVariableNode {
     pub variable: SymbolId,
@@ -260,7 +444,28 @@ pub struct IrInterpolateStringNode {
 impl InterpolateStringNode<IrVariant> for IrInterpolateStringNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct IrInstantiateTypeNode {}
+pub struct IrInstantiateRecordNode {}
+
+impl InstantiateRecordNode<IrVariant> for IrInstantiateRecordNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrInstantiateTupleNode {
+    pub nodes: Box<[Rc<IrTreeNode>]>,
+}
+
+impl InstantiateTupleNode<IrVariant> for IrInstantiateTupleNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrInstantiateTypeNode {
+    pub r#type: SymbolId,
+    pub arguments: Box<[IrNamedArgument]>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrNamedArgument {
+    pub argument: StringTableId,
+    pub value: Rc<IrTreeNode>,
+}
 
 impl InstantiateTypeNode<IrVariant> for IrInstantiateTypeNode {}
 
@@ -320,6 +525,11 @@ pub struct IrLiteralInt16Node {
 
 impl LiteralInt16Node<IrVariant> for IrLiteralInt16Node {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrLiteralNoneNode {}
+
+impl LiteralNoneNode<IrVariant> for IrLiteralNoneNode {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct IrLiteralNumberNode {
     pub value: BigDecimal,
@@ -371,12 +581,66 @@ impl LiteralUint16Node<IrVariant> for IrLiteralUint16Node {}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct IrLoopNode {
+    pub label: Option<StringTableId>,
     pub block: Rc<IrBlockNode>,
 }
 
 impl LoopNode<IrVariant> for IrLoopNode {}
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct IrReturnFromFunctionNode {}
+pub struct IrIteratorLoopNode {
+    pub label: Option<StringTableId>,
+    pub variable: SymbolId,
+    pub iterable: SymbolId,
+    pub value: Rc<IrTreeNode>,
+    pub iterator: SymbolId,
+    pub iter: Option<Rc<IrTreeNode>>,
+    pub next: Option<Rc<IrTreeNode>>,
+    pub block: Rc<IrBlockNode>,
+}
+
+impl IteratorLoopNode<IrVariant> for IrIteratorLoopNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrRangeLoopNode {
+    pub label: Option<StringTableId>,
+    pub variable: SymbolId,
+    pub start: Rc<IrTreeNode>,
+    pub end: Rc<IrTreeNode>,
+    pub inclusive: bool,
+    pub step: Option<Rc<IrTreeNode>>,
+    pub block: Rc<IrBlockNode>,
+}
+
+impl RangeLoopNode<IrVariant> for IrRangeLoopNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrMatchNode {
+    pub node: Rc<IrTreeNode>,
+    pub arms: Box<[IrMatchArm]>,
+}
+
+impl MatchNode<IrVariant> for IrMatchNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrMatchArm {
+    // none for otherwise
+    pub variant: Option<TypeId>,
+    pub bindings: Box<[SymbolId]>,
+    pub block: Rc<IrBlockNode>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrReturnFromFunctionNode {
+    pub node: Option<Rc<IrTreeNode>>,
+}
 
 impl ReturnFromFunctionNode<IrVariant> for IrReturnFromFunctionNode {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrTryNode {
+    pub node: Rc<IrTreeNode>,
+    pub error: TypeId,
+}
+
+impl TryNode<IrVariant> for IrTryNode {}
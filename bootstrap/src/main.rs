@@ -1,9 +1,8 @@
-use std::fs::File;
-use std::{env, io};
-use std::io::Read;
+use std::env;
 use std::path::PathBuf;
 use std::process::exit;
 
+mod bind;
 mod build;
 mod cli;
 mod common;
@@ -17,6 +16,12 @@ fn main() {
         exit(1)
     }
     //
+    // bind header.h prints an elodie package with the external declarations of the header
+    if args.get(1).unwrap() == "bind" {
+        bind::bind_header_from_file(PathBuf::from(args.get(2).unwrap()));
+        return;
+    }
+
     if args.get(1).unwrap() == "build" {
         let file = PathBuf::from(args.get(2).unwrap());
 
@@ -58,7 +63,14 @@ fn main() {
     //
     //     // println!("{}",code);
     //
-        build::build_c_code_from_file(file);
+        // --lib builds a static library, together with --shared a shared one
+        let target = match (args.contains(&"--lib".to_string()), args.contains(&"--shared".to_string())) {
+            (false, _) => build::Target::Executable,
+            (true, false) => build::Target::StaticLibrary,
+            (true, true) => build::Target::SharedLibrary,
+        };
+
+        build::build_c_code_from_file(file, target);
 
         return;
     }
@@ -74,15 +86,6 @@ fn main() {
     // }
 }
 
-fn load_library_file(filename: &str) -> io::Result<String> {
-    let manifest_dir = "/home/ddymke/repo/elodie/src/lib/";
-    let file_path = PathBuf::from(manifest_dir).join(filename);
-
-    let mut file = File::open(file_path)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-    Ok(contents)
-}
 //
 // fn load_test_runner() -> io::Result<String> {
 //     let manifest_dir = "/home/ddymke/repo/elodie/src/test-runner/index.ec";
@@ -3,6 +3,9 @@
 
 #include "val.h"
 #include "val-bool.h"
+#include "val-clo.h"
+#include "val-dyn.h"
+#include "val-enm.h"
 #include "val-fld.h"
 #include "val-fn.h"
 #include "val-lst.h"
@@ -0,0 +1,31 @@
+#ifndef CORE_VAL_CLO_H
+#define CORE_VAL_CLO_H
+
+#include "core/core.h"
+#include "val.h"
+
+// a compiled function together with the environment of the values it captured,
+// the function gets the closure itself as first argument
+struct val_clo {
+    struct val base;
+    void *fn;
+    size_t count;
+    struct val *env[];
+};
+
+ELODIE_API struct val_clo *
+val_clo_new(struct mem *mem, void *fn, size_t count);
+
+ELODIE_API void
+val_clo_capture(struct val_clo *self, size_t idx, struct val *value);
+
+ELODIE_API struct val *
+val_clo_env_at(struct val_clo *self, size_t idx);
+
+ELODIE_API void
+val_clo_free(struct val_clo *self);
+
+ELODIE_API void
+val_clo_free_safe(struct val_clo **self);
+
+#endif //CORE_VAL_CLO_H
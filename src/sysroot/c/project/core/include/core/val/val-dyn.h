@@ -0,0 +1,29 @@
+#ifndef CORE_VAL_DYN_H
+#define CORE_VAL_DYN_H
+
+#include "core/core.h"
+#include "val.h"
+
+// a value used as trait value, the vtable holds the functions the type of the value implements the trait with
+struct val_dyn {
+    struct val base;
+    void **vtable;
+    struct val *value;
+};
+
+ELODIE_API struct val_dyn *
+val_dyn_new(struct mem *mem, void **vtable, struct val *value);
+
+ELODIE_API void *
+val_dyn_fn_at(struct val_dyn *self, size_t idx);
+
+ELODIE_API struct val *
+val_dyn_unwrap(struct val_dyn *self);
+
+ELODIE_API void
+val_dyn_free(struct val_dyn *self);
+
+ELODIE_API void
+val_dyn_free_safe(struct val_dyn **self);
+
+#endif //CORE_VAL_DYN_H
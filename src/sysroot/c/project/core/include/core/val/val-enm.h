@@ -0,0 +1,30 @@
+#ifndef CORE_VAL_ENM_H
+#define CORE_VAL_ENM_H
+
+#include "core/core.h"
+#include "val.h"
+
+// a value of a sum type, the tag tells which variant it is and the payload holds the values of its variables
+struct val_enm {
+    struct val base;
+    uint32_t tag;
+    size_t count;
+    struct val *payload[];
+};
+
+ELODIE_API struct val_enm *
+val_enm_new(struct mem *mem, uint32_t tag, size_t count);
+
+ELODIE_API void
+val_enm_set(struct val_enm *self, size_t idx, struct val *value);
+
+ELODIE_API struct val *
+val_enm_at(struct val_enm *self, size_t idx);
+
+ELODIE_API void
+val_enm_free(struct val_enm *self);
+
+ELODIE_API void
+val_enm_free_safe(struct val_enm **self);
+
+#endif //CORE_VAL_ENM_H
@@ -3,6 +3,9 @@
 
 enum val_kind {
   VAL_KIND_BOOL,
+  VAL_KIND_CLO,
+  VAL_KIND_DYN,
+  VAL_KIND_ENM,
   VAL_KIND_FLD,
   VAL_KIND_FN,
   VAL_KIND_F4,
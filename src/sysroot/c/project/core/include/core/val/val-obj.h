@@ -17,14 +17,30 @@ struct val_obj {
 
   struct ptr_list props;    // FIXME objects of same type must have same props as well --> put this into a global place
   struct ptr_list values;
+  struct ptr_list fields;   // values of the declared fields in order of declaration, kept alive by the object
+
+  // called right before the object gets freed, NULL if its type is no Drop
+  void (*drop) (struct val_obj *self);
 };
 
 ELODIE_API struct val_obj *
 val_obj_new (struct mem *mem, struct val_str_view ident);
 
+ELODIE_API struct val_obj *
+val_obj_drop_set (struct val_obj *self, void (*drop) (struct val_obj *self));
+
 ELODIE_API void
 val_obj_append (struct val_obj *self, struct val_fld *field, struct val *value);
 
+ELODIE_API struct val_obj *
+val_obj_field_append (struct val_obj *self, struct val *value);
+
+ELODIE_API struct val *
+val_obj_field_at (struct val_obj *self, size_t idx);
+
+ELODIE_API void
+val_obj_field_replace (struct val_obj *self, size_t idx, struct val *value);
+
 ELODIE_API struct val *
 val_obj_val_at (struct val_obj *self, size_t idx);
 
@@ -40,6 +40,10 @@ val_str_equal (struct val_str *lhs, struct val_str *rhs);
 ELODIE_API struct val_str *
 val_str_concat (struct val_str *self, struct val_str *other, struct mem *mem);
 
+// the character at index as new string, moves index past it - null once the end is reached
+ELODIE_API struct val_str *
+val_str_next_char (struct val_str *self, size_t *index, struct mem *mem);
+
 ELODIE_API void
 val_str_free (struct val_str *self);
 
@@ -109,6 +109,18 @@ val_free(struct val *self) {
             val_bool_free((struct val_bool *) self);
             break;
         }
+        case VAL_KIND_CLO: {
+            val_clo_free((struct val_clo *) self);
+            break;
+        }
+        case VAL_KIND_DYN: {
+            val_dyn_free((struct val_dyn *) self);
+            break;
+        }
+        case VAL_KIND_ENM: {
+            val_enm_free((struct val_enm *) self);
+            break;
+        }
         case VAL_KIND_FLD: {
             val_fld_free((struct val_fld *) self);
             break;
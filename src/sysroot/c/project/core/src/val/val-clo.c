@@ -0,0 +1,53 @@
+#include "core/check.h"
+#include "core/val/val-clo.h"
+
+struct val_clo *
+val_clo_new(struct mem *mem, void *fn, size_t count) {
+    CHECK_NOT_NULL(mem);
+    CHECK_NOT_NULL(fn);
+    struct val_clo *result = mem_allocate(mem, sizeof(struct val_clo) + count * sizeof(struct val *));
+    val_init(&result->base, VAL_KIND_CLO, mem);
+    result->fn = fn;
+    result->count = count;
+    for (size_t idx = 0; idx < count; idx++) {
+        result->env[idx] = NULL;
+    }
+    return result;
+}
+
+// the closure keeps the captured value alive until it gets freed itself
+void
+val_clo_capture(struct val_clo *self, size_t idx, struct val *value) {
+    CHECK_NOT_NULL(self);
+    CHECK_NOT_NULL(value);
+    CHECK_LESS_THAN(idx, self->count);
+    CHECK_NULL(self->env[idx]);
+    val_rc_inc(value);
+    self->env[idx] = value;
+}
+
+struct val *
+val_clo_env_at(struct val_clo *self, size_t idx) {
+    CHECK_NOT_NULL(self);
+    CHECK_LESS_THAN(idx, self->count);
+    CHECK_NOT_NULL(self->env[idx]);
+    return self->env[idx];
+}
+
+void
+val_clo_free(struct val_clo *self) {
+    CHECK_NOT_NULL(self);
+    for (size_t idx = 0; idx < self->count; idx++) {
+        if (self->env[idx] != NULL) {
+            val_rc_dec(self->env[idx]);
+        }
+    }
+    mem_deallocate(self->base.mem, self);
+}
+
+void
+val_clo_free_safe(struct val_clo **self) {
+    CHECK_NOT_NULL(self);
+    val_clo_free(*self);
+    *self = NULL;
+}
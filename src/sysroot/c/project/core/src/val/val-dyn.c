@@ -0,0 +1,47 @@
+#include "core/check.h"
+#include "core/val/val-dyn.h"
+
+// the trait value keeps the value alive until it gets freed itself
+struct val_dyn *
+val_dyn_new(struct mem *mem, void **vtable, struct val *value) {
+    CHECK_NOT_NULL(mem);
+    CHECK_NOT_NULL(vtable);
+    CHECK_NOT_NULL(value);
+    struct val_dyn *result = mem_allocate(mem, sizeof(struct val_dyn));
+    val_init(&result->base, VAL_KIND_DYN, mem);
+    result->vtable = vtable;
+    val_rc_inc(value);
+    result->value = value;
+    return result;
+}
+
+void *
+val_dyn_fn_at(struct val_dyn *self, size_t idx) {
+    CHECK_NOT_NULL(self);
+    CHECK_NOT_NULL(self->vtable[idx]);
+    return self->vtable[idx];
+}
+
+// hands the value to the caller and releases the trait value
+struct val *
+val_dyn_unwrap(struct val_dyn *self) {
+    CHECK_NOT_NULL(self);
+    struct val *result = self->value;
+    val_rc_inc(result);
+    val_rc_dec(&self->base);
+    return result;
+}
+
+void
+val_dyn_free(struct val_dyn *self) {
+    CHECK_NOT_NULL(self);
+    val_rc_dec(self->value);
+    mem_deallocate(self->base.mem, self);
+}
+
+void
+val_dyn_free_safe(struct val_dyn **self) {
+    CHECK_NOT_NULL(self);
+    val_dyn_free(*self);
+    *self = NULL;
+}
@@ -0,0 +1,52 @@
+#include "core/check.h"
+#include "core/val/val-enm.h"
+
+struct val_enm *
+val_enm_new(struct mem *mem, uint32_t tag, size_t count) {
+    CHECK_NOT_NULL(mem);
+    struct val_enm *result = mem_allocate(mem, sizeof(struct val_enm) + count * sizeof(struct val *));
+    val_init(&result->base, VAL_KIND_ENM, mem);
+    result->tag = tag;
+    result->count = count;
+    for (size_t idx = 0; idx < count; idx++) {
+        result->payload[idx] = NULL;
+    }
+    return result;
+}
+
+// the sum value keeps the value alive until it gets freed itself
+void
+val_enm_set(struct val_enm *self, size_t idx, struct val *value) {
+    CHECK_NOT_NULL(self);
+    CHECK_NOT_NULL(value);
+    CHECK_LESS_THAN(idx, self->count);
+    CHECK_NULL(self->payload[idx]);
+    val_rc_inc(value);
+    self->payload[idx] = value;
+}
+
+struct val *
+val_enm_at(struct val_enm *self, size_t idx) {
+    CHECK_NOT_NULL(self);
+    CHECK_LESS_THAN(idx, self->count);
+    CHECK_NOT_NULL(self->payload[idx]);
+    return self->payload[idx];
+}
+
+void
+val_enm_free(struct val_enm *self) {
+    CHECK_NOT_NULL(self);
+    for (size_t idx = 0; idx < self->count; idx++) {
+        if (self->payload[idx] != NULL) {
+            val_rc_dec(self->payload[idx]);
+        }
+    }
+    mem_deallocate(self->base.mem, self);
+}
+
+void
+val_enm_free_safe(struct val_enm **self) {
+    CHECK_NOT_NULL(self);
+    val_enm_free(*self);
+    *self = NULL;
+}
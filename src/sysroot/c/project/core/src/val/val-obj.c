@@ -23,10 +23,20 @@ val_obj_new(struct mem *mem, struct val_str_view ident) {
 
     ptr_list_init(&result->props, default_config(mem));
     ptr_list_init(&result->values, default_config(mem));
+    ptr_list_init(&result->fields, default_config(mem));
+    result->drop = NULL;
 
     return result;
 }
 
+o *
+val_obj_drop_set(o *self, void (*drop)(o *self)) {
+    CHECK_NOT_NULL(self);
+    CHECK_NOT_NULL(drop);
+    self->drop = drop;
+    return self;
+}
+
 void
 val_obj_append(o *self, f *field, v *value) {
     CHECK_NOT_NULL(self);
@@ -72,6 +82,35 @@ val_obj_append(o *self, f *field, v *value) {
     CHECK_EQUAL(ptr_list_count(&self->props), ptr_list_count(&self->values));
 }
 
+// the object keeps the value alive until it gets freed itself
+o *
+val_obj_field_append(o *self, v *value) {
+    CHECK_NOT_NULL(self);
+    CHECK_NOT_NULL(value);
+    val_rc_inc(value);
+    ptr_list_append(&self->fields, value);
+    return self;
+}
+
+v *
+val_obj_field_at(o *self, size_t idx) {
+    CHECK_NOT_NULL(self);
+    CHECK_LESS_THAN(idx, ptr_list_count(&self->fields));
+    return ptr_list_at(&self->fields, idx);
+}
+
+// the previous value gets released, the new one is kept alive by the object
+void
+val_obj_field_replace(o *self, size_t idx, v *value) {
+    CHECK_NOT_NULL(self);
+    CHECK_NOT_NULL(value);
+    CHECK_LESS_THAN(idx, ptr_list_count(&self->fields));
+    v *previous = ptr_list_at(&self->fields, idx);
+    val_rc_inc(value);
+    ptr_list_replace(&self->fields, idx, value);
+    val_rc_dec(previous);
+}
+
 v *
 val_obj_val_at(o *self, size_t idx) {
     CHECK_NOT_NULL(self);
@@ -130,6 +169,12 @@ val_obj_clear(o *self) {
 void
 val_obj_free(o *self) {
     CHECK_NOT_NULL(self);
+    if (self->drop != NULL) {
+        // drop gets a live object, whatever it does with self must not free it a second time
+        self->base.rc = 1;
+        self->drop(self);
+        self->base.rc = 0;
+    }
     val_str_free_safe(&self->ident);
 
     for (size_t idx = 0; idx < ptr_list_count(&self->props); idx++) {
@@ -138,6 +183,11 @@ val_obj_free(o *self) {
     }
     ptr_list_reset(&self->props);
     ptr_list_reset(&self->values);
+
+    for (size_t idx = 0; idx < ptr_list_count(&self->fields); idx++) {
+        val_rc_dec(ptr_list_at(&self->fields, idx));
+    }
+    ptr_list_reset(&self->fields);
     mem_deallocate(self->base.mem, self);
 }
 
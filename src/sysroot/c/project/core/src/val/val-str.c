@@ -123,6 +123,32 @@ val_str_concat(struct val_str *self, struct val_str *other, struct mem *mem) {
     return result;
 }
 
+struct val_str *
+val_str_next_char(struct val_str *self, size_t *index, struct mem *mem) {
+    CHECK_NOT_NULL(self);
+    CHECK_NOT_NULL(index);
+    CHECK_NOT_NULL(mem);
+    if (*index >= self->count) return NULL;
+
+    // a character takes as many bytes as its utf-8 lead byte says
+    u1 lead = (u1) self->data[*index];
+    size_t count = 1;
+    if ((lead & 0xE0) == 0xC0) count = 2;
+    else if ((lead & 0xF0) == 0xE0) count = 3;
+    else if ((lead & 0xF8) == 0xF0) count = 4;
+    if (*index + count > self->count) count = self->count - *index;
+
+    struct val_str *result = mem_allocate(mem, sizeof(struct val_str));
+    val_init(&result->base, VAL_KIND_STR, mem);
+    result->count = count;
+    result->data = mem_allocate(mem, count + 1);
+    memcpy(result->data, self->data + *index, count);
+    result->data[count] = '\0';
+
+    *index += count;
+    return result;
+}
+
 void
 val_str_free(struct val_str *self) {
     CHECK_NOT_NULL(self);
@@ -29,6 +29,12 @@ val_rc_dec(struct val *self) {
         switch (self->kind) {
             case VAL_KIND_BOOL:
                 return val_bool_free_safe((struct val_bool **) &self);
+            case VAL_KIND_CLO:
+                return val_clo_free_safe((struct val_clo **) &self);
+            case VAL_KIND_DYN:
+                return val_dyn_free_safe((struct val_dyn **) &self);
+            case VAL_KIND_ENM:
+                return val_enm_free_safe((struct val_enm **) &self);
             case VAL_KIND_F4:
                 return val_f4_free_safe((struct val_f4 **) &self);
             case VAL_KIND_F8:
@@ -55,6 +61,8 @@ val_rc_dec(struct val *self) {
                 return val_u16_free_safe((struct val_u16 **) &self);
             case VAL_KIND_NUM:
                 return val_num_free_safe((struct val_num **) &self);
+            case VAL_KIND_OBJ:
+                return val_obj_free_safe((struct val_obj **) &self);
             case VAL_KIND_STR:
                 return val_str_free_safe((struct val_str **) &self);
             default:
@@ -0,0 +1,45 @@
+#include "../unit-test.h"
+
+#include "core/val/val-api.h"
+
+static int
+some_fn (struct val_clo *self)
+{
+	return AS_NUM (val_clo_env_at (self, 0))->data > 0;
+}
+
+TEST(val_clo_new, ok)
+{
+	auto tm = mem_test_new_default (128);
+
+	struct val_clo *test_instance = val_clo_new (MEM(tm), (void *) some_fn, 0);
+	ASSERT_EQ (VAL_KIND_CLO, test_instance->base.kind);
+	ASSERT_EQ (MEM (tm), test_instance->base.mem);
+	ASSERT_EQ ((void *) some_fn, test_instance->fn);
+	ASSERT_EQ (0, test_instance->count);
+
+	val_clo_free_safe (&test_instance);
+	mem_test_verify (tm);
+	mem_test_free (tm);
+}
+
+TEST(val_clo_capture, ok)
+{
+	auto tm = mem_test_new_default (256);
+
+	struct val_num *value = val_num_new (MEM(tm), 28.10);
+	struct val_clo *test_instance = val_clo_new (MEM(tm), (void *) some_fn, 1);
+
+	val_clo_capture (test_instance, 0, AS_VAL (value));
+	ASSERT_EQ (2, value->base.rc);
+	ASSERT_EQ (AS_VAL (value), val_clo_env_at (test_instance, 0));
+	ASSERT_EQ (1, ((int (*) (struct val_clo *)) test_instance->fn) (test_instance));
+
+	// the captured value outlives the scope it was declared in
+	val_rc_dec (AS_VAL (value));
+	ASSERT_EQ (1, value->base.rc);
+
+	val_rc_dec (AS_VAL (test_instance));
+	mem_test_verify (tm);
+	mem_test_free (tm);
+}
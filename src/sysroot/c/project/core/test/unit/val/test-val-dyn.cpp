@@ -0,0 +1,49 @@
+#include "../unit-test.h"
+
+#include "core/val/val-api.h"
+
+static int
+some_fn (struct val *self)
+{
+	return AS_NUM (self)->data > 0;
+}
+
+static void *some_vtable[] = {(void *) some_fn};
+
+TEST(val_dyn_new, ok)
+{
+	auto tm = mem_test_new_default (256);
+
+	struct val_num *value = val_num_new (MEM(tm), 28.10);
+	struct val_dyn *test_instance = val_dyn_new (MEM(tm), some_vtable, AS_VAL (value));
+	ASSERT_EQ (VAL_KIND_DYN, test_instance->base.kind);
+	ASSERT_EQ (MEM (tm), test_instance->base.mem);
+	ASSERT_EQ (AS_VAL (value), test_instance->value);
+	ASSERT_EQ (2, value->base.rc);
+	ASSERT_EQ (1, ((int (*) (struct val *)) val_dyn_fn_at (test_instance, 0)) (test_instance->value));
+
+	val_dyn_free_safe (&test_instance);
+	ASSERT_EQ (1, value->base.rc);
+
+	val_rc_dec (AS_VAL (value));
+	mem_test_verify (tm);
+	mem_test_free (tm);
+}
+
+TEST(val_dyn_unwrap, ok)
+{
+	auto tm = mem_test_new_default (256);
+
+	struct val_num *value = val_num_new (MEM(tm), 28.10);
+	struct val_dyn *test_instance = val_dyn_new (MEM(tm), some_vtable, AS_VAL (value));
+	val_rc_dec (AS_VAL (value));
+
+	// the value outlives the trait value it got unwrapped from
+	struct val *result = val_dyn_unwrap (test_instance);
+	ASSERT_EQ (AS_VAL (value), result);
+	ASSERT_EQ (1, result->rc);
+
+	val_rc_dec (result);
+	mem_test_verify (tm);
+	mem_test_free (tm);
+}
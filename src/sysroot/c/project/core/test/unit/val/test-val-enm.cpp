@@ -0,0 +1,54 @@
+#include "../unit-test.h"
+
+#include "core/val/val-api.h"
+
+TEST(val_enm_new, ok)
+{
+	auto tm = mem_test_new_default (256);
+
+	struct val_enm *test_instance = val_enm_new (MEM(tm), 2, 0);
+	ASSERT_EQ (VAL_KIND_ENM, test_instance->base.kind);
+	ASSERT_EQ (MEM (tm), test_instance->base.mem);
+	ASSERT_EQ (2, test_instance->tag);
+	ASSERT_EQ (0, test_instance->count);
+
+	val_enm_free_safe (&test_instance);
+	ASSERT_TRUE (test_instance == NULL);
+
+	mem_test_verify (tm);
+	mem_test_free (tm);
+}
+
+TEST(val_enm_set, ok)
+{
+	auto tm = mem_test_new_default (256);
+
+	struct val_num *value = val_num_new (MEM(tm), 28.10);
+	struct val_enm *test_instance = val_enm_new (MEM(tm), 1, 1);
+	val_enm_set (test_instance, 0, AS_VAL (value));
+	ASSERT_EQ (AS_VAL (value), val_enm_at (test_instance, 0));
+	ASSERT_EQ (2, value->base.rc);
+
+	val_enm_free_safe (&test_instance);
+	ASSERT_EQ (1, value->base.rc);
+
+	val_rc_dec (AS_VAL (value));
+	mem_test_verify (tm);
+	mem_test_free (tm);
+}
+
+TEST(val_enm_free, releases_set_payload_only)
+{
+	auto tm = mem_test_new_default (256);
+
+	struct val_num *value = val_num_new (MEM(tm), 28.10);
+	struct val_enm *test_instance = val_enm_new (MEM(tm), 0, 2);
+	val_enm_set (test_instance, 1, AS_VAL (value));
+	val_rc_dec (AS_VAL (value));
+
+	// the payload which never got set stays untouched
+	val_rc_dec (AS_VAL (test_instance));
+
+	mem_test_verify (tm);
+	mem_test_free (tm);
+}
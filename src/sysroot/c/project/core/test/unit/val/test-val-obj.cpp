@@ -100,4 +100,65 @@ TEST(val_obj_free_safe, ok)
 
 	mem_test_verify (tm);
 	mem_test_free (tm);
-}
\ No newline at end of file
+}
+static int dropped = 0;
+
+static void
+count_drop (struct val_obj *self)
+{
+	ASSERT_EQ(1, self->base.rc);
+	dropped += 1;
+}
+
+TEST(val_obj_drop_set, called_when_freed)
+{
+	auto tm = mem_test_new_default (1024);
+	dropped = 0;
+
+	struct val_obj *test_instance = val_obj_drop_set (val_obj_new (MEM(tm), val_str_view_from_c_str ("some_obj")), count_drop);
+	ASSERT_EQ(0, dropped);
+
+	val_rc_dec (AS_VAL (test_instance));
+	ASSERT_EQ(1, dropped);
+
+	mem_test_verify (tm);
+	mem_test_free (tm);
+}
+
+TEST(val_obj_field_append, keeps_value_alive)
+{
+	auto tm = mem_test_new_default (1024);
+
+	struct val_num *value = val_num_new (MEM(tm), 28.10);
+	struct val_obj *test_instance = val_obj_new (MEM(tm), val_str_view_from_c_str ("some_obj"));
+	val_obj_field_append (test_instance, AS_VAL (value));
+	ASSERT_EQ (AS_VAL (value), val_obj_field_at (test_instance, 0));
+	ASSERT_EQ (2, value->base.rc);
+
+	val_rc_dec (AS_VAL (test_instance));
+	ASSERT_EQ (1, value->base.rc);
+
+	val_rc_dec (AS_VAL (value));
+	mem_test_verify (tm);
+	mem_test_free (tm);
+}
+
+TEST(val_obj_field_replace, releases_previous_value)
+{
+	auto tm = mem_test_new_default (1024);
+
+	struct val_num *previous = val_num_new (MEM(tm), 28.10);
+	struct val_num *value = val_num_new (MEM(tm), 20.19);
+	struct val_obj *test_instance = val_obj_new (MEM(tm), val_str_view_from_c_str ("some_obj"));
+	val_obj_field_append (test_instance, AS_VAL (previous));
+	val_rc_dec (AS_VAL (previous));
+
+	val_obj_field_replace (test_instance, 0, AS_VAL (value));
+	ASSERT_EQ (AS_VAL (value), val_obj_field_at (test_instance, 0));
+	ASSERT_EQ (2, value->base.rc);
+
+	val_rc_dec (AS_VAL (test_instance));
+	val_rc_dec (AS_VAL (value));
+	mem_test_verify (tm);
+	mem_test_free (tm);
+}
@@ -195,6 +195,33 @@ TEST(val_str_concat, ok)
 	mem_test_free (tm);
 }
 
+TEST(val_str_next_char, ok)
+{
+	auto tm = mem_test_new_default (128);
+
+	struct val_str *test_instance = val_str_new_from_c_str (MEM(tm), "aé");
+	size_t index = 0;
+
+	struct val_str *first = val_str_next_char (test_instance, &index, MEM(tm));
+	ASSERT_EQ(1, val_str_count (first));
+	ASSERT_TRUE(strncmp (first->data, "a", first->count) == 0);
+	ASSERT_EQ(1, index);
+
+	struct val_str *second = val_str_next_char (test_instance, &index, MEM(tm));
+	ASSERT_EQ(2, val_str_count (second));
+	ASSERT_TRUE(strncmp (second->data, "é", second->count) == 0);
+	ASSERT_EQ(3, index);
+
+	ASSERT_TRUE(val_str_next_char (test_instance, &index, MEM(tm)) == nullptr);
+
+	val_str_free_safe(&first);
+	val_str_free_safe(&second);
+	val_str_free_safe(&test_instance);
+
+	mem_test_verify (tm);
+	mem_test_free (tm);
+}
+
 TEST(val_str_free_safe, ok)
 {
 	auto tm = mem_test_new_default (128);
@@ -0,0 +1,16 @@
+#ifndef RT_LIST_H
+#define RT_LIST_H
+
+struct val;
+struct val_num;
+
+// a list is an object whose fields are its elements
+
+void rt_list_append(struct val *list, struct val *value);
+
+// elements are counted from 1, the caller owns the returned element
+struct val *rt_list_get(struct val *list, const struct val_num *idx);
+
+struct val_num *rt_list_length(struct val *list);
+
+#endif
@@ -0,0 +1,23 @@
+#include "rt/list.h"
+
+#include "core/algo/algo-list.h"
+#include "core/check.h"
+#include "core/val/val-api.h"
+
+void rt_list_append(struct val *list, struct val *value) {
+    CHECK_EQUAL(list->kind, VAL_KIND_OBJ);
+    val_obj_field_append(AS_OBJ(list), value);
+}
+
+struct val *rt_list_get(struct val *list, const struct val_num *idx) {
+    CHECK_EQUAL(list->kind, VAL_KIND_OBJ);
+    CHECK_GREATER_THAN(idx->data, 0);
+    struct val *result = val_obj_field_at(AS_OBJ(list), (size_t) idx->data - 1);
+    val_rc_inc(result);
+    return result;
+}
+
+struct val_num *rt_list_length(struct val *list) {
+    CHECK_EQUAL(list->kind, VAL_KIND_OBJ);
+    return val_num_new(list->mem, (double) ptr_list_count(&AS_OBJ(list)->fields));
+}
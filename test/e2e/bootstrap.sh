@@ -5,6 +5,7 @@ set -eu
 DIFF_TOOL="${DIFF_TOOL:-diff --ignore-blank-lines --ignore-all-space}"
 TEST_DIR="${1:-./test/e2e}"
 BIN="${2:-../bootstrap/target/debug/bootstrap}"
+SYSROOT_BUILD="${SYSROOT_BUILD:-/home/ddymke/repo/elodie/src/sysroot/c/build/project}"
 
 EXIT_CODE=0
 OK_COUNT=0
@@ -19,15 +20,26 @@ for FILE in "$TEST_DIR"/**/*.ec; do
         exit 1
     fi
 
-    if ! ${BIN} build "$FILE" 2> /dev/null; then
+    BASE_NAME=$(basename "$FILE" .ec)
+    # a program next to a c client of the same name is built as library, the client is what runs
+    CLIENT="${FILE%.ec}.c"
+
+    if [[ -f "$CLIENT" ]]; then
+        if ! ${BIN} build "$FILE" --lib 2> /dev/null || ! gcc "$CLIENT" -std=gnu2x -I"/tmp/elodie/$BASE_NAME" \
+            "/tmp/elodie/$BASE_NAME/lib$BASE_NAME.a" -L"$SYSROOT_BUILD/core" -lcore -L"$SYSROOT_BUILD/rt" -lrt -lm \
+            -o "/tmp/elodie/$BASE_NAME/$BASE_NAME" 2> /dev/null; then
+            printf "\e[31mFail\e[0m\t$FILE (Failed to build library)\n"
+            ERR_COUNT=$((ERR_COUNT + 1))
+            EXIT_CODE=1
+            continue
+        fi
+    elif ! ${BIN} build "$FILE" 2> /dev/null; then
         printf "\e[31mFail\e[0m\t$FILE (Failed to build C code)\n"
         ERR_COUNT=$((ERR_COUNT + 1))
         EXIT_CODE=1
         continue
     fi
 
-    BASE_NAME=$(basename "$FILE" .ec)
-
     if ! "/tmp/elodie/$BASE_NAME/$BASE_NAME" > "/tmp/elodie/$BASE_NAME/$BASE_NAME.out" 2> /dev/null; then
         printf "\e[31mFail\e[0m\t$FILE (Execution failed)\n"
         ERR_COUNT=$((ERR_COUNT + 1))
@@ -0,0 +1,22 @@
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "greeter.h"
+
+int main(void) {
+    greeter_init();
+
+    greeter_Greeter *greeter = greeter_make();
+    greeter_release(greeter);
+
+    printf("%d\n", greeter_twice(21));
+
+    char *greeting = greeter_greeting();
+    char *echo = greeter_echo("elodie");
+    printf("%s %s\n", greeting, echo);
+    free(greeting);
+    free(echo);
+
+    greeter_shutdown();
+    return 0;
+}